@@ -3,6 +3,47 @@ export const SUPPORTED_LANGUAGES = [
   { id: "typescript", label: "TypeScript" },
   { id: "python", label: "Python" },
   { id: "rust", label: "Rust" },
+  { id: "rust-grpc", label: "Rust (gRPC)" },
+  { id: "rust-websocket", label: "Rust (WebSocket)" },
+  { id: "rust-tui", label: "Rust (TUI)" },
+  { id: "rust-datetime", label: "Rust (Date/Time)" },
+  { id: "rust-encoding", label: "Rust (Hashing/Encoding)" },
+  { id: "rust-compression", label: "Rust (Compression)" },
+  { id: "rust-csv", label: "Rust (CSV)" },
+  { id: "rust-config", label: "Rust (Config)" },
+  { id: "rust-http-client", label: "Rust (HTTP Client)" },
+  { id: "rust-graphql", label: "Rust (GraphQL)" },
+  { id: "rust-state-machine", label: "Rust (State Machine)" },
+  { id: "rust-plugins", label: "Rust (Plugins)" },
+  { id: "rust-visitor", label: "Rust (Visitor)" },
+  { id: "rust-typed-builder", label: "Rust (Typestate Builder)" },
+  { id: "rust-units", label: "Rust (Units)" },
+  { id: "rust-matrix", label: "Rust (Matrix)" },
+  { id: "rust-orphan-workaround", label: "Rust (Orphan Rule Workaround)" },
+  { id: "rust-trait-methods", label: "Rust (Trait Methods)" },
+  { id: "rust-hrtb", label: "Rust (HRTB)" },
+  { id: "rust-macro-export", label: "Rust (Exported Macros)" },
+  { id: "rust-features", label: "Rust (Cargo Features)" },
+  { id: "rust-edition2024", label: "Rust (Edition 2024)" },
+  { id: "rust-script-style", label: "Rust (Cargo Script)" },
+  { id: "rust-track-caller", label: "Rust (Track Caller)" },
+  { id: "rust-linkage", label: "Rust (Linkage)" },
+  { id: "rust-expressions", label: "Rust (Expressions)" },
+  { id: "rust-unicode", label: "Rust (Unicode)" },
+  { id: "rust-adapters", label: "Rust (Iterator Adapters)" },
+  { id: "rust-slices", label: "Rust (Slices/Arrays)" },
+  { id: "rust-mem-utils", label: "Rust (std::mem)" },
+  { id: "rust-signals", label: "Rust (Signals)" },
+  { id: "rust-thread-local", label: "Rust (thread_local!)" },
+  { id: "rust-crossbeam", label: "Rust (Crossbeam)" },
+  { id: "rust-retry", label: "Rust (Retry)" },
+  { id: "rust-random", label: "Rust (UUID/Random)" },
+  { id: "rust-dom", label: "Rust (DOM/web-sys)" },
+  { id: "rust-bindings-generated", label: "Rust (bindgen Output)" },
+  { id: "rust-allocator-api", label: "Rust (Allocator API)" },
+  { id: "rust-vec-capacity", label: "Rust (Vec Capacity)" },
+  { id: "rust-nested-generics", label: "Rust (Nested Generics)" },
+  { id: "rust-async-recursion", label: "Rust (Async recursion)" },
   { id: "go", label: "Go" },
   { id: "java", label: "Java" },
   { id: "c", label: "C" },
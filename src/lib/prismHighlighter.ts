@@ -30,6 +30,47 @@ const prismLanguageMap: Record<SupportedLanguage, string> = {
   typescript: "typescript",
   python: "python",
   rust: "rust",
+  "rust-grpc": "rust",
+  "rust-websocket": "rust",
+  "rust-tui": "rust",
+  "rust-datetime": "rust",
+  "rust-encoding": "rust",
+  "rust-compression": "rust",
+  "rust-csv": "rust",
+  "rust-config": "rust",
+  "rust-http-client": "rust",
+  "rust-graphql": "rust",
+  "rust-state-machine": "rust",
+  "rust-plugins": "rust",
+  "rust-visitor": "rust",
+  "rust-typed-builder": "rust",
+  "rust-units": "rust",
+  "rust-matrix": "rust",
+  "rust-orphan-workaround": "rust",
+  "rust-trait-methods": "rust",
+  "rust-hrtb": "rust",
+  "rust-macro-export": "rust",
+  "rust-features": "rust",
+  "rust-edition2024": "rust",
+  "rust-script-style": "rust",
+  "rust-track-caller": "rust",
+  "rust-linkage": "rust",
+  "rust-expressions": "rust",
+  "rust-unicode": "rust",
+  "rust-adapters": "rust",
+  "rust-slices": "rust",
+  "rust-mem-utils": "rust",
+  "rust-signals": "rust",
+  "rust-thread-local": "rust",
+  "rust-crossbeam": "rust",
+  "rust-retry": "rust",
+  "rust-random": "rust",
+  "rust-dom": "rust",
+  "rust-bindings-generated": "rust",
+  "rust-allocator-api": "rust",
+  "rust-vec-capacity": "rust",
+  "rust-nested-generics": "rust",
+  "rust-async-recursion": "rust",
   go: "go",
   java: "java",
   c: "c",
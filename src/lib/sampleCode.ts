@@ -7,6 +7,47 @@ const sampleCodePaths: Record<SupportedLanguage, string> = {
   typescript: "typescript.ts",
   python: "python.py",
   rust: "rust.rs",
+  "rust-grpc": "rust/grpc.rs",
+  "rust-websocket": "rust/websocket.rs",
+  "rust-tui": "rust/tui.rs",
+  "rust-datetime": "rust/datetime.rs",
+  "rust-encoding": "rust/encoding.rs",
+  "rust-compression": "rust/compression.rs",
+  "rust-csv": "rust/csv.rs",
+  "rust-config": "rust/config.rs",
+  "rust-http-client": "rust/http_client.rs",
+  "rust-graphql": "rust/graphql.rs",
+  "rust-state-machine": "rust/state_machine.rs",
+  "rust-plugins": "rust/plugins.rs",
+  "rust-visitor": "rust/visitor.rs",
+  "rust-typed-builder": "rust/typed_builder.rs",
+  "rust-units": "rust/units.rs",
+  "rust-matrix": "rust/matrix.rs",
+  "rust-orphan-workaround": "rust/orphan_workaround.rs",
+  "rust-trait-methods": "rust/trait_methods.rs",
+  "rust-hrtb": "rust/hrtb.rs",
+  "rust-macro-export": "rust/macro_export.rs",
+  "rust-features": "rust/features.rs",
+  "rust-edition2024": "rust/edition2024.rs",
+  "rust-script-style": "rust/script_style.rs",
+  "rust-track-caller": "rust/track_caller.rs",
+  "rust-linkage": "rust/linkage.rs",
+  "rust-expressions": "rust/expressions.rs",
+  "rust-unicode": "rust/unicode.rs",
+  "rust-adapters": "rust/adapters.rs",
+  "rust-slices": "rust/slices.rs",
+  "rust-mem-utils": "rust/mem_utils.rs",
+  "rust-signals": "rust/signals.rs",
+  "rust-thread-local": "rust/thread_local.rs",
+  "rust-crossbeam": "rust/crossbeam.rs",
+  "rust-retry": "rust/retry.rs",
+  "rust-random": "rust/random.rs",
+  "rust-dom": "rust/dom.rs",
+  "rust-bindings-generated": "rust/bindings_generated.rs",
+  "rust-allocator-api": "rust/allocator_api.rs",
+  "rust-vec-capacity": "rust/vec_capacity.rs",
+  "rust-nested-generics": "rust/nested_generics.rs",
+  "rust-async-recursion": "rust/async_recursion.rs",
   go: "go.go",
   java: "java.java",
   c: "c.c",
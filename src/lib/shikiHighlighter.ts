@@ -7,14 +7,19 @@ import zennTheme from "@/themes/zenn.json";
 import type { ThemeRegistration } from "shiki";
 import { createDiffTransformer } from "@/transformers/diffTransformer";
 
+function resolveShikiLang(lang: SupportedLanguage): string {
+  if (lang === "diff") {
+    return "typescript";
+  }
+  return lang.startsWith("rust-") ? "rust" : lang;
+}
+
 let highlighterPromise: Promise<Highlighter> | null = null;
 
 async function getHighlighter(): Promise<Highlighter> {
   if (!highlighterPromise) {
     const langs = [
-      ...new Set(
-        SUPPORTED_LANGUAGES.map((l) => (l.id === "diff" ? "typescript" : l.id))
-      ),
+      ...new Set(SUPPORTED_LANGUAGES.map((l) => resolveShikiLang(l.id))),
     ];
 
     highlighterPromise = createHighlighter({
@@ -32,7 +37,7 @@ export async function highlightWithShiki(
   const highlighter = await getHighlighter();
 
   return highlighter.codeToHtml(code, {
-    lang: lang === "diff" ? "typescript" : lang,
+    lang: resolveShikiLang(lang),
     theme: "zenn",
     transformers: lang === "diff" ? [createDiffTransformer()] : [],
   });
@@ -1,11 +1,55 @@
 //! Rust Sample Code
 //! Demonstrates various syntax features and token types
+#![cfg_attr(feature = "nightly", feature(allocator_api))]
+#![cfg_attr(feature = "nightly", feature(gen_blocks))]
 
 use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
 use std::sync::{Arc, Mutex};
 use std::marker::PhantomData;
 
+// Module declarations
+mod grpc;
+mod websocket;
+mod tui;
+mod datetime;
+mod encoding;
+mod compression;
+mod csv;
+mod config;
+mod http_client;
+mod graphql;
+mod state_machine;
+mod plugins;
+mod visitor;
+mod typed_builder;
+mod units;
+mod matrix;
+mod orphan_workaround;
+mod trait_methods;
+mod hrtb;
+mod macro_export;
+mod features;
+mod edition2024;
+mod track_caller;
+mod linkage;
+mod expressions;
+mod unicode;
+mod adapters;
+mod slices;
+mod mem_utils;
+mod signals;
+mod thread_local;
+mod crossbeam;
+mod retry;
+mod random;
+mod dom;
+mod bindings_generated;
+mod allocator_api;
+mod vec_capacity;
+mod nested_generics;
+mod async_recursion;
+
 // Constants and statics
 const PI: f64 = 3.14159265359;
 const MAX_SIZE: usize = 0xFF;
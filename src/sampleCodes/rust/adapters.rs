@@ -0,0 +1,148 @@
+//! Iterator Adapters Sample
+//! Demonstrates functional data-munging pipelines over access logs
+
+use std::collections::{BTreeSet, HashMap};
+use std::iter;
+
+#[derive(Debug, Clone)]
+pub struct Hit<'a> {
+    pub ts: u64,
+    pub path: &'a str,
+    pub status: u16,
+    pub bytes: usize,
+}
+
+pub fn parse(log: &str) -> impl Iterator<Item = Hit<'_>> + '_ {
+    log.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(Hit {
+                ts: fields.next()?.parse().ok()?,
+                path: fields.next()?,
+                status: fields.next()?.parse().ok()?,
+                bytes: fields.next()?.parse().ok()?,
+            })
+        })
+}
+
+// Running totals with scan
+pub fn cumulative_bytes(hits: &[Hit<'_>]) -> Vec<usize> {
+    hits.iter()
+        .scan(0, |total, hit| {
+            *total += hit.bytes;
+            Some(*total)
+        })
+        .collect()
+}
+
+// Group into a HashMap<_, _> with fold, then sort by count
+pub fn top_paths<'a>(hits: &[Hit<'a>], n: usize) -> Vec<(&'a str, usize)> {
+    let counts: HashMap<_, _> = hits.iter().fold(HashMap::new(), |mut acc, hit| {
+        *acc.entry(hit.path).or_insert(0usize) += 1;
+        acc
+    });
+    let mut sorted: Vec<_> = counts.into_iter().collect();
+    sorted.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    sorted.into_iter().take(n).collect()
+}
+
+// Collapse consecutive identical statuses with peekable
+pub fn status_runs(hits: &[Hit<'_>]) -> Vec<(u16, usize)> {
+    let mut runs = Vec::new();
+    let mut iter = hits.iter().map(|h| h.status).peekable();
+    while let Some(status) = iter.next() {
+        let mut len = 1;
+        while iter.next_if_eq(&status).is_some() {
+            len += 1;
+        }
+        runs.push((status, len));
+    }
+    runs
+}
+
+// Sliding windows over timestamps to find bursts
+pub fn bursts(hits: &[Hit<'_>], window: usize, max_span: u64) -> Vec<u64> {
+    let timestamps: Vec<u64> = hits.iter().map(|h| h.ts).collect();
+    timestamps
+        .windows(window)
+        .filter(|w| w[w.len() - 1] - w[0] <= max_span)
+        .map(|w| w[0])
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+pub fn demo() {
+    let log = "\
+        # ts path status bytes
+        1000 /articles/rust 200 5120
+        1001 /articles/rust 200 5120
+        1001 /api/likes 201 64
+        1002 /articles/go 404 512
+        1002 /articles/go 404 512
+        1030 /articles/rust 200 5120
+        oops
+        1031 /books/shiki 200 20480
+    ";
+    let hits: Vec<Hit> = parse(log).collect();
+
+    println!("cumulative: {:?}", cumulative_bytes(&hits));
+    println!("top: {:?}", top_paths(&hits, 2));
+    println!("runs: {:?}", status_runs(&hits));
+    println!("bursts: {:?}", bursts(&hits, 3, 2));
+
+    // zip + chain + step_by
+    let header = iter::once("idx".to_string());
+    let indices = (0..hits.len()).step_by(2).map(|i| i.to_string());
+    let row: Vec<String> = header.chain(indices).collect();
+    println!("{}", row.join(" | "));
+
+    let deltas: Vec<i64> = hits
+        .iter()
+        .zip(hits.iter().skip(1))
+        .map(|(a, b)| b.ts as i64 - a.ts as i64)
+        .collect();
+    println!("deltas: {deltas:?}");
+
+    // flat_map + take_while + skip_while
+    let segments: Vec<&str> = hits
+        .iter()
+        .flat_map(|h| h.path.split('/').filter(|s| !s.is_empty()))
+        .skip_while(|&s| s == "api")
+        .take_while(|&s| s != "books")
+        .collect();
+    println!("segments: {segments:?}");
+
+    // chunks + enumerate + rev + partition
+    for (page, chunk) in hits.chunks(3).enumerate().rev() {
+        let (ok, err): (Vec<&Hit>, Vec<&Hit>) = chunk.iter().partition(|h| h.status < 400);
+        println!("page {page}: {} ok, {} errors", ok.len(), err.len());
+    }
+
+    // min_by_key / max_by / position / any / all / unzip
+    let smallest = hits.iter().min_by_key(|h| h.bytes).map(|h| h.path);
+    let largest = hits.iter().max_by(|a, b| a.bytes.cmp(&b.bytes)).map(|h| h.path);
+    let first_error = hits.iter().position(|h| h.status >= 400);
+    let (paths, sizes): (Vec<&str>, Vec<usize>) = hits.iter().map(|h| (h.path, h.bytes)).unzip();
+    println!(
+        "{smallest:?} {largest:?} {first_error:?} any_created={} all_small={} {}/{}",
+        hits.iter().any(|h| h.status == 201),
+        hits.iter().all(|h| h.bytes < 1 << 20),
+        paths.len(),
+        sizes.iter().sum::<usize>(),
+    );
+
+    // cycle + take + successors + repeat_with
+    let spinner: String = "|/-\\".chars().cycle().take(10).collect();
+    let doubling: Vec<u32> = iter::successors(Some(1u32), |&n| n.checked_mul(2)).take(8).collect();
+    let mut seed = 7u32;
+    let noise: Vec<u32> = iter::repeat_with(|| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        seed % 100
+    })
+    .take(5)
+    .collect();
+    println!("{spinner} {doubling:?} {noise:?}");
+}
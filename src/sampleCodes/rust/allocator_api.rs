@@ -0,0 +1,154 @@
+//! Allocator API Sample
+//! Demonstrates the nightly Allocator trait and allocator-parameterized collections
+//!
+//! Only compiled with the "nightly" feature, which also enables
+//! `#![feature(allocator_api)]` at the crate root.
+#![cfg(feature = "nightly")]
+
+use std::alloc::{AllocError, Allocator, Global, Layout};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::ptr::NonNull;
+
+// A fixed-capacity bump allocator over a borrowed buffer
+pub struct Bump<'buf> {
+    start: NonNull<u8>,
+    capacity: usize,
+    offset: Cell<usize>,
+    _buffer: std::marker::PhantomData<&'buf mut [u8]>,
+}
+
+impl<'buf> Bump<'buf> {
+    pub fn new(buffer: &'buf mut [u8]) -> Self {
+        Self {
+            start: NonNull::from(&mut buffer[..]).cast(),
+            capacity: buffer.len(),
+            offset: Cell::new(0),
+            _buffer: std::marker::PhantomData,
+        }
+    }
+
+    pub fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    pub fn reset(&mut self) {
+        self.offset.set(0);
+    }
+}
+
+unsafe impl Allocator for Bump<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.start.as_ptr() as usize;
+        let current = base + self.offset.get();
+        let aligned = current.checked_next_multiple_of(layout.align()).ok_or(AllocError)?;
+        let end = aligned.checked_add(layout.size()).ok_or(AllocError)?;
+
+        if end > base + self.capacity {
+            return Err(AllocError);
+        }
+        self.offset.set(end - base);
+
+        let ptr = unsafe { NonNull::new_unchecked(aligned as *mut u8) };
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // Only the most recent allocation can be reclaimed
+        let end = ptr.as_ptr() as usize + layout.size() - self.start.as_ptr() as usize;
+        if end == self.offset.get() {
+            self.offset.set(end - layout.size());
+        }
+    }
+}
+
+// Wraps any allocator and counts calls
+pub struct Counting<A: Allocator = Global> {
+    inner: A,
+    allocations: Cell<usize>,
+    bytes: Cell<usize>,
+}
+
+impl<A: Allocator> Counting<A> {
+    pub const fn new(inner: A) -> Self {
+        Self { inner, allocations: Cell::new(0), bytes: Cell::new(0) }
+    }
+
+    pub fn stats(&self) -> (usize, usize) {
+        (self.allocations.get(), self.bytes.get())
+    }
+}
+
+unsafe impl<A: Allocator> Allocator for Counting<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let block = self.inner.allocate(layout)?;
+        self.allocations.set(self.allocations.get() + 1);
+        self.bytes.set(self.bytes.get() + block.len());
+        Ok(block)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.inner.allocate_zeroed(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.inner.deallocate(ptr, layout) }
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocations.set(self.allocations.get() + 1);
+        unsafe { self.inner.grow(ptr, old_layout, new_layout) }
+    }
+}
+
+pub fn tokenize_in<'a, A: Allocator + Copy>(src: &str, alloc: A) -> Vec<(usize, usize), A> {
+    let mut spans = Vec::with_capacity_in(src.len() / 4, alloc);
+    let mut start = None;
+    for (i, c) in src.char_indices() {
+        match (start, c.is_alphanumeric()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, src.len()));
+    }
+    spans
+}
+
+pub fn demo() -> Result<(), AllocError> {
+    let mut storage = [0u8; 4096];
+    let bump = Bump::new(&mut storage);
+
+    let mut numbers: Vec<u32, &Bump> = Vec::new_in(&bump);
+    numbers.extend([1, 1, 2, 3, 5, 8, 13]);
+    let boxed: Box<[u8; 16], &Bump> = Box::new_in([0xAA; 16], &bump);
+    let mut queue: VecDeque<&str, &Bump> = VecDeque::with_capacity_in(4, &bump);
+    queue.push_back("zenn");
+
+    let spans = tokenize_in("fn main() { println!(\"hi\"); }", &bump);
+    println!("{numbers:?} {:?} {queue:?} {spans:?}", &boxed[..4]);
+    println!("bump used {} of 4096 bytes", bump.used());
+
+    let counting = Counting::new(Global);
+    let mut words: Vec<String, &Counting> = Vec::new_in(&counting);
+    for w in "the quick brown fox jumps".split(' ') {
+        words.push(w.to_uppercase());
+    }
+    let layout = Layout::array::<u64>(32).map_err(|_| AllocError)?;
+    let block = counting.allocate_zeroed(layout)?;
+    unsafe { counting.deallocate(block.cast(), layout) };
+
+    let (allocs, bytes) = counting.stats();
+    println!("{allocs} allocations, {bytes} bytes");
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Async Recursion Sample
+//! Demonstrates a recursive directory walker with boxed futures
+
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{self, StreamExt, TryStreamExt};
+use tokio::fs;
+
+/// A future that borrows from its caller, boxed so it can recurse
+pub type WalkFuture<'a, T> = Pin<Box<dyn Future<Output = io::Result<T>> + Send + 'a>>;
+
+#[derive(Debug, Clone, Default)]
+pub struct Entry {
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub max_depth: usize,
+    pub follow_hidden: bool,
+    pub concurrency: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { max_depth: 16, follow_hidden: false, concurrency: 8 }
+    }
+}
+
+// An async fn cannot call itself directly: its future would contain itself
+pub fn walk<'a>(dir: &'a Path, depth: usize, options: &'a Options) -> WalkFuture<'a, Vec<Entry>> {
+    Box::pin(async move {
+        let mut entries = Vec::new();
+        if depth > options.max_depth {
+            return Ok(entries);
+        }
+        let mut reader = fs::read_dir(dir).await?;
+        while let Some(child) = reader.next_entry().await? {
+            let path = child.path();
+            let hidden = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.'));
+            if hidden && !options.follow_hidden {
+                continue;
+            }
+            let meta = child.metadata().await?;
+            if meta.is_dir() {
+                entries.extend(walk(&path, depth + 1, options).await?);
+            } else {
+                entries.push(Entry { path, bytes: meta.len(), depth });
+            }
+        }
+        Ok(entries)
+    })
+}
+
+// futures' alias for the same shape, built with `.boxed()`
+pub fn total_size(dir: PathBuf, options: Options) -> BoxFuture<'static, io::Result<u64>> {
+    async move {
+        let mut reader = fs::read_dir(&dir).await?;
+        let mut children = Vec::new();
+        while let Some(child) = reader.next_entry().await? {
+            children.push(child);
+        }
+        stream::iter(children)
+            .map(|child| async move {
+                let meta = child.metadata().await?;
+                match meta.is_dir() {
+                    true => total_size(child.path(), options).await,
+                    false => Ok(meta.len()),
+                }
+            })
+            .buffer_unordered(options.concurrency.max(1))
+            .try_fold(0u64, |sum, bytes| async move { Ok(sum + bytes) })
+            .await
+    }
+    .boxed()
+}
+
+/// Depth-first search that stops at the first match
+pub fn find<'a, F>(dir: &'a Path, matches: &'a F) -> BoxFuture<'a, io::Result<Option<PathBuf>>>
+where
+    F: Fn(&Path) -> bool + Sync,
+{
+    async move {
+        let mut reader = fs::read_dir(dir).await?;
+        while let Some(child) = reader.next_entry().await? {
+            let path = child.path();
+            if matches(&path) {
+                return Ok(Some(path));
+            }
+            if child.file_type().await?.is_dir() {
+                if let found @ Some(_) = find(&path, matches).await? {
+                    return Ok(found);
+                }
+            }
+        }
+        Ok(None)
+    }
+    .boxed()
+}
+
+/// A tree of directory sizes, built by joining boxed child futures
+#[derive(Debug)]
+pub enum Node {
+    File { name: String, bytes: u64 },
+    Dir { name: String, children: Vec<Node> },
+}
+
+impl Node {
+    pub fn bytes(&self) -> u64 {
+        match self {
+            Node::File { bytes, .. } => *bytes,
+            Node::Dir { children, .. } => children.iter().map(Node::bytes).sum(),
+        }
+    }
+
+    pub fn build(path: PathBuf) -> BoxFuture<'static, io::Result<Node>> {
+        let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        async move {
+            let meta = fs::metadata(&path).await?;
+            if !meta.is_dir() {
+                return Ok(Node::File { name, bytes: meta.len() });
+            }
+            let mut reader = fs::read_dir(&path).await?;
+            let mut pending: Vec<BoxFuture<'static, io::Result<Node>>> = Vec::new();
+            while let Some(child) = reader.next_entry().await? {
+                pending.push(Node::build(child.path()));
+            }
+            let mut children = futures::future::try_join_all(pending).await?;
+            children.sort_by(|a, b| b.bytes().cmp(&a.bytes()));
+            Ok(Node::Dir { name, children })
+        }
+        .boxed()
+    }
+}
+
+// Since Rust 1.77 a boxed recursive call is enough inside an async fn
+pub async fn count_files(dir: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    let mut reader = fs::read_dir(dir).await?;
+    while let Some(child) = reader.next_entry().await? {
+        count += match child.file_type().await?.is_dir() {
+            true => Box::pin(count_files(&child.path())).await?,
+            false => 1,
+        };
+    }
+    Ok(count)
+}
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    let root = PathBuf::from(std::env::args().nth(1).unwrap_or_else(|| ".".into()));
+    let options = Options { max_depth: 4, ..Options::default() };
+
+    let entries = walk(&root, 0, &options).await?;
+    let largest = entries.iter().max_by_key(|e| e.bytes);
+    println!("{} file(s), largest: {:?}", entries.len(), largest.map(|e| &e.path));
+
+    let bytes = total_size(root.clone(), options).await?;
+    println!("total: {:.1} KiB", bytes as f64 / 1024.0);
+
+    let is_manifest = |p: &Path| p.file_name().is_some_and(|n| n == "Cargo.toml");
+    if let Some(manifest) = find(&root, &is_manifest).await? {
+        println!("manifest at {}", manifest.display());
+    }
+
+    let tree = Node::build(root.clone()).await?;
+    if let Node::Dir { children, .. } = &tree {
+        for child in children.iter().take(5) {
+            let (Node::File { name, .. } | Node::Dir { name, .. }) = child;
+            println!("{:>10}  {name}", child.bytes());
+        }
+    }
+    println!("{} file(s) counted", count_files(&root).await?);
+    Ok(())
+}
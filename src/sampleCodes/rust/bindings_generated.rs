@@ -0,0 +1,171 @@
+/* automatically generated by rust-bindgen 0.69.4 */
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(dead_code)]
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct __BindgenBitfieldUnit<Storage> {
+    storage: Storage,
+}
+impl<Storage> __BindgenBitfieldUnit<Storage> {
+    #[inline]
+    pub const fn new(storage: Storage) -> Self {
+        Self { storage }
+    }
+}
+impl<Storage> __BindgenBitfieldUnit<Storage>
+where
+    Storage: AsRef<[u8]> + AsMut<[u8]>,
+{
+    #[inline]
+    pub fn get_bit(&self, index: usize) -> bool {
+        debug_assert!(index / 8 < self.storage.as_ref().len());
+        let byte_index = index / 8;
+        let byte = self.storage.as_ref()[byte_index];
+        let bit_index = if cfg!(target_endian = "big") { 7 - (index % 8) } else { index % 8 };
+        let mask = 1 << bit_index;
+        byte & mask == mask
+    }
+}
+#[repr(C)]
+#[derive(Default)]
+pub struct __IncompleteArrayField<T>(::std::marker::PhantomData<T>, [T; 0]);
+impl<T> __IncompleteArrayField<T> {
+    #[inline]
+    pub const fn new() -> Self {
+        __IncompleteArrayField(::std::marker::PhantomData, [])
+    }
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self as *const _ as *const T
+    }
+    #[inline]
+    pub unsafe fn as_slice(&self, len: usize) -> &[T] {
+        ::std::slice::from_raw_parts(self.as_ptr(), len)
+    }
+}
+impl<T> ::std::fmt::Debug for __IncompleteArrayField<T> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        fmt.write_str("__IncompleteArrayField")
+    }
+}
+pub const HL_VERSION_MAJOR: u32 = 2;
+pub const HL_VERSION_MINOR: u32 = 4;
+pub const HL_MAX_SCOPE_DEPTH: u32 = 64;
+pub const HL_DEFAULT_THEME: &[u8; 5] = b"zenn\0";
+pub type __uint8_t = ::std::os::raw::c_uchar;
+pub type __uint32_t = ::std::os::raw::c_uint;
+pub type hl_color_t = __uint32_t;
+pub type hl_status_t = ::std::os::raw::c_int;
+pub const hl_status_HL_OK: hl_status_t = 0;
+pub const hl_status_HL_ERR_GRAMMAR: hl_status_t = -1;
+pub const hl_status_HL_ERR_THEME: hl_status_t = -2;
+pub const hl_status_HL_ERR_OOM: hl_status_t = -12;
+pub const hl_font_style_HL_FONT_NONE: hl_font_style = 0;
+pub const hl_font_style_HL_FONT_ITALIC: hl_font_style = 1;
+pub const hl_font_style_HL_FONT_BOLD: hl_font_style = 2;
+pub const hl_font_style_HL_FONT_UNDERLINE: hl_font_style = 4;
+pub type hl_font_style = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct hl_theme {
+    _unused: [u8; 0],
+}
+#[doc = " Resolved style for a single token."]
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct hl_style {
+    #[doc = " Foreground color as 0xRRGGBBAA."]
+    pub foreground: hl_color_t,
+    pub background: hl_color_t,
+    pub _bitfield_align_1: [u8; 0],
+    pub _bitfield_1: __BindgenBitfieldUnit<[u8; 1usize]>,
+    pub __bindgen_padding_0: [u8; 3usize],
+}
+#[test]
+fn bindgen_test_layout_hl_style() {
+    const UNINIT: ::std::mem::MaybeUninit<hl_style> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<hl_style>(),
+        12usize,
+        concat!("Size of: ", stringify!(hl_style))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<hl_style>(),
+        4usize,
+        concat!("Alignment of ", stringify!(hl_style))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).background) as usize - ptr as usize },
+        4usize,
+        concat!("Offset of field: ", stringify!(hl_style), "::", stringify!(background))
+    );
+}
+impl hl_style {
+    #[inline]
+    pub fn italic(&self) -> ::std::os::raw::c_uint {
+        unsafe { ::std::mem::transmute(self._bitfield_1.get_bit(0usize) as u32) }
+    }
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union hl_value {
+    pub color: hl_color_t,
+    pub index: ::std::os::raw::c_int,
+    pub bytes: [__uint8_t; 4usize],
+}
+impl Default for hl_value {
+    fn default() -> Self {
+        let mut s = ::std::mem::MaybeUninit::<Self>::uninit();
+        unsafe {
+            ::std::ptr::write_bytes(s.as_mut_ptr(), 0, 1);
+            s.assume_init()
+        }
+    }
+}
+#[doc = " A token span produced by the tokenizer."]
+#[repr(C)]
+#[derive(Debug)]
+pub struct hl_token {
+    pub start: usize,
+    pub end: usize,
+    pub style: hl_style,
+    pub scope_count: usize,
+    pub scopes: __IncompleteArrayField<*const ::std::os::raw::c_char>,
+}
+pub type hl_token_cb = ::std::option::Option<
+    unsafe extern "C" fn(token: *const hl_token, user_data: *mut ::std::os::raw::c_void) -> bool,
+>;
+extern "C" {
+    #[doc = " Loads a theme from a JSON buffer.\n\n Returns NULL and sets `err` on failure."]
+    pub fn hl_theme_load(
+        json: *const ::std::os::raw::c_char,
+        len: usize,
+        err: *mut hl_status_t,
+    ) -> *mut hl_theme;
+}
+extern "C" {
+    #[doc = " Frees a theme previously returned by [`hl_theme_load`]."]
+    pub fn hl_theme_free(theme: *mut hl_theme);
+}
+extern "C" {
+    #[doc = " Tokenizes `source` and invokes `cb` for every token."]
+    pub fn hl_tokenize(
+        theme: *const hl_theme,
+        lang: *const ::std::os::raw::c_char,
+        source: *const ::std::os::raw::c_char,
+        source_len: usize,
+        cb: hl_token_cb,
+        user_data: *mut ::std::os::raw::c_void,
+    ) -> hl_status_t;
+}
+extern "C" {
+    pub static mut hl_default_style: hl_style;
+}
+extern "C" {
+    pub fn hl_version() -> *const ::std::os::raw::c_char;
+}
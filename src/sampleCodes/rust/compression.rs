@@ -0,0 +1,104 @@
+//! Compression Sample
+//! Demonstrates streaming gzip encoding/decoding with Read/Write adapters
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use flate2::bufread::MultiGzDecoder;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::{Compression, GzBuilder};
+
+const BUFFER_SIZE: usize = 64 * 1024;
+const FAST: Compression = Compression::fast();
+const BEST: Compression = Compression::best();
+
+pub fn compress_file(src: &Path, dst: &Path, level: Compression) -> io::Result<u64> {
+    let mut input = BufReader::with_capacity(BUFFER_SIZE, File::open(src)?);
+    let output = BufWriter::new(File::create(dst)?);
+
+    let mut encoder = GzEncoder::new(output, level);
+    let copied = io::copy(&mut input, &mut encoder)?;
+
+    // finish() writes the gzip trailer and hands back the inner writer
+    encoder.finish()?.flush()?;
+    Ok(copied)
+}
+
+pub fn decompress_to_string(path: &Path) -> io::Result<String> {
+    let mut decoder = GzDecoder::new(File::open(path)?);
+    let mut text = String::new();
+    decoder.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+// Manual copy loop with progress reporting
+pub fn copy_with_progress<R, W, F>(mut reader: R, mut writer: W, mut on_progress: F) -> io::Result<u64>
+where
+    R: Read,
+    W: Write,
+    F: FnMut(u64),
+{
+    let mut buf = vec![0u8; BUFFER_SIZE];
+    let mut total = 0u64;
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buf[..n])?;
+        total += n as u64;
+        on_progress(total);
+    }
+
+    writer.flush()?;
+    Ok(total)
+}
+
+// Count lines in concatenated .gz log archives without extracting them
+pub fn count_log_lines(path: &Path, needle: &str) -> io::Result<usize> {
+    let reader = BufReader::new(File::open(path)?);
+    let decoder = BufReader::new(MultiGzDecoder::new(reader));
+
+    let mut matches = 0;
+    for line in decoder.lines() {
+        if line?.contains(needle) {
+            matches += 1;
+        }
+    }
+    Ok(matches)
+}
+
+pub fn demo() -> io::Result<()> {
+    let payload = "zenn ".repeat(10_000);
+
+    let mut encoder = GzBuilder::new()
+        .filename("article.txt")
+        .comment("generated sample")
+        .write(Vec::new(), BEST);
+    encoder.write_all(payload.as_bytes())?;
+    let compressed = encoder.finish()?;
+
+    let ratio = compressed.len() as f64 / payload.len() as f64 * 100.0;
+    println!("{} -> {} bytes ({:.2}%)", payload.len(), compressed.len(), ratio);
+
+    let mut restored = Vec::with_capacity(payload.len());
+    copy_with_progress(GzDecoder::new(&compressed[..]), &mut restored, |n| {
+        if n % (BUFFER_SIZE as u64 * 4) == 0 {
+            eprintln!("  {} bytes decompressed", n);
+        }
+    })?;
+    assert_eq!(restored, payload.as_bytes());
+
+    for level in [FAST, Compression::default(), BEST] {
+        let mut enc = GzEncoder::new(io::sink(), level);
+        enc.write_all(payload.as_bytes())?;
+        println!("level {} -> {} bytes in", level.level(), enc.total_in());
+    }
+
+    Ok(())
+}
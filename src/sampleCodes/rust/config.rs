@@ -0,0 +1,157 @@
+//! Configuration Sample
+//! Demonstrates layered serde configuration loaded from TOML and YAML
+
+use std::fmt;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub database: DatabaseConfig,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    #[serde(default, rename = "feature")]
+    pub features: Vec<FeatureFlag>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ServerConfig {
+    pub host: IpAddr,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    pub public_url: Option<String>,
+    #[serde(with = "humantime_serde", default = "default_timeout")]
+    pub request_timeout: Duration,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    pub url: String,
+    pub max_connections: u32,
+    pub migrations: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub level: LogLevel,
+    pub json: bool,
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    #[default]
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeatureFlag {
+    pub name: String,
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub rollout_percent: Option<u8>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: "postgres://localhost/zenn".to_owned(),
+            max_connections: 10,
+            migrations: None,
+        }
+    }
+}
+
+fn default_port() -> u16 {
+    8080
+}
+
+fn default_timeout() -> Duration {
+    Duration::from_secs(30)
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Yaml(serde_yaml::Error),
+    UnsupportedFormat(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read config: {}", e),
+            Self::Toml(e) => write!(f, "invalid TOML: {}", e.message()),
+            Self::Yaml(e) => write!(f, "invalid YAML: {}", e),
+            Self::UnsupportedFormat(ext) => write!(f, "unsupported config format `.{}`", ext),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+pub fn load(path: &Path) -> Result<Config, ConfigError> {
+    let text = std::fs::read_to_string(path).map_err(ConfigError::Io)?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&text).map_err(ConfigError::Toml),
+        Some("yaml" | "yml") => serde_yaml::from_str(&text).map_err(ConfigError::Yaml),
+        Some(other) => Err(ConfigError::UnsupportedFormat(other.to_owned())),
+        None => Err(ConfigError::UnsupportedFormat(String::new())),
+    }
+}
+
+const EXAMPLE_TOML: &str = r#"
+# Application configuration
+[server]
+host = "127.0.0.1"
+public_url = "https://zenn.dev"
+request_timeout = "15s"
+
+[database]
+url = "postgres://app:secret@db:5432/zenn"
+max_connections = 32
+
+[logging]
+level = "debug"
+json = true
+
+[[feature]]
+name = "new-editor"
+enabled = true
+rollout_percent = 25
+
+[[feature]]
+name = "dark-mode"
+"#;
+
+pub fn demo() {
+    match toml::from_str::<Config>(EXAMPLE_TOML) {
+        Ok(config) => {
+            let ServerConfig { host, port, .. } = &config.server;
+            println!("listening on {}:{}", host, port);
+            println!("db pool size = {}", config.database.max_connections);
+            for flag in config.features.iter().filter(|f| f.enabled) {
+                println!("feature {} at {:?}%", flag.name, flag.rollout_percent);
+            }
+        }
+        Err(err) => {
+            if let Some(span) = err.span() {
+                eprintln!("error at bytes {:?}: {}", span, err.message());
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Crossbeam Sample
+//! Demonstrates scoped threads, bounded/unbounded channels, and select!
+
+use std::time::{Duration, Instant};
+
+use crossbeam::channel::{self, after, bounded, never, tick, unbounded, Receiver, Sender};
+use crossbeam::select;
+use crossbeam::thread;
+
+#[derive(Debug)]
+pub enum Job {
+    Highlight { lang: &'static str, source: String },
+    Shutdown,
+}
+
+#[derive(Debug)]
+pub struct Rendered {
+    pub worker: usize,
+    pub lang: &'static str,
+    pub html_len: usize,
+}
+
+// Scoped threads can borrow from the enclosing stack frame
+pub fn parallel_word_counts(articles: &[String], workers: usize) -> Vec<usize> {
+    let chunk_size = articles.len().div_ceil(workers.max(1));
+    let mut counts = vec![0; articles.len()];
+
+    thread::scope(|s| {
+        for (articles, counts) in articles.chunks(chunk_size).zip(counts.chunks_mut(chunk_size)) {
+            s.spawn(move |_| {
+                for (article, slot) in articles.iter().zip(counts.iter_mut()) {
+                    *slot = article.split_whitespace().count();
+                }
+            });
+        }
+    })
+    .expect("a worker thread panicked");
+
+    counts
+}
+
+fn worker(id: usize, jobs: Receiver<Job>, results: Sender<Rendered>) {
+    for job in jobs.iter() {
+        match job {
+            Job::Highlight { lang, source } => {
+                let html_len = source.len() * 3;
+                if results.send(Rendered { worker: id, lang, html_len }).is_err() {
+                    break;
+                }
+            }
+            Job::Shutdown => break,
+        }
+    }
+}
+
+pub fn run_pipeline(sources: Vec<(&'static str, String)>) -> Vec<Rendered> {
+    let (job_tx, job_rx) = bounded::<Job>(4);
+    let (result_tx, result_rx) = unbounded::<Rendered>();
+    let (cancel_tx, cancel_rx) = bounded::<()>(0);
+    let worker_count = 3;
+    let job_count = sources.len();
+
+    thread::scope(|s| {
+        for id in 0..worker_count {
+            let (jobs, results) = (job_rx.clone(), result_tx.clone());
+            s.spawn(move |_| worker(id, jobs, results));
+        }
+        drop(result_tx);
+
+        s.spawn(|_| {
+            for (lang, source) in sources {
+                select! {
+                    send(job_tx, Job::Highlight { lang, source }) -> res => res.unwrap(),
+                    recv(cancel_rx) -> _ => return,
+                }
+            }
+            for _ in 0..worker_count {
+                job_tx.send(Job::Shutdown).ok();
+            }
+        });
+
+        let deadline = after(Duration::from_secs(5));
+        let heartbeat = tick(Duration::from_millis(500));
+        let mut collected = Vec::with_capacity(job_count);
+        let start = Instant::now();
+
+        while collected.len() < job_count {
+            select! {
+                recv(result_rx) -> msg => match msg {
+                    Ok(rendered) => collected.push(rendered),
+                    Err(_) => break,
+                },
+                recv(heartbeat) -> _ => eprintln!("{}/{} done after {:?}", collected.len(), job_count, start.elapsed()),
+                recv(deadline) -> _ => {
+                    eprintln!("deadline reached, cancelling");
+                    drop(cancel_tx);
+                    break;
+                }
+            }
+        }
+
+        collected
+    })
+    .unwrap()
+}
+
+// Optional receivers via `never()`
+pub fn wait_any(primary: &Receiver<String>, fallback: Option<&Receiver<String>>) -> Option<String> {
+    let fallback = fallback.cloned().unwrap_or_else(never);
+    select! {
+        recv(primary) -> msg => msg.ok(),
+        recv(fallback) -> msg => msg.ok().map(|m| format!("(fallback) {m}")),
+        default(Duration::from_millis(250)) => None,
+    }
+}
+
+pub fn demo() {
+    let articles = vec!["Rust で Shiki".to_string(), "one two three".into(), "crossbeam select".into()];
+    println!("{:?}", parallel_word_counts(&articles, 2));
+
+    let sources = vec![
+        ("rust", "fn main() {}".to_string()),
+        ("go", "package main".to_string()),
+        ("ts", "const x = 1;".to_string()),
+    ];
+    for Rendered { worker, lang, html_len } in run_pipeline(sources) {
+        println!("worker {worker} rendered {lang} ({html_len} bytes)");
+    }
+
+    let (tx, rx) = channel::unbounded();
+    tx.send("hello".to_string()).unwrap();
+    println!("{:?}", wait_any(&rx, None));
+}
@@ -0,0 +1,104 @@
+//! CSV Sample
+//! Demonstrates csv reader/writer builders with serde records
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io;
+
+use csv::{ReaderBuilder, StringRecord, Trim, WriterBuilder};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct Sale {
+    date: String,
+    region: String,
+    product: String,
+    #[serde(deserialize_with = "csv::invalid_option")]
+    quantity: Option<u32>,
+    unit_price: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct RegionTotal<'a> {
+    region: &'a str,
+    orders: usize,
+    revenue: f64,
+}
+
+const SAMPLE: &str = "\
+date;region;product;quantity;unit_price
+2024-04-01;Tokyo;Keyboard;3;12800
+2024-04-01;Osaka;Mouse;n/a;4980
+2024-04-02; Tokyo ;Monitor;1;39800
+";
+
+pub fn summarize(input: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(b';')
+        .quote(b'"')
+        .trim(Trim::All)
+        .has_headers(true)
+        .from_reader(input.as_bytes());
+
+    let headers: StringRecord = reader.headers()?.clone();
+    println!("columns: {:?}", headers.iter().collect::<Vec<_>>());
+
+    let mut totals: BTreeMap<String, (usize, f64)> = BTreeMap::new();
+    for (line, result) in reader.deserialize::<Sale>().enumerate() {
+        let sale = match result {
+            Ok(sale) => sale,
+            Err(err) => {
+                eprintln!("skipping row {}: {}", line + 2, err);
+                continue;
+            }
+        };
+
+        let quantity = sale.quantity.unwrap_or(0);
+        let entry = totals.entry(sale.region).or_insert((0, 0.0));
+        entry.0 += 1;
+        entry.1 += f64::from(quantity) * sale.unit_price;
+    }
+
+    let mut writer = WriterBuilder::new()
+        .delimiter(b',')
+        .terminator(csv::Terminator::CRLF)
+        .from_writer(Vec::new());
+
+    for (region, (orders, revenue)) in &totals {
+        writer.serialize(RegionTotal { region, orders: *orders, revenue: *revenue })?;
+    }
+
+    writer.flush()?;
+    Ok(writer.into_inner()?)
+}
+
+// Raw record access without serde
+pub fn print_column(input: &str, column: &str) -> Result<(), Box<dyn Error>> {
+    let mut reader = ReaderBuilder::new().delimiter(b';').from_reader(input.as_bytes());
+    let index = reader
+        .headers()?
+        .iter()
+        .position(|h| h == column)
+        .ok_or_else(|| format!("no column named {:?}", column))?;
+
+    let mut record = StringRecord::new();
+    while reader.read_record(&mut record)? {
+        let position = record.position().map(|p| p.line()).unwrap_or_default();
+        println!("{:>3}: {}", position, &record[index]);
+    }
+    Ok(())
+}
+
+pub fn demo() -> Result<(), Box<dyn Error>> {
+    let output = summarize(SAMPLE)?;
+    io::Write::write_all(&mut io::stdout(), &output)?;
+
+    print_column(SAMPLE, "product")?;
+
+    let mut stdout = csv::Writer::from_writer(io::stdout());
+    stdout.write_record(["sku", "name", "note"])?;
+    stdout.write_record(&["KB-01", "Keyboard", "contains \"quotes\", commas"])?;
+    stdout.flush()?;
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Date/Time Sample
+//! Demonstrates chrono parsing, formatting, arithmetic, and time zones
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone,
+    Timelike, Utc, Weekday,
+};
+use chrono_tz::Asia::Tokyo;
+use chrono_tz::Tz;
+
+const LOG_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const JST_OFFSET_SECS: i32 = 9 * 3600;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidRfc3339(String),
+    InvalidLogLine(String),
+}
+
+// Parsing from different string representations
+pub fn parse_timestamps(raw: &str) -> Result<DateTime<Utc>, ParseError> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    NaiveDateTime::parse_from_str(raw, LOG_FORMAT)
+        .map(|naive| naive.and_utc())
+        .map_err(|_| ParseError::InvalidRfc3339(raw.to_owned()))
+}
+
+pub fn parse_published_date(line: &str) -> Result<NaiveDate, ParseError> {
+    let value = line
+        .strip_prefix("published_at: ")
+        .ok_or_else(|| ParseError::InvalidLogLine(line.to_owned()))?;
+    NaiveDate::parse_from_str(value.trim_matches('"'), "%Y-%m-%d")
+        .map_err(|e| ParseError::InvalidLogLine(format!("{}: {}", value, e)))
+}
+
+// Formatting with strftime-style specifiers
+pub fn describe(dt: &DateTime<Utc>) -> String {
+    let local: DateTime<Tz> = dt.with_timezone(&Tokyo);
+    format!(
+        "{} (JST {}) — {} week {:02}",
+        dt.format(LOG_FORMAT),
+        local.format("%H:%M %Z"),
+        local.format("%A"),
+        local.iso_week().week(),
+    )
+}
+
+pub fn http_date(dt: &DateTime<Utc>) -> String {
+    dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+// Duration arithmetic
+pub fn next_business_day(date: NaiveDate) -> NaiveDate {
+    let mut next = date + Duration::days(1);
+    while matches!(next.weekday(), Weekday::Sat | Weekday::Sun) {
+        next = next.succ_opt().expect("date out of range");
+    }
+    next
+}
+
+pub fn humanize(elapsed: Duration) -> String {
+    match elapsed.num_seconds() {
+        s if s < 60 => format!("{}秒前", s),
+        s if s < 3_600 => format!("{}分前", s / 60),
+        s if s < 86_400 => format!("{}時間前", s / 3_600),
+        _ => format!("{}日前", elapsed.num_days()),
+    }
+}
+
+// Time zone conversion
+pub fn convert_zones(now: DateTime<Utc>) -> [(String, String); 3] {
+    let jst = FixedOffset::east_opt(JST_OFFSET_SECS).unwrap();
+    let new_york: Tz = "America/New_York".parse().unwrap();
+
+    [
+        ("UTC".into(), now.to_rfc3339()),
+        ("JST".into(), now.with_timezone(&jst).to_rfc3339()),
+        ("NYC".into(), now.with_timezone(&new_york).format("%+").to_string()),
+    ]
+}
+
+pub fn demo() {
+    let release = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+    let deadline = release + Duration::hours(36) - Duration::minutes(30);
+    println!("release:  {}", describe(&release));
+    println!("deadline: {}", http_date(&deadline));
+
+    let now = Local::now();
+    let start_of_day = now.with_hour(0).and_then(|t| t.with_minute(0)).unwrap();
+    println!("{} since midnight", humanize(now - start_of_day));
+
+    match parse_timestamps("2024-02-30T10:00:00+09:00") {
+        Ok(dt) => println!("parsed {}", dt.timestamp_millis()),
+        Err(ParseError::InvalidRfc3339(raw)) => eprintln!("not RFC 3339: {:?}", raw),
+        Err(err) => eprintln!("{:?}", err),
+    }
+
+    for (zone, text) in convert_zones(Utc::now()) {
+        println!("{:<4} {}", zone, text);
+    }
+}
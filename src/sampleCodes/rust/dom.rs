@@ -0,0 +1,130 @@
+//! DOM Manipulation Sample
+//! Demonstrates web-sys/js-sys APIs, event listener closures, and JsCast
+
+use js_sys::{Array, Date, Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    console, window, Document, Element, Event, HtmlButtonElement, HtmlElement, HtmlInputElement,
+    KeyboardEvent, MutationObserver, MutationObserverInit, Response, Storage,
+};
+
+const STORAGE_KEY: &str = "zenn-theme-preference";
+
+fn document() -> Result<Document, JsValue> {
+    window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document available"))
+}
+
+fn local_storage() -> Option<Storage> {
+    window()?.local_storage().ok().flatten()
+}
+
+// Toggle the code-block theme and persist the choice
+#[wasm_bindgen]
+pub fn toggle_theme() -> Result<String, JsValue> {
+    let body = document()?.body().ok_or("missing <body>")?;
+    let classes = body.class_list();
+    let next = if classes.toggle("znc-dark")? { "dark" } else { "light" };
+
+    if let Some(storage) = local_storage() {
+        storage.set_item(STORAGE_KEY, next)?;
+    }
+    console::log_2(&"theme switched to".into(), &next.into());
+    Ok(next.to_owned())
+}
+
+#[wasm_bindgen(start)]
+pub fn start() -> Result<(), JsValue> {
+    console_error_panic_hook::set_once();
+    let document = document()?;
+
+    // query_selector returns Option<Element>; dyn_into narrows it to a concrete type
+    let button: HtmlButtonElement = document
+        .query_selector("#theme-toggle")?
+        .ok_or("missing #theme-toggle")?
+        .dyn_into()?;
+
+    let on_click = Closure::wrap(Box::new(move |event: Event| {
+        event.prevent_default();
+        if let Err(err) = toggle_theme() {
+            console::error_1(&err);
+        }
+    }) as Box<dyn FnMut(Event)>);
+    button.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())?;
+    on_click.forget();
+
+    // Filter code blocks as the user types
+    let search: HtmlInputElement = document.get_element_by_id("search").unwrap().unchecked_into();
+    let blocks = document.query_selector_all("pre > code")?;
+    let on_input = Closure::<dyn FnMut(_)>::new(move |event: KeyboardEvent| {
+        let input = event
+            .target()
+            .and_then(|t| t.dyn_into::<HtmlInputElement>().ok())
+            .map(|el| el.value().to_lowercase())
+            .unwrap_or_default();
+
+        for i in 0..blocks.length() {
+            let Some(node) = blocks.item(i) else { continue };
+            if let Some(el) = node.dyn_ref::<HtmlElement>() {
+                let text = el.text_content().unwrap_or_default().to_lowercase();
+                let hidden = !input.is_empty() && !text.contains(&input);
+                el.style().set_property("display", if hidden { "none" } else { "" }).ok();
+            }
+        }
+    });
+    search.set_oninput(Some(on_input.as_ref().unchecked_ref()));
+    on_input.forget();
+
+    observe_new_blocks(&document)?;
+    Ok(())
+}
+
+fn observe_new_blocks(document: &Document) -> Result<(), JsValue> {
+    let callback = Closure::<dyn FnMut(Array, MutationObserver)>::new(|records: Array, _observer| {
+        let added: u32 = records
+            .iter()
+            .filter_map(|r| Reflect::get(&r, &"addedNodes".into()).ok())
+            .map(|nodes| Reflect::get(&nodes, &"length".into()).ok().and_then(|v| v.as_f64()).unwrap_or(0.0) as u32)
+            .sum();
+        console::debug_1(&format!("{added} nodes added at {}", Date::new_0().to_iso_string()).into());
+    });
+
+    let observer = MutationObserver::new(callback.as_ref().unchecked_ref())?;
+    let options = MutationObserverInit::new();
+    options.set_child_list(true);
+    options.set_subtree(true);
+
+    let target: Element = document.document_element().ok_or("no root element")?;
+    observer.observe_with_options(&target, &options)?;
+    callback.forget();
+    Ok(())
+}
+
+// Calling fetch() and awaiting the returned Promise
+#[wasm_bindgen]
+pub async fn load_theme(url: String) -> Result<JsValue, JsValue> {
+    let window = window().ok_or("no window")?;
+    let response: Response = JsFuture::from(window.fetch_with_str(&url)).await?.dyn_into()?;
+
+    if !response.ok() {
+        return Err(JsError::new(&format!("HTTP {}", response.status())).into());
+    }
+
+    let json = JsFuture::from(response.json()?).await?;
+    let name = Reflect::get(&json, &JsValue::from_str("name"))?;
+    console::log_1(&name);
+    Ok(json)
+}
+
+#[wasm_bindgen]
+pub fn delay(ms: i32) -> Promise {
+    Promise::new(&mut |resolve: Function, _reject: Function| {
+        window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms)
+            .expect("setTimeout failed");
+    })
+}
@@ -0,0 +1,128 @@
+//! Edition 2024 Sample
+//! Demonstrates let chains, precise capturing, gen blocks, and other new syntax
+//!
+//! Requires `edition = "2024"`; gen blocks additionally need a nightly toolchain,
+//! enabled by the `gen_blocks` feature at the crate root in `rust.rs`.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+#[derive(Debug)]
+pub struct Article {
+    pub slug: String,
+    pub tags: Vec<String>,
+    pub published: Option<u32>,
+}
+
+// if-let chains
+pub fn featured_tag<'a>(article: &'a Article, counts: &HashMap<&str, usize>) -> Option<&'a str> {
+    if let Some(first) = article.tags.first()
+        && let Some(&count) = counts.get(first.as_str())
+        && count >= 10
+        && article.published.is_some()
+    {
+        Some(first)
+    } else {
+        None
+    }
+}
+
+// while-let chains
+pub fn drain_valid(queue: &mut Vec<Option<Article>>) -> Vec<String> {
+    let mut slugs = Vec::new();
+    while let Some(entry) = queue.pop()
+        && let Some(article) = entry
+        && !article.slug.is_empty()
+    {
+        slugs.push(article.slug);
+    }
+    slugs
+}
+
+// RPIT now captures all in-scope lifetimes by default; `use<..>` narrows it
+pub fn slugs<'a>(articles: &'a [Article], prefix: &str) -> impl Iterator<Item = &'a str> + use<'a> {
+    let prefix = prefix.to_owned();
+    articles
+        .iter()
+        .map(|a| a.slug.as_str())
+        .filter(move |slug| slug.starts_with(&prefix))
+}
+
+pub fn counter() -> impl FnMut() -> u32 + use<> {
+    let mut n = 0;
+    move || {
+        n += 1;
+        n
+    }
+}
+
+pub trait Feed {
+    // Return-position impl Trait in traits, with precise capturing
+    fn titles(&self) -> impl Iterator<Item = String> + use<'_, Self>;
+
+    fn refresh(&mut self) -> impl Future<Output = usize> + Send;
+}
+
+// gen blocks produce iterators without a hand-written state machine
+#[cfg(feature = "nightly")]
+pub fn fibonacci() -> impl Iterator<Item = u64> {
+    gen {
+        let (mut a, mut b) = (0u64, 1u64);
+        loop {
+            yield a;
+            (a, b) = (b, a + b);
+        }
+    }
+}
+
+#[cfg(feature = "nightly")]
+pub fn tokenize(src: &str) -> impl Iterator<Item = &str> + use<'_> {
+    gen move {
+        for word in src.split_whitespace() {
+            if let Some(rest) = word.strip_prefix('#') {
+                yield "#";
+                yield rest;
+            } else {
+                yield word;
+            }
+        }
+    }
+}
+
+// `unsafe extern` blocks and the `safe` qualifier
+unsafe extern "C" {
+    pub safe fn abs(value: i32) -> i32;
+    pub unsafe fn strlen(s: *const std::ffi::c_char) -> usize;
+}
+
+// `unsafe` attributes
+#[unsafe(no_mangle)]
+pub extern "C" fn zenn_version() -> u32 {
+    2024
+}
+
+// Reserved `gen` keyword must be written as a raw identifier
+pub fn r#gen(seed: u64) -> u64 {
+    seed.wrapping_mul(6364136223846793005).wrapping_add(1)
+}
+
+pub async fn demo() {
+    let articles = vec![
+        Article { slug: "rust-shiki".into(), tags: vec!["rust".into()], published: Some(2024) },
+        Article { slug: "rust-wasm".into(), tags: vec![], published: None },
+    ];
+
+    let counts = HashMap::from([("rust", 42)]);
+    println!("{:?}", featured_tag(&articles[0], &counts));
+    println!("{:?}", slugs(&articles, "rust-").collect::<Vec<_>>());
+
+    let mut next = counter();
+    println!("{} {} {}", next(), next(), abs(-7));
+
+    // async closures
+    let fetch = async |slug: &str| format!("fetched {slug}");
+    println!("{}", fetch("rust-shiki").await);
+
+    let mut queue: Vec<Option<Article>> = articles.into_iter().map(Some).collect();
+    println!("{:?} seed={}", drain_valid(&mut queue), r#gen(zenn_version() as u64));
+}
@@ -0,0 +1,105 @@
+//! Hashing and Encoding Sample
+//! Demonstrates sha2 digests, base64 engines, and hex round-trips
+
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::{alphabet, Engine as _};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+// Custom engine that accepts both padded and unpadded input
+const LENIENT: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+// Generic over any Digest implementation
+pub fn digest_hex<D: Digest>(data: impl AsRef<[u8]>) -> String {
+    let mut hasher = D::new();
+    hasher.update(data.as_ref());
+    hex::encode(hasher.finalize())
+}
+
+pub fn hash_file<D>(path: &Path) -> io::Result<Vec<u8>>
+where
+    D: Digest + Default,
+{
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = D::default();
+    let mut buffer = [0u8; CHUNK_SIZE];
+
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+
+    Ok(hasher.finalize().to_vec())
+}
+
+// Signed tokens in the form `<payload>.<signature>`
+pub fn sign(secret: &[u8], payload: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(payload.as_bytes());
+    let signature = mac.finalize().into_bytes();
+
+    format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(payload),
+        URL_SAFE_NO_PAD.encode(signature)
+    )
+}
+
+pub fn verify(secret: &[u8], token: &str) -> Option<String> {
+    let (payload_b64, signature_b64) = token.split_once('.')?;
+    let payload = LENIENT.decode(payload_b64).ok()?;
+    let signature = LENIENT.decode(signature_b64).ok()?;
+
+    let mut mac = HmacSha256::new_from_slice(secret).ok()?;
+    mac.update(&payload);
+    mac.verify_slice(&signature).ok()?;
+
+    String::from_utf8(payload).ok()
+}
+
+pub fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    let sha256 = digest_hex::<Sha256>("zenn");
+    let sha512 = digest_hex::<Sha512>(b"zenn");
+    println!("sha256: {}\nsha512: {}…", sha256, &sha512[..32]);
+
+    // Chained one-shot API
+    let checksum = Sha256::new()
+        .chain_update(b"header:")
+        .chain_update([0xDE, 0xAD, 0xBE, 0xEF])
+        .finalize();
+    println!("checksum = {:x}", checksum);
+
+    // hex and base64 round-trips
+    let bytes = hex::decode("48656c6c6f2c205a656e6e21")?;
+    assert_eq!(hex::encode_upper(&bytes), "48656C6C6F2C205A656E6E21");
+
+    let encoded = STANDARD.encode(&bytes);
+    let decoded = STANDARD.decode(encoded.as_bytes())?;
+    assert_eq!(bytes, decoded);
+    println!("{} -> {}", String::from_utf8_lossy(&decoded), encoded);
+
+    let token = sign(b"s3cr3t", r#"{"sub":"alice","exp":1735689600}"#);
+    match verify(b"s3cr3t", &token) {
+        Some(payload) => println!("valid token: {}", payload),
+        None => println!("invalid token"),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! Operator Precedence Sample
+//! Demonstrates dense expressions mixing casts, shifts, ranges, and matches
+
+use std::ops::RangeInclusive;
+
+const MASK: u32 = !0 >> 4 & 0x0FFF_FFF0 | 1 << 3;
+const PAGE: usize = 4 * 1024;
+
+pub fn pack_rgba(r: u8, g: u8, b: u8, a: f32) -> u32 {
+    (r as u32) << 24 | (g as u32) << 16 | (b as u32) << 8 | (a.clamp(0.0, 1.0) * 255.0).round() as u32
+}
+
+pub fn unpack(rgba: u32) -> (u8, u8, u8, f32) {
+    (
+        (rgba >> 24 & 0xff) as u8,
+        (rgba >> 16 & 0xff) as u8,
+        (rgba >> 8 & 0xff) as u8,
+        (rgba & 0xff) as f32 / 255.0,
+    )
+}
+
+pub fn luminance(rgba: u32) -> f64 {
+    let (r, g, b, _) = unpack(rgba);
+    let linear = |c: u8| -> f64 {
+        let c = c as f64 / 255.0;
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+pub fn align_up(addr: usize, align: usize) -> usize {
+    debug_assert!(align.is_power_of_two() && align <= PAGE);
+    addr + align - 1 & !(align - 1)
+}
+
+pub fn classify(n: i64) -> &'static str {
+    match n {
+        i64::MIN..=-1 => "negative",
+        0 => "zero",
+        x if x & (x - 1) == 0 => "power of two",
+        x if x % 2 == 0 && x % 3 == 0 || x % 5 == 0 => "round-ish",
+        1..=9 | 11..=99 => "small",
+        _ => "large",
+    }
+}
+
+pub fn checksum(bytes: &[u8]) -> u16 {
+    let sum = bytes
+        .chunks(2)
+        .map(|pair| (pair[0] as u32) << 8 | *pair.get(1).unwrap_or(&0) as u32)
+        .fold(0u32, |acc, word| acc.wrapping_add(word));
+    !((sum & 0xffff) + (sum >> 16)) as u16
+}
+
+pub fn window(len: usize, cursor: usize, radius: usize) -> RangeInclusive<usize> {
+    cursor.saturating_sub(radius)..=(cursor + radius).min(len.saturating_sub(1))
+}
+
+pub fn score(hits: u32, misses: u32, streak: Option<u32>) -> f64 {
+    let total = (hits + misses).max(1) as f64;
+    let base = hits as f64 / total * 100.0 - (misses as f64).powi(2) / total;
+    let bonus = streak.filter(|&s| s >= 3).map_or(0.0, |s| (s - 2) as f64 * 1.5);
+    (base + bonus).clamp(0.0, 100.0) * if misses == 0 { 1.1 } else { 1.0 }
+}
+
+pub fn demo() {
+    let rgba = pack_rgba(0x1a, 0x26, 0x38, 1.0);
+    let ratio = (luminance(0xffffffff) + 0.05) / (luminance(rgba) + 0.05);
+    println!("#{:08x} contrast {:.2}:1", rgba, ratio);
+
+    let flags = 0b1010_0110u8;
+    let toggled = flags ^ 1 << 2 & !flags | (flags >> 1 & 0x0f) << 4;
+    let is_set = |bit: u8| flags & 1 << bit != 0;
+    println!("{:#010b} -> {:#010b}, bit5={}", flags, toggled, is_set(5));
+
+    let values = [3i64, -4, 0, 16, 30, 7, 250];
+    let labels: Vec<_> = values.iter().map(|&v| (v, classify(v))).collect();
+    println!("{labels:?}");
+
+    let idx = -(values.len() as isize) + 3;
+    let wrapped = idx.rem_euclid(values.len() as isize) as usize;
+    let range = window(values.len(), wrapped, 2);
+    println!("{:?} -> {:?}", range.clone(), &values[range]);
+
+    let x = 7.5_f64;
+    let y = -x.abs().sqrt() * 2.0 + (x as i32 % 3) as f64 - -1.0;
+    let z = (y < 0.0) as u8 + !(x > 10.0) as u8 * 2;
+    let in_bounds = (0.0..=1.0).contains(&(y / x).abs()) && z >= 2 || x.is_nan();
+    println!("y={y:.3} z={z} ok={in_bounds}");
+
+    println!(
+        "aligned={} mask={:#x} sum={:#06x} score={:.1}",
+        align_up(4097, 256),
+        MASK,
+        checksum(b"zenn!"),
+        score(42, 3, Some(7)),
+    );
+
+    let total: u64 = (1..=100u64).filter(|n| n % 3 == 0 || n % 5 == 0).map(|n| n * n).sum::<u64>() >> 1;
+    let maybe = Some(5).map(|n| n << 1).and_then(|n| (n < 20).then_some(n)).unwrap_or_default() as f32 / 3.0;
+    println!("{total} {maybe:.2}");
+}
@@ -0,0 +1,121 @@
+//! Cargo Features Sample
+//! Demonstrates feature-gated modules, cfg_attr derives, and cfg combinators
+//!
+//! The features used below are declared in the crate manifest as:
+//!
+//! ```toml
+//! [features]
+//! default = ["std", "json"]
+//! std = []
+//! json = ["dep:serde", "dep:serde_json"]
+//! metrics = ["dep:prometheus"]
+//! tracing = ["dep:tracing"]
+//! unstable = []
+//! ```
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "camelCase"))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderStats {
+    pub blocks: usize,
+    pub tokens: usize,
+    #[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
+    pub slowest_lang: Option<String>,
+    #[cfg(feature = "metrics")]
+    pub histogram: Vec<f64>,
+}
+
+// Whole module only exists with the feature enabled
+#[cfg(feature = "metrics")]
+pub mod metrics {
+    use std::sync::LazyLock;
+
+    use prometheus::{register_histogram, register_int_counter, Histogram, IntCounter};
+
+    pub static RENDERED_BLOCKS: LazyLock<IntCounter> = LazyLock::new(|| {
+        register_int_counter!("zenn_rendered_blocks_total", "Code blocks rendered").unwrap()
+    });
+
+    pub static RENDER_SECONDS: LazyLock<Histogram> = LazyLock::new(|| {
+        register_histogram!("zenn_render_seconds", "Time spent highlighting").unwrap()
+    });
+
+    pub fn observe(seconds: f64) {
+        RENDERED_BLOCKS.inc();
+        RENDER_SECONDS.observe(seconds);
+    }
+}
+
+// No-op fallback keeps call sites free of cfg noise
+#[cfg(not(feature = "metrics"))]
+pub mod metrics {
+    #[inline(always)]
+    pub fn observe(_seconds: f64) {}
+}
+
+#[cfg(all(feature = "json", feature = "std"))]
+pub fn to_json(stats: &RenderStats) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(stats)
+}
+
+#[cfg(any(feature = "tracing", debug_assertions))]
+macro_rules! trace_render {
+    ($($arg:tt)*) => { eprintln!("[render] {}", format_args!($($arg)*)) };
+}
+
+#[cfg(not(any(feature = "tracing", debug_assertions)))]
+macro_rules! trace_render {
+    ($($arg:tt)*) => {};
+}
+
+pub fn render(blocks: &[(&str, &str)]) -> RenderStats {
+    let mut per_lang: HashMap<String, usize> = HashMap::new();
+    let mut tokens = 0;
+
+    for &(lang, code) in blocks {
+        #[cfg(feature = "std")]
+        let start = std::time::Instant::now();
+
+        let count = code.split_whitespace().count();
+        tokens += count;
+        *per_lang.entry(lang.to_owned()).or_insert(0) += count;
+        trace_render!("{lang}: {count} tokens");
+
+        #[cfg(feature = "std")]
+        metrics::observe(start.elapsed().as_secs_f64());
+    }
+
+    RenderStats {
+        blocks: blocks.len(),
+        tokens,
+        slowest_lang: per_lang.into_iter().max_by_key(|(_, n)| *n).map(|(lang, _)| lang),
+        #[cfg(feature = "metrics")]
+        histogram: Vec::new(),
+    }
+}
+
+pub const fn build_profile() -> &'static str {
+    if cfg!(all(feature = "unstable", not(target_arch = "wasm32"))) {
+        "unstable"
+    } else if cfg!(any(target_os = "linux", target_os = "macos")) {
+        "unix"
+    } else {
+        "default"
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_camel_case() {
+        let stats = render(&[("rust", "fn main() {}")]);
+        assert!(to_json(&stats).unwrap().contains("\"slowestLang\""));
+    }
+}
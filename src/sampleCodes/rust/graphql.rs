@@ -0,0 +1,127 @@
+//! GraphQL Client Sample
+//! Demonstrates graphql_client derives and generated query modules
+
+use graphql_client::{reqwest::post_graphql, GraphQLQuery, Response};
+use reqwest::Client;
+
+const ENDPOINT: &str = "https://api.github.com/graphql";
+
+// Custom scalars referenced by the schema
+type DateTime = String;
+type URI = String;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/schema.graphql",
+    query_path = "graphql/repository_issues.graphql",
+    response_derives = "Debug, Clone, PartialEq",
+    variables_derives = "Debug, Default",
+    normalization = "rust"
+)]
+pub struct RepositoryIssues;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "graphql/schema.graphql",
+    query_path = "graphql/add_comment.graphql",
+    response_derives = "Debug"
+)]
+pub struct AddComment;
+
+use repository_issues::{IssueState, RepositoryIssuesRepositoryIssuesNodes as IssueNode};
+
+#[derive(Debug)]
+pub enum QueryError {
+    Http(reqwest::Error),
+    GraphQL(Vec<graphql_client::Error>),
+    MissingData(&'static str),
+}
+
+impl From<reqwest::Error> for QueryError {
+    fn from(err: reqwest::Error) -> Self {
+        QueryError::Http(err)
+    }
+}
+
+fn unwrap_response<T>(response: Response<T>) -> Result<T, QueryError> {
+    if let Some(errors) = response.errors.filter(|errors| !errors.is_empty()) {
+        return Err(QueryError::GraphQL(errors));
+    }
+    response.data.ok_or(QueryError::MissingData("response had no data"))
+}
+
+pub async fn open_issues(
+    client: &Client,
+    owner: &str,
+    name: &str,
+) -> Result<Vec<IssueNode>, QueryError> {
+    let variables = repository_issues::Variables {
+        owner: owner.to_string(),
+        name: name.to_string(),
+        first: 20,
+        states: Some(vec![IssueState::OPEN]),
+        ..Default::default()
+    };
+
+    let response = post_graphql::<RepositoryIssues, _>(client, ENDPOINT, variables).await?;
+    let data: repository_issues::ResponseData = unwrap_response(response)?;
+
+    let nodes = data
+        .repository
+        .ok_or(QueryError::MissingData("repository"))?
+        .issues
+        .nodes
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect();
+
+    Ok(nodes)
+}
+
+pub async fn comment(client: &Client, subject_id: String, body: &str) -> Result<URI, QueryError> {
+    let request_body = AddComment::build_query(add_comment::Variables {
+        subject_id,
+        body: body.to_owned(),
+    });
+
+    let response: Response<add_comment::ResponseData> = client
+        .post(ENDPOINT)
+        .json(&request_body)
+        .send()
+        .await?
+        .json()
+        .await?;
+
+    let comment_edge = unwrap_response(response)?
+        .add_comment
+        .and_then(|payload| payload.comment_edge)
+        .and_then(|edge| edge.node)
+        .ok_or(QueryError::MissingData("addComment.commentEdge.node"))?;
+
+    Ok(comment_edge.url)
+}
+
+pub async fn demo(token: &str) -> Result<(), QueryError> {
+    let client = Client::builder()
+        .user_agent("graphql-sample/0.1")
+        .default_headers(
+            std::iter::once((
+                reqwest::header::AUTHORIZATION,
+                reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+            ))
+            .collect(),
+        )
+        .build()?;
+
+    for issue in open_issues(&client, "zenn-dev", "zenn-editor").await? {
+        let labels: Vec<_> = issue
+            .labels
+            .iter()
+            .flat_map(|conn| conn.nodes.iter().flatten().flatten())
+            .map(|label| label.name.as_str())
+            .collect();
+        println!("#{:<5} {} [{}]", issue.number, issue.title, labels.join(", "));
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! gRPC Service Sample
+//! Demonstrates a tonic service implementation and server setup
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+
+// Generated code from `proto/inventory.proto`
+pub mod inventory {
+    tonic::include_proto!("inventory.v1");
+}
+
+use inventory::inventory_service_server::{InventoryService, InventoryServiceServer};
+use inventory::{GetItemRequest, Item, ListItemsRequest, ListItemsResponse, PutItemRequest};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+// Service state shared across requests
+#[derive(Debug, Default)]
+pub struct Inventory {
+    items: Arc<RwLock<HashMap<String, Item>>>,
+}
+
+#[tonic::async_trait]
+impl InventoryService for Inventory {
+    async fn get_item(
+        &self,
+        request: Request<GetItemRequest>,
+    ) -> Result<Response<Item>, Status> {
+        let remote = request.remote_addr();
+        let GetItemRequest { sku } = request.into_inner();
+        tracing::debug!(?remote, %sku, "get_item");
+
+        if sku.is_empty() {
+            return Err(Status::invalid_argument("sku must not be empty"));
+        }
+
+        let items = self.items.read().await;
+        match items.get(&sku) {
+            Some(item) => Ok(Response::new(item.clone())),
+            None => Err(Status::not_found(format!("item `{}` does not exist", sku))),
+        }
+    }
+
+    async fn put_item(
+        &self,
+        request: Request<PutItemRequest>,
+    ) -> Result<Response<Item>, Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| Status::unauthenticated("missing authorization header"))?;
+
+        if !token.starts_with("Bearer ") {
+            return Err(Status::permission_denied("invalid token scheme"));
+        }
+
+        let item = request
+            .into_inner()
+            .item
+            .ok_or_else(|| Status::invalid_argument("item is required"))?;
+
+        let mut items = self.items.write().await;
+        items.insert(item.sku.clone(), item.clone());
+        Ok(Response::new(item))
+    }
+
+    async fn list_items(
+        &self,
+        request: Request<ListItemsRequest>,
+    ) -> Result<Response<ListItemsResponse>, Status> {
+        let ListItemsRequest { page_size, page_token } = request.into_inner();
+        let page_size = match page_size {
+            0 => DEFAULT_PAGE_SIZE,
+            n if n > 1_000 => return Err(Status::out_of_range("page_size too large")),
+            n => n as usize,
+        };
+
+        let items = self.items.read().await;
+        let mut skus: Vec<&String> = items.keys().collect();
+        skus.sort();
+
+        let page: Vec<Item> = skus
+            .into_iter()
+            .skip_while(|sku| page_token.as_deref().is_some_and(|token| sku.as_str() <= token))
+            .take(page_size)
+            .map(|sku| items[sku].clone())
+            .collect();
+
+        let next_page_token = page.last().map(|item| item.sku.clone());
+        Ok(Response::new(ListItemsResponse { items: page, next_page_token }))
+    }
+}
+
+// Interceptor attached to every request
+fn require_client_id(request: Request<()>) -> Result<Request<()>, Status> {
+    match request.metadata().get("x-client-id") {
+        Some(_) => Ok(request),
+        None => Err(Status::failed_precondition("x-client-id header is required")),
+    }
+}
+
+pub async fn serve(addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
+    let service = Inventory::default();
+
+    let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
+    health_reporter
+        .set_serving::<InventoryServiceServer<Inventory>>()
+        .await;
+
+    println!("InventoryService listening on {}", addr);
+
+    Server::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .concurrency_limit_per_connection(256)
+        .add_service(health_service)
+        .add_service(InventoryServiceServer::with_interceptor(service, require_client_id))
+        .serve_with_shutdown(addr, async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await?;
+
+    Ok(())
+}
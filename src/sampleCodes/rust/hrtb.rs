@@ -0,0 +1,134 @@
+//! Higher-Ranked Trait Bounds Sample
+//! Demonstrates `for<'a>` closure bounds in a middleware-style pipeline
+
+use std::borrow::Cow;
+
+// A stage borrows its input and returns a slice of the same buffer
+pub type Stage = Box<dyn for<'a> Fn(&'a [u8]) -> &'a [u8] + Send + Sync>;
+
+pub struct Pipeline {
+    stages: Vec<(&'static str, Stage)>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    pub fn stage<F>(mut self, name: &'static str, f: F) -> Self
+    where
+        F: for<'a> Fn(&'a [u8]) -> &'a [u8] + Send + Sync + 'static,
+    {
+        self.stages.push((name, Box::new(f)));
+        self
+    }
+
+    pub fn run<'buf>(&self, mut input: &'buf [u8]) -> &'buf [u8] {
+        for (name, stage) in &self.stages {
+            let before = input.len();
+            input = stage(input);
+            tracing::trace!(stage = name, removed = before - input.len());
+        }
+        input
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Free functions satisfy the higher-ranked bound directly
+fn trim_bom(input: &[u8]) -> &[u8] {
+    input.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(input)
+}
+
+fn trim_ascii(input: &[u8]) -> &[u8] {
+    input.trim_ascii()
+}
+
+fn until_nul(input: &[u8]) -> &[u8] {
+    match input.iter().position(|&b| b == 0) {
+        Some(end) => &input[..end],
+        None => input,
+    }
+}
+
+// Generic struct storing a higher-ranked callback
+pub struct Extractor<F>
+where
+    F: for<'a> Fn(&'a str) -> Option<&'a str>,
+{
+    name: String,
+    extract: F,
+}
+
+impl<F> Extractor<F>
+where
+    F: for<'a> Fn(&'a str) -> Option<&'a str>,
+{
+    pub fn new(name: impl Into<String>, extract: F) -> Self {
+        Self { name: name.into(), extract }
+    }
+
+    pub fn apply<'doc>(&self, lines: &[&'doc str]) -> Vec<&'doc str> {
+        lines.iter().filter_map(|line| (self.extract)(line)).collect()
+    }
+}
+
+// HRTB on a trait rather than a closure type
+pub trait Normalize {
+    fn normalize<'a>(&self, input: &'a str) -> Cow<'a, str>;
+}
+
+impl<F> Normalize for F
+where
+    F: for<'a> Fn(&'a str) -> Cow<'a, str>,
+{
+    fn normalize<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        self(input)
+    }
+}
+
+fn apply_all<'a, N>(normalizers: &[N], input: &'a str) -> Cow<'a, str>
+where
+    N: Normalize,
+{
+    normalizers.iter().fold(Cow::Borrowed(input), |acc, n| match acc {
+        Cow::Borrowed(s) => n.normalize(s),
+        Cow::Owned(s) => Cow::Owned(n.normalize(&s).into_owned()),
+    })
+}
+
+fn collapse_spaces(input: &str) -> Cow<'_, str> {
+    if input.contains("  ") {
+        Cow::Owned(input.split_whitespace().collect::<Vec<_>>().join(" "))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+pub fn demo() {
+    let pipeline = Pipeline::new()
+        .stage("bom", trim_bom)
+        .stage("nul", until_nul)
+        .stage("ascii", trim_ascii)
+        .stage("header", |input| {
+            input.strip_prefix(b"#!").map_or(input, |rest| {
+                let newline = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+                &rest[newline..]
+            })
+        });
+
+    let raw = b"\xEF\xBB\xBF  #!zenn\nhello, world  \0garbage".to_vec();
+    let cleaned = pipeline.run(&raw);
+    println!("{:?}", String::from_utf8_lossy(cleaned));
+
+    let titles = Extractor::new("title", |line: &str| line.strip_prefix("title: "));
+    let front_matter = ["title: Rust の HRTB", "emoji: 🦀", "title: second"];
+    println!("{}: {:?}", titles.name, titles.apply(&front_matter));
+
+    let normalizers = [collapse_spaces as fn(&str) -> Cow<'_, str>];
+    println!("{}", apply_all(&normalizers, "too   many    spaces"));
+}
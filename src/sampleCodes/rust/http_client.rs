@@ -0,0 +1,151 @@
+//! HTTP Client Sample
+//! Demonstrates a reqwest client with typed responses and retries
+
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, USER_AGENT};
+use reqwest::{Client, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use thiserror::Error;
+
+const BASE_URL: &str = "https://zenn.dev/api";
+const MAX_RETRIES: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Deserialize)]
+pub struct Article {
+    pub id: u64,
+    pub title: String,
+    pub slug: String,
+    #[serde(rename = "liked_count")]
+    pub likes: u32,
+    pub published_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArticlesPage {
+    articles: Vec<Article>,
+    next_page: Option<u32>,
+}
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("resource not found: {0}")]
+    NotFound(String),
+    #[error("rate limited, retry after {retry_after:?}")]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("server returned {status}: {body}")]
+    Status { status: StatusCode, body: String },
+}
+
+impl ApiError {
+    fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::Transport(e) => e.is_timeout() || e.is_connect(),
+            ApiError::RateLimited { .. } => true,
+            ApiError::Status { status, .. } => status.is_server_error(),
+            ApiError::NotFound(_) => false,
+        }
+    }
+}
+
+pub struct ZennClient {
+    http: Client,
+}
+
+impl ZennClient {
+    pub fn new(token: Option<&str>) -> Result<Self, ApiError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(USER_AGENT, HeaderValue::from_static("zenn-sample/0.1"));
+        if let Some(token) = token {
+            let mut value = HeaderValue::from_str(&format!("Bearer {token}")).unwrap();
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .connect_timeout(Duration::from_secs(3))
+            .default_headers(headers)
+            .gzip(true)
+            .build()?;
+
+        Ok(Self { http })
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, ApiError> {
+        let url = format!("{BASE_URL}{path}");
+        let response = self.http.get(&url).send().await?;
+
+        match response.status() {
+            status if status.is_success() => Ok(response.json::<T>().await?),
+            StatusCode::NOT_FOUND => Err(ApiError::NotFound(url)),
+            StatusCode::TOO_MANY_REQUESTS => {
+                let retry_after = response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok()?.parse::<u64>().ok())
+                    .map(Duration::from_secs);
+                Err(ApiError::RateLimited { retry_after })
+            }
+            status => Err(ApiError::Status {
+                status,
+                body: response.text().await.unwrap_or_default(),
+            }),
+        }
+    }
+
+    // Exponential backoff around any request
+    async fn with_retry<T, F, Fut>(&self, mut request: F) -> Result<T, ApiError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, ApiError>>,
+    {
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_RETRIES {
+            match request().await {
+                Ok(value) => return Ok(value),
+                Err(err) if attempt < MAX_RETRIES && err.is_retryable() => {
+                    let delay = match &err {
+                        ApiError::RateLimited { retry_after: Some(d) } => *d,
+                        _ => backoff,
+                    };
+                    tracing::warn!(attempt, ?delay, error = %err, "retrying request");
+                    tokio::time::sleep(delay).await;
+                    backoff = (backoff * 2).min(Duration::from_secs(10));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        unreachable!("loop always returns on the final attempt")
+    }
+
+    pub async fn user_articles(&self, username: &str) -> Result<Vec<Article>, ApiError> {
+        let mut page = Some(1);
+        let mut all = Vec::new();
+
+        while let Some(n) = page {
+            let path = format!("/articles?username={username}&order=latest&page={n}");
+            let ArticlesPage { articles, next_page } =
+                self.with_retry(|| self.get_json(&path)).await?;
+            all.extend(articles);
+            page = next_page;
+        }
+
+        Ok(all)
+    }
+}
+
+pub async fn demo() -> Result<(), ApiError> {
+    let client = ZennClient::new(std::env::var("ZENN_TOKEN").ok().as_deref())?;
+    let mut articles = client.user_articles("zenn").await?;
+    articles.sort_by(|a, b| b.likes.cmp(&a.likes));
+
+    for Article { title, likes, .. } in articles.iter().take(5) {
+        println!("{likes:>5} ♥  {title}");
+    }
+    Ok(())
+}
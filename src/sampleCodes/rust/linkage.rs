@@ -0,0 +1,97 @@
+//! Linkage Attributes Sample
+//! Demonstrates #[link], #[link_section], #[used], and symbol-level attributes
+
+use std::ffi::{c_char, c_double, c_int, c_void, CStr};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Link against system libraries
+#[link(name = "m")]
+unsafe extern "C" {
+    fn cbrt(x: c_double) -> c_double;
+    fn hypot(x: c_double, y: c_double) -> c_double;
+}
+
+#[link(name = "z", kind = "dylib")]
+unsafe extern "C" {
+    fn zlibVersion() -> *const c_char;
+}
+
+#[cfg_attr(target_os = "macos", link(name = "CoreFoundation", kind = "framework"))]
+unsafe extern "C" {}
+
+#[link(name = "highlight", kind = "static", modifiers = "+whole-archive")]
+unsafe extern "C" {
+    #[link_name = "hl_render_v2"]
+    fn hl_render(src: *const c_char, len: usize, out: *mut c_void) -> c_int;
+}
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_load() {
+    INITIALIZED.store(true, Ordering::Release);
+}
+
+// Run `on_load` before main via the platform constructor section
+#[used]
+#[cfg_attr(target_os = "linux", unsafe(link_section = ".init_array"))]
+#[cfg_attr(target_os = "macos", unsafe(link_section = "__DATA,__mod_init_func"))]
+#[cfg_attr(windows, unsafe(link_section = ".CRT$XCU"))]
+static INIT_HOOK: extern "C" fn() = on_load;
+
+// Custom sections for embedded metadata
+#[used]
+#[unsafe(link_section = ".note.zenn")]
+static BUILD_NOTE: [u8; 16] = *b"zenn-theme\0v1.0\0";
+
+#[unsafe(no_mangle)]
+#[unsafe(link_section = ".text.hot")]
+pub extern "C" fn zenn_theme_background() -> u32 {
+    0x1a2638
+}
+
+#[unsafe(export_name = "zenn_theme_foreground")]
+pub extern "C" fn foreground() -> u32 {
+    0xffffff
+}
+
+#[inline(never)]
+#[cold]
+#[unsafe(link_section = ".text.unlikely")]
+fn report_failure(code: c_int) -> ! {
+    panic!("highlighter returned {code}")
+}
+
+#[repr(C, align(16))]
+pub struct Header {
+    magic: [u8; 4],
+    version: u16,
+    flags: u16,
+}
+
+#[unsafe(no_mangle)]
+#[used]
+pub static ZENN_HEADER: Header = Header { magic: *b"ZENN", version: 1, flags: 0 };
+
+pub fn demo() {
+    assert!(INITIALIZED.load(Ordering::Acquire), "constructor did not run");
+
+    let (cube_root, distance) = unsafe { (cbrt(27.0), hypot(3.0, 4.0)) };
+    println!("cbrt(27) = {cube_root}, hypot(3, 4) = {distance}");
+
+    let version = unsafe { CStr::from_ptr(zlibVersion()) };
+    println!("zlib {}", version.to_string_lossy());
+
+    let source = c"fn main() {}";
+    let status = unsafe {
+        hl_render(source.as_ptr(), source.count_bytes(), std::ptr::null_mut())
+    };
+    if status != 0 {
+        report_failure(status);
+    }
+
+    println!(
+        "background=#{:06x} note={:?}",
+        zenn_theme_background(),
+        std::str::from_utf8(&BUILD_NOTE).unwrap_or_default()
+    );
+}
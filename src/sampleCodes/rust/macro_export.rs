@@ -0,0 +1,131 @@
+//! Exported Macros Sample
+//! Demonstrates `#[macro_export]`, `$crate` paths, and internal helper rules
+
+use std::collections::BTreeMap;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use std::collections::BTreeMap;
+    pub use std::format_args;
+
+    pub fn log(level: &str, module: &str, args: std::fmt::Arguments<'_>) {
+        eprintln!("[{level:<5}] {module}: {args}");
+    }
+}
+
+/// Builds a [`BTreeMap`] from `key => value` pairs.
+///
+/// Keys are converted with [`Into`], so string literals can be used for
+/// `String` keys.
+///
+/// # Examples
+///
+/// ```
+/// let theme = zenn_sample::btreemap! {
+///     "keyword" => "#ff8fa3",
+///     "string" => "#a5d6ff",
+/// };
+/// assert_eq!(theme.len(), 2);
+/// ```
+#[macro_export]
+macro_rules! btreemap {
+    () => {
+        $crate::macro_export::__private::BTreeMap::new()
+    };
+    ($($key:expr => $value:expr),+ $(,)?) => {{
+        let mut map = $crate::macro_export::__private::BTreeMap::new();
+        $(
+            map.insert(::core::convert::Into::into($key), $value);
+        )+
+        map
+    }};
+}
+
+/// Logs a message with the calling module path attached.
+#[macro_export]
+macro_rules! log {
+    // The internal rule comes first: the catch-all below would match it too
+    (@emit $level:literal, $($arg:tt)+) => {
+        $crate::macro_export::__private::log(
+            $level,
+            ::core::module_path!(),
+            $crate::macro_export::__private::format_args!($($arg)+),
+        )
+    };
+
+    // Public entry points forward to the internal `@emit` rule
+    (error, $($arg:tt)+) => { $crate::log!(@emit "ERROR", $($arg)+) };
+    (warn, $($arg:tt)+) => { $crate::log!(@emit "WARN", $($arg)+) };
+    ($($arg:tt)+) => { $crate::log!(@emit "INFO", $($arg)+) };
+}
+
+/// Declares a newtype ID with `Display`, `From<u64>`, and a `new` constructor.
+#[macro_export]
+macro_rules! define_id {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident($prefix:literal);) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        $vis struct $name(u64);
+
+        impl $name {
+            pub const PREFIX: &'static str = $prefix;
+
+            pub const fn new(raw: u64) -> Self {
+                Self(raw)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(f, "{}_{:08x}", Self::PREFIX, self.0)
+            }
+        }
+
+        impl ::core::convert::From<u64> for $name {
+            fn from(raw: u64) -> Self {
+                Self::new(raw)
+            }
+        }
+    };
+}
+
+// Crate-internal helper that is not exported
+macro_rules! count {
+    () => { 0usize };
+    ($head:tt $($tail:tt)*) => { 1usize + count!($($tail)*) };
+}
+
+macro_rules! palette {
+    ($($role:ident = $hex:literal),* $(,)?) => {
+        pub const ROLE_COUNT: usize = count!($($role)*);
+
+        pub fn palette() -> BTreeMap<&'static str, &'static str> {
+            $crate::btreemap! { $(stringify!($role) => $hex),* }
+        }
+    };
+}
+
+palette! {
+    keyword = "#ff8fa3",
+    function = "#38c7ff",
+    constant = "#ffc56d",
+    comment = "#94a1b3",
+}
+
+define_id! {
+    /// Identifier for a published article.
+    pub struct ArticleId("art");
+}
+
+pub fn demo() {
+    let colors = palette();
+    crate::log!("loaded {} of {} roles", colors.len(), ROLE_COUNT);
+
+    let id = ArticleId::from(0xBEEF);
+    crate::log!(warn, "article {} has no emoji", id);
+
+    let empty: BTreeMap<String, u8> = btreemap!();
+    if empty.is_empty() {
+        crate::log!(error, "nothing to render for {:?}", ArticleId::PREFIX);
+    }
+}
@@ -0,0 +1,148 @@
+//! Const-Generic Matrix Sample
+//! Demonstrates const generics with array-of-array storage
+
+use std::array;
+use std::fmt;
+use std::ops::{Add, Index, IndexMut, Mul};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    data: [[f64; C]; R],
+}
+
+pub type Vector<const N: usize> = Matrix<N, 1>;
+pub type Mat3 = Matrix<3, 3>;
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    pub const ROWS: usize = R;
+    pub const COLS: usize = C;
+
+    pub const fn zeros() -> Self {
+        Self { data: [[0.0; C]; R] }
+    }
+
+    pub const fn from_rows(data: [[f64; C]; R]) -> Self {
+        Self { data }
+    }
+
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> f64) -> Self {
+        Self { data: array::from_fn(|r| array::from_fn(|c| f(r, c))) }
+    }
+
+    pub fn transpose(&self) -> Matrix<C, R> {
+        Matrix::from_fn(|r, c| self.data[c][r])
+    }
+
+    pub fn row(&self, r: usize) -> &[f64; C] {
+        &self.data[r]
+    }
+
+    pub fn column(&self, c: usize) -> impl Iterator<Item = f64> + '_ {
+        self.data.iter().map(move |row| row[c])
+    }
+
+    pub fn map(self, f: impl Fn(f64) -> f64) -> Self {
+        Self { data: self.data.map(|row| row.map(&f)) }
+    }
+}
+
+impl<const N: usize> Matrix<N, N> {
+    pub fn identity() -> Self {
+        Self::from_fn(|r, c| if r == c { 1.0 } else { 0.0 })
+    }
+
+    pub fn trace(&self) -> f64 {
+        (0..N).map(|i| self.data[i][i]).sum()
+    }
+
+    pub fn pow(self, mut exp: u32) -> Self {
+        let mut base = self;
+        let mut acc = Self::identity();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        acc
+    }
+}
+
+fn dot<const N: usize>(a: impl IntoIterator<Item = f64>, b: &[f64; N]) -> f64 {
+    a.into_iter().zip(b.iter()).map(|(x, y)| x * y).sum()
+}
+
+// (R x C) * (C x K) = (R x K)
+impl<const R: usize, const C: usize, const K: usize> Mul<Matrix<C, K>> for Matrix<R, C> {
+    type Output = Matrix<R, K>;
+
+    fn mul(self, rhs: Matrix<C, K>) -> Self::Output {
+        let rhs_t = rhs.transpose();
+        Matrix::from_fn(|r, k| dot(self.data[r], rhs_t.row(k)))
+    }
+}
+
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self {
+        for (lhs_row, rhs_row) in self.data.iter_mut().zip(rhs.data) {
+            for (a, b) in lhs_row.iter_mut().zip(rhs_row) {
+                *a += b;
+            }
+        }
+        self
+    }
+}
+
+impl<const R: usize, const C: usize> Index<(usize, usize)> for Matrix<R, C> {
+    type Output = f64;
+
+    fn index(&self, (r, c): (usize, usize)) -> &f64 {
+        &self.data[r][c]
+    }
+}
+
+impl<const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<R, C> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut f64 {
+        &mut self.data[r][c]
+    }
+}
+
+impl<const R: usize, const C: usize> fmt::Display for Matrix<R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.data {
+            let cells: Vec<String> = row.iter().map(|v| format!("{v:>8.3}")).collect();
+            writeln!(f, "[{}]", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn rotation_z(theta: f64) -> Mat3 {
+    let (sin, cos) = theta.sin_cos();
+    Matrix::from_rows([
+        [cos, -sin, 0.0],
+        [sin, cos, 0.0],
+        [0.0, 0.0, 1.0],
+    ])
+}
+
+pub fn demo() {
+    let a = Matrix::<2, 3>::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = Matrix::<3, 2>::from_fn(|r, c| (r * 2 + c) as f64);
+    let product: Matrix<2, 2> = a * b;
+    print!("{}", product);
+
+    let point: Vector<3> = Matrix::from_rows([[1.0], [0.0], [1.0]]);
+    let rotated = rotation_z(std::f64::consts::FRAC_PI_2) * point;
+    println!("rotated = ({:.1}, {:.1})", rotated[(0, 0)], rotated[(1, 0)]);
+
+    let fib = Matrix::<2, 2>::from_rows([[1.0, 1.0], [1.0, 0.0]]).pow(10);
+    println!("fib(10) = {}, trace = {}", fib[(0, 1)], fib.trace());
+
+    let mut m = Mat3::identity() + Mat3::zeros().map(|_| 0.5);
+    m[(2, 0)] = -1.0;
+    println!("{}x{} column 0: {:?}", Mat3::ROWS, Mat3::COLS, m.column(0).collect::<Vec<_>>());
+}
@@ -0,0 +1,147 @@
+//! std::mem Sample
+//! Demonstrates swap/replace/take, size_of/align_of, and state transitions
+
+use std::collections::VecDeque;
+use std::mem::{self, ManuallyDrop, MaybeUninit};
+
+#[derive(Debug, Default)]
+pub enum Upload {
+    #[default]
+    Idle,
+    Buffering(Vec<u8>),
+    Sending { sent: usize, payload: Vec<u8> },
+    Done(usize),
+}
+
+impl Upload {
+    pub fn push(&mut self, chunk: &[u8]) {
+        match self {
+            Upload::Idle => *self = Upload::Buffering(chunk.to_vec()),
+            Upload::Buffering(buf) => buf.extend_from_slice(chunk),
+            _ => panic!("cannot push while {:?}", mem::discriminant(self)),
+        }
+    }
+
+    // mem::take moves the payload out without cloning it
+    pub fn start(&mut self) {
+        if let Upload::Buffering(buf) = self {
+            let payload = mem::take(buf);
+            *self = Upload::Sending { sent: 0, payload };
+        }
+    }
+
+    pub fn advance(&mut self, n: usize) {
+        // mem::replace returns the old state so it can be consumed by value
+        let state = mem::replace(self, Upload::Idle);
+        *self = match state {
+            Upload::Sending { sent, payload } if sent + n >= payload.len() => Upload::Done(payload.len()),
+            Upload::Sending { sent, payload } => Upload::Sending { sent: sent + n, payload },
+            other => other,
+        };
+    }
+}
+
+pub struct DoubleBuffer<T> {
+    front: Vec<T>,
+    back: Vec<T>,
+}
+
+impl<T> DoubleBuffer<T> {
+    pub fn new() -> Self {
+        Self { front: Vec::new(), back: Vec::new() }
+    }
+
+    pub fn write(&mut self, value: T) {
+        self.back.push(value);
+    }
+
+    // Swap the two buffers in place and hand out the new front
+    pub fn flip(&mut self) -> &[T] {
+        mem::swap(&mut self.front, &mut self.back);
+        self.back.clear();
+        &self.front
+    }
+}
+
+impl<T> Default for DoubleBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[repr(C)]
+struct Padded {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+#[repr(C, packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+    c: u16,
+}
+
+#[repr(align(64))]
+struct CacheLine([u8; 8]);
+
+macro_rules! layout {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            println!(
+                "{:<24} size={:>3} align={:>2}",
+                stringify!($ty),
+                mem::size_of::<$ty>(),
+                mem::align_of::<$ty>(),
+            );
+        )+
+    };
+}
+
+pub fn demo() {
+    layout!(u8, u64, usize, char, &str, String, Vec<u8>, Option<Box<u8>>, Option<u32>);
+    layout!(Padded, Packed, CacheLine, Upload, (), [u16; 3]);
+
+    assert_eq!(mem::size_of::<Option<&u8>>(), mem::size_of::<&u8>());
+    assert!(mem::align_of_val(&CacheLine([0; 8])) >= 64);
+    println!("size_of_val(\"zenn\") = {}", mem::size_of_val("zenn"));
+
+    let mut upload = Upload::default();
+    upload.push(b"hello ");
+    upload.push(b"world");
+    upload.start();
+    upload.advance(4);
+    upload.advance(100);
+    println!("{upload:?}");
+
+    let mut frames = DoubleBuffer::new();
+    frames.write("frame 1");
+    frames.write("frame 2");
+    println!("front = {:?}", frames.flip());
+
+    let mut a = String::from("left");
+    let mut b = String::from("right");
+    mem::swap(&mut a, &mut b);
+    let old = mem::replace(&mut a, "new".into());
+    let taken = mem::take(&mut b);
+    println!("{a:?} {b:?} {old:?} {taken:?}");
+
+    let mut queue: VecDeque<u32> = (1..=5).collect();
+    let drained = mem::take(&mut queue);
+    println!("{} drained, {} left", drained.len(), queue.len());
+
+    // Explicit drop control
+    let guard = ManuallyDrop::new(vec![1, 2, 3]);
+    println!("kept alive: {:?}", *guard);
+    let logger = String::from("flush me");
+    mem::drop(logger);
+    mem::forget(String::from("leaked on purpose"));
+
+    let mut slots: [MaybeUninit<u64>; 4] = [const { MaybeUninit::uninit() }; 4];
+    for (i, slot) in slots.iter_mut().enumerate() {
+        slot.write(1 << (i * 8));
+    }
+    let values: [u64; 4] = unsafe { mem::transmute(slots) };
+    println!("{values:x?} zeroed={}", unsafe { mem::zeroed::<u32>() });
+}
@@ -0,0 +1,83 @@
+//! Nested Generics Sample
+//! Demonstrates deeply nested generic types, closures returning closures, and combinator chains
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+type Handler<T> = Box<dyn Fn(&T) -> Result<Option<Vec<(String, T)>>, String> + Send + Sync>;
+type Registry<K, V> = Arc<Mutex<HashMap<K, Vec<Box<dyn Fn(V) -> Option<V> + Send>>>>>;
+
+pub struct Pipeline<T: Clone> {
+    stages: Vec<(String, Handler<T>)>,
+    cache: BTreeMap<String, Arc<Mutex<Option<Vec<T>>>>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Pipeline<T> {
+    pub fn new() -> Self {
+        Self { stages: Vec::new(), cache: BTreeMap::new() }
+    }
+
+    pub fn stage(mut self, name: &str, handler: impl Fn(&T) -> Result<Option<Vec<(String, T)>>, String> + Send + Sync + 'static) -> Self {
+        self.stages.push((name.to_owned(), Box::new(handler)));
+        self
+    }
+
+    pub fn run(&self, input: &T) -> Result<Vec<(String, T)>, String> {
+        self.stages.iter().try_fold(Vec::new(), |mut acc, (name, handler)| {
+            match handler(input)? {
+                Some(items) => acc.extend(items.into_iter().map(|(k, v)| (format!("{name}/{k}"), v))),
+                None => {}
+            }
+            Ok(acc)
+        })
+    }
+}
+
+// A closure that returns a closure that returns a closure
+pub fn adder() -> impl Fn(i32) -> Box<dyn Fn(i32) -> Box<dyn Fn(i32) -> i32>> {
+    |a| Box::new(move |b| Box::new(move |c| a + b + c))
+}
+
+pub fn compose<A, B, C>(f: impl Fn(A) -> B, g: impl Fn(B) -> C) -> impl Fn(A) -> C {
+    move |x| g(f(x))
+}
+
+pub fn group_scores(rows: &[(&str, &[(u32, Option<f64>)])]) -> HashMap<String, Vec<(u32, f64)>> {
+    rows.iter()
+        .map(|(name, scores)| {
+            let kept: Vec<(u32, f64)> = scores
+                .iter()
+                .filter_map(|(id, score)| score.map(|s| (*id, (s * 100.0).round() / 100.0)))
+                .filter(|(_, s)| s.is_finite() && (*s >= 0.0))
+                .collect();
+            ((*name).to_owned(), kept)
+        })
+        .filter(|(_, kept)| !kept.is_empty())
+        .collect()
+}
+
+pub fn register<K: std::hash::Hash + Eq, V: 'static>(registry: &Registry<K, V>, key: K, f: impl Fn(V) -> Option<V> + Send + 'static) {
+    registry.lock().unwrap().entry(key).or_insert_with(Vec::new).push(Box::new(f));
+}
+
+pub fn demo() -> Result<(), String> {
+    let pipeline = Pipeline::<Vec<Option<(u8, [i16; 2])>>>::new()
+        .stage("pairs", |rows| Ok(Some(rows.iter().flatten().map(|(k, [a, b])| (k.to_string(), vec![Some((*k, [*b, *a]))])).collect())))
+        .stage("empty", |_| Ok(None));
+    let out = pipeline.run(&vec![Some((1, [2, 3])), None, Some((4, [5, 6]))])?;
+    println!("{out:?}");
+
+    let sum = adder()(1)(2)(3);
+    let shout = compose(|s: &str| s.trim().to_owned(), |s: String| format!("{}!", s.to_uppercase()));
+    println!("{sum} {}", shout("  zenn  "));
+
+    let grouped = group_scores(&[("alice", &[(1, Some(0.914)), (2, None)]), ("bob", &[(3, Some(f64::NAN))])]);
+    println!("{:?}", grouped.get("alice").map(|v| v.iter().map(|(id, s)| (id, s)).collect::<Vec<_>>()));
+
+    let registry: Registry<&str, i64> = Arc::new(Mutex::new(HashMap::new()));
+    register(&registry, "double", |x| x.checked_mul(2));
+    register(&registry, "double", |x| (x > 0).then(|| x - 1));
+    let results: Vec<Option<i64>> = registry.lock().unwrap()["double"].iter().map(|f| f(21)).collect();
+    println!("{results:?}");
+    Ok(())
+}
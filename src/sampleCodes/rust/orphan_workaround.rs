@@ -0,0 +1,140 @@
+//! Orphan Rule Workaround Sample
+//! Demonstrates local newtypes over foreign types with Deref forwarding
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+
+use semver::Version;
+use serde::ser::{SerializeSeq, Serializer};
+use serde::Serialize;
+use url::Url;
+
+// `impl fmt::Display for Vec<Url>` is rejected: neither the trait nor the type is local.
+// Wrapping the foreign type in a local newtype makes the impl legal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mirrors(Vec<Url>);
+
+impl Mirrors {
+    pub fn new(urls: impl IntoIterator<Item = Url>) -> Self {
+        Self(urls.into_iter().collect())
+    }
+
+    pub fn into_inner(self) -> Vec<Url> {
+        self.0
+    }
+}
+
+impl Deref for Mirrors {
+    type Target = Vec<Url>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Mirrors {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl fmt::Display for Mirrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for url in &self.0 {
+            if !first {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", url.host_str().unwrap_or("<no host>"))?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Mirrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for url in &self.0 {
+            seq.serialize_element(url.as_str())?;
+        }
+        seq.end()
+    }
+}
+
+impl FromIterator<Url> for Mirrors {
+    fn from_iter<I: IntoIterator<Item = Url>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'a> IntoIterator for &'a Mirrors {
+    type Item = &'a Url;
+    type IntoIter = std::slice::Iter<'a, Url>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+// Transparent single-field wrapper with a foreign trait impl
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct DisplayVersion(pub Version);
+
+impl fmt::Display for DisplayVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Version { major, minor, patch, ref pre, .. } = self.0;
+        if pre.is_empty() {
+            write!(f, "v{major}.{minor}.{patch}")
+        } else {
+            write!(f, "v{major}.{minor}.{patch} ({pre})")
+        }
+    }
+}
+
+impl From<Version> for DisplayVersion {
+    fn from(version: Version) -> Self {
+        Self(version)
+    }
+}
+
+// Wrapping a foreign type to implement a foreign trait with a different meaning
+pub struct PrettyPath<'a>(pub &'a PathBuf);
+
+impl fmt::Display for PrettyPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match dirs::home_dir().and_then(|home| self.0.strip_prefix(home).ok().map(PathBuf::from)) {
+            Some(relative) => write!(f, "~/{}", relative.display()),
+            None => write!(f, "{}", self.0.display()),
+        }
+    }
+}
+
+pub fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    let mut mirrors: Mirrors = ["https://ftp.jaist.ac.jp/pub/", "https://mirrors.kernel.org/"]
+        .iter()
+        .map(|s| Url::parse(s))
+        .collect::<Result<_, _>>()?;
+
+    // Vec methods are reachable through DerefMut
+    mirrors.push(Url::parse("https://mirror.example.com/")?);
+    mirrors.retain(|url| url.scheme() == "https");
+    println!("mirrors: {} ({} total)", mirrors, mirrors.len());
+    println!("{}", serde_json::to_string_pretty(&mirrors)?);
+
+    let mut versions: Vec<DisplayVersion> = ["1.2.3", "1.10.0", "2.0.0-rc.1"]
+        .into_iter()
+        .map(Version::parse)
+        .map(|v| v.map(DisplayVersion::from))
+        .collect::<Result<_, _>>()?;
+    versions.sort();
+    for version in &versions {
+        println!("{version}");
+    }
+
+    let config = dirs::home_dir().unwrap_or_default().join(".config/zenn/theme.json");
+    println!("config at {}", PrettyPath(&config));
+    Ok(())
+}
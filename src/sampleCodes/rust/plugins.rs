@@ -0,0 +1,173 @@
+//! Plugin Architecture Sample
+//! Demonstrates trait objects, TypeId registries, and Any downcasting
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+pub trait Plugin: Any + Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    fn on_load(&mut self, _ctx: &mut Context) -> Result<(), PluginError> {
+        Ok(())
+    }
+
+    fn on_render(&self, input: &str) -> Option<String>;
+
+    // Upcast helpers so callers can downcast to concrete plugin types
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+#[derive(Debug)]
+pub enum PluginError {
+    AlreadyRegistered(&'static str),
+    MissingDependency { plugin: &'static str, needs: &'static str },
+}
+
+#[derive(Default)]
+pub struct Context {
+    resources: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Context {
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> Option<T> {
+        self.resources
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast::<T>().ok())
+            .map(|boxed| *boxed)
+    }
+
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.resources
+            .get(&TypeId::of::<T>())
+            .and_then(|boxed| boxed.downcast_ref::<T>())
+    }
+}
+
+#[derive(Default)]
+pub struct Registry {
+    plugins: Vec<Box<dyn Plugin>>,
+    by_type: HashMap<TypeId, usize>,
+    context: Context,
+}
+
+impl Registry {
+    pub fn register<P: Plugin>(&mut self, mut plugin: P) -> Result<&mut Self, PluginError> {
+        let id = TypeId::of::<P>();
+        if self.by_type.contains_key(&id) {
+            return Err(PluginError::AlreadyRegistered(plugin.name()));
+        }
+
+        plugin.on_load(&mut self.context)?;
+        self.by_type.insert(id, self.plugins.len());
+        self.plugins.push(Box::new(plugin));
+        Ok(self)
+    }
+
+    pub fn get<P: Plugin>(&self) -> Option<&P> {
+        let index = *self.by_type.get(&TypeId::of::<P>())?;
+        self.plugins[index].as_any().downcast_ref::<P>()
+    }
+
+    pub fn get_mut<P: Plugin>(&mut self) -> Option<&mut P> {
+        let index = *self.by_type.get(&TypeId::of::<P>())?;
+        self.plugins[index].as_any_mut().downcast_mut::<P>()
+    }
+
+    pub fn render(&self, input: &str) -> String {
+        self.plugins
+            .iter()
+            .fold(input.to_owned(), |acc, plugin| plugin.on_render(&acc).unwrap_or(acc))
+    }
+}
+
+// Concrete plugins
+#[derive(Debug, Default)]
+pub struct Emoji {
+    replaced: usize,
+}
+
+#[derive(Debug)]
+pub struct Footnotes {
+    prefix: String,
+}
+
+macro_rules! impl_any {
+    () => {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    };
+}
+
+impl Plugin for Emoji {
+    fn name(&self) -> &'static str {
+        "emoji"
+    }
+
+    fn on_render(&self, input: &str) -> Option<String> {
+        input.contains(":tada:").then(|| input.replace(":tada:", "🎉"))
+    }
+
+    impl_any!();
+}
+
+impl Plugin for Footnotes {
+    fn name(&self) -> &'static str {
+        "footnotes"
+    }
+
+    fn on_load(&mut self, ctx: &mut Context) -> Result<(), PluginError> {
+        match ctx.get::<String>() {
+            Some(prefix) => {
+                self.prefix = prefix.clone();
+                Ok(())
+            }
+            None => Err(PluginError::MissingDependency { plugin: "footnotes", needs: "String" }),
+        }
+    }
+
+    fn on_render(&self, input: &str) -> Option<String> {
+        Some(format!("{input}\n\n{}", self.prefix))
+    }
+
+    impl_any!();
+}
+
+fn describe(value: &dyn Any) -> String {
+    if let Some(n) = value.downcast_ref::<i32>() {
+        format!("i32 = {n}")
+    } else if let Some(s) = value.downcast_ref::<&str>() {
+        format!("str = {s:?}")
+    } else if value.is::<Emoji>() {
+        "an Emoji plugin".to_owned()
+    } else {
+        format!("unknown type {:?}", value.type_id())
+    }
+}
+
+pub fn demo() -> Result<(), PluginError> {
+    let mut registry = Registry::default();
+    registry.context.insert(String::from("---"));
+    registry
+        .register(Emoji::default())?
+        .register(Footnotes { prefix: String::new() })?;
+
+    if let Some(emoji) = registry.get_mut::<Emoji>() {
+        emoji.replaced += 1;
+    }
+
+    println!("{}", registry.render("Release :tada:"));
+    println!("{}", describe(&42_i32));
+    println!("{}", describe(registry.get::<Emoji>().unwrap()));
+
+    let boxed: Box<dyn Any + Send> = Box::new(Footnotes { prefix: "*".into() });
+    match boxed.downcast::<Footnotes>() {
+        Ok(footnotes) => println!("got {:?}", footnotes),
+        Err(other) => println!("not footnotes: {:?}", other.type_id()),
+    }
+    Ok(())
+}
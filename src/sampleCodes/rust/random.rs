@@ -0,0 +1,92 @@
+//! Randomness Sample
+//! Demonstrates uuid generation/parsing and rand RNG usage
+
+use rand::distributions::{Alphanumeric, Distribution, Uniform, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::{thread_rng, Rng, SeedableRng};
+use uuid::{uuid, Uuid, Version};
+
+const NAMESPACE_ZENN: Uuid = uuid!("6ba7b811-9dad-11d1-80b4-00c04fd430c8");
+const SEED: u64 = 0x5EED_2024;
+
+#[derive(Debug)]
+pub struct Article {
+    pub id: Uuid,
+    pub slug: String,
+    pub likes: u32,
+}
+
+// Zenn slugs are 14 lowercase alphanumeric characters
+pub fn random_slug<R: Rng + ?Sized>(rng: &mut R) -> String {
+    rng.sample_iter(&Alphanumeric)
+        .take(14)
+        .map(|b| char::from(b).to_ascii_lowercase())
+        .collect()
+}
+
+pub fn stable_id(slug: &str) -> Uuid {
+    Uuid::new_v5(&NAMESPACE_ZENN, slug.as_bytes())
+}
+
+pub fn parse_ids(inputs: &[&str]) -> Vec<Result<Uuid, uuid::Error>> {
+    inputs.iter().map(|s| Uuid::parse_str(s)).collect()
+}
+
+pub fn demo() {
+    // UUIDs
+    let id = Uuid::new_v4();
+    println!("v4       {}", id);
+    println!("hyphen   {}", id.hyphenated());
+    println!("simple   {}", id.simple());
+    println!("urn      {}", id.urn());
+    println!("braced   {}", id.braced());
+    println!("version  {:?}", id.get_version());
+    assert_eq!(id.get_version(), Some(Version::Random));
+
+    for result in parse_ids(&["67e55044-10b1-426f-9247-bb680e5fe0c8", "not-a-uuid", "{01234567-89AB-CDEF-0123-456789ABCDEF}"]) {
+        match result {
+            Ok(uuid) => println!("ok   {uuid} bytes={:02x?}", &uuid.as_bytes()[..4]),
+            Err(err) => println!("err  {err}"),
+        }
+    }
+
+    // Thread-local RNG for quick values
+    let mut rng = thread_rng();
+    let dice: u8 = rng.gen_range(1..=6);
+    let percent = rng.gen_range(0..100);
+    let coin: bool = rng.gen_bool(0.5);
+    let unit: f64 = rng.gen();
+    println!("dice={dice} percent={percent} coin={coin} unit={unit:.3}");
+
+    // Shuffling and sampling
+    let mut tags = vec!["rust", "shiki", "zenn", "theme", "wasm"];
+    tags.shuffle(&mut rng);
+    let picked: Vec<_> = tags.choose_multiple(&mut rng, 2).collect();
+    let any_lang = ["ts", "go", "py"].iter().choose(&mut rng);
+    println!("{tags:?} {picked:?} {any_lang:?}");
+
+    // Deterministic RNG for reproducible fixtures
+    let mut seeded = StdRng::seed_from_u64(SEED);
+    let articles: Vec<Article> = (0..3)
+        .map(|_| {
+            let slug = random_slug(&mut seeded);
+            Article { id: stable_id(&slug), slug, likes: seeded.gen_range(0..500) }
+        })
+        .collect();
+    for Article { id, slug, likes } in &articles {
+        println!("{slug} {id} ♥{likes}");
+    }
+
+    // Distributions
+    let latency = Uniform::new_inclusive(20u32, 250);
+    let samples: Vec<u32> = latency.sample_iter(&mut seeded).take(5).collect();
+    let weights = [70, 20, 10];
+    let status = WeightedIndex::new(weights).expect("weights are positive");
+    let codes = [200, 404, 500];
+    let drawn: Vec<u16> = (0..8).map(|_| codes[status.sample(&mut seeded)]).collect();
+    println!("latency={samples:?} status={drawn:?}");
+
+    let mut again = StdRng::seed_from_u64(SEED);
+    assert_eq!(random_slug(&mut again), articles[0].slug);
+}
@@ -0,0 +1,147 @@
+//! Retry Sample
+//! Demonstrates a generic retry helper with exponential backoff and jitter
+
+use std::fmt::Display;
+use std::thread::sleep;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub multiplier: f64,
+    pub jitter: f64,
+    pub max_attempts: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_attempts: 5,
+        }
+    }
+}
+
+impl Backoff {
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let exp = self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        let base = self.initial.mul_f64(exp).min(self.max);
+        let spread = base.as_secs_f64() * self.jitter;
+        let offset = (pseudo_random() * 2.0 - 1.0) * spread;
+        Duration::from_secs_f64((base.as_secs_f64() + offset).max(0.0))
+    }
+}
+
+fn pseudo_random() -> f64 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().subsec_nanos();
+    (nanos.wrapping_mul(2_654_435_761) % 10_000) as f64 / 10_000.0
+}
+
+#[derive(Debug)]
+pub enum RetryError<E> {
+    Exhausted { attempts: u32, last: E, elapsed: Duration },
+    Permanent(E),
+}
+
+impl<E: Display> Display for RetryError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exhausted { attempts, last, elapsed } => {
+                write!(f, "gave up after {attempts} attempts in {elapsed:?}: {last}")
+            }
+            Self::Permanent(err) => write!(f, "permanent failure: {err}"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for RetryError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Exhausted { last, .. } | Self::Permanent(last) => Some(last),
+        }
+    }
+}
+
+pub fn retry<T, E, F, P>(policy: &Backoff, mut op: F, mut is_retryable: P) -> Result<T, RetryError<E>>
+where
+    F: FnMut(u32) -> Result<T, E>,
+    P: FnMut(&E) -> bool,
+{
+    let start = Instant::now();
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        match op(attempt) {
+            Ok(value) => return Ok(value),
+            Err(err) if !is_retryable(&err) => return Err(RetryError::Permanent(err)),
+            Err(err) if attempt >= policy.max_attempts => {
+                return Err(RetryError::Exhausted { attempts: attempt, last: err, elapsed: start.elapsed() });
+            }
+            Err(_) => sleep(policy.delay_for(attempt)),
+        }
+    }
+}
+
+// Convenience wrapper: retry every error with the default policy
+pub fn retry_all<T, E>(op: impl FnMut() -> Result<T, E>) -> Result<T, RetryError<E>> {
+    let mut op = op;
+    retry(&Backoff::default(), |_| op(), |_| true)
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    Timeout,
+    Unavailable(u16),
+    BadRequest(String),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Timeout => f.write_str("timed out"),
+            FetchError::Unavailable(code) => write!(f, "service unavailable ({code})"),
+            FetchError::BadRequest(msg) => write!(f, "bad request: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+pub fn demo() {
+    let policy = Backoff { initial: Duration::from_millis(10), max_attempts: 4, ..Default::default() };
+    for attempt in 1..=policy.max_attempts {
+        println!("attempt {attempt}: wait ~{:?}", policy.delay_for(attempt));
+    }
+
+    let result = retry(
+        &policy,
+        |attempt| match attempt {
+            1 => Err(FetchError::Timeout),
+            2 => Err(FetchError::Unavailable(503)),
+            n => Ok(format!("succeeded on attempt {n}")),
+        },
+        |err| matches!(err, FetchError::Timeout | FetchError::Unavailable(500..=599)),
+    );
+    println!("{result:?}");
+
+    let permanent: Result<(), _> = retry(
+        &policy,
+        |_| Err(FetchError::BadRequest("missing slug".into())),
+        |err| !matches!(err, FetchError::BadRequest(_)),
+    );
+    if let Err(err) = permanent {
+        println!("{err}");
+    }
+
+    let mut calls = 0;
+    let flaky = retry_all(|| {
+        calls += 1;
+        if calls < 3 { Err("flaky") } else { Ok(calls) }
+    });
+    println!("{flaky:?} after {calls} calls");
+}
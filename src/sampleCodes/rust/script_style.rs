@@ -0,0 +1,78 @@
+#!/usr/bin/env -S cargo +nightly -Zscript
+---cargo
+[package]
+edition = "2024"
+
+[dependencies]
+clap = { version = "4", features = ["derive"] }
+walkdir = "2"
+---
+//! Single-file cargo script that counts fenced code blocks in Zenn articles.
+#![doc = include_str!("../README.md")]
+#![allow(clippy::needless_return, dead_code)]
+#![warn(missing_docs, unreachable_pub)]
+#![deny(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::Parser;
+use walkdir::WalkDir;
+
+/// Command-line arguments.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    /// Directory containing `articles/*.md`.
+    #[arg(default_value = "articles")]
+    root: PathBuf,
+
+    /// Only report languages with at least this many blocks.
+    #[arg(short, long, default_value_t = 1)]
+    min: usize,
+}
+
+/// Extracts the language tag from a fence line such as ```` ```rust:main.rs ````.
+fn fence_lang(line: &str) -> Option<&str> {
+    let info = line.trim_start().strip_prefix("```")?;
+    let lang = info.split([':', ' ']).next()?;
+    (!lang.is_empty()).then_some(lang)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+
+    for entry in WalkDir::new(&args.root).into_iter().filter_map(Result::ok) {
+        if entry.path().extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+
+        let Ok(text) = std::fs::read_to_string(entry.path()) else {
+            eprintln!("warning: could not read {}", entry.path().display());
+            continue;
+        };
+
+        let mut inside = false;
+        for line in text.lines() {
+            if line.trim_start().starts_with("```") {
+                if !inside && let Some(lang) = fence_lang(line) {
+                    *counts.entry(lang.to_owned()).or_default() += 1;
+                }
+                inside = !inside;
+            }
+        }
+    }
+
+    if counts.is_empty() {
+        eprintln!("no code blocks found under {}", args.root.display());
+        return ExitCode::FAILURE;
+    }
+
+    for (lang, n) in counts.iter().filter(|(_, n)| **n >= args.min) {
+        println!("{lang:<12} {n:>5}");
+    }
+    ExitCode::SUCCESS
+}
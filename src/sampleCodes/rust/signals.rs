@@ -0,0 +1,129 @@
+//! Signal Handling Sample
+//! Demonstrates ctrl-c handlers, shutdown flags, and cleanup on exit
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+static SIGNALS_SEEN: AtomicUsize = AtomicUsize::new(0);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Removes the lock file even when the loop exits early
+struct LockFile {
+    path: PathBuf,
+}
+
+impl LockFile {
+    fn acquire(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        File::options().write(true).create_new(true).open(&path)?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            eprintln!("warning: failed to remove {}: {err}", self.path.display());
+        }
+    }
+}
+
+pub fn install_handler() -> Result<(), ctrlc::Error> {
+    ctrlc::set_handler(|| {
+        let count = SIGNALS_SEEN.fetch_add(1, Ordering::SeqCst) + 1;
+        if count >= 2 {
+            eprintln!("\nforced exit");
+            std::process::exit(130);
+        }
+        eprintln!("\nshutting down… (press Ctrl-C again to force)");
+        SHUTDOWN.store(true, Ordering::SeqCst);
+    })
+}
+
+pub fn run_watcher(out: &Path) -> io::Result<usize> {
+    let _lock = LockFile::acquire(out.with_extension("lock"))?;
+    let mut writer = BufWriter::new(File::create(out)?);
+    let mut ticks = 0;
+
+    while !SHUTDOWN.load(Ordering::SeqCst) {
+        ticks += 1;
+        writeln!(writer, "tick {ticks}")?;
+        if ticks % 10 == 0 {
+            writer.flush()?;
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    // Cleanup: flush buffered output before the lock guard drops
+    writer.flush()?;
+    println!("wrote {ticks} ticks to {}", out.display());
+    Ok(ticks)
+}
+
+// Shared-flag variant for worker threads
+pub fn run_workers(count: usize) -> Vec<usize> {
+    let running = Arc::new(AtomicBool::new(true));
+    {
+        let running = Arc::clone(&running);
+        ctrlc::set_handler(move || running.store(false, Ordering::Release))
+            .expect("handler can only be installed once");
+    }
+
+    let handles: Vec<_> = (0..count)
+        .map(|id| {
+            let running = Arc::clone(&running);
+            thread::Builder::new()
+                .name(format!("worker-{id}"))
+                .spawn(move || {
+                    let mut processed = 0;
+                    while running.load(Ordering::Acquire) {
+                        processed += 1;
+                        thread::sleep(POLL_INTERVAL * (id as u32 + 1));
+                    }
+                    processed
+                })
+                .expect("failed to spawn worker")
+        })
+        .collect();
+
+    handles.into_iter().map(|h| h.join().unwrap_or(0)).collect()
+}
+
+// Async variant with tokio::signal
+pub async fn serve_until_signal() -> io::Result<()> {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+    #[cfg(unix)]
+    let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+
+    loop {
+        #[cfg(unix)]
+        tokio::select! {
+            _ = interval.tick() => println!("still serving"),
+            _ = tokio::signal::ctrl_c() => break,
+            _ = terminate.recv() => break,
+        }
+
+        #[cfg(not(unix))]
+        tokio::select! {
+            _ = interval.tick() => println!("still serving"),
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    println!("graceful shutdown complete");
+    Ok(())
+}
+
+pub fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    install_handler()?;
+    let ticks = run_watcher(Path::new("watch.log"))?;
+    std::process::exit(if ticks > 0 { 0 } else { 1 });
+}
@@ -0,0 +1,132 @@
+//! Slices and Arrays Sample
+//! Demonstrates array patterns, conversions, and multi-dimensional indexing
+
+use std::array::TryFromSliceError;
+
+const BLOCK: usize = 1024;
+const GRID_W: usize = 8;
+const GRID_H: usize = 4;
+
+pub type Grid = [[u8; GRID_W]; GRID_H];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacketHeader {
+    pub magic: [u8; 4],
+    pub version: u8,
+    pub flags: u8,
+    pub length: u16,
+}
+
+impl TryFrom<&[u8]> for PacketHeader {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let head: &[u8; 8] = bytes.get(..8).unwrap_or(&[]).try_into()?;
+        let [m0, m1, m2, m3, version, flags, len_hi, len_lo] = *head;
+        Ok(Self {
+            magic: [m0, m1, m2, m3],
+            version,
+            flags,
+            length: u16::from_be_bytes([len_hi, len_lo]),
+        })
+    }
+}
+
+// Slice patterns
+pub fn describe(values: &[i32]) -> String {
+    match values {
+        [] => "empty".into(),
+        [one] => format!("one: {one}"),
+        [first, second] => format!("pair: {first}, {second}"),
+        [first, .., last] if first == last => format!("bookended by {first}"),
+        [first, middle @ .., last] => format!("{first}..{last} with {} inside", middle.len()),
+    }
+}
+
+pub fn parse_command(args: &[&str]) -> Option<(&'static str, usize)> {
+    match args {
+        ["new", name] => Some(("new", name.len())),
+        ["preview", rest @ ..] if !rest.is_empty() => Some(("preview", rest.len())),
+        [cmd, ..] if cmd.starts_with('-') => None,
+        _ => None,
+    }
+}
+
+pub fn checksum(data: &[u8]) -> [u8; 4] {
+    let mut acc = [0u8; 4];
+    for chunk in data.chunks(4) {
+        for (slot, byte) in acc.iter_mut().zip(chunk) {
+            *slot ^= byte;
+        }
+    }
+    acc
+}
+
+pub fn blur(grid: &Grid) -> Grid {
+    let mut out: Grid = [[0; GRID_W]; GRID_H];
+    for y in 0..GRID_H {
+        for x in 0..GRID_W {
+            let mut sum = 0u32;
+            let mut n = 0u32;
+            for dy in [-1i32, 0, 1] {
+                for dx in [-1i32, 0, 1] {
+                    let (ny, nx) = (y as i32 + dy, x as i32 + dx);
+                    if (0..GRID_H as i32).contains(&ny) && (0..GRID_W as i32).contains(&nx) {
+                        sum += grid[ny as usize][nx as usize] as u32;
+                        n += 1;
+                    }
+                }
+            }
+            out[y][x] = (sum / n) as u8;
+        }
+    }
+    out
+}
+
+pub fn demo() -> Result<(), TryFromSliceError> {
+    // Repeat expressions and array::map
+    let mut buffer = [0u8; BLOCK];
+    buffer[..4].copy_from_slice(b"ZENN");
+    buffer[4..8].copy_from_slice(&[1, 0b0000_0011, 0x01, 0x00]);
+    let squares: [u32; 6] = [1, 2, 3, 4, 5, 6].map(|n| n * n);
+    let names = ["rust", "go", "zig"].map(str::to_uppercase);
+
+    let header = PacketHeader::try_from(&buffer[..])?;
+    println!("{header:?} {squares:?} {names:?}");
+
+    // split_at / split_first / split_last / split_once-style helpers
+    let (head, tail) = buffer.split_at(8);
+    let (&first, rest) = head.split_first().expect("header is non-empty");
+    if let Some((last, _)) = tail.split_last() {
+        println!("first={first:#04x} rest={} last={last}", rest.len());
+    }
+
+    // try_into for slice -> fixed array conversions
+    let magic: [u8; 4] = buffer[0..4].try_into()?;
+    let word = u32::from_le_bytes(buffer[4..8].try_into()?);
+    println!("{:?} {word:#010x} {:?}", std::str::from_utf8(&magic), checksum(&buffer[..16]));
+
+    // Two-dimensional arrays
+    let mut grid: Grid = [[0; GRID_W]; GRID_H];
+    grid[1][3] = 255;
+    grid[GRID_H - 1][GRID_W - 1] = 90;
+    for row in blur(&grid).iter() {
+        let line: Vec<String> = row.iter().map(|v| format!("{v:3}")).collect();
+        println!("{}", line.join(""));
+    }
+
+    let flat: Vec<u8> = grid.as_flattened().to_vec();
+    let column: Vec<u8> = grid.iter().map(|row| row[3]).collect();
+    println!("{} cells, column 3 = {column:?}", flat.len());
+
+    println!("{}", describe(&[1, 2, 3, 1]));
+    println!("{}", describe(&[4, 8, 15, 16, 23, 42]));
+    println!("{:?}", parse_command(&["preview", "rust", "go"]));
+
+    let mut scores = [72, 95, 88, 61, 99];
+    scores.sort_unstable_by(|a, b| b.cmp(a));
+    let [best, second, ..] = scores;
+    scores.rotate_left(2);
+    println!("{best} {second} {scores:?} {:?}", scores.iter().position(|&s| s == 88));
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Async State Machine Sample
+//! Demonstrates an enum-driven connection lifecycle with timeouts
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{sleep, timeout};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_RECONNECTS: u8 = 3;
+
+#[derive(Debug)]
+pub enum Connection {
+    Disconnected { attempts: u8 },
+    Connecting { addr: SocketAddr, since: Instant, attempts: u8 },
+    Handshaking { stream: TcpStream },
+    Ready { stream: TcpStream, session_id: u64 },
+    Backoff { until: Instant, attempts: u8 },
+    Closed { reason: CloseReason },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CloseReason {
+    Requested,
+    IdleTimeout,
+    TooManyFailures(u8),
+    Protocol(String),
+}
+
+impl fmt::Display for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Disconnected { .. } => "disconnected",
+            Self::Connecting { .. } => "connecting",
+            Self::Handshaking { .. } => "handshaking",
+            Self::Ready { .. } => "ready",
+            Self::Backoff { .. } => "backoff",
+            Self::Closed { .. } => "closed",
+        };
+        f.write_str(name)
+    }
+}
+
+impl Connection {
+    // Each transition consumes the current state and returns the next one
+    pub async fn step(self, addr: SocketAddr) -> Connection {
+        match self {
+            Connection::Disconnected { attempts } if attempts >= MAX_RECONNECTS => {
+                Connection::Closed { reason: CloseReason::TooManyFailures(attempts) }
+            }
+            Connection::Disconnected { attempts } => Connection::Connecting {
+                addr,
+                since: Instant::now(),
+                attempts,
+            },
+
+            Connection::Connecting { addr, since, attempts } => {
+                match timeout(CONNECT_TIMEOUT, TcpStream::connect(addr)).await {
+                    Ok(Ok(stream)) => {
+                        tracing::info!(elapsed = ?since.elapsed(), "tcp connected");
+                        Connection::Handshaking { stream }
+                    }
+                    Ok(Err(err)) => {
+                        tracing::warn!(%err, "connect failed");
+                        Connection::backoff(attempts + 1)
+                    }
+                    Err(_elapsed) => Connection::backoff(attempts + 1),
+                }
+            }
+
+            Connection::Handshaking { mut stream } => match Self::handshake(&mut stream).await {
+                Ok(session_id) => Connection::Ready { stream, session_id },
+                Err(reason) => Connection::Closed { reason },
+            },
+
+            Connection::Ready { mut stream, session_id } => {
+                let mut buf = [0u8; 1024];
+                match timeout(IDLE_TIMEOUT, stream.read(&mut buf)).await {
+                    Ok(Ok(0)) => Connection::Disconnected { attempts: 0 },
+                    Ok(Ok(n)) => {
+                        tracing::debug!(session_id, bytes = n, "received frame");
+                        Connection::Ready { stream, session_id }
+                    }
+                    Ok(Err(_)) => Connection::Disconnected { attempts: 1 },
+                    Err(_) => Connection::Closed { reason: CloseReason::IdleTimeout },
+                }
+            }
+
+            Connection::Backoff { until, attempts } => {
+                let now = Instant::now();
+                if until > now {
+                    sleep(until - now).await;
+                }
+                Connection::Disconnected { attempts }
+            }
+
+            closed @ Connection::Closed { .. } => closed,
+        }
+    }
+
+    fn backoff(attempts: u8) -> Connection {
+        let delay = Duration::from_millis(250 * 2u64.pow(attempts as u32));
+        Connection::Backoff { until: Instant::now() + delay, attempts }
+    }
+
+    async fn handshake(stream: &mut TcpStream) -> Result<u64, CloseReason> {
+        stream
+            .write_all(b"HELLO zenn/1\r\n")
+            .await
+            .map_err(|e| CloseReason::Protocol(e.to_string()))?;
+
+        let mut reply = [0u8; 16];
+        let n = stream
+            .read(&mut reply)
+            .await
+            .map_err(|e| CloseReason::Protocol(e.to_string()))?;
+
+        match &reply[..n] {
+            [b'O', b'K', b' ', rest @ ..] => std::str::from_utf8(rest)
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .ok_or_else(|| CloseReason::Protocol("bad session id".into())),
+            other => Err(CloseReason::Protocol(format!("unexpected reply {:?}", other))),
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Connection::Closed { .. })
+    }
+}
+
+pub async fn drive(addr: SocketAddr) -> CloseReason {
+    let mut state = Connection::Disconnected { attempts: 0 };
+    loop {
+        let previous = state.to_string();
+        state = state.step(addr).await;
+        tracing::trace!(from = %previous, to = %state, "transition");
+
+        if let Connection::Closed { reason } = state {
+            break reason;
+        }
+    }
+}
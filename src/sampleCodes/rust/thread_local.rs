@@ -0,0 +1,105 @@
+//! thread_local! Sample
+//! Demonstrates per-thread state with RefCell/Cell interiors
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::thread;
+
+thread_local! {
+    // Per-thread request counter
+    static REQUESTS: Cell<u64> = const { Cell::new(0) };
+
+    // Lazily initialised per-thread cache
+    static CACHE: RefCell<HashMap<String, usize>> = RefCell::new(HashMap::with_capacity(64));
+
+    // Reusable scratch buffer to avoid allocating on every call
+    pub static SCRATCH: RefCell<String> = RefCell::new(String::with_capacity(1024));
+
+    static THREAD_NAME: String = thread::current().name().unwrap_or("<unnamed>").to_owned();
+}
+
+pub fn record_request() -> u64 {
+    REQUESTS.with(|count| {
+        count.set(count.get() + 1);
+        count.get()
+    })
+}
+
+pub fn cached_len(key: &str) -> usize {
+    CACHE.with(|cache| {
+        if let Some(&hit) = cache.borrow().get(key) {
+            return hit;
+        }
+        let computed = key.chars().count();
+        cache.borrow_mut().insert(key.to_owned(), computed);
+        computed
+    })
+}
+
+pub fn render_line(parts: &[&str]) -> String {
+    SCRATCH.with_borrow_mut(|buf| {
+        buf.clear();
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                buf.push_str(" · ");
+            }
+            buf.push_str(part);
+        }
+        buf.clone()
+    })
+}
+
+// Scoped override that restores the previous value on drop
+thread_local! {
+    static INDENT: Cell<usize> = const { Cell::new(0) };
+}
+
+pub struct IndentGuard(usize);
+
+pub fn indent() -> IndentGuard {
+    let previous = INDENT.replace(INDENT.get() + 2);
+    IndentGuard(previous)
+}
+
+impl Drop for IndentGuard {
+    fn drop(&mut self) {
+        INDENT.set(self.0);
+    }
+}
+
+pub fn log(msg: &str) {
+    let pad = INDENT.get();
+    THREAD_NAME.with(|name| println!("[{name}] {:pad$}{msg}", ""));
+}
+
+pub fn demo() {
+    let handles: Vec<_> = ["alpha", "beta", "gamma"]
+        .into_iter()
+        .map(|name| {
+            thread::Builder::new()
+                .name(name.to_owned())
+                .spawn(move || {
+                    log("start");
+                    {
+                        let _g = indent();
+                        for word in ["zenn", "shiki", "zenn", name] {
+                            record_request();
+                            log(&format!("{word} -> {}", cached_len(word)));
+                        }
+                    }
+                    log(&render_line(&["done", name]));
+                    REQUESTS.get()
+                })
+                .unwrap()
+        })
+        .collect();
+
+    let totals: Vec<u64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    println!("per-thread requests: {totals:?}");
+
+    // The main thread has its own, untouched copies
+    assert_eq!(REQUESTS.get(), 0);
+    let cache_size = CACHE.with_borrow(|c| c.len());
+    let try_access = REQUESTS.try_with(|c| c.get()).is_ok();
+    println!("main cache size = {cache_size}, accessible = {try_access}");
+}
@@ -0,0 +1,125 @@
+//! Track Caller Sample
+//! Demonstrates `#[track_caller]` assertion helpers and `Location::caller()`
+
+use std::fmt::Debug;
+use std::panic::Location;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CallSite {
+    pub file: &'static str,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl From<&'static Location<'static>> for CallSite {
+    fn from(location: &'static Location<'static>) -> Self {
+        Self {
+            file: location.file(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
+}
+
+impl std::fmt::Display for CallSite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+// Panics report the caller's location instead of this function's
+#[track_caller]
+pub fn assert_near(actual: f64, expected: f64, epsilon: f64) {
+    let diff = (actual - expected).abs();
+    if diff > epsilon {
+        let caller = Location::caller();
+        panic!(
+            "assertion `actual ≈ expected` failed at {}:{}\n  actual: {actual}\n  expected: {expected} (±{epsilon}, off by {diff:e})",
+            caller.file(),
+            caller.line(),
+        );
+    }
+}
+
+#[track_caller]
+pub fn assert_sorted<T: PartialOrd + Debug>(items: &[T]) {
+    if let Some(i) = items.windows(2).position(|w| w[0] > w[1]) {
+        panic!("slice is not sorted at index {i}: {:?} > {:?}", items[i], items[i + 1]);
+    }
+}
+
+// #[track_caller] propagates through nested annotated functions
+#[track_caller]
+pub fn expect_some<T>(value: Option<T>, what: &str) -> T {
+    match value {
+        Some(v) => v,
+        None => panic!("expected {what} to be present"),
+    }
+}
+
+#[track_caller]
+pub fn here() -> CallSite {
+    Location::caller().into()
+}
+
+// Collects failures instead of panicking on the first one
+#[derive(Default)]
+pub struct SoftAssert {
+    failures: Vec<(CallSite, String)>,
+}
+
+impl SoftAssert {
+    #[track_caller]
+    pub fn check(&mut self, condition: bool, message: impl Into<String>) -> &mut Self {
+        if !condition {
+            self.failures.push((here(), message.into()));
+        }
+        self
+    }
+
+    #[track_caller]
+    pub fn check_eq<T: PartialEq + Debug>(&mut self, left: T, right: T) -> &mut Self {
+        let site = CallSite::from(Location::caller());
+        if left != right {
+            self.failures.push((site, format!("{left:?} != {right:?}")));
+        }
+        self
+    }
+
+    #[track_caller]
+    pub fn finish(self) {
+        if self.failures.is_empty() {
+            return;
+        }
+        let report: Vec<String> = self
+            .failures
+            .iter()
+            .map(|(site, msg)| format!("  - {site}: {msg}"))
+            .collect();
+        panic!("{} soft assertion(s) failed:\n{}", self.failures.len(), report.join("\n"));
+    }
+}
+
+// Closures can be annotated too (feature `closure_track_caller` on nightly)
+pub fn checker() -> impl Fn(bool) {
+    #[cfg_attr(feature = "nightly", track_caller)]
+    |ok: bool| assert!(ok, "checked at {}", Location::caller())
+}
+
+pub fn demo() {
+    println!("demo called from {}", here());
+
+    assert_near(0.1 + 0.2, 0.3, 1e-9);
+    assert_sorted(&[1, 2, 3, 5, 8]);
+
+    let theme = expect_some(std::env::var("THEME").ok().or(Some("zenn".into())), "THEME");
+
+    let mut soft = SoftAssert::default();
+    soft.check(!theme.is_empty(), "theme name must not be empty")
+        .check_eq(theme.len(), 4)
+        .check_eq("#1a2638".len(), 7);
+    soft.finish();
+
+    let result = std::panic::catch_unwind(|| assert_sorted(&["b", "a"]));
+    assert!(result.is_err());
+}
@@ -0,0 +1,159 @@
+//! Trait Methods Sample
+//! Demonstrates generic methods, `where Self: Sized`, and dyn compatibility
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+pub trait Storage {
+    // Required methods: object-safe, usable through `dyn Storage`
+    fn get_raw(&self, key: &str) -> Option<&[u8]>;
+    fn put_raw(&mut self, key: &str, value: Vec<u8>);
+    fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_>;
+
+    // Provided methods built on the required ones
+    fn contains(&self, key: &str) -> bool {
+        self.get_raw(key).is_some()
+    }
+
+    fn len(&self) -> usize {
+        self.keys().count()
+    }
+
+    fn get_str(&self, key: &str) -> Option<&str> {
+        self.get_raw(key).and_then(|bytes| std::str::from_utf8(bytes).ok())
+    }
+
+    // Generic methods would make the trait non-dyn-compatible,
+    // so they opt out of the vtable with `where Self: Sized`
+    fn get<T>(&self, key: &str) -> Option<T>
+    where
+        Self: Sized,
+        T: serde::de::DeserializeOwned,
+    {
+        serde_json::from_slice(self.get_raw(key)?).ok()
+    }
+
+    fn put<T>(&mut self, key: &str, value: &T) -> Result<(), serde_json::Error>
+    where
+        Self: Sized,
+        T: serde::Serialize + ?Sized,
+    {
+        self.put_raw(key, serde_json::to_vec(value)?);
+        Ok(())
+    }
+
+    fn update<F>(&mut self, key: &str, f: F)
+    where
+        Self: Sized,
+        F: FnOnce(Option<&[u8]>) -> Vec<u8>,
+    {
+        let next = f(self.get_raw(key));
+        self.put_raw(key, next);
+    }
+
+    // Consuming `self` by value also requires Sized
+    fn into_sorted(self) -> Vec<(String, Vec<u8>)>
+    where
+        Self: Sized,
+    {
+        let mut pairs: Vec<_> = self
+            .keys()
+            .map(|k| (k.to_owned(), self.get_raw(k).unwrap_or_default().to_vec()))
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
+    fn boxed(self) -> Box<dyn Storage>
+    where
+        Self: Sized + 'static,
+    {
+        Box::new(self)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: BTreeMap<String, Vec<u8>>,
+}
+
+impl Storage for MemoryStorage {
+    fn get_raw(&self, key: &str) -> Option<&[u8]> {
+        self.entries.get(key).map(Vec::as_slice)
+    }
+
+    fn put_raw(&mut self, key: &str, value: Vec<u8>) {
+        self.entries.insert(key.to_owned(), value);
+    }
+
+    fn keys(&self) -> Box<dyn Iterator<Item = &str> + '_> {
+        Box::new(self.entries.keys().map(String::as_str))
+    }
+
+    // Overriding a provided method with a faster version
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+// Extension trait with a blanket impl for every Storage, sized or not
+pub trait StorageExt: Storage {
+    fn dump(&self) -> String {
+        self.keys()
+            .map(|k| format!("{k} = {:?}", self.get_str(k).unwrap_or("<binary>")))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<S: Storage + ?Sized> StorageExt for S {}
+
+// Generic associated function vs. method call syntax
+pub trait Describe {
+    fn type_name() -> &'static str
+    where
+        Self: Sized,
+    {
+        std::any::type_name::<Self>()
+    }
+
+    fn describe(&self) -> String;
+}
+
+impl<T: Debug> Describe for Option<T> {
+    fn describe(&self) -> String {
+        match self {
+            Some(value) => format!("Some({value:?})"),
+            None => "None".into(),
+        }
+    }
+}
+
+fn print_all(stores: &[&dyn Storage]) {
+    for (i, store) in stores.iter().enumerate() {
+        // Only the dyn-compatible part of the trait is callable here
+        println!("store #{i}: {} keys, has 'theme': {}", store.len(), store.contains("theme"));
+    }
+}
+
+pub fn demo() -> Result<(), serde_json::Error> {
+    let mut memory = MemoryStorage::default();
+    memory.put("theme", &"zenn")?;
+    memory.put("tags", &["rust", "shiki"])?;
+    memory.update("visits", |old| {
+        let n = old.and_then(|b| std::str::from_utf8(b).ok()?.parse::<u32>().ok()).unwrap_or(0);
+        (n + 1).to_string().into_bytes()
+    });
+
+    let tags: Option<Vec<String>> = memory.get("tags");
+    println!("{} {}", <Option<Vec<String>> as Describe>::type_name(), tags.describe());
+
+    let other: Box<dyn Storage> = MemoryStorage::default().boxed();
+    print_all(&[&memory, other.as_ref()]);
+    println!("{}", memory.dump());
+
+    for (key, value) in memory.into_sorted() {
+        println!("{key}: {} bytes", value.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//! TUI Sample
+//! Demonstrates a ratatui application with crossterm input handling
+
+use std::io::{self, Stdout};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+
+type Tui = Terminal<CrosstermBackend<Stdout>>;
+
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Default)]
+struct App {
+    tasks: Vec<String>,
+    state: ListState,
+    progress: u16,
+    should_quit: bool,
+}
+
+impl App {
+    fn next(&mut self) {
+        let i = match self.state.selected() {
+            Some(i) if i + 1 < self.tasks.len() => i + 1,
+            _ => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    fn previous(&mut self) {
+        let i = match self.state.selected() {
+            Some(0) | None => self.tasks.len().saturating_sub(1),
+            Some(i) => i - 1,
+        };
+        self.state.select(Some(i));
+    }
+
+    fn on_tick(&mut self) {
+        self.progress = (self.progress + 5) % 100;
+    }
+}
+
+// Terminal setup and the restore-on-exit cleanup pattern
+fn init_terminal() -> io::Result<Tui> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    Terminal::new(CrosstermBackend::new(io::stdout()))
+}
+
+fn restore_terminal(terminal: &mut Tui) -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()
+}
+
+fn ui(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(5),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled("zenn", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::raw(" task board — "),
+        Span::styled("q", Style::default().fg(Color::Yellow)),
+        Span::raw(" to quit"),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(" Help "))
+    .wrap(Wrap { trim: true });
+    frame.render_widget(title, chunks[0]);
+
+    let items: Vec<ListItem> = app
+        .tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| ListItem::new(format!("{:>2}. {}", i + 1, task)))
+        .collect();
+    let list = List::new(items)
+        .block(Block::bordered().title(" Tasks "))
+        .highlight_style(Style::new().black().on_light_cyan())
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, chunks[1], &mut app.state);
+
+    render_gauge(frame, chunks[2], app.progress);
+}
+
+fn render_gauge(frame: &mut Frame, area: Rect, progress: u16) {
+    let gauge = Gauge::default()
+        .block(Block::bordered().title(" Sync "))
+        .gauge_style(Style::default().fg(Color::Green))
+        .percent(progress);
+    frame.render_widget(gauge, area);
+}
+
+fn run(terminal: &mut Tui, mut app: App) -> io::Result<()> {
+    let mut last_tick = Instant::now();
+
+    while !app.should_quit {
+        terminal.draw(|frame| ui(frame, &mut app))?;
+
+        let timeout = TICK_RATE.saturating_sub(last_tick.elapsed());
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match (key.code, key.modifiers) {
+                    (KeyCode::Char('q'), _) | (KeyCode::Esc, _) => app.should_quit = true,
+                    (KeyCode::Char('c'), KeyModifiers::CONTROL) => app.should_quit = true,
+                    (KeyCode::Down | KeyCode::Char('j'), _) => app.next(),
+                    (KeyCode::Up | KeyCode::Char('k'), _) => app.previous(),
+                    _ => {}
+                }
+            }
+        }
+
+        if last_tick.elapsed() >= TICK_RATE {
+            app.on_tick();
+            last_tick = Instant::now();
+        }
+    }
+
+    Ok(())
+}
+
+pub fn start() -> io::Result<()> {
+    let mut terminal = init_terminal()?;
+    let app = App {
+        tasks: vec!["Write article".into(), "Review theme".into(), "Publish".into()],
+        ..Default::default()
+    };
+
+    let result = run(&mut terminal, app);
+    restore_terminal(&mut terminal)?;
+    result
+}
@@ -0,0 +1,155 @@
+//! Typestate Builder Sample
+//! Demonstrates compile-time required fields with marker generics
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+// Marker types tracking which required fields have been set
+pub struct NameMissing;
+pub struct NameSet(String);
+pub struct EmailMissing;
+pub struct EmailSet(String);
+
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub name: String,
+    pub email: String,
+    pub display_name: Option<String>,
+    pub session_ttl: Duration,
+    pub admin: bool,
+}
+
+pub struct AccountBuilder<N, E> {
+    name: N,
+    email: E,
+    display_name: Option<String>,
+    session_ttl: Duration,
+    admin: bool,
+}
+
+impl Account {
+    pub fn builder() -> AccountBuilder<NameMissing, EmailMissing> {
+        AccountBuilder {
+            name: NameMissing,
+            email: EmailMissing,
+            display_name: None,
+            session_ttl: Duration::from_secs(3600),
+            admin: false,
+        }
+    }
+}
+
+// Setters for required fields change the type parameter
+impl<E> AccountBuilder<NameMissing, E> {
+    pub fn name(self, name: impl Into<String>) -> AccountBuilder<NameSet, E> {
+        AccountBuilder {
+            name: NameSet(name.into()),
+            email: self.email,
+            display_name: self.display_name,
+            session_ttl: self.session_ttl,
+            admin: self.admin,
+        }
+    }
+}
+
+impl<N> AccountBuilder<N, EmailMissing> {
+    pub fn email(self, email: impl Into<String>) -> AccountBuilder<N, EmailSet> {
+        let AccountBuilder { name, display_name, session_ttl, admin, .. } = self;
+        AccountBuilder { name, email: EmailSet(email.into()), display_name, session_ttl, admin }
+    }
+}
+
+// Optional setters are available in every state
+impl<N, E> AccountBuilder<N, E> {
+    pub fn display_name(mut self, display_name: &str) -> Self {
+        self.display_name = Some(display_name.to_owned());
+        self
+    }
+
+    pub fn session_ttl(self, ttl: Duration) -> Self {
+        Self { session_ttl: ttl, ..self }
+    }
+
+    pub fn admin(self, admin: bool) -> Self {
+        Self { admin, ..self }
+    }
+}
+
+// build() only exists once both required fields are set
+impl AccountBuilder<NameSet, EmailSet> {
+    pub fn build(self) -> Account {
+        let AccountBuilder {
+            name: NameSet(name),
+            email: EmailSet(email),
+            display_name,
+            session_ttl,
+            admin,
+        } = self;
+
+        Account { name, email, display_name, session_ttl, admin }
+    }
+}
+
+// The same idea with a sealed state trait and PhantomData
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub trait RequestState: sealed::Sealed {}
+
+pub enum Draft {}
+pub enum Signed {}
+
+impl sealed::Sealed for Draft {}
+impl sealed::Sealed for Signed {}
+impl RequestState for Draft {}
+impl RequestState for Signed {}
+
+pub struct Request<S: RequestState = Draft> {
+    body: Vec<u8>,
+    signature: Option<[u8; 32]>,
+    _state: PhantomData<fn() -> S>,
+}
+
+impl Request<Draft> {
+    pub const fn new() -> Self {
+        Request { body: Vec::new(), signature: None, _state: PhantomData }
+    }
+
+    pub fn body(mut self, bytes: &[u8]) -> Self {
+        self.body.extend_from_slice(bytes);
+        self
+    }
+
+    pub fn sign(self, key: &[u8; 32]) -> Request<Signed> {
+        let mut signature = [0u8; 32];
+        for (i, byte) in self.body.iter().enumerate() {
+            signature[i % 32] ^= byte ^ key[i % 32];
+        }
+        Request { body: self.body, signature: Some(signature), _state: PhantomData }
+    }
+}
+
+impl Request<Signed> {
+    pub fn send(&self) -> usize {
+        let signature = self.signature.expect("signed requests always carry a signature");
+        self.body.len() + signature.len()
+    }
+}
+
+pub fn demo() {
+    let account = Account::builder()
+        .display_name("Alice")
+        .email("alice@example.com")
+        .admin(true)
+        .name("alice")
+        .session_ttl(Duration::from_secs(15 * 60))
+        .build();
+    println!("{:#?}", account);
+
+    // Does not compile: `build` is not defined for AccountBuilder<NameSet, EmailMissing>
+    // let incomplete = Account::builder().name("bob").build();
+
+    let sent = Request::new().body(b"{\"ok\":true}").sign(&[7; 32]).send();
+    println!("sent {sent} bytes");
+}
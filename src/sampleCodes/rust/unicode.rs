@@ -0,0 +1,121 @@
+//! Unicode Sample
+//! Demonstrates non-ASCII identifiers, char classification, and escapes
+
+use std::collections::BTreeMap;
+
+// Non-ASCII identifiers are valid Rust
+pub struct 記事 {
+    pub タイトル: String,
+    pub いいね数: u32,
+}
+
+pub fn 合計いいね(記事一覧: &[記事]) -> u32 {
+    記事一覧.iter().map(|記事| 記事.いいね数).sum()
+}
+
+pub struct Données {
+    pub résumé: String,
+    pub année: u16,
+}
+
+const ΔT: f64 = 0.016;
+const 円周率: f64 = std::f64::consts::PI;
+
+// Escape forms in char and string literals
+pub const SNOWMAN: char = '\u{2603}';
+pub const CRAB: char = '\u{1F980}';
+pub const ZERO_WIDTH_JOINER: char = '\u{200D}';
+pub const BYTE_ESCAPES: &[u8] = b"\x7f\x00\t\r\n\\\"";
+pub const MIXED: &str = "tab:\t quote:\" unicode:\u{00e9} nul:\0 backslash:\\";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CharStats {
+    pub alphabetic: usize,
+    pub numeric: usize,
+    pub whitespace: usize,
+    pub uppercase: usize,
+    pub control: usize,
+    pub cjk: usize,
+    pub other: usize,
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{309F}'   // Hiragana
+        | '\u{30A0}'..='\u{30FF}' // Katakana
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+    )
+}
+
+pub fn classify(text: &str) -> CharStats {
+    let mut stats = CharStats::default();
+    for c in text.chars() {
+        match c {
+            c if is_cjk(c) => stats.cjk += 1,
+            c if c.is_uppercase() => {
+                stats.uppercase += 1;
+                stats.alphabetic += 1;
+            }
+            c if c.is_alphabetic() => stats.alphabetic += 1,
+            c if c.is_numeric() => stats.numeric += 1,
+            c if c.is_whitespace() => stats.whitespace += 1,
+            c if c.is_control() => stats.control += 1,
+            _ => stats.other += 1,
+        }
+    }
+    stats
+}
+
+pub fn slugify(title: &str) -> String {
+    title
+        .chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            c if c.is_ascii_alphanumeric() => c,
+            _ => '-',
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+pub fn code_points(s: &str) -> Vec<String> {
+    s.char_indices()
+        .map(|(i, c)| format!("{i:>2}: U+{:04X} {:?} ({} bytes)", c as u32, c, c.len_utf8()))
+        .collect()
+}
+
+pub fn demo() {
+    let 記事一覧 = vec![
+        記事 { タイトル: "Rust と Shiki".into(), いいね数: 120 },
+        記事 { タイトル: "テーマ作成入門".into(), いいね数: 45 },
+    ];
+    println!("合計: {}", 合計いいね(&記事一覧));
+    for 記事 in &記事一覧 {
+        println!("{} ({}文字)", 記事.タイトル, 記事.タイトル.chars().count());
+    }
+
+    let fr = Données { résumé: "Thème sombre pour Zenn".into(), année: 2024 };
+    println!("{} — {} → {}", fr.année, fr.résumé, slugify(&fr.résumé));
+
+    println!("{:?}", classify("Hello, 世界! 123 ÅNGSTRÖM\t\u{7}"));
+    println!("{} {} {:?}", SNOWMAN, CRAB, format!("👩{ZERO_WIDTH_JOINER}💻"));
+    println!("{MIXED} {:?}", BYTE_ESCAPES);
+
+    let upper: String = "straße".to_uppercase();
+    let folded: BTreeMap<char, usize> = "Ünïcödé".to_lowercase().chars().fold(BTreeMap::new(), |mut m, c| {
+        *m.entry(c).or_insert(0) += 1;
+        m
+    });
+    println!("{upper} {folded:?} ΔT={ΔT} π={円周率:.4}");
+
+    for line in code_points("é🦀") {
+        println!("{line}");
+    }
+    assert_eq!(char::from_u32(0x1F980), Some(CRAB));
+    assert!(char::from_digit(7, 10).is_some_and(|c| c.is_ascii_digit()));
+}
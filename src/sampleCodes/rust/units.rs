@@ -0,0 +1,154 @@
+//! Units of Measure Sample
+//! Demonstrates phantom types and zero-sized unit markers
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Sub};
+
+// Zero-sized unit markers
+#[derive(Debug, Clone, Copy)]
+pub struct Meters;
+#[derive(Debug, Clone, Copy)]
+pub struct Kilometers;
+#[derive(Debug, Clone, Copy)]
+pub struct Seconds;
+#[derive(Debug, Clone, Copy)]
+pub struct Hours;
+
+// Derived unit
+#[derive(Debug, Clone, Copy)]
+pub struct Per<N, D>(PhantomData<(N, D)>);
+
+pub trait Unit {
+    const SYMBOL: &'static str;
+}
+
+impl Unit for Meters {
+    const SYMBOL: &'static str = "m";
+}
+impl Unit for Kilometers {
+    const SYMBOL: &'static str = "km";
+}
+impl Unit for Seconds {
+    const SYMBOL: &'static str = "s";
+}
+impl Unit for Hours {
+    const SYMBOL: &'static str = "h";
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct Quantity<U> {
+    value: f64,
+    _unit: PhantomData<U>,
+}
+
+impl<U> Quantity<U> {
+    pub const fn new(value: f64) -> Self {
+        Self { value, _unit: PhantomData }
+    }
+
+    pub const fn value(self) -> f64 {
+        self.value
+    }
+}
+
+impl<U: Unit> fmt::Display for Quantity<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} {}", self.value, U::SYMBOL)
+    }
+}
+
+impl<N: Unit, D: Unit> fmt::Display for Quantity<Per<N, D>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} {}/{}", self.value, N::SYMBOL, D::SYMBOL)
+    }
+}
+
+// Same-unit arithmetic keeps the unit
+impl<U> Add for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Quantity::new(self.value + rhs.value)
+    }
+}
+
+impl<U> Sub for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Quantity::new(self.value - rhs.value)
+    }
+}
+
+// Cross-unit arithmetic changes the unit parameter
+impl<N, D> Div<Quantity<D>> for Quantity<N> {
+    type Output = Quantity<Per<N, D>>;
+
+    fn div(self, rhs: Quantity<D>) -> Self::Output {
+        Quantity::new(self.value / rhs.value)
+    }
+}
+
+impl<N, D> Mul<Quantity<D>> for Quantity<Per<N, D>> {
+    type Output = Quantity<N>;
+
+    fn mul(self, rhs: Quantity<D>) -> Self::Output {
+        Quantity::new(self.value * rhs.value)
+    }
+}
+
+impl<U> Mul<f64> for Quantity<U> {
+    type Output = Quantity<U>;
+
+    fn mul(self, scalar: f64) -> Self::Output {
+        Quantity::new(self.value * scalar)
+    }
+}
+
+// Conversions between compatible units
+impl From<Quantity<Kilometers>> for Quantity<Meters> {
+    fn from(km: Quantity<Kilometers>) -> Self {
+        Quantity::new(km.value * 1_000.0)
+    }
+}
+
+impl From<Quantity<Hours>> for Quantity<Seconds> {
+    fn from(h: Quantity<Hours>) -> Self {
+        Quantity::new(h.value * 3_600.0)
+    }
+}
+
+impl From<Quantity<Per<Kilometers, Hours>>> for Quantity<Per<Meters, Seconds>> {
+    fn from(speed: Quantity<Per<Kilometers, Hours>>) -> Self {
+        Quantity::new(speed.value / 3.6)
+    }
+}
+
+pub type Speed = Quantity<Per<Meters, Seconds>>;
+
+pub fn travel_time(distance: Quantity<Meters>, speed: Speed) -> Quantity<Seconds> {
+    Quantity::new(distance.value() / speed.value())
+}
+
+pub fn demo() {
+    let leg1 = Quantity::<Kilometers>::new(12.5);
+    let leg2 = Quantity::<Meters>::new(800.0);
+    let total: Quantity<Meters> = Quantity::from(leg1) + leg2;
+
+    let elapsed: Quantity<Seconds> = Quantity::<Hours>::new(0.75).into();
+    let speed = total / elapsed;
+    println!("{} in {} = {}", total, elapsed, speed);
+
+    let highway: Speed = Quantity::<Per<Kilometers, Hours>>::new(100.0).into();
+    println!("100 km/h = {}", highway);
+    println!("10 km takes {}", travel_time(Quantity::new(10_000.0), highway));
+
+    // The compiler rejects mixing units:
+    // let nonsense = total + elapsed;
+    //                        ^^^^^^^ expected `Quantity<Meters>`, found `Quantity<Seconds>`
+
+    assert_eq!(std::mem::size_of::<Quantity<Per<Meters, Seconds>>>(), 8);
+    assert_eq!(std::mem::size_of::<Meters>(), 0);
+}
@@ -0,0 +1,133 @@
+//! Vec Capacity Sample
+//! Demonstrates capacity management, spare_capacity_mut, and unsafe set_len
+
+use std::io::{self, Read};
+use std::mem::MaybeUninit;
+
+const READ_CHUNK: usize = 16 * 1024;
+
+pub fn growth_pattern(n: usize) -> Vec<(usize, usize)> {
+    let mut v: Vec<u64> = Vec::new();
+    let mut changes = vec![(v.len(), v.capacity())];
+    for i in 0..n {
+        let before = v.capacity();
+        v.push(i as u64);
+        if v.capacity() != before {
+            changes.push((v.len(), v.capacity()));
+        }
+    }
+    changes
+}
+
+// Fill uninitialised spare capacity directly, then commit the length
+pub fn squares(n: usize) -> Vec<u32> {
+    let mut out = Vec::with_capacity(n);
+    let spare: &mut [MaybeUninit<u32>] = &mut out.spare_capacity_mut()[..n];
+    for (i, slot) in spare.iter_mut().enumerate() {
+        slot.write((i * i) as u32);
+    }
+    // SAFETY: the first `n` elements were initialised in the loop above
+    unsafe { out.set_len(n) };
+    out
+}
+
+pub fn read_to_end_fast<R: Read>(reader: &mut R) -> io::Result<Vec<u8>> {
+    let mut buf: Vec<u8> = Vec::with_capacity(READ_CHUNK);
+
+    loop {
+        if buf.capacity() - buf.len() < READ_CHUNK {
+            buf.reserve(READ_CHUNK);
+        }
+
+        let spare = buf.spare_capacity_mut();
+        // Zero the spare region once so handing it to `read` is sound
+        for slot in spare.iter_mut() {
+            slot.write(0);
+        }
+        let spare_len = spare.len();
+        let dst = unsafe { std::slice::from_raw_parts_mut(spare.as_mut_ptr().cast::<u8>(), spare_len) };
+
+        let n = match reader.read(dst) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        // SAFETY: `read` initialised `n` bytes past the current length
+        unsafe { buf.set_len(buf.len() + n) };
+    }
+
+    buf.shrink_to_fit();
+    Ok(buf)
+}
+
+pub struct RingLog {
+    lines: Vec<String>,
+    limit: usize,
+}
+
+impl RingLog {
+    pub fn with_limit(limit: usize) -> Self {
+        let mut lines = Vec::new();
+        lines.reserve_exact(limit);
+        Self { lines, limit }
+    }
+
+    pub fn push(&mut self, line: impl Into<String>) {
+        if self.lines.len() == self.limit {
+            self.lines.remove(0);
+        }
+        self.lines.push(line.into());
+        debug_assert!(self.lines.capacity() >= self.limit);
+    }
+
+    pub fn compact(&mut self) {
+        self.lines.retain(|l| !l.is_empty());
+        self.lines.shrink_to(self.limit / 2);
+    }
+}
+
+// Reuse one allocation across iterations instead of allocating per line
+pub fn longest_line_lengths(text: &str) -> Vec<usize> {
+    let mut scratch = String::with_capacity(256);
+    let mut lengths = Vec::with_capacity(text.lines().size_hint().0);
+
+    for line in text.lines() {
+        scratch.clear();
+        scratch.extend(line.chars().filter(|c| !c.is_whitespace()));
+        lengths.push(scratch.len());
+    }
+    lengths
+}
+
+pub fn demo() -> io::Result<()> {
+    for (len, cap) in growth_pattern(40) {
+        println!("len={len:<3} cap={cap}");
+    }
+
+    let sq = squares(8);
+    println!("{sq:?} (cap {})", sq.capacity());
+
+    let mut exact: Vec<u8> = Vec::new();
+    exact.reserve_exact(10);
+    exact.extend_from_slice(b"zenn");
+    println!("len={} cap={} spare={}", exact.len(), exact.capacity(), exact.spare_capacity_mut().len());
+
+    let data = read_to_end_fast(&mut io::repeat(b'z').take(40_000))?;
+    println!("read {} bytes (cap {})", data.len(), data.capacity());
+
+    let mut log = RingLog::with_limit(3);
+    for i in 0..5 {
+        log.push(format!("event {i}"));
+    }
+    log.compact();
+    println!("{:?}", log.lines);
+
+    let mut drain_me: Vec<i32> = (1..=10).collect();
+    let evens: Vec<i32> = drain_me.extract_if(.., |n| *n % 2 == 0).collect();
+    drain_me.truncate(3);
+    let tail = drain_me.split_off(1);
+    println!("{drain_me:?} {tail:?} {evens:?} {:?}", longest_line_lengths("a b\nccc  d"));
+    Ok(())
+}
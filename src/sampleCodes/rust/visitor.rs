@@ -0,0 +1,231 @@
+//! Visitor Pattern Sample
+//! Demonstrates double dispatch and walk_* helpers over an expression AST
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+// A small expression AST (no parser sample exists yet, so it lives here)
+pub mod ast {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Expr {
+        Number(f64),
+        Ident(String),
+        Unary { op: UnaryOp, operand: Box<Expr> },
+        Binary { op: BinOp, lhs: Box<Expr>, rhs: Box<Expr> },
+        Call { callee: String, args: Vec<Expr> },
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Stmt {
+        Let { name: String, value: Expr },
+        Expr(Expr),
+        Block(Vec<Stmt>),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UnaryOp {
+        Neg,
+        Not,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum BinOp {
+        Add,
+        Sub,
+        Mul,
+        Div,
+    }
+}
+
+use ast::{BinOp, Expr, Stmt, UnaryOp};
+
+pub trait Visitor: Sized {
+    type Output;
+
+    fn finish(self) -> Self::Output;
+
+    // Default methods recurse; overrides can intercept specific nodes
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        walk_expr(self, expr);
+    }
+
+    fn visit_ident(&mut self, _name: &str) {}
+
+    fn visit_number(&mut self, _value: f64) {}
+}
+
+pub fn walk_stmt<V: Visitor>(visitor: &mut V, stmt: &Stmt) {
+    match stmt {
+        Stmt::Let { value, .. } => visitor.visit_expr(value),
+        Stmt::Expr(expr) => visitor.visit_expr(expr),
+        Stmt::Block(stmts) => stmts.iter().for_each(|s| visitor.visit_stmt(s)),
+    }
+}
+
+pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
+    match expr {
+        Expr::Number(value) => visitor.visit_number(*value),
+        Expr::Ident(name) => visitor.visit_ident(name),
+        Expr::Unary { operand, .. } => visitor.visit_expr(operand),
+        Expr::Binary { lhs, rhs, .. } => {
+            visitor.visit_expr(lhs);
+            visitor.visit_expr(rhs);
+        }
+        Expr::Call { args, .. } => {
+            for arg in args {
+                visitor.visit_expr(arg);
+            }
+        }
+    }
+}
+
+// accept() provides the classic double-dispatch entry point
+pub trait Accept {
+    fn accept<V: Visitor>(&self, visitor: &mut V);
+}
+
+impl Accept for Stmt {
+    fn accept<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit_stmt(self);
+    }
+}
+
+impl Accept for Expr {
+    fn accept<V: Visitor>(&self, visitor: &mut V) {
+        visitor.visit_expr(self);
+    }
+}
+
+impl<T: Accept> Accept for [T] {
+    fn accept<V: Visitor>(&self, visitor: &mut V) {
+        self.iter().for_each(|node| node.accept(visitor));
+    }
+}
+
+// Collects every identifier and how often it is referenced
+#[derive(Default)]
+pub struct IdentCounter {
+    counts: HashMap<String, usize>,
+}
+
+impl Visitor for IdentCounter {
+    type Output = Vec<(String, usize)>;
+
+    fn visit_ident(&mut self, name: &str) {
+        *self.counts.entry(name.to_owned()).or_default() += 1;
+    }
+
+    fn finish(self) -> Self::Output {
+        let mut counts: Vec<_> = self.counts.into_iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+}
+
+// Pretty-printer that overrides visit_expr entirely
+pub struct Printer {
+    out: String,
+    depth: usize,
+}
+
+impl Visitor for Printer {
+    type Output = String;
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        let indent = "    ".repeat(self.depth);
+        match stmt {
+            Stmt::Let { name, value } => {
+                write!(self.out, "{indent}let {name} = ").unwrap();
+                self.visit_expr(value);
+                self.out.push_str(";\n");
+            }
+            Stmt::Expr(expr) => {
+                self.out.push_str(&indent);
+                self.visit_expr(expr);
+                self.out.push_str(";\n");
+            }
+            Stmt::Block(stmts) => {
+                self.out.push_str(&format!("{indent}{{\n"));
+                self.depth += 1;
+                walk_stmt(self, &Stmt::Block(stmts.clone()));
+                self.depth -= 1;
+                self.out.push_str(&format!("{indent}}}\n"));
+            }
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Number(n) => write!(self.out, "{n}").unwrap(),
+            Expr::Ident(name) => self.out.push_str(name),
+            Expr::Unary { op, operand } => {
+                self.out.push(match op {
+                    UnaryOp::Neg => '-',
+                    UnaryOp::Not => '!',
+                });
+                self.visit_expr(operand);
+            }
+            Expr::Binary { op, lhs, rhs } => {
+                let symbol = match op {
+                    BinOp::Add => "+",
+                    BinOp::Sub => "-",
+                    BinOp::Mul => "*",
+                    BinOp::Div => "/",
+                };
+                self.out.push('(');
+                self.visit_expr(lhs);
+                write!(self.out, " {symbol} ").unwrap();
+                self.visit_expr(rhs);
+                self.out.push(')');
+            }
+            Expr::Call { callee, args } => {
+                write!(self.out, "{callee}(").unwrap();
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    self.visit_expr(arg);
+                }
+                self.out.push(')');
+            }
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+pub fn demo() {
+    let program = vec![
+        Stmt::Let {
+            name: "area".into(),
+            value: Expr::Binary {
+                op: BinOp::Mul,
+                lhs: Box::new(Expr::Ident("pi".into())),
+                rhs: Box::new(Expr::Call {
+                    callee: "pow".into(),
+                    args: vec![Expr::Ident("r".into()), Expr::Number(2.0)],
+                }),
+            },
+        },
+        Stmt::Block(vec![Stmt::Expr(Expr::Unary {
+            op: UnaryOp::Neg,
+            operand: Box::new(Expr::Ident("area".into())),
+        })]),
+    ];
+
+    let mut counter = IdentCounter::default();
+    program.accept(&mut counter);
+    for (name, count) in counter.finish() {
+        println!("{name}: {count}");
+    }
+
+    let mut printer = Printer { out: String::new(), depth: 0 };
+    program.as_slice().accept(&mut printer);
+    print!("{}", printer.finish());
+}
@@ -0,0 +1,93 @@
+//! WebSocket Sample
+//! Demonstrates a tokio-tungstenite client and a broadcasting server
+
+use std::net::SocketAddr;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::protocol::{frame::coding::CloseCode, CloseFrame};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use tokio_tungstenite::{accept_async, connect_async};
+
+const CHANNEL_CAPACITY: usize = 128;
+
+// Client: connect, send a greeting, and print replies
+pub async fn run_client(url: &str) -> Result<(), WsError> {
+    let (ws_stream, response) = connect_async(url).await?;
+    println!("Connected with HTTP status {}", response.status());
+
+    let (mut write, mut read) = ws_stream.split();
+    write.send(Message::Text("hello from client".into())).await?;
+
+    while let Some(message) = read.next().await {
+        match message? {
+            Message::Text(text) => println!("<- {}", text),
+            Message::Binary(bytes) => println!("<- {} bytes", bytes.len()),
+            Message::Ping(payload) => write.send(Message::Pong(payload)).await?,
+            Message::Close(Some(CloseFrame { code, reason })) => {
+                println!("Server closed connection: {:?} {}", code, reason);
+                break;
+            }
+            Message::Close(None) => break,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+// Server: every text message is forwarded to all connected peers
+pub async fn run_server(addr: SocketAddr) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    let (tx, _rx) = broadcast::channel::<String>(CHANNEL_CAPACITY);
+    println!("Listening on ws://{}", addr);
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let tx = tx.clone();
+        let rx = tx.subscribe();
+
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, peer, tx, rx).await {
+                eprintln!("[{}] connection error: {}", peer, err);
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    peer: SocketAddr,
+    tx: broadcast::Sender<String>,
+    mut rx: broadcast::Receiver<String>,
+) -> Result<(), WsError> {
+    let ws_stream = accept_async(stream).await?;
+    let (mut sink, mut source) = ws_stream.split();
+
+    loop {
+        tokio::select! {
+            incoming = source.next() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    let _ = tx.send(format!("{}: {}", peer, text));
+                }
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => continue,
+                Some(Err(err)) => return Err(err),
+            },
+            outgoing = rx.recv() => match outgoing {
+                Ok(line) => sink.send(Message::Text(line.into())).await?,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    eprintln!("[{}] lagged behind by {} messages", peer, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+
+    sink.send(Message::Close(Some(CloseFrame {
+        code: CloseCode::Normal,
+        reason: "bye".into(),
+    })))
+    .await
+}
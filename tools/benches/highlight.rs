@@ -0,0 +1,55 @@
+//! Tokenize and resolve timings for each sample file.
+//!
+//! Run with `cargo bench -p zenn-theme-tools`; pass a filter such as
+//! `rust/` to time a subset. Zenn articles can embed dozens of code blocks,
+//! so a grammar or rule change that slows one sample down shows up here
+//! before it shows up in page loads.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use zenn_theme_tools::resolver::Resolver;
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::tokenizer::Tokenizer;
+use zenn_theme_tools::{repo_root, samples, SAMPLES_DIR, THEME_PATH};
+
+fn highlight(c: &mut Criterion) {
+    let root = repo_root();
+    let theme = Theme::load(&root.join(THEME_PATH)).expect("theme loads");
+    let resolver = Resolver::new(&theme);
+    let tokenizer = Tokenizer::new();
+    let samples = samples::discover(&root.join(SAMPLES_DIR)).expect("samples load");
+
+    let mut tokenize = c.benchmark_group("tokenize");
+    for sample in &samples {
+        let Some(syntax) = tokenizer.syntax_for(&sample.path) else {
+            continue;
+        };
+        let name = sample.path.display().to_string();
+        tokenize.throughput(Throughput::Bytes(sample.source.len() as u64));
+        tokenize.bench_with_input(BenchmarkId::from_parameter(&name), sample, |b, sample| {
+            b.iter(|| tokenizer.tokenize(syntax, &sample.path, &sample.source).unwrap())
+        });
+    }
+    tokenize.finish();
+
+    let mut resolve = c.benchmark_group("resolve");
+    for sample in &samples {
+        let Some(syntax) = tokenizer.syntax_for(&sample.path) else {
+            continue;
+        };
+        let tokens = tokenizer.tokenize(syntax, &sample.path, &sample.source).unwrap();
+        let name = sample.path.display().to_string();
+        resolve.throughput(Throughput::Elements(tokens.len() as u64));
+        resolve.bench_with_input(BenchmarkId::from_parameter(&name), &tokens, |b, tokens| {
+            b.iter(|| {
+                for token in tokens {
+                    std::hint::black_box(resolver.resolve(&token.scopes));
+                }
+            })
+        });
+    }
+    resolve.finish();
+}
+
+criterion_group!(benches, highlight);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+//! Exposes the locked syntect version as `SYNTECT_VERSION`, so cache keys
+//! change when `cargo update` brings in different bundled grammars.
+
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let lock = Path::new(env!("CARGO_MANIFEST_DIR")).join("../Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock.display());
+    let text = fs::read_to_string(&lock).unwrap_or_default();
+    let version = text
+        .split("[[package]]")
+        .find(|package| package.lines().any(|line| line.trim() == "name = \"syntect\""))
+        .and_then(|package| package.lines().find_map(|line| line.trim().strip_prefix("version = ")))
+        .map_or("unlocked", |version| version.trim_matches('"'));
+    println!("cargo:rustc-env=SYNTECT_VERSION={version}");
+}
@@ -0,0 +1,26 @@
+//! Arbitrary selector text matched against an arbitrary scope stack.
+//!
+//! The input is split at the first newline: the first line is the selector
+//! list, the rest a whitespace-separated stack, outermost first.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zenn_theme_tools::selector::{self, Selector};
+
+fuzz_target!(|input: &str| {
+    let (selectors, stack) = input.split_once('\n').unwrap_or((input, ""));
+    let stack: Vec<&str> = stack.split_whitespace().collect();
+
+    for selector in Selector::parse_list(selectors) {
+        let _ = selector.to_string();
+        let _ = selector.depth();
+        let _ = selector.matches(&stack);
+        let _ = selector.is_excluded(&stack);
+        let _ = selector.matches_anywhere(&stack);
+        if let Some(scope) = stack.last() {
+            let _ = selector::matches_scope(scope, &selector.scope);
+            let _ = selector::parents_match(&stack[..stack.len() - 1], &selector.parents);
+        }
+    }
+});
@@ -0,0 +1,26 @@
+//! Arbitrary bytes through the theme deserializer and, when they parse,
+//! through validation and resolver construction.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use zenn_theme_tools::resolver::Resolver;
+use zenn_theme_tools::theme::Theme;
+
+const STACKS: &[&[&str]] = &[
+    &[],
+    &["source.rust"],
+    &["source.rust", "meta.function.rust", "entity.name.function.rust"],
+    &["text.html.markdown", "markup.heading.1.markdown", "punctuation.definition.heading.markdown"],
+];
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(theme) = serde_json::from_slice::<Theme>(data) else {
+        return;
+    };
+    let _ = theme.validate();
+    let resolver = Resolver::new(&theme);
+    for stack in STACKS {
+        resolver.resolve(stack);
+    }
+});
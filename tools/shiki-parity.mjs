@@ -0,0 +1,41 @@
+// Tokenizes samples with the real Shiki for `tools parity`.
+//
+// Reads `{ theme, samples: [{ lang, source }] }` as JSON on stdin and writes,
+// per sample, every raw token with its scope stack and the style Shiki gives
+// it, or null when Shiki has no grammar for the language.
+import { readFileSync } from "node:fs";
+import { bundledLanguages, createHighlighter } from "shiki";
+
+const FONT_STYLES = ["italic", "bold", "underline", "strikethrough"];
+
+const input = JSON.parse(readFileSync(0, "utf8"));
+const theme = JSON.parse(readFileSync(input.theme, "utf8"));
+const langs = [...new Set(input.samples.map((s) => s.lang))].filter((lang) => lang in bundledLanguages);
+const highlighter = await createHighlighter({ themes: [theme], langs });
+
+const output = input.samples.map(({ lang, source }) => {
+  if (!langs.includes(lang)) {
+    return null;
+  }
+  const lines = highlighter.codeToTokensBase(source, { lang, theme: theme.name, includeExplanation: true });
+  return lines.flatMap((line, index) => {
+    let column = 1;
+    return line.flatMap((token) => {
+      // -1 means no rule set a style
+      const bits = Math.max(token.fontStyle ?? 0, 0);
+      return token.explanation.map((part) => {
+        const raw = {
+          line: index + 1,
+          column,
+          text: part.content,
+          scopes: part.scopes.map((scope) => scope.scopeName),
+          foreground: token.color ?? "",
+          fontStyle: FONT_STYLES.filter((_, bit) => bits & (1 << bit)).join(" "),
+        };
+        column += [...part.content].length;
+        return raw;
+      });
+    });
+  });
+});
+process.stdout.write(JSON.stringify(output));
@@ -0,0 +1,225 @@
+//! 24-bit ANSI output for highlighted tokens, for judging colors in a
+//! terminal.
+
+use std::fmt::Write;
+
+use crate::colors::Rgba;
+use crate::diff::TokenChange;
+use crate::resolver::{FontStyle, Resolver};
+use crate::tokenizer::Token;
+
+const RESET: &str = "\x1b[0m";
+
+/// Renders tokens line by line on the theme's background, padding every
+/// line to `width` columns so the block reads as one rectangle.
+pub fn highlight(resolver: &Resolver, tokens: &[Token], line_count: usize, width: usize) -> String {
+    let background = parse(resolver.default_background(), Rgba::BLACK);
+    let bg = sgr(48, background);
+
+    let mut out = String::new();
+    let mut tokens = tokens.iter().peekable();
+    for line in 1..=line_count.max(1) {
+        let mut columns = 0;
+        out.push_str(&bg);
+        while let Some(token) = tokens.next_if(|t| t.line == line) {
+            write_token(&mut out, resolver, background, token);
+            columns += token.text.chars().count();
+        }
+        let _ = writeln!(out, "{}{RESET}", " ".repeat(width.saturating_sub(columns)));
+    }
+    out
+}
+
+/// A unified diff of one sample as two themes highlight it. Every changed
+/// line appears as `-` under `before` and `+` under `after`, followed by its
+/// changed tokens with old→new color swatches; hunks keep `context`
+/// unchanged lines on either side.
+pub fn token_diff(
+    before: &Resolver,
+    after: &Resolver,
+    tokens: &[Token],
+    changes: &[TokenChange],
+    line_count: usize,
+    context: usize,
+) -> String {
+    let mut lines: Vec<Vec<&Token>> = vec![Vec::new(); line_count.max(1)];
+    for token in tokens {
+        if let Some(line) = lines.get_mut(token.line - 1) {
+            line.push(token);
+        }
+    }
+    let mut changed: Vec<Vec<&TokenChange>> = vec![Vec::new(); lines.len()];
+    for change in changes {
+        if let Some(line) = changed.get_mut(tokens[change.index].line - 1) {
+            line.push(change);
+        }
+    }
+
+    // Merge the context windows of changed lines into hunks
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for index in (0..lines.len()).filter(|&i| !changed[i].is_empty()) {
+        let (start, end) = (index.saturating_sub(context), (index + context + 1).min(lines.len()));
+        match hunks.last_mut() {
+            Some((_, last)) if start <= *last => *last = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut out = String::new();
+    for (start, end) in hunks {
+        let _ = writeln!(out, "@@ -{0},{1} +{0},{1} @@", start + 1, end - start);
+        for index in start..end {
+            if changed[index].is_empty() {
+                let _ = writeln!(out, " {}", render_line(after, &lines[index]));
+                continue;
+            }
+            let _ = writeln!(out, "-{}", render_line(before, &lines[index]));
+            let _ = writeln!(out, "+{}", render_line(after, &lines[index]));
+            for change in &changed[index] {
+                let _ = writeln!(
+                    out,
+                    "     {} {} → {} {}  `{}`",
+                    swatch(before, &change.before.foreground),
+                    change.before,
+                    swatch(after, &change.after.foreground),
+                    change.after,
+                    tokens[change.index].text.trim()
+                );
+            }
+        }
+    }
+    out
+}
+
+/// One line of tokens on the theme's background, without padding.
+fn render_line(resolver: &Resolver, tokens: &[&Token]) -> String {
+    let background = parse(resolver.default_background(), Rgba::BLACK);
+    let mut out = sgr(48, background);
+    for token in tokens {
+        write_token(&mut out, resolver, background, token);
+    }
+    out.push_str(RESET);
+    out
+}
+
+/// Writes `token` in its resolved style, leaving the line background set.
+fn write_token(out: &mut String, resolver: &Resolver, background: Rgba, token: &Token) {
+    let style = resolver.resolve(&token.scopes);
+    let foreground = parse(style.foreground, Rgba::WHITE).over(background);
+    if style.background_rule.is_some() {
+        out.push_str(&sgr(48, parse(style.background, background).over(background)));
+    }
+    let _ = write!(
+        out,
+        "{}{}{}{RESET}{}",
+        sgr(38, foreground),
+        attributes(style.font_style),
+        token.text,
+        sgr(48, background)
+    );
+}
+
+/// A block of `hex` on the theme's background.
+pub(crate) fn swatch(resolver: &Resolver, hex: &str) -> String {
+    let background = parse(resolver.default_background(), Rgba::BLACK);
+    format!("{}{}██{RESET}", sgr(48, background), sgr(38, parse(hex, Rgba::WHITE).over(background)))
+}
+
+/// `\x1b[<38|48>;2;r;g;bm` for a foreground or background color.
+fn sgr(plane: u8, color: Rgba) -> String {
+    let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("\x1b[{plane};2;{};{};{}m", channel(color.r), channel(color.g), channel(color.b))
+}
+
+fn attributes(style: FontStyle) -> String {
+    [(FontStyle::BOLD, "1"), (FontStyle::ITALIC, "3"), (FontStyle::UNDERLINE, "4"), (FontStyle::STRIKETHROUGH, "9")]
+        .into_iter()
+        .filter(|(flag, _)| style.contains(*flag))
+        .map(|(_, code)| format!("\x1b[{code}m"))
+        .collect()
+}
+
+fn parse(hex: &str, fallback: Rgba) -> Rgba {
+    Rgba::parse(hex).unwrap_or(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::diff::token_changes;
+    use crate::theme::Theme;
+    use crate::tokenizer::Tokenizer;
+
+    fn token(line: usize, column: usize, text: &str, scope: &str) -> Token {
+        Token { line, column, text: text.to_owned(), scopes: vec!["source".to_owned(), scope.to_owned()] }
+    }
+
+    #[test]
+    fn highlight_writes_truecolor_font_styles_and_resets() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#eeeeee", "background": "#102030" } },
+                { "scope": "keyword", "settings": { "foreground": "#ff8000", "fontStyle": "bold italic" } },
+                { "scope": "markup.deleted", "settings": { "foreground": "#ffffff80", "fontStyle": "underline strikethrough" } },
+                { "scope": "invalid", "settings": { "background": "#ff0000" } }
+            ]
+        }))
+        .unwrap();
+        let resolver = Resolver::new(&theme);
+        let tokens =
+            [token(1, 1, "if", "keyword"), token(1, 3, " x", "variable"), token(3, 1, "old", "markup.deleted")];
+        let out = highlight(&resolver, &tokens, 3, 6);
+        let lines: Vec<&str> = out.lines().collect();
+        let bg = "\x1b[48;2;16;32;48m";
+
+        assert_eq!(
+            lines[0],
+            format!("{bg}\x1b[38;2;255;128;0m\x1b[1m\x1b[3mif\x1b[0m{bg}\x1b[38;2;238;238;238m x\x1b[0m{bg}  \x1b[0m")
+        );
+        // A line without tokens is all padding, reset at the end
+        assert_eq!(lines[1], format!("{bg}      \x1b[0m"));
+        // Translucent foregrounds are blended into the background
+        assert!(lines[2].starts_with(&format!("{bg}\x1b[38;2;136;144;152m\x1b[4m\x1b[9mold\x1b[0m{bg}")));
+        assert!(lines.iter().all(|line| line.ends_with(RESET)));
+
+        // A token's own background is set before it and the line's restored after
+        let out = highlight(&resolver, &[token(1, 1, "bad", "invalid")], 1, 0);
+        assert_eq!(out, format!("{bg}\x1b[48;2;255;0;0m\x1b[38;2;238;238;238mbad\x1b[0m{bg}\x1b[0m\n"));
+    }
+
+    #[test]
+    fn token_diff_groups_changed_lines_into_hunks() {
+        let theme = |keyword: &str| -> Theme {
+            serde_json::from_value(json!({
+                "name": "t",
+                "tokenColors": [
+                    { "settings": { "foreground": "#ffffff", "background": "#000000" } },
+                    { "scope": ["keyword", "storage"], "settings": { "foreground": keyword } }
+                ]
+            }))
+            .unwrap()
+        };
+        let (before, after) = (theme("#ff8fa3"), theme("#ff6b8b"));
+        let (before, after) = (Resolver::new(&before), Resolver::new(&after));
+        let source = "fn a() {}\n\n\n\n\nfn b() {}\nb();\n";
+        let tokenizer = Tokenizer::new();
+        let path = Path::new("sample.rs");
+        let tokens = tokenizer.tokenize(tokenizer.syntax_for(path).unwrap(), path, source).unwrap();
+
+        let changes = token_changes(&before, &after, &tokens);
+        assert_eq!(changes.iter().map(|c| tokens[c.index].text.as_str()).collect::<Vec<_>>(), ["fn", "fn"]);
+        assert_eq!(changes[0].before.foreground, "#ff8fa3");
+
+        let out = token_diff(&before, &after, &tokens, &changes, source.lines().count(), 1);
+        let headers: Vec<&str> = out.lines().filter(|l| l.starts_with("@@")).collect();
+        assert_eq!(headers, ["@@ -1,2 +1,2 @@", "@@ -5,3 +5,3 @@"]);
+        assert_eq!(out.lines().filter(|l| l.starts_with('-')).count(), 2);
+        assert!(out.contains("#ff8fa3 → ") && out.contains(" #ff6b8b  `fn`"));
+        assert!(token_changes(&before, &before, &tokens).is_empty());
+    }
+}
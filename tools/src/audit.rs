@@ -0,0 +1,218 @@
+//! Finds scopes that no theme rule colors.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::cache::{self, Cache};
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::theme::Theme;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::{vscode, Result};
+
+/// The SARIF rule every audit finding is reported under.
+pub const SARIF_RULE: &str = "unthemed-scope";
+
+/// An innermost scope whose tokens fall through to the default foreground.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unthemed {
+    pub count: usize,
+    pub line: usize,
+    pub column: usize,
+    pub example: String,
+}
+
+impl Unthemed {
+    /// One line describing the finding for `scope`, for annotations.
+    pub fn message(&self, scope: &str) -> String {
+        format!("`{scope}` falls through to the default foreground ({} token(s), e.g. `{}`)", self.count, self.example)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum FileReport {
+    Audited { unthemed: BTreeMap<String, Unthemed> },
+    NoGrammar,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub files: Vec<(PathBuf, FileReport)>,
+}
+
+impl Report {
+    pub fn unthemed_count(&self) -> usize {
+        self.files
+            .iter()
+            .map(|(_, file)| match file {
+                FileReport::Audited { unthemed } => unthemed.len(),
+                FileReport::NoGrammar => 0,
+            })
+            .sum()
+    }
+
+    /// The report as a SARIF 2.1.0 log, one result per unthemed scope and
+    /// file, located at its first token. `base` is prefixed to the report's
+    /// paths, which should then be relative to the repository root.
+    pub fn sarif(&self, base: &Path) -> Value {
+        let mut results = Vec::new();
+        for (path, file) in &self.files {
+            let FileReport::Audited { unthemed } = file else {
+                continue;
+            };
+            let uri = base.join(path).to_string_lossy().replace('\\', "/");
+            for (scope, entry) in unthemed {
+                results.push(json!({
+                    "ruleId": SARIF_RULE,
+                    "level": "warning",
+                    "message": { "text": entry.message(scope) },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" },
+                            "region": {
+                                "startLine": entry.line,
+                                "startColumn": entry.column,
+                                "snippet": { "text": entry.example },
+                            },
+                        },
+                    }],
+                    "properties": { "scope": scope, "count": entry.count },
+                }));
+            }
+        }
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "zenn-theme-tools audit",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": vscode::REPOSITORY,
+                        "rules": [{
+                            "id": SARIF_RULE,
+                            "shortDescription": { "text": "Scope no theme rule colors" },
+                            "fullDescription": {
+                                "text": "Tokens in this scope are drawn in the default foreground because no \
+                                         tokenColors rule matches it.",
+                            },
+                            "defaultConfiguration": { "level": "warning" },
+                        }],
+                    },
+                },
+                // Our columns count characters, not UTF-16 code units
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }],
+        })
+    }
+}
+
+/// Audits the samples in parallel, reporting them in their given order.
+pub fn run(resolver: &Resolver, tokenizer: &Tokenizer, samples: &[Sample]) -> Result<Report> {
+    let files = samples
+        .par_iter()
+        .map(|sample| Ok((sample.path.clone(), sample_report(resolver, tokenizer, sample)?)))
+        .collect::<Result<_>>()?;
+    Ok(Report { files })
+}
+
+/// [`run`], reusing the reports of samples that, with their grammar and
+/// `theme`, are unchanged since an earlier run.
+pub fn run_cached(cache: &Cache, theme: &Theme, tokenizer: &Tokenizer, samples: &[Sample]) -> Result<Report> {
+    let resolver = Resolver::new(theme);
+    let theme = cache::theme_hash(theme).to_le_bytes();
+    let files = samples
+        .par_iter()
+        .map(|sample| {
+            let key = cache::sample_key(tokenizer, sample).write(&theme).finish();
+            let file = cache.get_or_compute("audit", key, || sample_report(&resolver, tokenizer, sample))?;
+            Ok((sample.path.clone(), file))
+        })
+        .collect::<Result<_>>()?;
+    Ok(Report { files })
+}
+
+fn sample_report(resolver: &Resolver, tokenizer: &Tokenizer, sample: &Sample) -> Result<FileReport> {
+    let Some(syntax) = tokenizer.syntax_for(&sample.path) else {
+        return Ok(FileReport::NoGrammar);
+    };
+    let lines = tokenizer.tokenize_lines(syntax, &sample.path, sample.source.as_bytes());
+    Ok(FileReport::Audited { unthemed: stream(resolver, lines, |_| {})? })
+}
+
+/// The unthemed scopes among one sample's tokens.
+pub fn file(resolver: &Resolver, tokens: &[Token]) -> BTreeMap<String, Unthemed> {
+    let mut unthemed = BTreeMap::new();
+    add(&mut unthemed, resolver, tokens);
+    unthemed
+}
+
+/// [`file`] over lines as [`Tokenizer::tokenize_lines`] yields them, so an
+/// input of any size is audited in bounded memory. `progress` is called
+/// with each line number as it is done.
+pub fn stream(
+    resolver: &Resolver,
+    lines: impl Iterator<Item = Result<(usize, Vec<Token>)>>,
+    mut progress: impl FnMut(usize),
+) -> Result<BTreeMap<String, Unthemed>> {
+    let mut unthemed = BTreeMap::new();
+    for line in lines {
+        let (number, tokens) = line?;
+        add(&mut unthemed, resolver, &tokens);
+        progress(number);
+    }
+    Ok(unthemed)
+}
+
+fn add(unthemed: &mut BTreeMap<String, Unthemed>, resolver: &Resolver, tokens: &[Token]) {
+    for token in tokens {
+        if token.text.trim().is_empty() || resolver.resolve(&token.scopes).foreground_rule.is_some() {
+            continue;
+        }
+        let Some(scope) = token.scopes.last() else {
+            continue;
+        };
+        unthemed
+            .entry(scope.clone())
+            .or_insert_with(|| Unthemed {
+                count: 0,
+                line: token.line,
+                column: token.column,
+                example: token.text.trim().to_owned(),
+            })
+            .count += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sarif_locates_each_unthemed_scope() {
+        let unthemed = BTreeMap::from([(
+            "variable.other.rust".to_owned(),
+            Unthemed { count: 3, line: 7, column: 5, example: "x".to_owned() },
+        )]);
+        let report = Report {
+            files: vec![
+                ("rust/a.rs".into(), FileReport::Audited { unthemed }),
+                ("misc/b.unknown".into(), FileReport::NoGrammar),
+            ],
+        };
+        let sarif = report.sarif(Path::new("src/sampleCodes"));
+        assert_eq!(sarif["version"], "2.1.0");
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], SARIF_RULE);
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/sampleCodes/rust/a.rs");
+        assert_eq!(location["region"], json!({ "startLine": 7, "startColumn": 5, "snippet": { "text": "x" } }));
+        assert_eq!(results[0]["properties"], json!({ "scope": "variable.other.rust", "count": 3 }));
+    }
+}
@@ -0,0 +1,19 @@
+//! The multi-command entry point; see `tools --help`.
+
+use std::process::ExitCode;
+
+use clap::Parser;
+use zenn_theme_tools::cli::{self, Cli};
+use zenn_theme_tools::i18n;
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let lang = cli.lang;
+    match cli::run(cli) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("{}", i18n::error_line(&err, lang));
+            ExitCode::FAILURE
+        }
+    }
+}
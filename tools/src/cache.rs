@@ -0,0 +1,249 @@
+//! On-disk cache of per-sample results under `target/`, so repeated runs
+//! and watch sessions only redo the samples, grammars or themes that
+//! changed.
+//!
+//! Every entry is keyed by a hash of everything it was computed from: the
+//! sample's path and source, the grammar's fingerprint, [`CACHE_VERSION`]
+//! for the code itself and, for results that depend on styling, the theme.
+//! Nothing is ever invalidated explicitly; a change to any input is a
+//! different key. Unreadable entries count as misses.
+//!
+//! Code changes are the one input the key cannot see, so [`CACHE_VERSION`]
+//! must be bumped by hand whenever they change what gets cached.
+
+use std::fs;
+use std::path::PathBuf;
+
+use rayon::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::samples::Sample;
+use crate::theme::Theme;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::Result;
+
+/// Where the `tools` command keeps its cache, relative to the repository
+/// root.
+pub const CACHE_DIR: &str = "target/tools-cache";
+
+/// The version of the code behind cached results. Bump it with any change
+/// to how tokens are split or scopes resolved (tokenizer.rs, resolver.rs,
+/// selector.rs) or to the shape of a cached type, [`Token`] included, so
+/// earlier entries stop matching. Entries in the old format would otherwise
+/// fail to deserialize at best, or be read back with the wrong meaning.
+pub const CACHE_VERSION: u32 = 1;
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` is stable across Rust
+/// releases, so entries survive a toolchain update.
+#[derive(Debug, Clone, Copy)]
+pub struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv {
+    /// Adds `bytes`, followed by a separator so `["ab", "c"]` and
+    /// `["a", "bc"]` differ.
+    pub fn write(&mut self, bytes: &[u8]) -> &mut Self {
+        for &byte in bytes.iter().chain(&[0xff]) {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+        self
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    /// `None` when caching is off.
+    dir: Option<PathBuf>,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir: Some(dir) }
+    }
+
+    /// A cache that always computes, for `--no-cache`.
+    pub fn disabled() -> Self {
+        Self { dir: None }
+    }
+
+    /// `compute`'s result for `key` in the `kind` namespace, from disk when
+    /// an earlier run stored it. A failed write only costs the next run.
+    pub fn get_or_compute<T: Serialize + DeserializeOwned>(
+        &self,
+        kind: &str,
+        key: u64,
+        compute: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        let Some(dir) = &self.dir else {
+            return compute();
+        };
+        let path = dir.join(kind).join(format!("{key:016x}.json"));
+        if let Some(value) = fs::read(&path).ok().and_then(|bytes| serde_json::from_slice(&bytes).ok()) {
+            tracing::trace!(kind, key = format_args!("{key:016x}"), "cache hit");
+            return Ok(value);
+        }
+        tracing::trace!(kind, key = format_args!("{key:016x}"), "cache miss");
+        let value = compute()?;
+        if let (Some(parent), Ok(bytes)) = (path.parent(), serde_json::to_vec(&value)) {
+            // Written aside and renamed, so a parallel reader never sees half
+            let partial = path.with_extension(format!("{}.partial", std::process::id()));
+            let _ = fs::create_dir_all(parent)
+                .and_then(|()| fs::write(&partial, bytes))
+                .and_then(|()| fs::rename(&partial, &path));
+        }
+        Ok(value)
+    }
+
+    /// The sample's tokens, or `None` without a grammar, as
+    /// [`Tokenizer::tokenize`] would produce them.
+    pub fn tokens(&self, tokenizer: &Tokenizer, sample: &Sample) -> Result<Option<Vec<Token>>> {
+        let Some(syntax) = tokenizer.syntax_for(&sample.path) else {
+            return Ok(None);
+        };
+        let key = sample_key(tokenizer, sample).finish();
+        self.get_or_compute("tokens", key, || tokenizer.tokenize(syntax, &sample.path, &sample.source)).map(Some)
+    }
+
+    /// [`Tokenizer::tokenize_all`] through the cache.
+    pub fn tokenize_all(&self, tokenizer: &Tokenizer, samples: &[Sample]) -> Result<Vec<Option<Vec<Token>>>> {
+        samples.par_iter().map(|sample| self.tokens(tokenizer, sample)).collect()
+    }
+}
+
+/// The key of everything about `sample` that its tokens depend on; extend
+/// it with [`theme_hash`] for styled results.
+pub fn sample_key(tokenizer: &Tokenizer, sample: &Sample) -> Fnv {
+    versioned_key(CACHE_VERSION, tokenizer, sample)
+}
+
+fn versioned_key(version: u32, tokenizer: &Tokenizer, sample: &Sample) -> Fnv {
+    let mut key = Fnv::default();
+    key.write(&version.to_le_bytes())
+        .write(sample.path.to_string_lossy().as_bytes())
+        .write(sample.source.as_bytes())
+        .write(&tokenizer.fingerprint().to_le_bytes());
+    key
+}
+
+/// A hash of the theme as it would be written, so formatting-only edits to
+/// the file keep their entries.
+pub fn theme_hash(theme: &Theme) -> u64 {
+    Fnv::default().write(theme.to_json().as_bytes()).finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn sample(source: &str) -> Sample {
+        Sample { path: PathBuf::from("a.rs"), source: source.to_owned() }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("zenn-cache-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn entries_are_reused_until_an_input_changes() {
+        let dir = temp_dir("inputs");
+        let cache = Cache::new(dir.clone());
+        let tokenizer = Tokenizer::new();
+        let computed = std::cell::Cell::new(0);
+        let run = |sample: &Sample, theme: &Theme| {
+            let key = sample_key(&tokenizer, sample).write(&theme_hash(theme).to_le_bytes()).finish();
+            cache
+                .get_or_compute("count", key, || {
+                    computed.set(computed.get() + 1);
+                    Ok(sample.source.len())
+                })
+                .unwrap()
+        };
+        let theme = |foreground: &str| -> Theme {
+            serde_json::from_value(
+                json!({ "name": "t", "tokenColors": [{ "settings": { "foreground": foreground } }] }),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(run(&sample("fn a() {}"), &theme("#ffffff")), 9);
+        assert_eq!(run(&sample("fn a() {}"), &theme("#ffffff")), 9);
+        assert_eq!(computed.get(), 1);
+        run(&sample("fn b() {}"), &theme("#ffffff"));
+        assert_eq!(computed.get(), 2);
+        run(&sample("fn a() {}"), &theme("#000000"));
+        assert_eq!(computed.get(), 3);
+
+        // A corrupt entry is recomputed, not an error
+        for entry in fs::read_dir(dir.join("count")).unwrap() {
+            fs::write(entry.unwrap().path(), "{").unwrap();
+        }
+        run(&sample("fn a() {}"), &theme("#ffffff"));
+        assert_eq!(computed.get(), 4);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn cached_tokens_match_fresh_ones() {
+        let dir = temp_dir("tokens");
+        let (cache, tokenizer) = (Cache::new(dir.clone()), Tokenizer::new());
+        let samples =
+            [sample("fn main() { let x = \"s\"; }\n"), Sample { path: "b.unknown".into(), source: "b".into() }];
+        let fresh = tokenizer.tokenize_all(&samples).unwrap();
+        assert_eq!(cache.tokenize_all(&tokenizer, &samples).unwrap(), fresh);
+        assert_eq!(cache.tokenize_all(&tokenizer, &samples).unwrap(), fresh);
+        assert_eq!(fs::read_dir(dir.join("tokens")).unwrap().count(), 1);
+        assert_eq!(Cache::disabled().tokenize_all(&tokenizer, &samples).unwrap(), fresh);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_new_code_version_misses() {
+        let dir = temp_dir("version");
+        let (cache, tokenizer) = (Cache::new(dir.clone()), Tokenizer::new());
+        let sample = sample("fn a() {}");
+        let computed = std::cell::Cell::new(0);
+        let run = |version: u32| {
+            let key = versioned_key(version, &tokenizer, &sample).finish();
+            cache.get_or_compute("count", key, || Ok(computed.replace(computed.get() + 1))).unwrap()
+        };
+        run(CACHE_VERSION);
+        run(CACHE_VERSION);
+        assert_eq!(computed.get(), 1);
+        run(CACHE_VERSION + 1);
+        assert_eq!(computed.get(), 2);
+        assert_eq!(
+            sample_key(&tokenizer, &sample).finish(),
+            versioned_key(CACHE_VERSION, &tokenizer, &sample).finish()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn grammar_changes_change_the_key() {
+        let dir = temp_dir("grammars");
+        fs::create_dir_all(&dir).unwrap();
+        let grammar = |name: &str| json!({ "name": name, "scopeName": "source.x", "fileTypes": ["x"], "patterns": [] });
+        fs::write(dir.join("x.json"), grammar("X").to_string()).unwrap();
+        let before = Tokenizer::with_grammars(&dir).unwrap().fingerprint();
+        assert_eq!(Tokenizer::with_grammars(&dir).unwrap().fingerprint(), before);
+        fs::write(dir.join("x.json"), grammar("Y").to_string()).unwrap();
+        assert_ne!(Tokenizer::with_grammars(&dir).unwrap().fingerprint(), before);
+        assert_ne!(Tokenizer::new().fingerprint(), before);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
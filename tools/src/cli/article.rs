@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::i18n::Message;
+use crate::markdown;
+use crate::resolver::Resolver;
+use crate::{Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Zenn article whose fenced code blocks are highlighted
+    #[arg(value_name = "ARTICLE")]
+    article: PathBuf,
+
+    /// Output file [default: target/preview/<article>.html]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme = cx.load_theme()?;
+    let source = fs::read_to_string(&args.article).map_err(|e| Error::io(&args.article, e))?;
+    let page = markdown::render(&Resolver::new(&theme), &cx.tokenizer()?, &args.article, &source)?;
+
+    let out = args.out.unwrap_or_else(|| {
+        let stem = args.article.file_stem().unwrap_or_default().to_string_lossy();
+        cx.root.join("target/preview").join(format!("{stem}.html"))
+    });
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(&out, page).map_err(|e| Error::io(&out, e))?;
+    cx.print(&format!("{}\n", cx.say(Message::Wrote { path: &out })), json!({ "written": [out] }));
+    Ok(ExitCode::SUCCESS)
+}
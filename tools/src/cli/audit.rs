@@ -0,0 +1,136 @@
+use std::fmt::Write;
+use std::fs::File;
+use std::io::{self, BufReader, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::live::{self, Live};
+use super::{annotation, Context, Format};
+use crate::audit::{self, FileReport, Report};
+use crate::i18n::{Lang, Message};
+use crate::resolver::Resolver;
+use crate::tokenizer::Tokenizer;
+use crate::{Error, Result};
+
+/// Lines between progress updates in `--file` mode.
+const PROGRESS_EVERY: usize = 10_000;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Sample directory [default: src/sampleCodes]
+    #[arg(long, value_name = "DIR")]
+    samples: Option<PathBuf>,
+
+    /// Keep running and print a coverage and contrast summary on every save
+    #[arg(long)]
+    watch: bool,
+
+    /// Audit one file of any size instead, e.g. a long article, reading and
+    /// tokenizing it a line at a time
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["samples", "watch"])]
+    file: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    if args.watch {
+        let mut seen = None;
+        return Live::new(cx, args.samples.as_deref())?.watch(cx, |live, changed| {
+            live::summarize(cx, live, changed, &mut seen);
+            Ok(())
+        });
+    }
+
+    let theme = cx.load_theme()?;
+    let mut report = match &args.file {
+        Some(path) => stream(&Resolver::new(&theme), &cx.tokenizer()?, path)?,
+        None => audit::run_cached(&cx.cache(), &theme, &cx.tokenizer()?, &cx.samples(args.samples.as_deref())?)?,
+    };
+    for (_, file) in &mut report.files {
+        if let FileReport::Audited { unthemed } = file {
+            unthemed.retain(|scope, _| !cx.config.audit.allows(scope));
+        }
+    }
+    // Sample paths are relative to their directory; these formats need them
+    // relative to the repository
+    let base = match &args.file {
+        Some(_) => PathBuf::new(),
+        None => cx.samples_base(args.samples.as_deref()),
+    };
+    match cx.format {
+        Format::Sarif => {
+            println!("{}", serde_json::to_string_pretty(&report.sarif(&base)).expect("reports serialize"));
+            return Ok(ExitCode::SUCCESS);
+        }
+        Format::Github => {
+            for (path, file) in &report.files {
+                let FileReport::Audited { unthemed } = file else { continue };
+                for (scope, e) in unthemed {
+                    println!(
+                        "{}",
+                        annotation("warning", &base.join(path), e.line, e.column, "Unthemed scope", &e.message(scope))
+                    );
+                }
+            }
+            // Like the annotations, the summary is for CI logs and stays in English
+            let summary = Message::Unthemed { scopes: report.unthemed_count(), files: report.files.len() };
+            println!("{}", summary.text(Lang::En));
+            return Ok(ExitCode::SUCCESS);
+        }
+        Format::Text | Format::Json => {}
+    }
+
+    let mut text = String::new();
+    let mut files = Vec::new();
+    for (path, file) in &report.files {
+        match file {
+            FileReport::NoGrammar => {
+                let _ = writeln!(text, "{}", cx.say(Message::NoGrammar { path }));
+                files.push(json!({ "path": path, "grammar": false, "unthemed": [] }));
+            }
+            FileReport::Audited { unthemed } => {
+                if !unthemed.is_empty() {
+                    let _ = writeln!(text, "{}", path.display());
+                }
+                for (scope, entry) in unthemed {
+                    let _ = writeln!(
+                        text,
+                        "  {scope:<48} {:>5}x  {}:{} `{}`",
+                        entry.count, entry.line, entry.column, entry.example
+                    );
+                }
+                let unthemed: Vec<_> = unthemed
+                    .iter()
+                    .map(|(scope, e)| {
+                        json!({ "scope": scope, "count": e.count, "line": e.line, "column": e.column, "example": e.example })
+                    })
+                    .collect();
+                files.push(json!({ "path": path, "grammar": true, "unthemed": unthemed }));
+            }
+        }
+    }
+    let _ =
+        writeln!(text, "{}", cx.say(Message::Unthemed { scopes: report.unthemed_count(), files: report.files.len() }));
+    cx.print(&text, json!({ "unthemed": report.unthemed_count(), "files": files }));
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Audits `path` as it is read, counting lines on stderr when that is a
+/// terminal.
+fn stream(resolver: &Resolver, tokenizer: &Tokenizer, path: &Path) -> Result<Report> {
+    let Some(syntax) = tokenizer.syntax_for(path) else {
+        return Ok(Report { files: vec![(path.to_path_buf(), FileReport::NoGrammar)] });
+    };
+    let reader = BufReader::new(File::open(path).map_err(|e| Error::io(path, e))?);
+    let tty = io::stderr().is_terminal();
+    let unthemed = audit::stream(resolver, tokenizer.tokenize_lines(syntax, path, reader), |line| {
+        if tty && line % PROGRESS_EVERY == 0 {
+            eprint!("\r{line} lines");
+        }
+    })?;
+    if tty {
+        eprint!("\r\x1b[K");
+    }
+    Ok(Report { files: vec![(path.to_path_buf(), FileReport::Audited { unthemed })] })
+}
@@ -0,0 +1,73 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::colorblind::{self, Confusion, Deficiency, MIN_DISTANCE};
+use crate::html::Options;
+use crate::i18n::Message;
+use crate::preview::{self, PreviewTheme};
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::tokenizer::Token;
+use crate::{Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Gallery of every sample as seen with each deficiency [default:
+    /// target/colorblind/index.html]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme = cx.load_theme()?;
+    let confusions: Vec<Confusion> =
+        Deficiency::ALL.into_iter().flat_map(|d| colorblind::confusions(&theme, d)).collect();
+
+    let mut themes = vec![("original".to_owned(), Resolver::new(&theme))];
+    themes.extend(Deficiency::ALL.map(|d| (d.name().to_owned(), Resolver::new(&d.simulate_theme(&theme)))));
+    let themes: Vec<PreviewTheme<'_>> =
+        themes.iter().map(|(name, resolver)| PreviewTheme { name, resolver, options: Options::default() }).collect();
+    let samples = cx.samples(None)?;
+    let tokenized = cx.cache().tokenize_all(&cx.tokenizer()?, &samples)?;
+    let tokenized: Vec<(&Sample, Option<&[Token]>)> =
+        samples.iter().zip(&tokenized).map(|(s, t)| (s, t.as_deref())).collect();
+
+    let out = args.out.unwrap_or_else(|| cx.root.join("target/colorblind/index.html"));
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(&out, preview::render_tokenized(&themes, &tokenized)).map_err(|e| Error::io(&out, e))?;
+
+    let mut text = String::new();
+    for Confusion { deficiency, a, b, distance, simulated_distance } in &confusions {
+        let _ = writeln!(
+            text,
+            "{:<12} {} {}  vs  {} {}  ({distance:.3} -> {simulated_distance:.3})",
+            deficiency.name(),
+            a.color,
+            a.label(),
+            b.color,
+            b.label()
+        );
+    }
+    let summary = Message::Confusable { pairs: confusions.len(), threshold: MIN_DISTANCE, path: &out };
+    let _ = writeln!(text, "{}", cx.say(summary));
+    let json = json!({
+        "threshold": MIN_DISTANCE,
+        "written": out,
+        "confusions": confusions.iter().map(|c| json!({
+            "deficiency": c.deficiency.name(),
+            "a": { "color": c.a.color, "selectors": c.a.selectors },
+            "b": { "color": c.b.color, "selectors": c.b.selectors },
+            "distance": c.distance,
+            "simulated_distance": c.simulated_distance,
+        })).collect::<Vec<_>>(),
+    });
+    cx.print(&text, json);
+    Ok(status(confusions.is_empty()))
+}
@@ -0,0 +1,64 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::compare::{self, SampleChanges};
+use crate::i18n::Message;
+use crate::resolver::Resolver;
+use crate::{Error, Result, THEME_PATH};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Revision to compare from
+    #[arg(long, default_value = "main")]
+    base: String,
+
+    /// Revision to compare to [default: the working tree]
+    #[arg(long)]
+    head: Option<String>,
+
+    /// Output file [default: target/compare/index.html]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    // git wants the path relative to the repository
+    let theme = match &cx.theme {
+        Some(path) => path.strip_prefix(&cx.root).unwrap_or(path).to_path_buf(),
+        None => PathBuf::from(THEME_PATH),
+    };
+    let base = compare::theme_at(&cx.root, Some(&args.base), &theme)?;
+    let head = compare::theme_at(&cx.root, args.head.as_deref(), &theme)?;
+    let (base_resolver, head_resolver) = (Resolver::new(&base), Resolver::new(&head));
+    let changes = compare::changes(&base_resolver, &head_resolver, &cx.tokenizer()?, &cx.samples(None)?)?;
+
+    let labels = (args.base.as_str(), args.head.as_deref().unwrap_or("working tree"));
+    let out = args.out.unwrap_or_else(|| cx.root.join("target/compare/index.html"));
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(&out, compare::to_html(labels, &base_resolver, &head_resolver, &changes))
+        .map_err(|e| Error::io(&out, e))?;
+
+    let total: usize = changes.iter().map(SampleChanges::token_count).sum();
+    let mut text = String::new();
+    for sample in &changes {
+        let _ = writeln!(text, "{:>5}  {}", sample.token_count(), sample.path.display());
+    }
+    let _ = writeln!(text, "{}", cx.say(Message::TokensChanged { tokens: total, samples: changes.len(), path: &out }));
+    let json = json!({
+        "changed_tokens": total,
+        "samples": changes
+            .iter()
+            .map(|s| json!({ "path": s.path, "changed_tokens": s.token_count() }))
+            .collect::<Vec<_>>(),
+        "written": [out],
+    });
+    cx.print(&text, json);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,93 @@
+use std::fmt::Write;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{annotation, status, Context, Format};
+use crate::contrast::{self, Entry};
+use crate::i18n::{Lang, Message};
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::tokenizer::Token;
+use crate::Result;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Minimum contrast ratio [default: `contrast.threshold`, else 4.5]
+    #[arg(long)]
+    threshold: Option<f64>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let threshold = args.threshold.unwrap_or(cx.config.contrast.threshold);
+    let theme = cx.load_theme()?;
+    let entries = contrast::check(&theme);
+    let failing = entries.iter().filter(|e| e.ratio < threshold).count();
+    if cx.format == Format::Github {
+        let samples = cx.samples(None)?;
+        let tokenized = cx.cache().tokenize_all(&cx.tokenizer()?, &samples)?;
+        let resolver = Resolver::new(&theme);
+        let samples: Vec<(&Sample, &[Token])> =
+            samples.iter().zip(&tokenized).filter_map(|(s, tokens)| Some((s, tokens.as_deref()?))).collect();
+        for e in entries.iter().filter(|e| e.ratio < threshold) {
+            println!("{}", annotate(cx, &resolver, &samples, e, threshold));
+        }
+        println!("{}", Message::ContrastFailing { failing, total: entries.len(), threshold }.text(Lang::En));
+        return Ok(status(failing == 0));
+    }
+
+    let mut text = String::new();
+    for e in &entries {
+        let mark = if e.ratio < threshold { "FAIL" } else { "ok" };
+        let _ = writeln!(text, "{mark:<4} {:>6.2}:1  {} on {}  {}", e.ratio, e.foreground, e.background, e.label);
+    }
+    let _ = writeln!(text, "{}", cx.say(Message::ContrastFailing { failing, total: entries.len(), threshold }));
+    let json = json!({
+        "threshold": threshold,
+        "failing": failing,
+        "entries": entries
+            .iter()
+            .map(|e| json!({
+                "label": e.label,
+                "foreground": e.foreground,
+                "background": e.background,
+                "ratio": e.ratio,
+                "pass": e.ratio >= threshold,
+            }))
+            .collect::<Vec<_>>(),
+    });
+    cx.print(&text, json);
+    Ok(status(failing == 0))
+}
+
+/// An error annotation on the first sample token drawn in `entry`'s color,
+/// else on the theme file.
+fn annotate(
+    cx: &Context,
+    resolver: &Resolver,
+    samples: &[(&Sample, &[Token])],
+    entry: &Entry,
+    threshold: f64,
+) -> String {
+    let message = format!(
+        "{} on {} is {:.2}:1, below {threshold}:1 ({})",
+        entry.foreground, entry.background, entry.ratio, entry.label
+    );
+    let first = samples.iter().find_map(|(sample, tokens)| {
+        tokens.iter().find_map(|token| {
+            let drawn = !token.text.trim().is_empty() && resolver.resolve(&token.scopes).foreground_rule == entry.rule;
+            drawn.then_some((&sample.path, token))
+        })
+    });
+    match first {
+        Some((path, token)) => {
+            let file = cx.samples_base(None).join(path);
+            annotation("error", &file, token.line, token.column, "Low contrast", &message)
+        }
+        None => {
+            let path = cx.theme_path();
+            let file = path.strip_prefix(&cx.root).unwrap_or(&path);
+            annotation("error", file, 0, 0, "Low contrast", &message)
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::coverage;
+use crate::i18n::Message;
+use crate::{Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory for matrix.html and matrix.json [default: target/coverage]
+    #[arg(long, value_name = "DIR")]
+    out: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme = cx.load_theme()?;
+    let tokenizer = cx.tokenizer()?;
+    let samples = cx.samples(None)?;
+    let tokenized = cx.cache().tokenize_all(&tokenizer, &samples)?;
+    let matrix = coverage::build_tokenized(&theme, &tokenizer, &samples, &tokenized);
+
+    let out = args.out.unwrap_or_else(|| cx.root.join("target/coverage"));
+    fs::create_dir_all(&out).map_err(|e| Error::io(&out, e))?;
+    let mut written = Vec::new();
+    for (name, contents) in [("matrix.html", matrix.to_html(&theme)), ("matrix.json", matrix.to_json(&theme))] {
+        let path = out.join(name);
+        fs::write(&path, contents).map_err(|e| Error::io(&path, e))?;
+        written.push(path);
+    }
+
+    let size = Message::CoverageSize { languages: matrix.languages.len(), groups: matrix.groups.len() };
+    let text = format!("{}\n{}\n", cx.say(size), cx.say(Message::Wrote { path: &out }));
+    cx.print(&text, json!({ "written": written, "languages": matrix.languages.len(), "groups": matrix.groups.len() }));
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,76 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::diff;
+use crate::i18n::Message;
+use crate::resolver::Resolver;
+use crate::theme::Theme;
+use crate::Result;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Theme to compare against, e.g. github-dark
+    #[arg(long, value_name = "PATH")]
+    reference: PathBuf,
+
+    /// Sample directory [default: src/sampleCodes]
+    #[arg(long, value_name = "DIR")]
+    samples: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let (ours, theirs) = (cx.load_theme()?, Theme::load(&args.reference)?);
+    let samples = cx.samples(args.samples.as_deref())?;
+    let tokenized = cx.cache().tokenize_all(&cx.tokenizer()?, &samples)?;
+    let report = diff::compare_tokenized(&Resolver::new(&ours), &Resolver::new(&theirs), &samples, &tokenized);
+
+    let mut rows: Vec<_> = report.differences.iter().collect();
+    rows.sort_by(|(a_key, a), (b_key, b)| b.count.cmp(&a.count).then_with(|| a_key.cmp(b_key)));
+
+    let mut text = format!("{:<44} {:<22} {:<22} {:>6}  first\n", "scope", "ours", "reference", "count");
+    for (key, d) in &rows {
+        let _ = writeln!(
+            text,
+            "{:<44} {:<22} {:<22} {:>6}  {}:{}:{} `{}`",
+            key.scope,
+            key.ours.to_string(),
+            key.theirs.to_string(),
+            d.count,
+            d.path.display(),
+            d.line,
+            d.column,
+            d.example
+        );
+    }
+    for path in &report.skipped {
+        let _ = writeln!(text, "{}", cx.say(Message::Skipped { path }));
+    }
+    let percent = if report.tokens == 0 { 0.0 } else { 100.0 * report.differing_tokens as f64 / report.tokens as f64 };
+    let differ = Message::TokensDiffer { differing: report.differing_tokens, total: report.tokens, percent };
+    let _ = writeln!(text, "{}", cx.say(differ));
+
+    let json = json!({
+        "tokens": report.tokens,
+        "differing_tokens": report.differing_tokens,
+        "differences": rows
+            .iter()
+            .map(|(key, d)| json!({
+                "scope": key.scope,
+                "ours": key.ours.to_string(),
+                "reference": key.theirs.to_string(),
+                "count": d.count,
+                "path": d.path,
+                "line": d.line,
+                "column": d.column,
+                "example": d.example,
+            }))
+            .collect::<Vec<_>>(),
+        "skipped": report.skipped,
+    });
+    cx.print(&text, json);
+    Ok(ExitCode::SUCCESS)
+}
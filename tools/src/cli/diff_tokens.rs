@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::i18n::Message;
+use crate::resolver::Resolver;
+use crate::theme::Theme;
+use crate::{ansi, diff, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Theme before the change
+    #[arg(value_name = "BEFORE")]
+    before: PathBuf,
+
+    /// Theme after the change
+    #[arg(value_name = "AFTER")]
+    after: PathBuf,
+
+    /// Sample to highlight, e.g. `rust` or `rust/grpc`
+    #[arg(long, value_name = "SAMPLE")]
+    sample: String,
+
+    /// Unchanged lines shown around each change
+    #[arg(long, short = 'U', value_name = "LINES", default_value_t = 2)]
+    context: usize,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let Some(sample) = cx.sample(&args.sample)? else {
+        return Ok(cx.fail(Message::NoSample { name: &args.sample }));
+    };
+    let tokenizer = cx.tokenizer()?;
+    let Some(syntax) = tokenizer.syntax_for(&sample.path) else {
+        return Ok(cx.fail(Message::NoGrammarFor { path: &sample.path }));
+    };
+    let tokens = tokenizer.tokenize(syntax, &sample.path, &sample.source)?;
+    let (before, after) = (Theme::load(&args.before)?, Theme::load(&args.after)?);
+    let (before, after) = (Resolver::new(&before), Resolver::new(&after));
+    let changes = diff::token_changes(&before, &after, &tokens);
+
+    let mut text = format!("--- {}\n+++ {}\n", args.before.display(), args.after.display());
+    let line_count = sample.source.lines().count();
+    text.push_str(&ansi::token_diff(&before, &after, &tokens, &changes, line_count, args.context));
+    text.push_str(&format!("{} of {} token(s) in {} changed\n", changes.len(), tokens.len(), sample.path.display()));
+
+    let json = json!({
+        "sample": sample.path,
+        "tokens": tokens.len(),
+        "changes": changes
+            .iter()
+            .map(|change| {
+                let token = &tokens[change.index];
+                json!({
+                    "line": token.line,
+                    "column": token.column,
+                    "text": token.text,
+                    "scope": token.scopes.last(),
+                    "before": change.before.to_string(),
+                    "after": change.after.to_string(),
+                })
+            })
+            .collect::<Vec<_>>(),
+    });
+    cx.print(&text, json);
+    Ok(ExitCode::SUCCESS)
+}
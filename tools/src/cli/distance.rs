@@ -0,0 +1,65 @@
+use std::fmt::Write;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::colorblind::MIN_DISTANCE;
+use crate::distance::Checker;
+use crate::i18n::Message;
+use crate::resolver::Resolver;
+use crate::{ansi, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// ΔE (OKLab) below which two colors on one line count as the same
+    #[arg(long, default_value_t = MIN_DISTANCE)]
+    threshold: f64,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let resolver = Resolver::new(&cx.load_theme()?);
+    let tokenizer = cx.tokenizer()?;
+    let mut checker = Checker::new(&resolver);
+    let samples = cx.samples(None)?;
+    for (sample, tokens) in samples.iter().zip(tokenizer.tokenize_all(&samples)?) {
+        if let Some(tokens) = tokens {
+            checker.add(&sample.path, &tokens);
+        }
+    }
+    let near = checker.below(args.threshold);
+
+    let mut text = String::new();
+    for pair in &near {
+        let _ = writeln!(
+            text,
+            "{} {}  {} {}  ΔE {:.3}  {} / {}  {} line(s), first {}:{}",
+            ansi::swatch(&resolver, &pair.a),
+            pair.a,
+            ansi::swatch(&resolver, &pair.b),
+            pair.b,
+            pair.distance,
+            pair.a_scope,
+            pair.b_scope,
+            pair.lines,
+            pair.path.display(),
+            pair.line
+        );
+    }
+    let _ = writeln!(text, "{}", cx.say(Message::NearPairs { pairs: near.len(), threshold: args.threshold }));
+    let json = json!({
+        "threshold": args.threshold,
+        "pairs": near
+            .iter()
+            .map(|pair| json!({
+                "a": { "color": pair.a, "scope": pair.a_scope },
+                "b": { "color": pair.b, "scope": pair.b_scope },
+                "distance": pair.distance,
+                "lines": pair.lines,
+                "first": { "path": pair.path, "line": pair.line },
+            }))
+            .collect::<Vec<_>>(),
+    });
+    cx.print(&text, json);
+    Ok(status(near.is_empty()))
+}
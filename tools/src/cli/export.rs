@@ -0,0 +1,32 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::export;
+use crate::i18n::Message;
+use crate::Result;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Output directory [default: target/dist]
+    #[arg(long, value_name = "DIR")]
+    out: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let out = args.out.unwrap_or_else(|| cx.root.join("target/dist"));
+    let mut written = Vec::new();
+    for (stem, theme) in cx.themes()? {
+        written.extend(export::write(&theme, &stem, &out, &cx.config.export.targets)?);
+    }
+
+    let mut text = String::new();
+    for path in &written {
+        let _ = writeln!(text, "{}", cx.say(Message::Wrote { path }));
+    }
+    cx.print(&text, json!({ "written": written }));
+    Ok(ExitCode::SUCCESS)
+}
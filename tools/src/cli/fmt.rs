@@ -0,0 +1,54 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::i18n::Message;
+use crate::normalize::normalize;
+use crate::theme::Theme;
+use crate::{theme_paths, Error, Result, THEMES_DIR};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Theme files to format [default: `--theme`, else every theme under
+    /// src/themes]
+    #[arg(value_name = "PATH")]
+    paths: Vec<PathBuf>,
+
+    /// Write nothing; fail if any file is not normalized
+    #[arg(long)]
+    check: bool,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let paths = match (args.paths.is_empty(), &cx.theme) {
+        (false, _) => args.paths,
+        (true, Some(path)) => vec![path.clone()],
+        (true, None) => theme_paths(&cx.root.join(THEMES_DIR))?,
+    };
+
+    let mut text = String::new();
+    let mut unformatted = Vec::new();
+    for path in paths {
+        let formatted = normalize(&Theme::load(&path)?).to_json();
+        if fs::read_to_string(&path).map_err(|e| Error::io(&path, e))? == formatted {
+            continue;
+        }
+        if args.check {
+            let _ = writeln!(text, "{}", path.display());
+        } else {
+            fs::write(&path, formatted).map_err(|e| Error::io(&path, e))?;
+            let _ = writeln!(text, "{}", cx.say(Message::Formatted { path: &path }));
+        }
+        unformatted.push(path);
+    }
+    if args.check && !unformatted.is_empty() {
+        let _ = writeln!(text, "{}", cx.say(Message::Unformatted { count: unformatted.len() }));
+    }
+    let key = if args.check { "unformatted" } else { "formatted" };
+    cx.print(&text, json!({ key: unformatted }));
+    Ok(status(!args.check || unformatted.is_empty()))
+}
@@ -0,0 +1,89 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::colors::Rgba;
+use crate::i18n::Message;
+use crate::palette::{self, Palette, Role, OVERLAYS};
+use crate::theme::ThemeKind;
+use crate::{contrast, Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Output file [default: target/invert/zenn-<kind>-draft.json]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+
+    /// Contrast every role is lifted to [default: `contrast.threshold`,
+    /// else 4.5]
+    #[arg(long)]
+    target: Option<f64>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let target = args.target.unwrap_or(cx.config.contrast.threshold);
+    let base = Palette::zenn();
+    let draft = palette::invert(&base, target);
+    let kind = if draft.background.relative_luminance() < 0.5 { ThemeKind::Dark } else { ThemeKind::Light };
+    let (suffix, label) = match kind {
+        ThemeKind::Dark => ("dark", "Dark"),
+        ThemeKind::Light => ("light", "Light"),
+    };
+    let name = format!("zenn-{suffix}-draft");
+    let theme = palette::generate_kind(&name, &format!("Zenn {label} (draft)"), kind, &draft);
+    // The hand-tuned variant of the same kind, for comparison
+    let shipped = OVERLAYS.iter().find(|o| o.kind == kind).map(|o| (o.name, o.palette(&base)));
+
+    let out = args.out.unwrap_or_else(|| cx.root.join(format!("target/invert/{name}.json")));
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(&out, theme.to_json()).map_err(|e| Error::io(&out, e))?;
+
+    let entries = contrast::check(&theme);
+    let failing = entries.iter().filter(|e| e.ratio < target).count();
+
+    let mut text = String::new();
+    let _ = write!(text, "{:<12} {:<9} {:<9}", "role", "base", "draft");
+    if let Some((name, _)) = shipped {
+        let _ = write!(text, " {name:<11} ΔE");
+    }
+    text.push('\n');
+    for role in Role::ALL {
+        let (from, to) = (base.get(role), draft.get(role));
+        let _ = write!(text, "{:<12} {from:<9} {to:<9}", format!("{role:?}").to_lowercase());
+        if let Some((_, palette)) = &shipped {
+            let _ = write!(text, " {:<11} {:.3}", palette.get(role), to.oklab_distance(palette.get(role)));
+        }
+        text.push('\n');
+    }
+    text.push('\n');
+    for e in entries.iter().filter(|e| e.ratio < target) {
+        let _ = writeln!(text, "FAIL {:>6.2}:1  {} on {}  {}", e.ratio, e.foreground, e.background, e.label);
+    }
+    let _ = writeln!(text, "{}", cx.say(Message::ContrastFailing { failing, total: entries.len(), threshold: target }));
+    let _ = writeln!(text, "{}", cx.say(Message::Wrote { path: &out }));
+
+    let hex = |color: Rgba| color.to_hex();
+    let json = json!({
+        "kind": suffix,
+        "target": target,
+        "written": out,
+        "roles": Role::ALL
+            .iter()
+            .map(|&role| json!({
+                "role": format!("{role:?}").to_lowercase(),
+                "base": hex(base.get(role)),
+                "draft": hex(draft.get(role)),
+                "shipped": shipped.as_ref().map(|(_, palette)| hex(palette.get(role))),
+            }))
+            .collect::<Vec<_>>(),
+        "failing": failing,
+    });
+    cx.print(&text, json);
+    Ok(status(failing == 0))
+}
@@ -0,0 +1,28 @@
+use std::fmt::Write;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::i18n::Message;
+use crate::inventory;
+use crate::lint;
+use crate::Result;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {}
+
+pub fn run(cx: &Context, _args: Args) -> Result<ExitCode> {
+    let path = cx.theme_path();
+    let theme = cx.load_theme()?;
+    let lints = lint::lint(&theme, &inventory::known_scopes(&cx.tokenizer()?));
+
+    let mut text = String::new();
+    for lint in &lints {
+        let _ = writeln!(text, "warning: {lint}");
+    }
+    let _ = writeln!(text, "{}", cx.say(Message::LintWarnings { count: lints.len(), path: &path }));
+    let warnings: Vec<String> = lints.iter().map(ToString::to_string).collect();
+    cx.print(&text, json!({ "theme": path, "warnings": warnings }));
+    Ok(status(lints.is_empty()))
+}
@@ -0,0 +1,260 @@
+//! The `--watch` modes: samples stay tokenized between saves, so a change
+//! re-tokenizes only the files that changed, and a theme edit only
+//! re-resolves. Tokens also go through the results cache, so a session
+//! starts from where the last one left off.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::audit::{self, FileReport, Report};
+use crate::cache::Cache;
+use crate::contrast;
+use crate::i18n::{self, Message};
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::theme::Theme;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::watch::Watcher;
+use crate::{Error, Result, SAMPLES_DIR};
+
+pub struct Live {
+    tokenizer: Tokenizer,
+    cache: Cache,
+    theme_path: PathBuf,
+    samples_dir: PathBuf,
+    pub theme: Theme,
+    /// Sorted by path; `None` tokens for samples without a grammar.
+    pub samples: Vec<(Sample, Option<Vec<Token>>)>,
+}
+
+impl Live {
+    pub fn new(cx: &Context, samples_dir: Option<&Path>) -> Result<Self> {
+        let samples_dir = samples_dir.map_or_else(|| cx.root.join(SAMPLES_DIR), Path::to_path_buf);
+        let samples_dir = fs::canonicalize(&samples_dir).map_err(|e| Error::io(&samples_dir, e))?;
+        let theme_path = cx.theme_path();
+        let theme_path = fs::canonicalize(&theme_path).map_err(|e| Error::io(&theme_path, e))?;
+        let mut live = Self {
+            tokenizer: cx.tokenizer()?,
+            cache: cx.cache(),
+            theme: Theme::load(&theme_path)?,
+            theme_path,
+            samples: Vec::new(),
+            samples_dir,
+        };
+        let samples = cx.samples(Some(&live.samples_dir))?;
+        let tokens = live.cache.tokenize_all(&live.tokenizer, &samples)?;
+        live.samples = samples.into_iter().zip(tokens).collect();
+        Ok(live)
+    }
+
+    /// Watches the themes and the samples, calling `report` once up front and
+    /// again after every batch of saves. Runs until interrupted; errors
+    /// after the first report are printed and watching goes on.
+    pub fn watch(mut self, cx: &Context, mut report: impl FnMut(&Live, &[PathBuf]) -> Result<()>) -> Result<ExitCode> {
+        // The whole directory, since editors save by renaming over the file
+        let themes_dir = self.theme_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let watcher = Watcher::new(&[themes_dir, self.samples_dir.clone()])?;
+        report(&self, &[])?;
+        while let Some(batch) = watcher.next_batch() {
+            // A half-saved file should not end the session
+            let (changed, errors) = self.apply(cx, &batch);
+            for err in &errors {
+                eprintln!("{}", i18n::error_line(err, cx.lang));
+            }
+            if changed.is_empty() {
+                continue;
+            }
+            if let Err(err) = report(&self, &changed) {
+                eprintln!("{}", i18n::error_line(&err, cx.lang));
+            }
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+
+    /// Reloads what `paths` touched and returns the theme and sample paths
+    /// that changed, with an error for each file that could not be
+    /// reloaded. Those keep their previous contents, the rest of the batch
+    /// still applies.
+    fn apply(&mut self, cx: &Context, paths: &[PathBuf]) -> (Vec<PathBuf>, Vec<Error>) {
+        let (mut changed, mut errors) = (Vec::new(), Vec::new());
+        for path in paths {
+            if path.parent() == self.theme_path.parent() {
+                if path.extension().is_some_and(|ext| ext == "json") {
+                    if path == &self.theme_path {
+                        match Theme::load(path) {
+                            Ok(theme) => self.theme = theme,
+                            Err(err) => errors.push(err),
+                        }
+                    }
+                    changed.push(path.clone());
+                }
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&self.samples_dir) else { continue };
+            match self.reload(cx, path, relative) {
+                Ok(true) => changed.push(relative.to_path_buf()),
+                Ok(false) => {}
+                Err(err) => errors.push(err),
+            }
+        }
+        (changed, errors)
+    }
+
+    /// Brings the sample at `relative` up to date with `path` on disk and
+    /// returns whether it changed.
+    fn reload(&mut self, cx: &Context, path: &Path, relative: &Path) -> Result<bool> {
+        let existing = self.samples.binary_search_by(|(s, _)| s.path.as_path().cmp(relative));
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            // Deleted, or a directory
+            Err(err) if err.kind() == io::ErrorKind::NotFound || path.is_dir() => {
+                let Ok(index) = existing else { return Ok(false) };
+                self.samples.remove(index);
+                return Ok(true);
+            }
+            Err(err) => return Err(Error::io(path, err)),
+        };
+        let sample = Sample { path: relative.to_path_buf(), source };
+        if cx.config.samples.filter(vec![sample.clone()]).is_empty() {
+            return Ok(false);
+        }
+        // Saves that leave the source as it was cost no tokenizing
+        if existing.is_ok_and(|index| self.samples[index].0.source == sample.source) {
+            return Ok(false);
+        }
+        let tokens = self.cache.tokens(&self.tokenizer, &sample)?;
+        match existing {
+            Ok(index) => self.samples[index] = (sample, tokens),
+            Err(index) => self.samples.insert(index, (sample, tokens)),
+        }
+        Ok(true)
+    }
+
+    pub fn audit(&self, cx: &Context) -> Report {
+        let resolver = Resolver::new(&self.theme);
+        let files = self
+            .samples
+            .iter()
+            .map(|(sample, tokens)| {
+                let file = match tokens {
+                    Some(tokens) => {
+                        let mut unthemed = audit::file(&resolver, tokens);
+                        unthemed.retain(|scope, _| !cx.config.audit.allows(scope));
+                        FileReport::Audited { unthemed }
+                    }
+                    None => FileReport::NoGrammar,
+                };
+                (sample.path.clone(), file)
+            })
+            .collect();
+        Report { files }
+    }
+}
+
+/// Prints the coverage and contrast summary after a change, naming the
+/// unthemed scopes that were not there before.
+pub fn summarize(cx: &Context, live: &Live, changed: &[PathBuf], seen: &mut Option<BTreeSet<String>>) {
+    let report = live.audit(cx);
+    let scopes: BTreeSet<String> = report
+        .files
+        .iter()
+        .flat_map(|(_, file)| match file {
+            FileReport::Audited { unthemed } => unthemed.keys().cloned().collect(),
+            FileReport::NoGrammar => Vec::new(),
+        })
+        .collect();
+    let new: Vec<&String> = match seen.as_ref() {
+        Some(before) => scopes.difference(before).collect(),
+        None => Vec::new(),
+    };
+
+    let threshold = cx.config.contrast.threshold;
+    let entries = contrast::check(&live.theme);
+    let failing = entries.iter().filter(|e| e.ratio < threshold).count();
+
+    let mut text = String::new();
+    for path in changed {
+        let _ = writeln!(text, "{}", cx.say(Message::Changed { path }));
+    }
+    for scope in &new {
+        let at = report.files.iter().find_map(|(path, file)| match file {
+            FileReport::Audited { unthemed } => unthemed.get(*scope).map(|e| (path, e)),
+            FileReport::NoGrammar => None,
+        });
+        if let Some((path, e)) = at {
+            let location = format!("{}:{}:{}", path.display(), e.line, e.column);
+            let _ =
+                writeln!(text, "{}", cx.say(Message::NewUnthemed { scope, location: &location, example: &e.example }));
+        }
+    }
+    let unthemed = cx.say(Message::Unthemed { scopes: report.unthemed_count(), files: report.files.len() });
+    let contrast = cx.say(Message::ContrastFailing { failing, total: entries.len(), threshold });
+    let _ = writeln!(text, "{unthemed}; {contrast}");
+    cx.print(
+        &text,
+        json!({
+            "changed": changed,
+            "new_unthemed": new,
+            "unthemed": report.unthemed_count(),
+            "contrast_failing": failing,
+        }),
+    );
+    *seen = Some(scopes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Format;
+    use crate::config::Config;
+    use crate::i18n::Lang;
+
+    #[test]
+    fn a_batch_applies_every_file_it_can() {
+        let root = std::env::temp_dir().join(format!("zenn-live-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (themes, samples) = (root.join("themes"), root.join("samples"));
+        fs::create_dir_all(&themes).unwrap();
+        fs::create_dir_all(&samples).unwrap();
+        fs::write(themes.join("t.json"), r#"{ "name": "t", "tokenColors": [] }"#).unwrap();
+        fs::write(samples.join("a.rs"), "fn a() {}\n").unwrap();
+        fs::write(samples.join("b.py"), "b = 1\n").unwrap();
+        let cx = Context {
+            root: root.clone(),
+            config: Config::default(),
+            theme: Some(themes.join("t.json")),
+            format: Format::Text,
+            lang: Lang::En,
+            no_cache: true,
+        };
+        let mut live = Live::new(&cx, Some(&samples)).unwrap();
+        let dir = live.samples_dir.clone();
+        let paths = |live: &Live| live.samples.iter().map(|(s, _)| s.path.clone()).collect::<Vec<_>>();
+        assert_eq!(paths(&live), [PathBuf::from("a.rs"), PathBuf::from("b.py")]);
+
+        fs::write(dir.join("a.rs"), "fn a() {}\n").unwrap();
+        let (changed, errors) = live.apply(&cx, &[dir.join("a.rs")]);
+        assert!(changed.is_empty() && errors.is_empty());
+
+        fs::write(dir.join("a.rs"), "fn a() { 1 }\n").unwrap();
+        fs::write(dir.join("c.rs"), "fn c() {}\n").unwrap();
+        fs::remove_file(dir.join("b.py")).unwrap();
+        fs::write(dir.join("d.rs"), [0xff, 0xfe]).unwrap();
+        let batch = ["d.rs", "a.rs", "b.py", "c.rs"].map(|name| dir.join(name));
+        let (changed, errors) = live.apply(&cx, &batch);
+        assert_eq!(changed, [PathBuf::from("a.rs"), PathBuf::from("b.py"), PathBuf::from("c.rs")]);
+        assert!(matches!(errors.as_slice(), [Error::Io { path, .. }] if path.ends_with("d.rs")), "{errors:?}");
+        assert_eq!(paths(&live), [PathBuf::from("a.rs"), PathBuf::from("c.rs")]);
+        let (sample, tokens) = &live.samples[0];
+        assert_eq!(sample.source, "fn a() { 1 }\n");
+        assert!(tokens.as_ref().is_some_and(|t| t.iter().any(|t| t.text == "1")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,369 @@
+//! The `tools` command tree: one binary for day-to-day theme work, with
+//! `--theme` and `--format` meaning the same thing in every subcommand.
+//! Defaults come from `tools.toml`; flags override them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use tracing_subscriber::EnvFilter;
+
+use crate::cache::{Cache, CACHE_DIR};
+use crate::config::Config;
+use crate::i18n::{self, Lang, Message};
+use crate::resolver::Resolver;
+use crate::samples::{self, Sample};
+use crate::theme::Theme;
+use crate::tokenizer::Tokenizer;
+use crate::{repo_root, theme_paths, Result, SAMPLES_DIR, THEMES_DIR, THEME_PATH};
+
+mod article;
+mod audit;
+mod colorblind;
+mod compare;
+mod contrast;
+mod coverage;
+mod diff;
+mod diff_tokens;
+mod distance;
+mod export;
+mod fmt;
+mod invert;
+mod lint;
+mod live;
+mod optimize;
+mod parity;
+mod preview;
+mod reference;
+mod report;
+mod repro;
+#[cfg(feature = "screenshots")]
+mod screenshots;
+mod semantic_diff;
+#[cfg(feature = "serve")]
+mod serve;
+mod snapshot;
+mod snippet_gen;
+mod stats;
+
+#[derive(Debug, Parser)]
+#[command(name = "tools", about = "Tooling for the Zenn Shiki theme")]
+pub struct Cli {
+    /// Theme JSON to work on [default: src/themes/zenn.json; preview and
+    /// export take every theme under src/themes]
+    #[arg(long, global = true, value_name = "PATH")]
+    pub theme: Option<PathBuf>,
+
+    /// How reports are printed
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub format: Format,
+
+    /// Language of headings, summaries and errors; JSON, SARIF and GitHub
+    /// output stay in English
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub lang: Lang,
+
+    /// Log to stderr what the tools do: -v for debug, -vv for trace, which
+    /// includes the candidate rules of every scope resolution. Without it
+    /// `RUST_LOG` applies, e.g. `RUST_LOG=zenn_theme_tools::resolver=trace`
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Recompute everything instead of reusing results kept under
+    /// target/tools-cache
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    /// SARIF 2.1.0 findings, for code-scanning uploads; commands without
+    /// findings print JSON instead
+    Sarif,
+    /// GitHub Actions `::warning`/`::error` annotations on the sample lines
+    /// of audit and contrast findings; other commands print text
+    Github,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Report sample scopes that fall through to the default foreground
+    Audit(audit::Args),
+    /// Write the HTML preview of every sample, or print one in the terminal
+    Preview(preview::Args),
+    /// Write the theme as JSON, .tmTheme, Vim, Helix and terminal schemes
+    Export(export::Args),
+    /// Compare how the theme and a reference theme style the samples
+    Diff(diff::Args),
+    /// Print a sample as a colored diff of how two theme files highlight it
+    DiffTokens(diff_tokens::Args),
+    /// Check every foreground against the background for WCAG contrast
+    Contrast(contrast::Args),
+    /// Flag pairs of token colors too close to tell apart where they meet
+    Distance(distance::Args),
+    /// Draft the palette's opposite-kind variant by mirroring OKLCH lightness
+    Invert(invert::Args),
+    /// Side-by-side HTML of the tokens that changed between two revisions
+    Compare(compare::Args),
+    /// Check that our tokenizer styles the samples the way Shiki does
+    Parity(parity::Args),
+    /// Write an HTML reference of every rule: its scopes, style and a
+    /// sample line it colors
+    Reference(reference::Args),
+    /// Write one HTML page of unthemed scopes, contrast, dead rules and
+    /// close colors, as sortable tables
+    Report(report::Args),
+    /// Print the smallest sample snippet that tokenizes to a scope on its
+    /// own, ready to paste into an issue
+    Repro(repro::Args),
+    /// Serve a live-reloading preview
+    #[cfg(feature = "serve")]
+    Serve(serve::Args),
+    /// Check the golden token snapshots, or rewrite them
+    Snapshot(snapshot::Args),
+    /// Write one HTML page of a Zenn article's code blocks as the theme
+    /// highlights them
+    Article(article::Args),
+    /// List token colors that protanopia, deuteranopia or tritanopia make
+    /// indistinguishable, and write the samples as seen with each
+    Colorblind(colorblind::Args),
+    /// Write a languages × scope groups matrix of the rules the samples
+    /// exercise
+    Coverage(coverage::Args),
+    /// Capture the preview with headless Chrome and compare it against a
+    /// baseline; needs Chrome or Chromium
+    #[cfg(feature = "screenshots")]
+    Screenshots(screenshots::Args),
+    /// List the tokens of an annotated Rust file that semantic highlighting
+    /// colors differently from the TextMate rules
+    SemanticDiff(semantic_diff::Args),
+    /// Print a skeleton sample of the scopes a grammar can produce that its
+    /// samples never do
+    SnippetGen(snippet_gen::Args),
+    /// Rewrite theme files in normalized form, or check that they are
+    Fmt(fmt::Args),
+    /// Warn about selectors that are always overridden or match no grammar
+    /// scope
+    Lint(lint::Args),
+    /// Drop redundant rules and fields, checking every sample token keeps
+    /// its style
+    Optimize(optimize::Args),
+    /// Count the sample tokens each rule styles, with dead rules and the
+    /// scopes left to the default foreground
+    Stats(stats::Args),
+}
+
+/// What every subcommand gets besides its own arguments.
+pub struct Context {
+    pub root: PathBuf,
+    pub config: Config,
+    pub theme: Option<PathBuf>,
+    pub format: Format,
+    pub lang: Lang,
+    pub no_cache: bool,
+}
+
+impl Context {
+    fn theme_path(&self) -> PathBuf {
+        self.theme.clone().unwrap_or_else(|| self.root.join(THEME_PATH))
+    }
+
+    fn load_theme(&self) -> Result<Theme> {
+        Theme::load(&self.theme_path())
+    }
+
+    /// The `--theme` file, or every shipped theme, by file stem.
+    fn themes(&self) -> Result<Vec<(String, Theme)>> {
+        let paths = match &self.theme {
+            Some(path) => vec![path.clone()],
+            None => theme_paths(&self.root.join(THEMES_DIR))?,
+        };
+        paths
+            .iter()
+            .map(|path| {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                Theme::load(path).map(|theme| (stem, theme))
+            })
+            .collect()
+    }
+
+    /// The samples under `dir` that `[samples]` selects.
+    fn samples(&self, dir: Option<&Path>) -> Result<Vec<Sample>> {
+        let samples = samples::discover(&dir.map_or_else(|| self.root.join(SAMPLES_DIR), Path::to_path_buf))?;
+        Ok(self.config.samples.filter(samples))
+    }
+
+    /// The selected sample at `name`, with or without its extension, e.g.
+    /// `rust/grpc`.
+    fn sample(&self, name: &str) -> Result<Option<Sample>> {
+        let name = Path::new(name);
+        Ok(self.samples(None)?.into_iter().find(|s| s.path == name || s.path.with_extension("") == name))
+    }
+
+    /// Where the samples under `dir` (default [`SAMPLES_DIR`]) are, relative
+    /// to the repository when inside it, for reports that point at files.
+    fn samples_base(&self, dir: Option<&Path>) -> PathBuf {
+        let canonical = |path: &Path| fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let dir = canonical(&dir.map_or_else(|| self.root.join(SAMPLES_DIR), Path::to_path_buf));
+        dir.strip_prefix(canonical(&self.root)).map_or(dir.clone(), Path::to_path_buf)
+    }
+
+    /// `message` in the `--lang` language.
+    fn say(&self, message: Message<'_>) -> String {
+        message.text(self.lang)
+    }
+
+    /// Prints `message` as an error and returns the failing exit code.
+    fn fail(&self, message: Message<'_>) -> ExitCode {
+        eprintln!("{}{}", i18n::error_prefix(self.lang), self.say(message));
+        ExitCode::FAILURE
+    }
+
+    fn tokenizer(&self) -> Result<Tokenizer> {
+        Tokenizer::discover(&self.root)
+    }
+
+    /// The results cache, unless `--no-cache` is given.
+    fn cache(&self) -> Cache {
+        if self.no_cache {
+            Cache::disabled()
+        } else {
+            Cache::new(self.root.join(CACHE_DIR))
+        }
+    }
+
+    /// Prints `text` or `json`, whichever `--format` asks for.
+    fn print(&self, text: &str, json: serde_json::Value) {
+        match self.format {
+            Format::Text | Format::Github => print!("{text}"),
+            Format::Json | Format::Sarif => {
+                println!("{}", serde_json::to_string_pretty(&json).expect("reports serialize"))
+            }
+        }
+    }
+}
+
+pub fn run(cli: Cli) -> Result<ExitCode> {
+    init_logging(cli.verbose);
+    let root = repo_root();
+    let cx = Context {
+        config: Config::load(&root)?,
+        root,
+        theme: cli.theme,
+        format: cli.format,
+        lang: cli.lang,
+        no_cache: cli.no_cache,
+    };
+    match cli.command {
+        Command::Audit(args) => audit::run(&cx, args),
+        Command::Preview(args) => preview::run(&cx, args),
+        Command::Export(args) => export::run(&cx, args),
+        Command::Diff(args) => diff::run(&cx, args),
+        Command::DiffTokens(args) => diff_tokens::run(&cx, args),
+        Command::Contrast(args) => contrast::run(&cx, args),
+        Command::Distance(args) => distance::run(&cx, args),
+        Command::Invert(args) => invert::run(&cx, args),
+        Command::Compare(args) => compare::run(&cx, args),
+        Command::Parity(args) => parity::run(&cx, args),
+        Command::Reference(args) => reference::run(&cx, args),
+        Command::Report(args) => report::run(&cx, args),
+        Command::Repro(args) => repro::run(&cx, args),
+        #[cfg(feature = "serve")]
+        Command::Serve(args) => serve::run(&cx, args),
+        Command::Snapshot(args) => snapshot::run(&cx, args),
+        Command::Article(args) => article::run(&cx, args),
+        Command::Colorblind(args) => colorblind::run(&cx, args),
+        Command::Coverage(args) => coverage::run(&cx, args),
+        #[cfg(feature = "screenshots")]
+        Command::Screenshots(args) => screenshots::run(&cx, args),
+        Command::SemanticDiff(args) => semantic_diff::run(&cx, args),
+        Command::SnippetGen(args) => snippet_gen::run(&cx, args),
+        Command::Fmt(args) => fmt::run(&cx, args),
+        Command::Lint(args) => lint::run(&cx, args),
+        Command::Optimize(args) => optimize::run(&cx, args),
+        Command::Stats(args) => stats::run(&cx, args),
+    }
+}
+
+/// Sends `tracing` events to stderr, filtered by `-v` or else `RUST_LOG`; only
+/// warnings by default.
+fn init_logging(verbose: u8) {
+    let filter = match verbose {
+        0 => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        1 => EnvFilter::new("zenn_theme_tools=debug"),
+        _ => EnvFilter::new("zenn_theme_tools=trace"),
+    };
+    let _ = tracing_subscriber::fmt().with_env_filter(filter).with_writer(std::io::stderr).without_time().try_init();
+}
+
+/// `FAILURE` when a check found problems.
+fn status(ok: bool) -> ExitCode {
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// A GitHub Actions workflow command that annotates `file` at `line` and
+/// `column` (1-based; 0 for the whole file) with `message`.
+fn annotation(level: &str, file: &Path, line: usize, column: usize, title: &str, message: &str) -> String {
+    // Workflow commands end at a newline, and properties at `,` or `:`
+    let data = |text: &str| text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A");
+    let property = |text: &str| data(text).replace(':', "%3A").replace(',', "%2C");
+    let mut line_properties = String::new();
+    if line > 0 {
+        line_properties = format!(",line={line}");
+        if column > 0 {
+            line_properties.push_str(&format!(",col={column}"));
+        }
+    }
+    format!(
+        "::{level} file={}{line_properties},title={}::{}",
+        property(&file.to_string_lossy().replace('\\', "/")),
+        property(title),
+        data(message)
+    )
+}
+
+fn resolvers(themes: &[(String, Theme)]) -> Vec<(String, Resolver)> {
+    themes.iter().map(|(name, theme)| (name.clone(), Resolver::new(theme))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn global_flags_reach_every_subcommand() {
+        Cli::command().debug_assert();
+        let cli = Cli::parse_from(["tools", "contrast", "--theme", "x.json", "--format", "json", "--threshold", "7"]);
+        assert_eq!(cli.theme.as_deref(), Some(Path::new("x.json")));
+        assert_eq!(cli.format, Format::Json);
+        assert!(matches!(cli.command, Command::Contrast(_)));
+        assert!(!cli.no_cache);
+        assert!(Cli::parse_from(["tools", "audit", "--no-cache"]).no_cache);
+        assert_eq!(Cli::parse_from(["tools", "export", "-vv"]).verbose, 2);
+        assert_eq!(Cli::parse_from(["tools", "audit", "--lang", "ja"]).lang, Lang::Ja);
+        let cli = Cli::parse_from(["tools", "stats", "--format", "json", "--no-cache", "--lang", "ja"]);
+        assert!(matches!(cli.command, Command::Stats(_)) && cli.no_cache && cli.format == Format::Json);
+    }
+
+    #[test]
+    fn annotations_escape_their_properties_and_message() {
+        assert_eq!(
+            annotation("warning", Path::new("src/a,b.rs"), 3, 7, "Contrast: low", "50% of\nit"),
+            "::warning file=src/a%2Cb.rs,line=3,col=7,title=Contrast%3A low::50%25 of%0Ait"
+        );
+        assert_eq!(annotation("error", Path::new("t.json"), 0, 0, "x", "y"), "::error file=t.json,title=x::y");
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::i18n::Message;
+use crate::optimize::{self, Difference};
+use crate::theme::Theme;
+use crate::{Error, Result};
+
+/// How many differing scope stacks are listed before giving up.
+const LIMIT: usize = 20;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Replace the theme file once every sample token is shown to resolve
+    /// to the same style
+    #[arg(long)]
+    write: bool,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let path = cx.theme_path();
+    let theme = cx.load_theme()?;
+    let optimized = optimize::optimize(&theme);
+
+    let samples = cx.samples(None)?;
+    let tokenized = cx.cache().tokenize_all(&cx.tokenizer()?, &samples)?;
+    let stacks: BTreeSet<&[String]> = tokenized.iter().flatten().flatten().map(|t| t.scopes.as_slice()).collect();
+    let differences = optimize::equivalent(&theme, &optimized, stacks.iter().copied(), LIMIT);
+
+    let before = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+    let after = optimized.to_json();
+    let size = |json: &str, theme: &Theme| {
+        let selectors = theme.token_colors.iter().map(|r| r.selectors().len()).sum::<usize>();
+        (json.len(), theme.token_colors.len(), selectors)
+    };
+    let (size_before, size_after) = (size(&before, &theme), size(&after, &optimized));
+
+    let mut text = String::new();
+    let _ = writeln!(text, "{}", cx.say(Message::OptimizedSize { before: size_before, after: size_after }));
+    let _ = writeln!(text, "{}", cx.say(Message::StacksChecked { stacks: stacks.len() }));
+    let written = differences.is_empty() && args.write && before != after;
+    if written {
+        fs::write(&path, &after).map_err(|e| Error::io(&path, e))?;
+        let _ = writeln!(text, "{}", cx.say(Message::Wrote { path: &path }));
+    }
+    for Difference { scopes, before, after } in &differences {
+        let _ = writeln!(text, "  {}: {before} -> {after}", scopes.join(" "));
+    }
+    if !differences.is_empty() {
+        let _ = writeln!(text, "{}", cx.say(Message::NotOptimized { tokens: differences.len() }));
+    }
+
+    let json = json!({
+        "theme": path,
+        "before": { "bytes": size_before.0, "rules": size_before.1, "selectors": size_before.2 },
+        "after": { "bytes": size_after.0, "rules": size_after.1, "selectors": size_after.2 },
+        "stacks": stacks.len(),
+        "written": written,
+        "differences": differences.iter().map(|d| json!({
+            "scopes": d.scopes,
+            "before": d.before,
+            "after": d.after,
+        })).collect::<Vec<_>>(),
+    });
+    cx.print(&text, json);
+    Ok(status(differences.is_empty()))
+}
@@ -0,0 +1,109 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::i18n::Message;
+use crate::parity::{self, Kind, Mismatch};
+use crate::resolver::Resolver;
+use crate::Result;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Sample directory [default: src/sampleCodes]
+    #[arg(long, value_name = "DIR")]
+    samples: Option<PathBuf>,
+
+    /// Mismatches listed per sample
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    limit: usize,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme_path = cx.theme_path();
+    let resolver = Resolver::new(&cx.load_theme()?);
+    let (tokenizer, samples) = (cx.tokenizer()?, cx.samples(args.samples.as_deref())?);
+    let shiki = parity::shiki_tokens(&cx.root, &theme_path, &samples)?;
+
+    let mut reports = Vec::new();
+    let mut skipped = Vec::new();
+    for (sample, shiki) in samples.iter().zip(shiki) {
+        let (Some(syntax), Some(shiki)) = (tokenizer.syntax_for(&sample.path), shiki) else {
+            skipped.push(sample.path.clone());
+            continue;
+        };
+        let ours = tokenizer.tokenize(syntax, &sample.path, &sample.source)?;
+        reports.push(parity::compare(&sample.path, &resolver, &ours, &shiki));
+    }
+
+    let mut text = String::new();
+    for report in &reports {
+        let (style, scope) = (report.count(Kind::Style), report.count(Kind::Scope));
+        if style == 0 && scope == 0 {
+            continue;
+        }
+        let _ = writeln!(
+            text,
+            "{}: {style} of {} char(s) styled differently, {scope} scoped differently",
+            report.path.display(),
+            report.chars
+        );
+        let mut listed: Vec<&Mismatch> = report.mismatches.iter().filter(|m| m.kind == Kind::Style).collect();
+        listed.extend(report.mismatches.iter().filter(|m| m.kind == Kind::Scope));
+        for m in listed.into_iter().take(args.limit) {
+            let last = |scopes: &[String]| scopes.last().cloned().unwrap_or_default();
+            let _ = writeln!(
+                text,
+                "  {}:{} {:<5} `{}`  ours {} {}  shiki {} {}",
+                m.line,
+                m.column,
+                m.kind.name(),
+                m.text,
+                last(&m.ours),
+                m.our_look,
+                last(&m.shiki),
+                m.shiki_look
+            );
+        }
+    }
+    for path in &skipped {
+        let _ = writeln!(text, "{}", cx.say(Message::Skipped { path }));
+    }
+    let chars: usize = reports.iter().map(|r| r.chars).sum();
+    let styled: usize = reports.iter().map(|r| r.count(Kind::Style)).sum();
+    let scoped: usize = reports.iter().map(|r| r.count(Kind::Scope)).sum();
+    let _ = writeln!(
+        text,
+        "{styled} of {chars} char(s) styled differently from Shiki, {scoped} scoped differently, across {} sample(s)",
+        reports.len()
+    );
+
+    let json = json!({
+        "chars": chars,
+        "styled_differently": styled,
+        "scoped_differently": scoped,
+        "samples": reports
+            .iter()
+            .map(|r| json!({
+                "path": r.path,
+                "chars": r.chars,
+                "mismatches": r.mismatches
+                    .iter()
+                    .map(|m| json!({
+                        "kind": m.kind.name(),
+                        "line": m.line,
+                        "column": m.column,
+                        "text": m.text,
+                        "ours": { "scopes": m.ours, "style": m.our_look.to_string() },
+                        "shiki": { "scopes": m.shiki, "style": m.shiki_look.to_string() },
+                    }))
+                    .collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+        "skipped": skipped,
+    });
+    cx.print(&text, json);
+    Ok(status(styled == 0))
+}
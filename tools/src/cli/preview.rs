@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::live::{self, Live};
+use super::{resolvers, Context};
+use crate::i18n::Message;
+use crate::preview::{self, PreviewTheme};
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::tokenizer::Token;
+use crate::{ansi, html, Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Output file [default: target/preview/index.html]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+
+    /// Print one sample, e.g. `rust/grpc`, highlighted in the terminal
+    /// instead
+    #[arg(long, value_name = "SAMPLE", conflicts_with_all = ["out", "watch", "brackets", "inspect"])]
+    tty: Option<String>,
+
+    /// Keep running, rewriting the page and printing a coverage and
+    /// contrast summary on every save
+    #[arg(long)]
+    watch: bool,
+
+    /// Color brackets by nesting depth from the themes' bracket pair
+    /// colors, as Shiki's colorized-brackets transformer would
+    #[arg(long)]
+    brackets: bool,
+
+    /// Tag every token with its scopes and the theme rule behind its color,
+    /// shown on hover; makes the page several times larger
+    #[arg(long)]
+    inspect: bool,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    if let Some(name) = args.tty {
+        return print_tty(cx, &name);
+    }
+
+    let out = args.out.unwrap_or_else(|| cx.root.join("target/preview/index.html"));
+    let options = html::Options { brackets: args.brackets, inspect: args.inspect };
+    if args.watch {
+        let mut seen = None;
+        return Live::new(cx, None)?.watch(cx, |live, changed| {
+            let samples: Vec<(&Sample, Option<&[Token]>)> =
+                live.samples.iter().map(|(sample, tokens)| (sample, tokens.as_deref())).collect();
+            write(cx, &out, options, |themes| Ok(preview::render_tokenized(themes, &samples)))?;
+            live::summarize(cx, live, changed, &mut seen);
+            Ok(())
+        });
+    }
+
+    let (tokenizer, samples) = (cx.tokenizer()?, cx.samples(None)?);
+    write(cx, &out, options, |themes| preview::render(themes, &tokenizer, &samples))?;
+    cx.print(&format!("wrote {}\n", out.display()), json!({ "written": [out] }));
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Renders the page for the current themes and writes it to `out`.
+fn write(
+    cx: &Context,
+    out: &Path,
+    options: html::Options,
+    render: impl FnOnce(&[PreviewTheme<'_>]) -> Result<String>,
+) -> Result<()> {
+    let themes = resolvers(&cx.themes()?);
+    let themes: Vec<PreviewTheme<'_>> =
+        themes.iter().map(|(name, resolver)| PreviewTheme { name, resolver, options }).collect();
+    let page = render(&themes)?;
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(out, page).map_err(|e| Error::io(out, e))
+}
+
+fn print_tty(cx: &Context, name: &str) -> Result<ExitCode> {
+    let Some(sample) = cx.sample(name)? else {
+        return Ok(cx.fail(Message::NoSample { name }));
+    };
+    let tokenizer = cx.tokenizer()?;
+    let Some(syntax) = tokenizer.syntax_for(&sample.path) else {
+        return Ok(cx.fail(Message::NoGrammarFor { path: &sample.path }));
+    };
+    let tokens = tokenizer.tokenize(syntax, &sample.path, &sample.source)?;
+
+    let line_count = sample.source.lines().count();
+    let width = sample.source.lines().map(|l| l.chars().count()).max().unwrap_or(0) + 1;
+    print!("{}", ansi::highlight(&Resolver::new(&cx.load_theme()?), &tokens, line_count, width));
+    Ok(ExitCode::SUCCESS)
+}
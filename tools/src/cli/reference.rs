@@ -0,0 +1,38 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::i18n::Message;
+use crate::reference;
+use crate::{Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Output file [default: target/reference/index.html]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme = cx.load_theme()?;
+    let samples = cx.samples(None)?;
+    let tokenized = cx.cache().tokenize_all(&cx.tokenizer()?, &samples)?;
+    let entries = reference::collect(&theme, &samples, &tokenized);
+
+    let out = args.out.unwrap_or_else(|| cx.root.join("target/reference/index.html"));
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(&out, reference::to_html(&theme, &entries, cx.lang)).map_err(|e| Error::io(&out, e))?;
+
+    let unused = entries.iter().filter(|e| e.example.is_none()).count();
+    let mut text = cx.say(Message::DeadRules { rules: unused, total: entries.len() });
+    text.push('\n');
+    text.push_str(&cx.say(Message::Wrote { path: &out }));
+    text.push('\n');
+    cx.print(&text, json!({ "written": out, "rules": entries.len(), "unused": unused }));
+    Ok(ExitCode::SUCCESS)
+}
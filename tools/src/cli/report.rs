@@ -0,0 +1,68 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::audit::FileReport;
+use crate::colorblind::MIN_DISTANCE;
+use crate::i18n::Message;
+use crate::report::Health;
+use crate::{Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Output file [default: target/report/index.html]
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+
+    /// Minimum contrast ratio [default: `contrast.threshold`, else 4.5]
+    #[arg(long)]
+    threshold: Option<f64>,
+
+    /// ΔE (OKLab) below which two colors on one line count as the same
+    #[arg(long, default_value_t = MIN_DISTANCE)]
+    min_distance: f64,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme = cx.load_theme()?;
+    let samples = cx.samples(None)?;
+    let tokenized = cx.cache().tokenize_all(&cx.tokenizer()?, &samples)?;
+    let threshold = args.threshold.unwrap_or(cx.config.contrast.threshold);
+    let mut health = Health::collect(&theme, &samples, &tokenized, threshold, args.min_distance);
+    for (_, file) in &mut health.audit.files {
+        if let FileReport::Audited { unthemed } = file {
+            unthemed.retain(|scope, _| !cx.config.audit.allows(scope));
+        }
+    }
+
+    let out = args.out.unwrap_or_else(|| cx.root.join("target/report/index.html"));
+    if let Some(dir) = out.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(&out, health.to_html(&theme, cx.lang)).map_err(|e| Error::io(&out, e))?;
+
+    let dead = health.stats.dead_rules(&theme).count();
+    let mut text = String::new();
+    for message in [
+        Message::Unthemed { scopes: health.audit.unthemed_count(), files: health.audit.files.len() },
+        Message::ContrastFailing { failing: health.failing_contrast(), total: health.contrast.len(), threshold },
+        Message::DeadRules { rules: dead, total: theme.token_colors.len() },
+        Message::NearPairs { pairs: health.near.len(), threshold: args.min_distance },
+        Message::Wrote { path: &out },
+    ] {
+        let _ = writeln!(text, "{}", cx.say(message));
+    }
+    let json = json!({
+        "written": out,
+        "unthemed": health.audit.unthemed_count(),
+        "contrast_failing": health.failing_contrast(),
+        "dead_rules": dead,
+        "near_pairs": health.near.len(),
+    });
+    cx.print(&text, json);
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,81 @@
+use std::fmt::Write;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::i18n::Message;
+use crate::repro::{self, MAX_LINES};
+use crate::resolver::Resolver;
+use crate::Result;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Scope to reproduce; a prefix matches at dot boundaries, so
+    /// `keyword.control` finds `keyword.control.rust`
+    #[arg(long)]
+    scope: String,
+
+    /// Longest window of lines to try around each occurrence
+    #[arg(long, default_value_t = MAX_LINES)]
+    max_lines: usize,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let resolver = Resolver::new(&cx.load_theme()?);
+    let tokenizer = cx.tokenizer()?;
+    let samples = cx.samples(None)?;
+    let tokenized = cx.cache().tokenize_all(&tokenizer, &samples)?;
+    let Some(repro) = repro::find(&tokenizer, &samples, &tokenized, &args.scope, args.max_lines)? else {
+        let occurrences = repro::occurrences(&tokenized, &args.scope);
+        let message = match occurrences {
+            0 => Message::NoRepro { scope: &args.scope },
+            _ => Message::NoStandaloneRepro { scope: &args.scope, occurrences, max_lines: args.max_lines },
+        };
+        return Ok(cx.fail(message));
+    };
+
+    let style = resolver.resolve(&repro.token.scopes);
+    let rule = match style.foreground_rule {
+        Some(index) => match resolver.matched_selector(index, &repro.token.scopes) {
+            Some(selector) => format!("tokenColors[{index}] {selector}"),
+            None => format!("tokenColors[{index}]"),
+        },
+        None => "default foreground".to_owned(),
+    };
+    let path = cx.samples_base(None).join(&repro.path);
+    let (first, last) = repro.lines;
+    let language = repro.path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+
+    let mut text = String::new();
+    let range = match first == last {
+        true => first.to_string(),
+        false => format!("{first}-{last}"),
+    };
+    let _ = writeln!(text, "{}:{range}", path.display());
+    let _ = writeln!(text, "```{language}\n{}```", repro.snippet);
+    let _ = writeln!(
+        text,
+        "`{}`: {}\n{} ({rule}){}",
+        repro.token.text.trim(),
+        repro.token.scopes.join(" "),
+        style.foreground,
+        match style.font_style.is_empty() {
+            true => String::new(),
+            false => format!(", {}", style.font_style),
+        }
+    );
+    let json = json!({
+        "scope": args.scope,
+        "path": path,
+        "lines": [first, last],
+        "snippet": repro.snippet,
+        "token": repro.token.text,
+        "scopes": repro.token.scopes,
+        "foreground": style.foreground,
+        "font_style": style.font_style.to_string(),
+        "rule": rule,
+    });
+    cx.print(&text, json);
+    Ok(ExitCode::SUCCESS)
+}
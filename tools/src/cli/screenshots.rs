@@ -0,0 +1,126 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{resolvers, status, Context};
+use crate::html::Options;
+use crate::i18n::Message;
+use crate::preview::{self, PreviewTheme};
+use crate::samples::Sample;
+use crate::screenshot::{self, png_path};
+use crate::tokenizer::Token;
+use crate::{pixels, Error, Result};
+
+/// Per-channel difference ignored as antialiasing noise.
+const TOLERANCE: u8 = 8;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory for the page, the captures, the baseline and the diff
+    /// images [default: target/screenshots]
+    #[arg(long, value_name = "DIR")]
+    out: Option<PathBuf>,
+
+    /// Fraction of changed pixels above which a sample is flagged
+    #[arg(long, default_value_t = 0.001)]
+    threshold: f64,
+
+    /// Replace the baseline with the new captures
+    #[arg(long)]
+    update: bool,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let out = args.out.unwrap_or_else(|| cx.root.join("target/screenshots"));
+    let samples = cx.samples(None)?;
+    let page = out.join("index.html");
+    write_page(cx, &page, &samples)?;
+
+    let current = out.join("current");
+    remove_dir(&current)?;
+    let captured = screenshot::capture(&page, &samples, &current)?;
+    let mut text = cx.say(Message::Captured { count: captured.len(), path: &current });
+    text.push('\n');
+
+    let baseline = out.join("baseline");
+    if args.update || !baseline.exists() {
+        remove_dir(&baseline)?;
+        copy_dir(&current, &baseline)?;
+        let _ = writeln!(text, "{}", cx.say(Message::Updated { path: &baseline }));
+        cx.print(&text, json!({ "captured": captured.len(), "baseline": baseline, "updated": true }));
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let diff_dir = out.join("diff");
+    remove_dir(&diff_dir)?;
+    let (mut new, mut flagged) = (Vec::new(), Vec::new());
+    for sample in &samples {
+        let before_path = png_path(&baseline, &sample.path);
+        if !before_path.exists() {
+            let _ = writeln!(text, "new   {}", sample.path.display());
+            new.push(&sample.path);
+            continue;
+        }
+        let before = screenshot::read_png(&before_path)?;
+        let after = screenshot::read_png(&png_path(&current, &sample.path))?;
+        let diff = pixels::diff(&before, &after, TOLERANCE);
+        if diff.ratio() > args.threshold {
+            let path = png_path(&diff_dir, &sample.path);
+            screenshot::write_png(&path, &diff.mask)?;
+            let _ = writeln!(
+                text,
+                "FAIL  {}  {:.3}% of pixels changed, see {}",
+                sample.path.display(),
+                diff.ratio() * 100.0,
+                path.display()
+            );
+            flagged.push(json!({ "sample": sample.path, "ratio": diff.ratio(), "diff": path }));
+        }
+    }
+    let summary =
+        Message::ScreenshotsChanged { changed: flagged.len(), total: samples.len(), threshold: args.threshold };
+    let _ = writeln!(text, "{}", cx.say(summary));
+    let ok = flagged.is_empty();
+    cx.print(&text, json!({ "captured": captured.len(), "threshold": args.threshold, "new": new, "changed": flagged }));
+    Ok(status(ok))
+}
+
+/// The preview page of every theme, without brackets or inspector markup so
+/// the captures only change with the colors.
+fn write_page(cx: &Context, page: &Path, samples: &[Sample]) -> Result<()> {
+    let themes = resolvers(&cx.themes()?);
+    let themes: Vec<PreviewTheme<'_>> =
+        themes.iter().map(|(name, resolver)| PreviewTheme { name, resolver, options: Options::default() }).collect();
+    let tokenized = cx.cache().tokenize_all(&cx.tokenizer()?, samples)?;
+    let tokenized: Vec<(&Sample, Option<&[Token]>)> =
+        samples.iter().zip(&tokenized).map(|(s, t)| (s, t.as_deref())).collect();
+
+    if let Some(dir) = page.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    fs::write(page, preview::render_tokenized(&themes, &tokenized)).map_err(|e| Error::io(page, e))
+}
+
+fn remove_dir(dir: &Path) -> Result<()> {
+    match fs::remove_dir_all(dir) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(Error::io(dir, e)),
+        _ => Ok(()),
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to).map_err(|e| Error::io(to, e))?;
+    for entry in fs::read_dir(from).map_err(|e| Error::io(from, e))? {
+        let path = entry.map_err(|e| Error::io(from, e))?.path();
+        let target = to.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target).map_err(|e| Error::io(&path, e))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::i18n::Message;
+use crate::semantic_diff::{self, Divergence};
+use crate::theme::FontStyle;
+use crate::{semantic, Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Rust file annotated as `semantic::annotations` reads it [default:
+    /// tools/tests/fixtures/semantic.rs]
+    #[arg(long, value_name = "FILE")]
+    file: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme = cx.load_theme()?;
+    let file = args.file.unwrap_or_else(|| cx.root.join("tools/tests/fixtures/semantic.rs"));
+    let source = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+    let tokens = semantic::annotations(&source);
+    let divergences = semantic_diff::compare(&theme, &cx.tokenizer()?, &file, &source, &tokens)?;
+
+    let mut text = String::new();
+    for Divergence { token, scope, textmate, semantic } in &divergences {
+        let _ = writeln!(text, "{}:{}:{} `{}`", file.display(), token.line, token.column, token.text);
+        let _ = writeln!(text, "  textmate  {} {:<14} {scope}", textmate.0, style_name(textmate.1));
+        let _ = writeln!(
+            text,
+            "  semantic  {} {:<14} {}",
+            semantic.0,
+            style_name(semantic.1),
+            kind(&token.token_type, &token.modifiers)
+        );
+    }
+    let _ = writeln!(text, "{}", cx.say(Message::Diverging { diverging: divergences.len(), total: tokens.len() }));
+    let json = json!({
+        "file": file,
+        "checked": tokens.len(),
+        "divergences": divergences.iter().map(|d| json!({
+            "line": d.token.line,
+            "column": d.token.column,
+            "text": d.token.text,
+            "scope": d.scope,
+            "kind": kind(&d.token.token_type, &d.token.modifiers),
+            "textmate": { "foreground": d.textmate.0, "font_style": d.textmate.1.to_string() },
+            "semantic": { "foreground": d.semantic.0, "font_style": d.semantic.1.to_string() },
+        })).collect::<Vec<_>>(),
+    });
+    cx.print(&text, json);
+    Ok(status(divergences.is_empty()))
+}
+
+/// The token type and its modifiers, as a semantic selector spells them.
+fn kind(token_type: &str, modifiers: &[String]) -> String {
+    std::iter::once(token_type).chain(modifiers.iter().map(String::as_str)).collect::<Vec<_>>().join(".")
+}
+
+fn style_name(style: FontStyle) -> String {
+    if style.is_empty() {
+        "-".to_owned()
+    } else {
+        style.to_string()
+    }
+}
@@ -0,0 +1,39 @@
+use std::process::ExitCode;
+
+use super::Context;
+use crate::i18n::Message;
+use crate::serve::Event;
+use crate::{html, serve, Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Port on 127.0.0.1 [default: `serve.port`, else 3001, next to
+    /// `next dev`]
+    #[arg(long)]
+    port: Option<u16>,
+
+    /// Color brackets by nesting depth, as `preview --brackets` does
+    #[arg(long)]
+    brackets: bool,
+
+    /// Tag every token with its scopes and theme rule, shown on hover, as
+    /// `preview --inspect` does
+    #[arg(long)]
+    inspect: bool,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let addr = format!("127.0.0.1:{}", args.port.unwrap_or(cx.config.serve.port));
+    let runtime = tokio::runtime::Runtime::new().map_err(|source| Error::Listen { addr: addr.clone(), source })?;
+    let options = html::Options { brackets: args.brackets, inspect: args.inspect };
+    let (lang, url) = (cx.lang, format!("http://{addr}"));
+    let report = move |event| {
+        let message = match event {
+            Event::Listening => Message::Serving { url: &url },
+            Event::Rerendered { files, elapsed } => Message::Rerendered { files, elapsed },
+        };
+        println!("{}", message.text(lang));
+    };
+    runtime.block_on(serve::serve(&cx.root, &addr, options, report))?;
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,38 @@
+use std::fmt::Write;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::{status, Context};
+use crate::i18n::Message;
+use crate::resolver::Resolver;
+use crate::tokenizer::Tokenizer;
+use crate::{snapshot, Result, SNAPSHOTS_DIR};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Rewrite the snapshots instead of checking them
+    #[arg(long)]
+    update: bool,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme = cx.load_theme()?;
+    let dir = cx.root.join(SNAPSHOTS_DIR);
+    // The bundled grammars, as in tests/snapshots.rs, so both agree
+    let failures = snapshot::check(&Resolver::new(&theme), &Tokenizer::new(), &cx.samples(None)?, &dir, args.update)?;
+
+    let mut text = String::new();
+    for failure in &failures {
+        let _ = writeln!(text, "{failure}");
+    }
+    if args.update {
+        let _ = writeln!(text, "{}", cx.say(Message::Updated { path: &dir }));
+    } else if failures.is_empty() {
+        let _ = writeln!(text, "{}", cx.say(Message::SnapshotsUpToDate));
+    } else {
+        let _ = writeln!(text, "{}", cx.say(Message::SnapshotsOutdated { count: failures.len() }));
+    }
+    cx.print(&text, json!({ "updated": args.update, "failures": failures }));
+    Ok(status(failures.is_empty()))
+}
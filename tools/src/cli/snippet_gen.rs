@@ -0,0 +1,73 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::i18n::Message;
+use crate::inventory;
+use crate::tokenizer::Token;
+use crate::{Error, Result};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Grammar name or extension, e.g. `rust` or `rs`
+    #[arg(value_name = "LANGUAGE")]
+    language: String,
+
+    /// Also count samples in subdirectories such as `rust/*.rs`, not only
+    /// the top-level `rust.rs`
+    #[arg(long)]
+    include_variants: bool,
+
+    /// Write the skeleton here instead of printing it
+    #[arg(long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let tokenizer = cx.tokenizer()?;
+    let Some(syntax) = tokenizer.find_syntax(&args.language) else {
+        return Ok(cx.fail(Message::NoGrammarNamed { language: &args.language }));
+    };
+    let Some(definition) = tokenizer.definition(syntax) else {
+        return Ok(cx.fail(Message::NoGrammarNamed { language: &syntax.name }));
+    };
+
+    let samples: Vec<_> = cx
+        .samples(None)?
+        .into_iter()
+        .filter(|sample| args.include_variants || sample.path.components().count() == 1)
+        .filter(|sample| tokenizer.syntax_for(&sample.path).is_some_and(|s| s.scope == syntax.scope))
+        .collect();
+    if samples.is_empty() {
+        return Ok(cx.fail(Message::NoSampleUses { grammar: &syntax.name }));
+    }
+    let tokenized = cx.cache().tokenize_all(&tokenizer, &samples)?;
+    let tokens: Vec<&Token> = tokenized.iter().flatten().flatten().collect();
+    let checked: Vec<String> = samples.iter().map(|s| s.path.display().to_string()).collect();
+
+    let grammar = inventory::grammar_scopes(&definition);
+    let missing = inventory::missing(&grammar, &inventory::observed_scopes(tokens));
+    let extension = syntax.file_extensions.first().map_or("txt", String::as_str);
+    let skeleton = inventory::skeleton(&syntax.name, extension, &checked, &missing);
+    eprintln!("{}", cx.say(Message::GrammarScopes { exercised: grammar.len() - missing.len(), total: grammar.len() }));
+
+    let json = json!({
+        "grammar": syntax.name,
+        "checked": checked,
+        "scopes": grammar.len(),
+        "missing": missing.iter().map(|(scope, regex)| json!({ "scope": scope, "regex": regex })).collect::<Vec<_>>(),
+    });
+    match args.out {
+        Some(out) => {
+            fs::write(&out, skeleton).map_err(|e| Error::io(&out, e))?;
+            let mut json = json;
+            json["written"] = json!(out);
+            cx.print(&format!("{}\n", cx.say(Message::Wrote { path: &out })), json);
+        }
+        None => cx.print(&skeleton, json),
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,65 @@
+use std::cmp::Reverse;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::json;
+
+use super::Context;
+use crate::i18n::{label, Message};
+use crate::stats;
+use crate::Result;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Sample directory to count [default: src/sampleCodes]
+    #[arg(long, value_name = "DIR")]
+    samples: Option<PathBuf>,
+}
+
+pub fn run(cx: &Context, args: Args) -> Result<ExitCode> {
+    let theme = cx.load_theme()?;
+    let samples = cx.samples(args.samples.as_deref())?;
+    let tokenized = cx.cache().tokenize_all(&cx.tokenizer()?, &samples)?;
+    let stats = stats::count(&theme, tokenized.iter().flatten().map(Vec::as_slice));
+
+    let percent = |count: usize| 100.0 * count as f64 / stats.tokens.max(1) as f64;
+    let selectors = |i: usize| theme.token_colors[i].selectors().join(", ");
+    let mut ranked: Vec<usize> =
+        (0..theme.token_colors.len()).filter(|&i| !theme.token_colors[i].is_default()).collect();
+    ranked.sort_by_key(|&i| Reverse(stats.rules[i]));
+    let dead: Vec<usize> = stats.dead_rules(&theme).collect();
+    let mut fallback: Vec<(&String, &usize)> = stats.fallback.iter().collect();
+    fallback.sort_by_key(|&(_, count)| Reverse(*count));
+
+    let mut text = String::new();
+    let _ = writeln!(text, "{}", label("Tokens per rule", cx.lang));
+    for &i in &ranked {
+        let count = stats.rules[i];
+        let _ = writeln!(text, "  #{i:<3} {count:>7}  {:>5.1}%  {}", percent(count), selectors(i));
+    }
+    let _ = writeln!(text, "\n{}", cx.say(Message::DeadRules { rules: dead.len(), total: ranked.len() }));
+    for &i in &dead {
+        let _ = writeln!(text, "  #{i:<3} {}", selectors(i));
+    }
+    let (tokens, percent_fallback) = (stats.fallback_tokens(), percent(stats.fallback_tokens()));
+    let _ = writeln!(text, "\n{}", cx.say(Message::FallbackTokens { tokens, percent: percent_fallback }));
+    for (scope, count) in &fallback {
+        let _ = writeln!(text, "  {count:>7}  {scope}");
+    }
+    let _ = writeln!(text, "\n{}", cx.say(Message::TokensAcross { tokens: stats.tokens, samples: samples.len() }));
+
+    let json = json!({
+        "tokens": stats.tokens,
+        "samples": samples.len(),
+        "rules": ranked.iter().map(|&i| json!({
+            "rule": i,
+            "selectors": theme.token_colors[i].selectors(),
+            "tokens": stats.rules[i],
+        })).collect::<Vec<_>>(),
+        "dead": dead,
+        "fallback": fallback.iter().map(|(scope, count)| json!({ "scope": scope, "tokens": count })).collect::<Vec<_>>(),
+    });
+    cx.print(&text, json);
+    Ok(ExitCode::SUCCESS)
+}
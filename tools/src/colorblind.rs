@@ -0,0 +1,177 @@
+//! Color-vision-deficiency simulation of the theme's token colors.
+//!
+//! Uses the Machado, Oliveira and Fernandes (2009) matrices at full
+//! severity, applied in linear sRGB. Two foregrounds count as confusable
+//! when they are told apart normally but their simulated colors fall within
+//! [`MIN_DISTANCE`] of each other in OKLab.
+
+use std::collections::BTreeMap;
+
+use crate::colors::Rgba;
+use crate::resolver::Resolver;
+use crate::theme::{Color, SemanticTokenStyle, Theme};
+
+/// OKLab distance below which two token colors read as the same; a few
+/// just-noticeable differences, since tokens are small and seen side by side.
+pub const MIN_DISTANCE: f64 = 0.05;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Deficiency {
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Deficiency {
+    pub const ALL: [Deficiency; 3] = [Deficiency::Protanopia, Deficiency::Deuteranopia, Deficiency::Tritanopia];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Deficiency::Protanopia => "protanopia",
+            Deficiency::Deuteranopia => "deuteranopia",
+            Deficiency::Tritanopia => "tritanopia",
+        }
+    }
+
+    fn matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            Deficiency::Protanopia => {
+                [[0.152286, 1.052583, -0.204868], [0.114503, 0.786281, 0.099216], [-0.003882, -0.048116, 1.051998]]
+            }
+            Deficiency::Deuteranopia => {
+                [[0.367322, 0.860646, -0.227968], [0.280085, 0.672501, 0.047413], [-0.011820, 0.042940, 0.968881]]
+            }
+            Deficiency::Tritanopia => {
+                [[1.255528, -0.076749, -0.178779], [-0.078411, 0.930809, 0.147602], [0.004733, 0.691367, 0.303900]]
+            }
+        }
+    }
+
+    /// How `color` appears to someone with this deficiency.
+    pub fn simulate(self, color: Rgba) -> Rgba {
+        let linear = color.linear();
+        let row = |m: [f64; 3]| (m[0] * linear[0] + m[1] * linear[1] + m[2] * linear[2]).clamp(0.0, 1.0);
+        let [r, g, b] = self.matrix();
+        Rgba::from_linear([row(r), row(g), row(b)], color.a)
+    }
+
+    /// `theme` with every color replaced by its simulation, for previews.
+    pub fn simulate_theme(self, theme: &Theme) -> Theme {
+        let convert = |color: &Color| Color::from(self.simulate(Rgba::from(color)));
+        let mut simulated = theme.clone();
+        simulated.colors.values_mut().for_each(|color| *color = convert(color));
+        for rule in &mut simulated.token_colors {
+            rule.settings.foreground = rule.settings.foreground.as_ref().map(convert);
+            rule.settings.background = rule.settings.background.as_ref().map(convert);
+        }
+        for style in simulated.semantic_token_colors.values_mut() {
+            match style {
+                SemanticTokenStyle::Color(color) => *color = convert(color),
+                SemanticTokenStyle::Style(style) => style.foreground = style.foreground.as_ref().map(convert),
+            }
+        }
+        simulated
+    }
+}
+
+/// One token foreground and the selectors drawn in it.
+#[derive(Debug, Clone)]
+pub struct Swatch {
+    pub color: String,
+    pub selectors: Vec<String>,
+}
+
+impl Swatch {
+    /// The first few selectors, for report lines.
+    pub fn label(&self) -> String {
+        match self.selectors.len() {
+            0 => "(default)".to_owned(),
+            1..=3 => self.selectors.join(", "),
+            n => format!("{} +{} more", self.selectors[..3].join(", "), n - 3),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Confusion {
+    pub deficiency: Deficiency,
+    pub a: Swatch,
+    pub b: Swatch,
+    pub distance: f64,
+    pub simulated_distance: f64,
+}
+
+/// The distinct token foregrounds of `theme`, the default first.
+pub fn swatches(theme: &Theme) -> Vec<Swatch> {
+    let default = Resolver::new(theme).default_foreground().to_lowercase();
+    let mut by_color: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for rule in theme.token_colors.iter().filter(|rule| !rule.is_default()) {
+        if let Some(foreground) = &rule.settings.foreground {
+            let selectors = by_color.entry(Rgba::from(foreground).to_hex()).or_default();
+            selectors.extend(rule.selectors().into_iter().map(str::to_owned));
+        }
+    }
+    let default_selectors = by_color.remove(&default).unwrap_or_default();
+    let mut swatches = vec![Swatch { color: default, selectors: default_selectors }];
+    swatches.extend(by_color.into_iter().map(|(color, selectors)| Swatch { color, selectors }));
+    swatches
+}
+
+/// Swatch pairs that are distinct normally but not under `deficiency`,
+/// closest first.
+pub fn confusions(theme: &Theme, deficiency: Deficiency) -> Vec<Confusion> {
+    let swatches = swatches(theme);
+    let background = Rgba::parse(Resolver::new(theme).default_background()).unwrap_or(Rgba::BLACK);
+    // Translucent foregrounds are seen composited over the code background
+    let seen = |swatch: &Swatch| Rgba::parse(&swatch.color).unwrap_or(Rgba::BLACK).over(background);
+
+    let mut found = Vec::new();
+    for (i, a) in swatches.iter().enumerate() {
+        for b in &swatches[i + 1..] {
+            let distance = seen(a).oklab_distance(seen(b));
+            let simulated_distance = deficiency.simulate(seen(a)).oklab_distance(deficiency.simulate(seen(b)));
+            if distance >= MIN_DISTANCE && simulated_distance < MIN_DISTANCE {
+                found.push(Confusion { deficiency, a: a.clone(), b: b.clone(), distance, simulated_distance });
+            }
+        }
+    }
+    found.sort_by(|x, y| x.simulated_distance.total_cmp(&y.simulated_distance));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn simulation_keeps_greys_and_collapses_red_green() {
+        for deficiency in Deficiency::ALL {
+            let grey = deficiency.simulate(Rgba::opaque(0.5, 0.5, 0.5));
+            assert!(grey.oklab_distance(Rgba::opaque(0.5, 0.5, 0.5)) < 0.01, "{}", deficiency.name());
+        }
+        let (red, green) = (Rgba::parse("#d0503c").unwrap(), Rgba::parse("#6a8a2a").unwrap());
+        let d = Deficiency::Deuteranopia;
+        assert!(red.oklab_distance(green) > 0.15);
+        assert!(d.simulate(red).oklab_distance(d.simulate(green)) < red.oklab_distance(green) / 2.0);
+    }
+
+    #[test]
+    fn reports_pairs_that_only_simulation_confuses() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "colors": { "editor.background": "#ffffff", "editor.foreground": "#000000" },
+            "tokenColors": [
+                { "scope": "string", "settings": { "foreground": "#c8452f" } },
+                { "scope": "constant.numeric", "settings": { "foreground": "#5d8a00" } },
+                { "scope": "keyword", "settings": { "foreground": "#1f4fd0" } }
+            ]
+        }))
+        .unwrap();
+        let found = confusions(&theme, Deficiency::Deuteranopia);
+        let pairs: Vec<(String, String)> = found.iter().map(|c| (c.a.label(), c.b.label())).collect();
+        assert_eq!(pairs, [("constant.numeric".to_owned(), "string".to_owned())]);
+        assert!(Deficiency::Deuteranopia.simulate_theme(&theme).validate().is_ok());
+    }
+}
@@ -0,0 +1,365 @@
+//! Color parsing, conversion and manipulation.
+//!
+//! [`Rgba`] holds gamma-encoded sRGB channels in `0.0..=1.0`. Conversions to
+//! OKLCH go through OKLab (Björn Ottosson's matrices) and are what the
+//! lighten/darken helpers operate in, since equal steps in OKLCH lightness
+//! look equal across hues; ramps interpolate in OKLab for the same reason.
+
+use std::fmt;
+
+use crate::theme::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgba {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    /// Hue in degrees, `0.0..360.0`.
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    /// Perceptual lightness, `0.0..=1.0`.
+    pub l: f64,
+    /// Chroma; sRGB colors stay below roughly `0.37`.
+    pub c: f64,
+    /// Hue in degrees, `0.0..360.0`.
+    pub h: f64,
+}
+
+impl Rgba {
+    pub const BLACK: Self = Self::opaque(0.0, 0.0, 0.0);
+    pub const WHITE: Self = Self::opaque(1.0, 1.0, 1.0);
+
+    pub const fn opaque(r: f64, g: f64, b: f64) -> Self {
+        Self { r, g, b, a: 1.0 }
+    }
+
+    /// Parses `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`.
+    pub fn parse(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#')?;
+        let expanded: String = match hex.len() {
+            3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 | 8 => hex.to_owned(),
+            _ => return None,
+        };
+        let channel = |i: usize| match expanded.get(i * 2..i * 2 + 2) {
+            Some(h) => u8::from_str_radix(h, 16).ok().map(|v| f64::from(v) / 255.0),
+            None => Some(1.0),
+        };
+        Some(Self { r: channel(0)?, g: channel(1)?, b: channel(2)?, a: channel(3)? })
+    }
+
+    /// `#rrggbb`, or `#rrggbbaa` when not fully opaque.
+    pub fn to_hex(self) -> String {
+        let byte = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let rgb = format!("#{:02x}{:02x}{:02x}", byte(self.r), byte(self.g), byte(self.b));
+        if byte(self.a) == 255 {
+            rgb
+        } else {
+            format!("{rgb}{:02x}", byte(self.a))
+        }
+    }
+
+    pub fn with_alpha(self, a: f64) -> Self {
+        Self { a, ..self }
+    }
+
+    /// Source-over compositing of `self` onto `backdrop`.
+    pub fn over(self, backdrop: Rgba) -> Rgba {
+        let a = self.a + backdrop.a * (1.0 - self.a);
+        if a == 0.0 {
+            return Rgba { r: 0.0, g: 0.0, b: 0.0, a: 0.0 };
+        }
+        let mix = |fg: f64, bg: f64| (fg * self.a + bg * backdrop.a * (1.0 - self.a)) / a;
+        Rgba { r: mix(self.r, backdrop.r), g: mix(self.g, backdrop.g), b: mix(self.b, backdrop.b), a }
+    }
+
+    /// WCAG relative luminance of the color, ignoring alpha.
+    pub fn relative_luminance(self) -> f64 {
+        let [r, g, b] = self.linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    pub(crate) fn linear(self) -> [f64; 3] {
+        let f = |c: f64| if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+        [f(self.r), f(self.g), f(self.b)]
+    }
+
+    pub(crate) fn from_linear([r, g, b]: [f64; 3], a: f64) -> Self {
+        let f = |c: f64| if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+        Self { r: f(r), g: f(g), b: f(b), a }
+    }
+
+    fn in_gamut(self) -> bool {
+        const EPS: f64 = 1e-6;
+        [self.r, self.g, self.b].iter().all(|c| (-EPS..=1.0 + EPS).contains(c))
+    }
+
+    fn clamped(self) -> Self {
+        Self { r: self.r.clamp(0.0, 1.0), g: self.g.clamp(0.0, 1.0), b: self.b.clamp(0.0, 1.0), a: self.a }
+    }
+
+    pub fn to_hsl(self) -> Hsl {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let l = (max + min) / 2.0;
+        let d = max - min;
+        if d == 0.0 {
+            return Hsl { h: 0.0, s: 0.0, l };
+        }
+        let s = d / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == self.r {
+            ((self.g - self.b) / d).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / d + 2.0
+        } else {
+            (self.r - self.g) / d + 4.0
+        };
+        Hsl { h: h * 60.0, s, l }
+    }
+
+    pub fn to_oklch(self) -> Oklch {
+        let [r, g, b] = self.linear();
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+
+        let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+        let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+        let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+
+        let c = a.hypot(b);
+        let h = if c < 1e-9 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+        Oklch { l: lightness, c, h }
+    }
+
+    /// Moves OKLCH lightness by `amount` (e.g. `0.05`), keeping hue.
+    pub fn lighten(self, amount: f64) -> Self {
+        let mut lch = self.to_oklch();
+        lch.l = (lch.l + amount).clamp(0.0, 1.0);
+        lch.to_rgba(self.a)
+    }
+
+    pub fn darken(self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Euclidean distance in OKLab (ΔE<sub>OK</sub>), ignoring alpha;
+    /// about `0.02` is a just-noticeable difference.
+    pub fn oklab_distance(self, other: Rgba) -> f64 {
+        let ([l1, a1, b1], [l2, a2, b2]) = (self.oklab(), other.oklab());
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// The color `t` of the way from `self` to `other` (`0.0..=1.0`), along
+    /// the straight line in OKLab, so equal steps look equal. Alpha is
+    /// interpolated linearly.
+    pub fn mix(self, other: Rgba, t: f64) -> Rgba {
+        let ([l1, a1, b1], [l2, a2, b2]) = (self.oklab(), other.oklab());
+        let lerp = |x: f64, y: f64| x + (y - x) * t;
+        let (a, b) = (lerp(a1, a2), lerp(b1, b2));
+        let c = a.hypot(b);
+        let h = if c < 1e-9 { 0.0 } else { b.atan2(a).to_degrees().rem_euclid(360.0) };
+        Oklch { l: lerp(l1, l2), c, h }.to_rgba(lerp(self.a, other.a))
+    }
+
+    /// `steps` evenly spaced colors from `self` to `to`, both included.
+    pub fn ramp(self, to: Rgba, steps: usize) -> Vec<Rgba> {
+        match steps {
+            0 => Vec::new(),
+            1 => vec![self],
+            _ => (0..steps).map(|i| self.mix(to, i as f64 / (steps - 1) as f64)).collect(),
+        }
+    }
+
+    /// `steps` colors between `self` and white, lightest last, excluding
+    /// both ends.
+    pub fn tints(self, steps: usize) -> Vec<Rgba> {
+        self.between(Rgba::WHITE, steps)
+    }
+
+    /// `steps` colors between `self` and black, darkest last, excluding
+    /// both ends.
+    pub fn shades(self, steps: usize) -> Vec<Rgba> {
+        self.between(Rgba::BLACK, steps)
+    }
+
+    fn between(self, to: Rgba, steps: usize) -> Vec<Rgba> {
+        let mut ramp = self.ramp(to, steps + 2);
+        ramp.truncate(steps + 1);
+        ramp.into_iter().skip(1).collect()
+    }
+
+    fn oklab(self) -> [f64; 3] {
+        let lch = self.to_oklch();
+        let (sin, cos) = lch.h.to_radians().sin_cos();
+        [lch.l, lch.c * cos, lch.c * sin]
+    }
+}
+
+impl From<&Color> for Rgba {
+    fn from(color: &Color) -> Self {
+        Rgba::parse(color.as_str()).expect("Color is validated on construction")
+    }
+}
+
+impl From<Rgba> for Color {
+    fn from(color: Rgba) -> Self {
+        color.to_hex().parse().expect("to_hex yields #rrggbb or #rrggbbaa")
+    }
+}
+
+impl fmt::Display for Rgba {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.to_hex())
+    }
+}
+
+impl Hsl {
+    pub fn to_rgba(self, a: f64) -> Rgba {
+        let c = (1.0 - (2.0 * self.l - 1.0).abs()) * self.s;
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        let m = self.l - c / 2.0;
+        Rgba { r: r + m, g: g + m, b: b + m, a }
+    }
+}
+
+impl Oklch {
+    /// Converts to sRGB, reducing chroma until the color fits the gamut.
+    pub fn to_rgba(self, alpha: f64) -> Rgba {
+        let direct = self.to_rgba_unclamped(alpha);
+        if direct.in_gamut() {
+            return direct.clamped();
+        }
+        let (mut lo, mut hi) = (0.0, self.c);
+        for _ in 0..24 {
+            let mid = (lo + hi) / 2.0;
+            let candidate = Oklch { c: mid, ..self };
+            if candidate.to_rgba_unclamped(alpha).in_gamut() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Oklch { c: lo, ..self }.to_rgba_unclamped(alpha).clamped()
+    }
+
+    fn to_rgba_unclamped(self, alpha: f64) -> Rgba {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+        let (a, b) = (self.c * cos, self.c * sin);
+
+        let l = (self.l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m = (self.l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s = (self.l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+        Rgba::from_linear(
+            [
+                4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+                -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+                -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+            ],
+            alpha,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba(hex: &str) -> Rgba {
+        Rgba::parse(hex).unwrap()
+    }
+
+    #[test]
+    fn parses_and_formats_hex() {
+        assert_eq!(rgba("#fff"), Rgba::WHITE);
+        assert_eq!(rgba("#1a2638").to_hex(), "#1a2638");
+        assert_eq!(rgba("#1a263880").to_hex(), "#1a263880");
+        assert_eq!(rgba("#f008").to_hex(), "#ff000088");
+        assert!(Rgba::parse("1a2638").is_none());
+        assert!(Rgba::parse("#12ab5").is_none());
+    }
+
+    #[test]
+    fn hsl_round_trips() {
+        for hex in ["#ff8fa3", "#38c7ff", "#ffc56d", "#94a1b3", "#808080"] {
+            assert_eq!(rgba(hex).to_hsl().to_rgba(1.0).to_hex(), hex);
+        }
+        let hsl = rgba("#ff0000").to_hsl();
+        assert_eq!((hsl.h, hsl.s, hsl.l), (0.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn oklch_round_trips() {
+        for hex in ["#ff8fa3", "#38c7ff", "#ffc56d", "#1a2638", "#ffffff", "#000000"] {
+            assert_eq!(rgba(hex).to_oklch().to_rgba(1.0).to_hex(), hex);
+        }
+        let white = Rgba::WHITE.to_oklch();
+        assert!((white.l - 1.0).abs() < 1e-6 && white.c < 1e-6);
+    }
+
+    #[test]
+    fn out_of_gamut_chroma_is_reduced() {
+        let vivid = Oklch { l: 0.9, c: 0.4, h: 30.0 }.to_rgba(1.0);
+        assert!(vivid.in_gamut());
+        assert!((vivid.to_oklch().l - 0.9).abs() < 0.01);
+    }
+
+    #[test]
+    fn composites_alpha_over_backdrop() {
+        let half_white = Rgba::WHITE.with_alpha(0.5);
+        assert_eq!(half_white.over(Rgba::BLACK).to_hex(), "#808080");
+        assert_eq!(rgba("#38c7ff").over(rgba("#1a2638")).to_hex(), "#38c7ff");
+    }
+
+    #[test]
+    fn lighten_and_darken_move_oklch_lightness() {
+        let base = rgba("#38c7ff");
+        let lighter = base.lighten(0.1);
+        assert!((lighter.to_oklch().l - base.to_oklch().l - 0.1).abs() < 0.01);
+        assert!(base.darken(0.1).relative_luminance() < base.relative_luminance());
+        assert!((Rgba::WHITE.relative_luminance() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ramps_step_evenly_through_oklab() {
+        let (comment, background) = (rgba("#94a1b3"), rgba("#1a2638"));
+        let ramp = comment.ramp(background, 5);
+        let hexes: Vec<String> = ramp.iter().map(|c| c.to_hex()).collect();
+        assert_eq!(hexes, ["#94a1b3", "#738092", "#536073", "#354255", "#1a2638"]);
+        let steps: Vec<f64> = ramp.windows(2).map(|w| w[0].oklab_distance(w[1])).collect();
+        assert!(steps.iter().all(|d| (d - steps[0]).abs() < 0.002), "{steps:?}");
+
+        assert!(comment.ramp(background, 0).is_empty());
+        assert_eq!(comment.ramp(background, 1), [comment]);
+        assert_eq!(rgba("#38c7ff").mix(rgba("#38c7ff00"), 0.5).to_hex(), "#38c7ff80");
+    }
+
+    #[test]
+    fn tints_and_shades_exclude_the_ends() {
+        let keyword = rgba("#ff8fa3");
+        let tints: Vec<String> = keyword.tints(3).iter().map(|c| c.to_hex()).collect();
+        let shades: Vec<String> = keyword.shades(3).iter().map(|c| c.to_hex()).collect();
+        assert_eq!(tints, ["#ffaeba", "#ffcad1", "#ffe5e8"]);
+        assert_eq!(shades, ["#ae606e", "#63343c", "#220e11"]);
+    }
+}
@@ -0,0 +1,199 @@
+//! Compares the theme at two git revisions over the sample corpus, so a
+//! reviewer sees exactly which tokens a color change touches.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::diff::Look;
+use crate::html::{self, escape};
+use crate::preview::STYLE;
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::theme::Theme;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::{Error, Result};
+
+/// The theme at `path` (relative to `root`) as of `rev`, or as it is in the
+/// working tree when `rev` is `None`.
+pub fn theme_at(root: &Path, rev: Option<&str>, path: &Path) -> Result<Theme> {
+    let Some(rev) = rev else {
+        return Theme::load(&root.join(path));
+    };
+    let spec = format!("{rev}:{}", path.display());
+    let git_error = |message: String| Error::Git { rev: rev.to_owned(), path: path.to_path_buf(), message };
+    let output =
+        Command::new("git").arg("show").arg(&spec).current_dir(root).output().map_err(|e| git_error(e.to_string()))?;
+    if !output.status.success() {
+        return Err(git_error(String::from_utf8_lossy(&output.stderr).trim().to_owned()));
+    }
+    Theme::parse(Path::new(&spec), &String::from_utf8_lossy(&output.stdout))
+}
+
+/// A source line with at least one token styled differently.
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub number: usize,
+    pub tokens: Vec<Token>,
+    /// Indices into `tokens` of the ones that changed.
+    pub changed: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SampleChanges {
+    pub path: PathBuf,
+    pub lines: Vec<Line>,
+}
+
+impl SampleChanges {
+    pub fn token_count(&self) -> usize {
+        self.lines.iter().map(|line| line.changed.len()).sum()
+    }
+}
+
+/// The samples, and within them the lines, where `base` and `head` style
+/// some token differently. Samples without a grammar are left out.
+pub fn changes(
+    base: &Resolver,
+    head: &Resolver,
+    tokenizer: &Tokenizer,
+    samples: &[Sample],
+) -> Result<Vec<SampleChanges>> {
+    let mut out = Vec::new();
+    for sample in samples {
+        let Some(syntax) = tokenizer.syntax_for(&sample.path) else { continue };
+        let mut lines: Vec<Line> = Vec::new();
+        for token in tokenizer.tokenize(syntax, &sample.path, &sample.source)? {
+            if lines.last().is_none_or(|line| line.number != token.line) {
+                lines.push(Line { number: token.line, tokens: Vec::new(), changed: Vec::new() });
+            }
+            let line = lines.last_mut().expect("just pushed");
+            if !token.text.trim().is_empty()
+                && Look::from(base.resolve(&token.scopes)) != Look::from(head.resolve(&token.scopes))
+            {
+                line.changed.push(line.tokens.len());
+            }
+            line.tokens.push(token);
+        }
+        lines.retain(|line| !line.changed.is_empty());
+        if !lines.is_empty() {
+            out.push(SampleChanges { path: sample.path.clone(), lines });
+        }
+    }
+    Ok(out)
+}
+
+const COMPARE_STYLE: &str = r#"
+.pair { display: grid; grid-template-columns: 1fr 1fr; gap: 1rem; }
+.unchanged { opacity: .3; }
+.changed { outline: 1px dashed currentColor; outline-offset: 1px; }
+.lineno { display: inline-block; width: 3.5em; opacity: .4; user-select: none; }
+table { border-collapse: collapse; font-size: .8rem; margin-top: .5rem; }
+td, th { padding: .15rem .6rem; text-align: left; }
+"#;
+
+/// A page with, per changed sample, its changed lines under both themes
+/// side by side (unchanged tokens faded) and a table of the changed tokens.
+pub fn to_html(labels: (&str, &str), base: &Resolver, head: &Resolver, changes: &[SampleChanges]) -> String {
+    let total: usize = changes.iter().map(SampleChanges::token_count).sum();
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"ja\">\n<head>\n<meta charset=\"utf-8\">\n<title>Zenn theme: {0} vs {1}</title>\n<style>{STYLE}{COMPARE_STYLE}</style>\n</head>\n<body>\n<h1>{0} → {1}</h1>\n<p>{total} token(s) in {2} sample(s) changed.</p>\n",
+        escape(labels.0),
+        escape(labels.1),
+        changes.len()
+    );
+
+    for sample in changes {
+        let path = sample.path.display().to_string();
+        let _ = write!(out, "<section>\n<h2>{}</h2>\n<div class=\"pair\">\n", escape(&path));
+        for (label, resolver) in [(labels.0, base), (labels.1, head)] {
+            let _ = write!(
+                out,
+                "<div>\n<div class=\"theme-name\">{}</div>\n<div class=\"znc\"><pre style=\"background-color:{};color:{}\"><code>",
+                escape(label),
+                resolver.default_background(),
+                resolver.default_foreground()
+            );
+            for (i, line) in sample.lines.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                let _ = write!(out, "<span class=\"lineno\">{}</span>", line.number);
+                for (index, token) in line.tokens.iter().enumerate() {
+                    let style = resolver.resolve(&token.scopes);
+                    let class = if line.changed.contains(&index) { "changed" } else { "unchanged" };
+                    let _ = write!(
+                        out,
+                        r#"<span class="{class}" style="color:{}{}">{}</span>"#,
+                        style.foreground,
+                        html::font_css(style.font_style),
+                        escape(&token.text)
+                    );
+                }
+            }
+            out.push_str("</code></pre></div>\n</div>\n");
+        }
+        out.push_str(
+            "</div>\n<table>\n<tr><th>at</th><th>token</th><th>scope</th><th>before</th><th>after</th></tr>\n",
+        );
+        for line in &sample.lines {
+            for &index in &line.changed {
+                let token = &line.tokens[index];
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}:{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    line.number,
+                    token.column,
+                    escape(token.text.trim()),
+                    escape(token.scopes.last().map_or("", String::as_str)),
+                    Look::from(base.resolve(&token.scopes)),
+                    Look::from(head.resolve(&token.scopes))
+                );
+            }
+        }
+        out.push_str("</table>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn keeps_only_lines_with_changed_tokens() {
+        let theme = |keyword: &str| -> Theme {
+            serde_json::from_value(json!({
+                "name": "t",
+                "tokenColors": [
+                    { "settings": { "foreground": "#ffffff", "background": "#000000" } },
+                    { "scope": ["keyword", "storage"], "settings": { "foreground": keyword } },
+                    { "scope": "string", "settings": { "foreground": "#ffc56d" } }
+                ]
+            }))
+            .unwrap()
+        };
+        let (base, head) = (theme("#ff8fa3"), theme("#ff6b8b"));
+        let (base, head) = (Resolver::new(&base), Resolver::new(&head));
+        let sample =
+            Sample { path: PathBuf::from("rust/sample.rs"), source: "let s = \"zenn\";\n\nfn main() {}\n".to_owned() };
+
+        let found = changes(&base, &head, &Tokenizer::new(), std::slice::from_ref(&sample)).unwrap();
+        assert_eq!(found.len(), 1);
+        let numbers: Vec<usize> = found[0].lines.iter().map(|l| l.number).collect();
+        assert_eq!(numbers, [1, 3]);
+        let changed: Vec<&str> =
+            found[0].lines.iter().flat_map(|l| l.changed.iter().map(|&i| l.tokens[i].text.as_str())).collect();
+        assert_eq!(changed, ["let", "=", "fn"]);
+
+        let page = to_html(("main", "HEAD"), &base, &head, &found);
+        assert!(page.contains("3 token(s) in 1 sample(s) changed"));
+        assert!(page.contains("<td>#ff8fa3</td><td>#ff6b8b</td>"));
+        assert!(changes(&base, &base, &Tokenizer::new(), &[sample]).unwrap().is_empty());
+    }
+}
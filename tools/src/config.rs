@@ -0,0 +1,222 @@
+//! Settings read from the checked-in `tools.toml` at the repository root.
+//!
+//! Every key is optional and a missing file means all defaults. Unknown
+//! keys and out-of-range values are errors naming the key, so a typo does
+//! not silently fall back to a default.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+
+use crate::contrast::AA_NORMAL;
+use crate::export::Target;
+use crate::samples::Sample;
+use crate::selector::matches_scope;
+use crate::{Error, Result};
+
+/// Path of the configuration file relative to the repository root.
+pub const CONFIG_PATH: &str = "tools.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub contrast: ContrastConfig,
+    pub audit: AuditConfig,
+    pub export: ExportConfig,
+    pub serve: ServeConfig,
+    pub samples: SamplesConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ContrastConfig {
+    /// Minimum WCAG ratio `tools contrast` accepts.
+    pub threshold: f64,
+}
+
+impl Default for ContrastConfig {
+    fn default() -> Self {
+        Self { threshold: AA_NORMAL }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuditConfig {
+    /// Scopes left to the default foreground on purpose; `tools audit`
+    /// skips these and every scope beneath them.
+    pub allow: Vec<String>,
+}
+
+impl AuditConfig {
+    pub fn allows(&self, scope: &str) -> bool {
+        self.allow.iter().any(|allowed| matches_scope(scope, allowed))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    /// Formats `tools export` writes.
+    pub targets: Vec<Target>,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self { targets: Target::ALL.to_vec() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServeConfig {
+    /// Port `tools serve` listens on, on 127.0.0.1.
+    pub port: u16,
+}
+
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self { port: 3001 }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SamplesConfig {
+    /// Globs over paths relative to the samples directory; empty means
+    /// every sample.
+    pub include: Vec<String>,
+    /// Globs removing samples `include` let through.
+    pub exclude: Vec<String>,
+}
+
+impl SamplesConfig {
+    /// The samples the globs select, in their original order.
+    pub fn filter(&self, samples: Vec<Sample>) -> Vec<Sample> {
+        // Validated on load
+        let (include, exclude) = (globs(&self.include).unwrap_or_default(), globs(&self.exclude).unwrap_or_default());
+        samples
+            .into_iter()
+            .filter(|sample| {
+                (self.include.is_empty() || include.is_match(&sample.path)) && !exclude.is_match(&sample.path)
+            })
+            .collect()
+    }
+}
+
+fn globs(patterns: &[String]) -> Result<GlobSet, (usize, String)> {
+    let mut builder = GlobSetBuilder::new();
+    for (i, pattern) in patterns.iter().enumerate() {
+        builder.add(Glob::new(pattern).map_err(|e| (i, e.kind().to_string()))?);
+    }
+    builder.build().map_err(|e| (0, e.to_string()))
+}
+
+impl Config {
+    /// Reads `tools.toml` under `root`, or the defaults when there is none.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(CONFIG_PATH);
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&path, &text),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(Error::io(&path, err)),
+        }
+    }
+
+    /// Parses and validates configuration read from `path`.
+    pub fn parse(path: &Path, text: &str) -> Result<Self> {
+        let invalid = |message: String| Error::Config { path: path.to_path_buf(), message };
+        let config: Config = toml::from_str(text).map_err(|e| invalid(e.to_string().trim_end().to_owned()))?;
+        config.validate().map_err(|(key, message)| invalid(format!("`{key}` {message}")))?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), (String, String)> {
+        let threshold = self.contrast.threshold;
+        if !(1.0..=21.0).contains(&threshold) {
+            return Err(("contrast.threshold".into(), format!("must be between 1 and 21, got {threshold}")));
+        }
+        if let Some(i) = self.audit.allow.iter().position(|s| s.is_empty() || s.contains(char::is_whitespace)) {
+            return Err((format!("audit.allow[{i}]"), "must be a single scope name".into()));
+        }
+        if self.export.targets.is_empty() {
+            return Err(("export.targets".into(), "must name at least one target".into()));
+        }
+        if self.serve.port == 0 {
+            return Err(("serve.port".into(), "must not be 0".into()));
+        }
+        for (key, patterns) in [("samples.include", &self.samples.include), ("samples.exclude", &self.samples.exclude)]
+        {
+            globs(patterns)
+                .map_err(|(i, message)| (format!("{key}[{i}]"), format!("is not a valid glob: {message}")))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn parse(text: &str) -> Result<Config> {
+        Config::parse(Path::new("tools.toml"), text)
+    }
+
+    #[test]
+    fn fills_defaults_and_filters_samples() {
+        assert_eq!(parse("").unwrap(), Config::default());
+
+        let config = parse(
+            r#"
+            [contrast]
+            threshold = 7
+
+            [audit]
+            allow = ["meta", "source"]
+
+            [export]
+            targets = ["json", "terminal-256"]
+
+            [samples]
+            include = ["rust/**"]
+            exclude = ["**/*_generated.rs"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.contrast.threshold, 7.0);
+        assert_eq!(config.export.targets, [Target::Json, Target::Terminal256]);
+        assert_eq!(config.serve.port, 3001);
+        assert!(config.audit.allows("meta.block.rust") && !config.audit.allows("metadata"));
+
+        let sample = |path: &str| Sample { path: PathBuf::from(path), source: String::new() };
+        let kept = config.samples.filter(vec![
+            sample("rust/dom.rs"),
+            sample("rust/bindings_generated.rs"),
+            sample("go/main.go"),
+        ]);
+        assert_eq!(kept.iter().map(|s| s.path.to_str().unwrap()).collect::<Vec<_>>(), ["rust/dom.rs"]);
+    }
+
+    #[test]
+    fn checked_in_file_is_valid() {
+        let config = Config::load(&crate::repo_root()).unwrap();
+        assert!(config.audit.allows("source.rust"));
+    }
+
+    #[test]
+    fn errors_name_the_offending_key() {
+        let message = |text: &str| parse(text).unwrap_err().to_string();
+        assert!(message("[contrast]\nthreshold = 30\n").contains("`contrast.threshold` must be between 1 and 21"));
+        assert!(message("[serve]\nport = 0\n").contains("`serve.port`"));
+        assert!(
+            message("[samples]\nexclude = [\"ok/*\", \"[\"]\n").contains("`samples.exclude[1]` is not a valid glob")
+        );
+        assert!(message("[export]\ntargets = [\"emacs\"]\n").contains("unknown variant `emacs`"));
+        assert!(message("[serve]\nprot = 3000\n").contains("unknown field `prot`"));
+    }
+}
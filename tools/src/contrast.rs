@@ -0,0 +1,121 @@
+//! WCAG 2.x contrast ratios of the theme's token colors.
+
+use crate::colors::Rgba;
+use crate::resolver::Resolver;
+use crate::theme::{Color, Theme};
+
+/// Minimum ratio WCAG AA asks of normal-size text.
+pub const AA_NORMAL: f64 = 4.5;
+
+/// Minimum ratio WCAG AAA asks of normal-size text.
+pub const AAA_NORMAL: f64 = 7.0;
+
+/// Contrast ratio of `foreground` drawn on `background`, compositing
+/// translucent colors (the background over black, as a page would).
+pub fn contrast_ratio(foreground: Rgba, background: Rgba) -> f64 {
+    let bg = background.over(Rgba::BLACK);
+    let fg = foreground.over(bg);
+    let (l1, l2) = (fg.relative_luminance(), bg.relative_luminance());
+    (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    /// Index into `tokenColors`, or `None` for the default foreground.
+    pub rule: Option<usize>,
+    pub label: String,
+    pub foreground: String,
+    pub background: String,
+    pub ratio: f64,
+}
+
+/// Every foreground the theme defines, paired with the background it is
+/// drawn on, sorted from lowest to highest contrast.
+pub fn check(theme: &Theme) -> Vec<Entry> {
+    let resolver = Resolver::new(theme);
+    let default_bg = resolver.default_background().to_owned();
+
+    let mut entries = vec![entry(None, "(default)".to_owned(), resolver.default_foreground(), &default_bg)];
+    for (index, rule) in theme.token_colors.iter().enumerate() {
+        let Some(foreground) = &rule.settings.foreground else {
+            continue;
+        };
+        if rule.is_default() {
+            continue;
+        }
+        let background = rule.settings.background.as_ref().map_or(default_bg.as_str(), Color::as_str);
+        entries.push(entry(Some(index), rule.selectors().join(", "), foreground.as_str(), background));
+    }
+    entries.sort_by(|a, b| a.ratio.total_cmp(&b.ratio).then(a.rule.cmp(&b.rule)));
+    entries
+}
+
+fn entry(rule: Option<usize>, label: String, foreground: &str, background: &str) -> Entry {
+    Entry {
+        rule,
+        label,
+        foreground: foreground.to_owned(),
+        background: background.to_owned(),
+        ratio: contrast_ratio(parse(foreground), parse(background)),
+    }
+}
+
+// Theme colors are validated on load; the fallbacks are literal hex too
+fn parse(hex: &str) -> Rgba {
+    Rgba::parse(hex).unwrap_or(Rgba::BLACK)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn rgba(hex: &str) -> Rgba {
+        Rgba::parse(hex).unwrap()
+    }
+
+    #[test]
+    fn ratios_match_the_wcag_reference_values() {
+        assert!((contrast_ratio(rgba("#000"), rgba("#fff")) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(rgba("#fff"), rgba("#000")) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(rgba("#777"), rgba("#fff")) - 4.48).abs() < 0.01);
+        assert!((contrast_ratio(rgba("#abcdef"), rgba("#abcdef")) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn translucent_colors_are_composited_first() {
+        // Black at alpha 128/255 over white is the gray 127/255
+        let half = contrast_ratio(rgba("#00000080"), rgba("#ffffff"));
+        assert!((half - contrast_ratio(rgba("#7f7f7f"), rgba("#ffffff"))).abs() < 1e-9);
+        // A fully transparent foreground vanishes into the background
+        assert!((contrast_ratio(rgba("#ff000000"), rgba("#ffffff")) - 1.0).abs() < 1e-9);
+        // A transparent background shows the black page beneath
+        assert!((contrast_ratio(rgba("#ffffff"), rgba("#ffffff00")) - 21.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn check_sorts_by_ratio_and_labels_each_rule() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "colors": { "editor.foreground": "#000000", "editor.background": "#ffffff" },
+            "tokenColors": [
+                { "settings": { "foreground": "#000000" } },
+                { "scope": ["comment", "string.quoted"], "settings": { "foreground": "#777777" } },
+                { "scope": "keyword", "settings": { "fontStyle": "bold" } },
+                { "scope": "markup.inserted", "settings": { "foreground": "#ffffff", "background": "#000000" } },
+                { "scope": "invalid", "settings": { "foreground": "#eeeeee" } }
+            ]
+        }))
+        .unwrap();
+        let entries = check(&theme);
+
+        let rules: Vec<Option<usize>> = entries.iter().map(|e| e.rule).collect();
+        assert_eq!(rules, [Some(4), Some(1), None, Some(3)]);
+        assert!(entries.windows(2).all(|w| w[0].ratio <= w[1].ratio));
+        assert_eq!(entries[1].label, "comment, string.quoted");
+        assert_eq!(entries[2].label, "(default)");
+        assert_eq!((entries[3].foreground.as_str(), entries[3].background.as_str()), ("#ffffff", "#000000"));
+        assert_eq!(entries[1].background, "#ffffff");
+    }
+}
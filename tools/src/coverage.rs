@@ -0,0 +1,186 @@
+//! Which theme rules each language's samples exercise, by scope group.
+//!
+//! A scope group is the first two segments of a token's innermost scope
+//! (`storage.modifier.rust` counts as `storage.modifier`). Structural groups
+//! (`meta`, `source`, `text`) wrap plain code and are left out. Groups that
+//! a theme rule targets but no sample produces still get a column, so the
+//! gaps show.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::html::escape;
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::selector::Selector;
+use crate::theme::Theme;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::Result;
+
+const STRUCTURAL: &[&str] = &["meta", "source", "text"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Cell {
+    /// Non-blank tokens in the group.
+    pub tokens: usize,
+    /// Indices of the `tokenColors` rules that styled any of them.
+    pub rules: BTreeSet<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Matrix {
+    pub groups: BTreeSet<String>,
+    /// Grammar name to the cells of the groups its samples produce.
+    pub languages: BTreeMap<String, BTreeMap<String, Cell>>,
+}
+
+/// The scope group of `scope`, or `None` for structural scopes.
+pub fn group(scope: &str) -> Option<String> {
+    let mut segments = scope.split('.');
+    let first = segments.next().filter(|s| !s.is_empty() && !STRUCTURAL.contains(s))?;
+    Some(match segments.next() {
+        Some(second) => format!("{first}.{second}"),
+        None => first.to_owned(),
+    })
+}
+
+pub fn build(theme: &Theme, tokenizer: &Tokenizer, samples: &[Sample]) -> Result<Matrix> {
+    Ok(build_tokenized(theme, tokenizer, samples, &tokenizer.tokenize_all(samples)?))
+}
+
+/// [`build`] for samples already tokenized; `None` for those without a
+/// grammar.
+pub fn build_tokenized(
+    theme: &Theme,
+    tokenizer: &Tokenizer,
+    samples: &[Sample],
+    tokenized: &[Option<Vec<Token>>],
+) -> Matrix {
+    let resolver = Resolver::new(theme);
+    let mut matrix = Matrix::default();
+    for rule in &theme.token_colors {
+        let targets = rule.selectors().into_iter().filter_map(Selector::parse);
+        matrix.groups.extend(targets.filter_map(|selector| group(&selector.scope)));
+    }
+
+    for (sample, tokens) in samples.iter().zip(tokenized) {
+        let (Some(syntax), Some(tokens)) = (tokenizer.syntax_for(&sample.path), tokens) else {
+            continue;
+        };
+        let cells = matrix.languages.entry(syntax.name.clone()).or_default();
+        for token in tokens {
+            let Some(group) = token.scopes.last().and_then(|scope| group(scope)) else {
+                continue;
+            };
+            if token.text.trim().is_empty() {
+                continue;
+            }
+            let style = resolver.resolve(&token.scopes);
+            let cell = cells.entry(group.clone()).or_default();
+            cell.tokens += 1;
+            cell.rules
+                .extend([style.foreground_rule, style.background_rule, style.font_style_rule].into_iter().flatten());
+            matrix.groups.insert(group);
+        }
+    }
+    matrix
+}
+
+impl Matrix {
+    /// The matrix plus each rule's selectors, for scripts.
+    pub fn to_json(&self, theme: &Theme) -> String {
+        let rules: Vec<Vec<&str>> = theme.token_colors.iter().map(|rule| rule.selectors()).collect();
+        let value = serde_json::json!({ "groups": self.groups, "languages": self.languages, "rules": rules });
+        let mut json = serde_json::to_string_pretty(&value).expect("the matrix always serializes");
+        json.push('\n');
+        json
+    }
+
+    /// A table with one row per language. Each cell shows the group's token
+    /// count on the color its rules produce; hover lists the rules.
+    pub fn to_html(&self, theme: &Theme) -> String {
+        let resolver = Resolver::new(theme);
+        let mut out = String::from(concat!(
+            "<!DOCTYPE html>\n<html lang=\"ja\">\n<head>\n<meta charset=\"utf-8\">\n<title>Scope coverage</title>\n<style>\n",
+            "body { font-family: -apple-system, BlinkMacSystemFont, sans-serif; font-size: 12px; margin: 1rem; }\n",
+            "table { border-collapse: collapse; }\n",
+            "th, td { border: 1px solid #d6e3ed; padding: 2px 4px; text-align: right; }\n",
+            "thead th { writing-mode: vertical-rl; transform: rotate(180deg); text-align: left; font-weight: normal; }\n",
+            "tbody th { text-align: left; white-space: nowrap; }\n",
+            "td.gap { background: #f5f6fa; }\n",
+            "td.unthemed { outline: 2px solid #f5a623; outline-offset: -2px; }\n",
+            "</style>\n</head>\n<body>\n<h1>Scope coverage</h1>\n",
+            "<p>Token counts per scope group; empty cells are groups a language's samples never produce, ",
+            "outlined ones fall through to the default foreground.</p>\n<table>\n<thead>\n<tr><th></th>",
+        ));
+        for group in &self.groups {
+            let _ = write!(out, "<th>{}</th>", escape(group));
+        }
+        out.push_str("</tr>\n</thead>\n<tbody>\n");
+
+        for (language, cells) in &self.languages {
+            let _ = write!(out, "<tr><th>{}</th>", escape(language));
+            for group in &self.groups {
+                let Some(cell) = cells.get(group) else {
+                    out.push_str("<td class=\"gap\"></td>");
+                    continue;
+                };
+                let foreground = cell
+                    .rules
+                    .iter()
+                    .find_map(|&i| theme.token_colors[i].settings.foreground.as_ref())
+                    .map_or(resolver.default_foreground(), |color| color.as_str());
+                let rules: Vec<String> = cell
+                    .rules
+                    .iter()
+                    .map(|&i| format!("#{i} {}", theme.token_colors[i].selectors().join(", ")))
+                    .collect();
+                let class = if cell.rules.is_empty() { " class=\"unthemed\"" } else { "" };
+                let _ = write!(
+                    out,
+                    "<td{class} style=\"background:{};color:{foreground}\" title=\"{}\">{}</td>",
+                    resolver.default_background(),
+                    escape(&rules.join("\n")),
+                    cell.tokens
+                );
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn groups_tokens_by_scope_prefix() {
+        assert_eq!(group("storage.modifier.rust").as_deref(), Some("storage.modifier"));
+        assert_eq!(group("comment").as_deref(), Some("comment"));
+        assert_eq!(group("meta.block.rust"), None);
+
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "scope": "keyword", "settings": { "foreground": "#ff0000" } },
+                { "scope": "markup.heading", "settings": { "foreground": "#00ff00" } }
+            ]
+        }))
+        .unwrap();
+        let samples = [Sample { path: PathBuf::from("a.rs"), source: "fn main() { let x = 1; }\n".to_owned() }];
+        let matrix = build(&theme, &Tokenizer::new(), &samples).unwrap();
+
+        let rust = &matrix.languages["Rust"];
+        assert_eq!(rust["keyword.operator"], Cell { tokens: 1, rules: BTreeSet::from([0]) });
+        assert_eq!(rust["storage.type"], Cell { tokens: 2, rules: BTreeSet::new() });
+        assert!(matrix.groups.contains("markup.heading") && !rust.contains_key("markup.heading"));
+    }
+}
@@ -0,0 +1,182 @@
+//! Compares how two themes style the same sample tokens.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::colors::Rgba;
+use crate::resolver::{FontStyle, Resolved, Resolver};
+use crate::samples::Sample;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::Result;
+
+/// The visible part of a resolved style, with the color normalized so
+/// `#fff` and `#FFFFFF` compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Look {
+    pub foreground: String,
+    pub font_style: FontStyle,
+}
+
+impl From<Resolved<'_>> for Look {
+    fn from(style: Resolved<'_>) -> Self {
+        let foreground = Rgba::parse(style.foreground).map_or_else(|| style.foreground.to_owned(), Rgba::to_hex);
+        Self { foreground, font_style: style.font_style }
+    }
+}
+
+impl fmt::Display for Look {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.foreground)?;
+        if !self.font_style.is_empty() {
+            write!(f, " {}", self.font_style)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DiffKey {
+    pub scope: String,
+    pub ours: Look,
+    pub theirs: Look,
+}
+
+#[derive(Debug, Clone)]
+pub struct Difference {
+    pub count: usize,
+    pub path: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    pub example: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DiffReport {
+    pub tokens: usize,
+    pub differing_tokens: usize,
+    pub differences: BTreeMap<DiffKey, Difference>,
+    /// Samples skipped for lack of a grammar.
+    pub skipped: Vec<PathBuf>,
+}
+
+/// A token two themes style differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenChange {
+    /// Index into the token list.
+    pub index: usize,
+    pub before: Look,
+    pub after: Look,
+}
+
+/// The non-blank tokens `before` and `after` style differently, in order.
+pub fn token_changes(before: &Resolver, after: &Resolver, tokens: &[Token]) -> Vec<TokenChange> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| !token.text.trim().is_empty())
+        .filter_map(|(index, token)| {
+            let (a, b) = (Look::from(before.resolve(&token.scopes)), Look::from(after.resolve(&token.scopes)));
+            (a != b).then_some(TokenChange { index, before: a, after: b })
+        })
+        .collect()
+}
+
+/// How `ours` and `theirs` style every non-blank token of `samples`. Pass
+/// the tokenizer [`Tokenizer::discover`] finds, as the other tools do, so
+/// the report reflects the grammars Zenn renders with.
+pub fn compare(ours: &Resolver, theirs: &Resolver, tokenizer: &Tokenizer, samples: &[Sample]) -> Result<DiffReport> {
+    Ok(compare_tokenized(ours, theirs, samples, &tokenizer.tokenize_all(samples)?))
+}
+
+/// [`compare`] for samples already tokenized; `None` for those without a
+/// grammar.
+pub fn compare_tokenized(
+    ours: &Resolver,
+    theirs: &Resolver,
+    samples: &[Sample],
+    tokenized: &[Option<Vec<Token>>],
+) -> DiffReport {
+    let mut report = DiffReport::default();
+    for (sample, tokens) in samples.iter().zip(tokenized) {
+        let Some(tokens) = tokens else {
+            report.skipped.push(sample.path.clone());
+            continue;
+        };
+        for token in tokens {
+            if token.text.trim().is_empty() {
+                continue;
+            }
+            report.tokens += 1;
+            let (a, b) = (Look::from(ours.resolve(&token.scopes)), Look::from(theirs.resolve(&token.scopes)));
+            if a == b {
+                continue;
+            }
+            report.differing_tokens += 1;
+            let scope = token.scopes.last().cloned().unwrap_or_default();
+            report
+                .differences
+                .entry(DiffKey { scope, ours: a, theirs: b })
+                .or_insert_with(|| Difference {
+                    count: 0,
+                    path: sample.path.clone(),
+                    line: token.line,
+                    column: token.column,
+                    example: token.text.trim().to_owned(),
+                })
+                .count += 1;
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use std::path::Path;
+
+    use super::*;
+    use crate::theme::Theme;
+
+    fn theme(keyword: &str, comment: &str) -> Theme {
+        serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#eeeeee", "background": "#111111" } },
+                { "scope": "keyword", "settings": { "foreground": keyword } },
+                { "scope": "comment", "settings": { "foreground": comment, "fontStyle": "italic" } }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reports_the_tokens_one_rule_changes() {
+        let samples = [
+            Sample { path: PathBuf::from("a.rs"), source: "// hi\nfn main() {\n    if x {}\n}\n".to_owned() },
+            Sample { path: PathBuf::from("b.unknown"), source: "fn\n".to_owned() },
+        ];
+        let tokenized = Tokenizer::new().tokenize_all(&samples).unwrap();
+        let ours = Resolver::new(&theme("#ff0000", "#fff"));
+
+        // `#fff` and `#FFFFFF` are the same color
+        let same = compare_tokenized(&ours, &Resolver::new(&theme("#FF0000", "#FFFFFF")), &samples, &tokenized);
+        assert!(same.tokens > 0);
+        assert_eq!(same.differing_tokens, 0);
+        assert!(same.differences.is_empty());
+        assert_eq!(same.skipped, [PathBuf::from("b.unknown")]);
+
+        let theirs = Resolver::new(&theme("#00ff00", "#fff"));
+        let report = compare_tokenized(&ours, &theirs, &samples, &tokenized);
+        assert_eq!(report.tokens, same.tokens);
+        assert_eq!(report.differing_tokens, 1);
+        let [(key, difference)] = report.differences.iter().collect::<Vec<_>>()[..] else {
+            panic!("{:?}", report.differences);
+        };
+        assert!(key.scope.starts_with("keyword.control"));
+        assert_eq!((key.ours.to_string(), key.theirs.to_string()), ("#ff0000".to_owned(), "#00ff00".to_owned()));
+        assert_eq!((difference.count, difference.example.as_str()), (1, "if"));
+        assert_eq!((difference.path.as_path(), difference.line, difference.column), (Path::new("a.rs"), 3, 5));
+    }
+}
@@ -0,0 +1,138 @@
+//! Perceptual distance between token colors that meet on a line.
+//!
+//! Two different foregrounds next to each other are meant to mark different
+//! things; when they are within a threshold in OKLab (ΔE<sub>OK</sub>) the
+//! reader sees one color and the distinction is lost. Only pairs that share
+//! a sample line are reported, since colors that never meet cannot be
+//! confused.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::colors::Rgba;
+use crate::resolver::Resolver;
+use crate::tokenizer::Token;
+
+/// Two distinct foregrounds that meet on at least one line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearPair {
+    pub a: String,
+    pub b: String,
+    /// Innermost scope of the first token drawn in each color.
+    pub a_scope: String,
+    pub b_scope: String,
+    /// ΔE<sub>OK</sub> as seen over the code background.
+    pub distance: f64,
+    /// Lines on which both colors appear, over every sample.
+    pub lines: usize,
+    /// The first such line.
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+/// Collects the foreground pairs that meet on sample lines, across calls.
+#[derive(Debug)]
+pub struct Checker<'a> {
+    resolver: &'a Resolver,
+    background: Rgba,
+    pairs: BTreeMap<(String, String), NearPair>,
+}
+
+impl<'a> Checker<'a> {
+    pub fn new(resolver: &'a Resolver) -> Self {
+        let background = Rgba::parse(resolver.default_background()).unwrap_or(Rgba::BLACK);
+        Self { resolver, background, pairs: BTreeMap::new() }
+    }
+
+    pub fn add(&mut self, path: &Path, tokens: &[Token]) {
+        let mut start = 0;
+        while start < tokens.len() {
+            let line = tokens[start].line;
+            let end = start + tokens[start..].iter().take_while(|t| t.line == line).count();
+            self.add_line(path, &tokens[start..end]);
+            start = end;
+        }
+    }
+
+    fn add_line(&mut self, path: &Path, tokens: &[Token]) {
+        // Each foreground on the line, lowercased hex, with its first scope
+        let mut colors: BTreeMap<String, &str> = BTreeMap::new();
+        for token in tokens.iter().filter(|t| !t.text.trim().is_empty()) {
+            let foreground = self.resolver.resolve(&token.scopes).foreground.to_lowercase();
+            colors.entry(foreground).or_insert_with(|| token.scopes.last().map_or("", String::as_str));
+        }
+        let colors: Vec<(String, &str)> = colors.into_iter().collect();
+        for (i, (a, a_scope)) in colors.iter().enumerate() {
+            for (b, b_scope) in &colors[i + 1..] {
+                let seen = |hex: &str| Rgba::parse(hex).unwrap_or(Rgba::BLACK).over(self.background);
+                let pair = self.pairs.entry((a.clone(), b.clone())).or_insert_with(|| NearPair {
+                    a: a.clone(),
+                    b: b.clone(),
+                    a_scope: (*a_scope).to_owned(),
+                    b_scope: (*b_scope).to_owned(),
+                    distance: seen(a).oklab_distance(seen(b)),
+                    lines: 0,
+                    path: path.to_path_buf(),
+                    line: tokens[0].line,
+                });
+                pair.lines += 1;
+            }
+        }
+    }
+
+    /// The pairs closer than `threshold`, closest first.
+    pub fn below(&self, threshold: f64) -> Vec<NearPair> {
+        let mut near: Vec<NearPair> = self.pairs.values().filter(|p| p.distance < threshold).cloned().collect();
+        near.sort_by(|x, y| x.distance.total_cmp(&y.distance).then_with(|| y.lines.cmp(&x.lines)));
+        near
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::theme::Theme;
+
+    #[test]
+    fn reports_close_colors_only_where_they_meet() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#ffffff", "background": "#000000" } },
+                { "scope": "entity.name.type", "settings": { "foreground": "#38c7ff" } },
+                { "scope": "entity.name.trait", "settings": { "foreground": "#3ac5fc" } },
+                { "scope": "string", "settings": { "foreground": "#39c6fe" } }
+            ]
+        }))
+        .unwrap();
+        let resolver = Resolver::new(&theme);
+        let token = |line: usize, column: usize, text: &str, scope: &str| Token {
+            line,
+            column,
+            text: text.to_owned(),
+            scopes: vec!["source.rust".to_owned(), scope.to_owned()],
+        };
+        // The type and trait colors meet twice; the string color never shares
+        // a line with either
+        let tokens = [
+            token(1, 1, "Foo", "entity.name.type"),
+            token(1, 4, " ", "entity.name.trait"),
+            token(1, 5, "Bar", "entity.name.trait"),
+            token(2, 1, "\"x\"", "string"),
+            token(3, 1, "Foo", "entity.name.type"),
+            token(3, 4, "Bar", "entity.name.trait"),
+            token(3, 7, "=", "keyword"),
+        ];
+
+        let mut checker = Checker::new(&resolver);
+        checker.add(Path::new("t.rs"), &tokens);
+        let near = checker.below(0.05);
+        assert_eq!(near.len(), 1);
+        assert_eq!((near[0].a.as_str(), near[0].b.as_str()), ("#38c7ff", "#3ac5fc"));
+        assert_eq!((near[0].a_scope.as_str(), near[0].b_scope.as_str()), ("entity.name.type", "entity.name.trait"));
+        assert_eq!((near[0].lines, near[0].line), (2, 1));
+        assert!(near[0].distance > 0.0 && near[0].distance < 0.01);
+    }
+}
@@ -0,0 +1,60 @@
+use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("invalid theme {path}: {source}")]
+    Theme {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("invalid rule #{index} in {path}: {message}")]
+    InvalidRule { path: PathBuf, index: usize, message: String },
+
+    #[error("unsupported grammar {path}: {message}")]
+    Grammar { path: PathBuf, message: String },
+
+    #[error("failed to tokenize {path}: {message}")]
+    Tokenize { path: PathBuf, message: String },
+
+    #[error("screenshot {path} failed: {message}")]
+    Screenshot { path: PathBuf, message: String },
+
+    #[error("failed to watch {path}: {message}")]
+    Watch { path: PathBuf, message: String },
+
+    #[error("invalid {path}: {message}")]
+    Config { path: PathBuf, message: String },
+
+    #[error("git show {rev}:{path} failed: {message}")]
+    Git { rev: String, path: PathBuf, message: String },
+
+    #[error("shiki failed: {message}")]
+    Shiki { message: String },
+
+    #[error("failed to listen on {addr}: {source}")]
+    Listen {
+        addr: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Self::Io { path: path.into(), source }
+    }
+}
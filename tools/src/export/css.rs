@@ -0,0 +1,100 @@
+//! CSS custom properties (`--zenn-code-keyword`, ...) for pages that embed
+//! Zenn's colors without Zenn's renderer.
+//!
+//! As with the Vim backend, each variable is the resolved foreground of a
+//! representative TextMate scope stack. The variables feed the ones of
+//! Shiki's `css-variables` theme (`createCssVariablesTheme`), whose output
+//! colors every token with `style="color: var(--shiki-token-keyword)"` and
+//! the like, so highlighting with that theme picks the colors up as is.
+//! Variables without a Shiki counterpart (`--zenn-code-number`, ...) are
+//! there for a page's own CSS. Font styles have no variable in that theme
+//! and are not exported.
+
+use std::fmt::Write;
+
+use crate::resolver::Resolver;
+use crate::theme::Theme;
+
+/// A `--zenn-code-<name>` variable, the `--shiki-token-<name>` variable it
+/// feeds (if Shiki has one) and the scope stack it is resolved from.
+const TOKENS: &[(&str, Option<&str>, &[&str])] = &[
+    ("keyword", Some("keyword"), &["source", "keyword"]),
+    ("string", Some("string"), &["source", "string.quoted.double"]),
+    ("string-expression", Some("string-expression"), &["source.js", "string.template.js"]),
+    ("comment", Some("comment"), &["source", "comment.line"]),
+    ("constant", Some("constant"), &["source", "constant.language"]),
+    ("number", None, &["source", "constant.numeric"]),
+    ("function", Some("function"), &["source", "entity.name.function"]),
+    ("parameter", Some("parameter"), &["source", "variable.parameter"]),
+    ("variable", None, &["source", "variable.other.readwrite"]),
+    ("type", None, &["source", "entity.name.type"]),
+    ("punctuation", Some("punctuation"), &["source", "punctuation"]),
+    ("tag", None, &["text.html", "entity.name.tag"]),
+    ("attribute", None, &["text.html", "entity.other.attribute-name"]),
+    ("link", Some("link"), &["text.html.markdown", "markup.underline.link"]),
+    ("inserted", Some("inserted"), &["source.diff", "markup.inserted"]),
+    ("deleted", Some("deleted"), &["source.diff", "markup.deleted"]),
+];
+
+pub fn css(theme: &Theme) -> String {
+    let resolver = Resolver::new(theme);
+    let name = theme.display_name.as_deref().unwrap_or(&theme.name);
+
+    let mut out = String::new();
+    let _ = writeln!(out, "/* {name}");
+    let _ = writeln!(out, " * Generated from {}.json by `cargo xtask export`; do not edit. */\n", theme.name);
+    out.push_str(":root {\n");
+    let _ = writeln!(out, "  --zenn-code-foreground: {};", resolver.default_foreground());
+    let _ = writeln!(out, "  --zenn-code-background: {};", resolver.default_background());
+    for (token, _, stack) in TOKENS {
+        let _ = writeln!(out, "  --zenn-code-{token}: {};", resolver.resolve(stack).foreground);
+    }
+    out.push_str("\n  /* Shiki's css-variables theme */\n");
+    out.push_str("  --shiki-foreground: var(--zenn-code-foreground);\n");
+    out.push_str("  --shiki-background: var(--zenn-code-background);\n");
+    for (token, shiki, _) in TOKENS {
+        if let Some(shiki) = shiki {
+            let _ = writeln!(out, "  --shiki-token-{shiki}: var(--zenn-code-{token});");
+        }
+    }
+    out.push_str("}\n\n");
+
+    // Shiki wraps each block in `<pre class="shiki ...">`, and the theme
+    // colors it through inline styles
+    out.push_str("pre.shiki {\n");
+    out.push_str("  color: var(--shiki-foreground);\n");
+    out.push_str("  background-color: var(--shiki-background);\n");
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn variables_follow_the_theme_rules() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#eeeeee", "background": "#111111" } },
+                { "scope": "comment", "settings": { "foreground": "#888888", "fontStyle": "italic" } },
+                { "scope": "markup.deleted", "settings": { "foreground": "#ff0000", "fontStyle": "strikethrough underline" } }
+            ]
+        }))
+        .unwrap();
+        let css = css(&theme);
+        assert!(css.contains("  --zenn-code-background: #111111;\n"));
+        assert!(css.contains("  --zenn-code-comment: #888888;\n"));
+        assert!(css.contains("  --zenn-code-keyword: #eeeeee;\n"));
+        assert!(css.contains("  --shiki-token-comment: var(--zenn-code-comment);\n"));
+        assert!(!css.contains("--shiki-token-number"));
+        assert!(css.contains("  --shiki-token-deleted: var(--zenn-code-deleted);\n"));
+        assert!(css.ends_with(
+            "pre.shiki {\n  color: var(--shiki-foreground);\n  background-color: var(--shiki-background);\n}\n"
+        ));
+        assert!(!css.contains(".zenn-code-"));
+    }
+}
@@ -0,0 +1,111 @@
+//! Helix themes (`~/.config/helix/themes/<name>.toml`).
+//!
+//! As with the Vim backend, each Helix scope is styled by resolving a
+//! representative TextMate scope stack against the theme.
+
+use std::fmt::Write;
+
+use crate::resolver::{FontStyle, Resolved, Resolver};
+use crate::theme::Theme;
+
+const SCOPES: &[(&str, &[&str])] = &[
+    ("attribute", &["source", "entity.other.attribute-name"]),
+    ("type", &["source", "entity.name.type"]),
+    ("type.builtin", &["source", "support.type.primitive"]),
+    ("constructor", &["source", "entity.name.function"]),
+    ("constant", &["source", "variable.other.constant"]),
+    ("constant.builtin", &["source", "constant.language"]),
+    ("constant.character", &["source", "constant.character"]),
+    ("constant.character.escape", &["source", "constant.character.escape"]),
+    ("constant.numeric", &["source", "constant.numeric"]),
+    ("string", &["source", "string.quoted.double"]),
+    ("string.regexp", &["source", "string.regexp"]),
+    ("string.special.url", &["text.html.markdown", "markup.underline.link"]),
+    ("comment", &["source", "comment.line"]),
+    ("variable", &["source", "variable.other.readwrite"]),
+    ("variable.parameter", &["source", "variable.parameter"]),
+    ("variable.other.member", &["source", "variable.other.property"]),
+    ("label", &["source", "entity.name.label"]),
+    ("punctuation", &["source", "punctuation"]),
+    ("punctuation.delimiter", &["source", "punctuation.separator"]),
+    ("punctuation.bracket", &["source", "punctuation.section"]),
+    ("keyword", &["source", "keyword"]),
+    ("keyword.control", &["source", "keyword.control"]),
+    ("keyword.control.import", &["source", "keyword.control.import"]),
+    ("keyword.operator", &["source", "keyword.operator"]),
+    ("keyword.function", &["source", "storage.type.function"]),
+    ("keyword.storage", &["source", "storage.type"]),
+    ("keyword.storage.modifier", &["source", "storage.modifier"]),
+    ("operator", &["source", "keyword.operator"]),
+    ("function", &["source", "entity.name.function"]),
+    ("function.builtin", &["source", "support.function"]),
+    ("function.macro", &["source.rust", "entity.name.function.macro.rust"]),
+    ("tag", &["text.html", "entity.name.tag"]),
+    ("namespace", &["source", "entity.name.namespace"]),
+    ("special", &["source", "constant.character.escape"]),
+    ("markup.heading", &["text.html.markdown", "markup.heading"]),
+    ("markup.bold", &["text.html.markdown", "markup.bold"]),
+    ("markup.italic", &["text.html.markdown", "markup.italic"]),
+    ("markup.link.url", &["text.html.markdown", "markup.underline.link"]),
+    ("markup.quote", &["text.html.markdown", "markup.quote"]),
+    ("markup.raw", &["text.html.markdown", "markup.raw"]),
+    ("diff.plus", &["source.diff", "markup.inserted"]),
+    ("diff.minus", &["source.diff", "markup.deleted"]),
+    ("diff.delta", &["source.diff", "markup.changed"]),
+    ("error", &["source", "invalid.illegal"]),
+];
+
+pub fn helix(theme: &Theme) -> String {
+    let resolver = Resolver::new(theme);
+    let name = theme.display_name.as_deref().unwrap_or(&theme.name);
+    let foreground = resolver.default_foreground();
+    let background = resolver.default_background();
+    let comment = resolver.resolve(&["source", "comment.line"]).foreground;
+
+    let mut out = String::new();
+    let _ = write!(out, "# {name}\n# Generated from {}.json by `cargo xtask export`; do not edit.\n\n", theme.name);
+    for (scope, stack) in SCOPES {
+        let _ = writeln!(out, "{} = {}", key(scope), style(&resolver.resolve(stack)));
+    }
+
+    out.push('\n');
+    let _ = writeln!(out, "{} = {{ bg = \"{background}\" }}", key("ui.background"));
+    let _ = writeln!(out, "{} = {{ fg = \"{foreground}\" }}", key("ui.text"));
+    let _ = writeln!(out, "{} = {{ fg = \"{comment}\" }}", key("ui.linenr"));
+    let _ = writeln!(out, "{} = {{ fg = \"{foreground}\" }}", key("ui.linenr.selected"));
+    let _ = writeln!(out, "{} = {{ modifiers = [\"reversed\"] }}", key("ui.cursor"));
+    let _ = writeln!(out, "{} = {{ fg = \"{comment}\" }}", key("ui.virtual.whitespace"));
+    if let Some(color) = theme.colors.get("editor.selectionBackground") {
+        let _ = writeln!(out, "{} = {{ bg = \"{color}\" }}", key("ui.selection"));
+    }
+    if let Some(color) = theme.colors.get("editor.lineHighlightBackground") {
+        let _ = writeln!(out, "{} = {{ bg = \"{color}\" }}", key("ui.cursorline.primary"));
+    }
+    out
+}
+
+/// Dotted Helix scopes are quoted so TOML reads them as one key.
+fn key(scope: &str) -> String {
+    format!("\"{scope}\"")
+}
+
+fn style(style: &Resolved<'_>) -> String {
+    let mut fields = vec![format!("fg = \"{}\"", style.foreground)];
+    if style.background_rule.is_some() {
+        fields.push(format!("bg = \"{}\"", style.background));
+    }
+    let modifiers: Vec<String> = [
+        (FontStyle::BOLD, "bold"),
+        (FontStyle::ITALIC, "italic"),
+        (FontStyle::UNDERLINE, "underlined"),
+        (FontStyle::STRIKETHROUGH, "crossed_out"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| style.font_style.contains(*flag))
+    .map(|(_, name)| format!("\"{name}\""))
+    .collect();
+    if !modifiers.is_empty() {
+        fields.push(format!("modifiers = [{}]", modifiers.join(", ")));
+    }
+    format!("{{ {} }}", fields.join(", "))
+}
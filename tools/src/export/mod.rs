@@ -0,0 +1,82 @@
+//! Converters from the theme model to other editors' and tools' formats.
+//!
+//! Each backend takes a loaded [`Theme`](crate::theme::Theme), so a rule
+//! change in the JSON reaches every format on the next export.
+
+pub mod css;
+pub mod helix;
+pub mod terminal;
+pub mod tmtheme;
+pub mod vim;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::theme::Theme;
+use crate::{Error, Result};
+
+/// One kind of file [`write`] produces, as `tools.toml` names it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Target {
+    Json,
+    Tmtheme,
+    Vim,
+    Helix,
+    /// Windows Terminal and iTerm2 schemes.
+    Terminal,
+    /// [`Target::Terminal`] downsampled to xterm-256.
+    #[serde(rename = "terminal-256")]
+    Terminal256,
+    /// CSS custom properties and classes for embedding in web pages.
+    Css,
+}
+
+impl Target {
+    pub const ALL: [Target; 7] =
+        [Target::Json, Target::Tmtheme, Target::Vim, Target::Helix, Target::Terminal, Target::Terminal256, Target::Css];
+
+    fn outputs(self, theme: &Theme, stem: &str) -> Vec<(String, String)> {
+        match self {
+            Target::Json => vec![(format!("{stem}.json"), theme.to_json())],
+            Target::Tmtheme => vec![(format!("{stem}.tmTheme"), tmtheme::tmtheme(theme))],
+            Target::Vim => vec![(format!("vim/colors/{stem}.vim"), vim::vim(theme))],
+            Target::Helix => vec![(format!("helix/{stem}.toml"), helix::helix(theme))],
+            Target::Terminal => vec![
+                (format!("terminal/{stem}.windows-terminal.json"), terminal::windows_terminal(theme)),
+                (format!("terminal/{stem}.itermcolors"), terminal::iterm(theme)),
+            ],
+            Target::Terminal256 => vec![
+                (format!("terminal/{stem}-256.windows-terminal.json"), terminal::windows_terminal_256(theme)),
+                (format!("terminal/{stem}-256.itermcolors"), terminal::iterm_256(theme)),
+            ],
+            Target::Css => vec![(format!("css/{stem}.css"), css::css(theme))],
+        }
+    }
+}
+
+/// Writes `theme` in every format under `out`, named after `stem`, and
+/// returns the paths written.
+pub fn write_all(theme: &Theme, stem: &str, out: &Path) -> Result<Vec<PathBuf>> {
+    write(theme, stem, out, &Target::ALL)
+}
+
+/// [`write_all`] limited to `targets`.
+pub fn write(theme: &Theme, stem: &str, out: &Path, targets: &[Target]) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for target in targets {
+        let _span = tracing::debug_span!("export", ?target, stem).entered();
+        for (name, contents) in target.outputs(theme, stem) {
+            let path = out.join(name);
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+            }
+            fs::write(&path, &contents).map_err(|e| Error::io(&path, e))?;
+            tracing::debug!(path = %path.display(), bytes = contents.len(), "wrote");
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
@@ -0,0 +1,238 @@
+//! 16-color terminal schemes for Windows Terminal and iTerm2, in truecolor
+//! and downsampled to the xterm 256-color palette.
+//!
+//! Each ANSI hue is aimed at the average lightness and chroma of the
+//! theme's accent colors. Accents are matched to the slots they are
+//! nearest to in OKLab, each used once and only when close enough; a slot
+//! left over gets the aimed-at color itself, so every slot keeps a
+//! recognizable hue even when the theme has no green.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::colors::{Oklch, Rgba};
+use crate::quantize;
+use crate::resolver::Resolver;
+use crate::theme::Theme;
+
+/// Slot names in ANSI order, as Windows Terminal spells them.
+const NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "purple", "cyan", "white"];
+
+/// OKLCH hues of the xterm red, green, yellow, blue, magenta and cyan.
+const HUES: [f64; 6] = [29.2, 142.5, 110.0, 264.1, 328.4, 194.8];
+
+/// Farthest an accent may be from the aimed-at color and still stand in.
+const MAX_DISTANCE: f64 = 0.12;
+
+/// Below this chroma a color counts as gray, not as an accent.
+const GRAY_CHROMA: f64 = 0.04;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scheme {
+    pub name: String,
+    pub background: Rgba,
+    pub foreground: Rgba,
+    pub cursor: Rgba,
+    pub selection: Rgba,
+    /// Normal colors `0..8`, then their bright variants.
+    pub ansi: [Rgba; 16],
+}
+
+pub fn scheme(theme: &Theme) -> Scheme {
+    let resolver = Resolver::new(theme);
+    let parse = |hex: &str| Rgba::parse(hex).unwrap_or(Rgba::BLACK);
+    let background = parse(resolver.default_background());
+    let foreground = parse(resolver.default_foreground());
+    let color = |key: &str| theme.colors.get(key).map(Rgba::from);
+
+    let accents: Vec<Rgba> = theme
+        .token_colors
+        .iter()
+        .filter_map(|rule| rule.settings.foreground.as_ref())
+        .map(|color| (color.as_str().to_ascii_lowercase(), Rgba::from(color)))
+        .filter(|(_, c)| c.a == 1.0 && c.to_oklch().c >= GRAY_CHROMA)
+        .collect::<BTreeMap<_, _>>()
+        .into_values()
+        .collect();
+    let (l, c) = if accents.is_empty() {
+        (0.75, 0.12)
+    } else {
+        let n = accents.len() as f64;
+        (
+            accents.iter().map(|a| a.to_oklch().l).sum::<f64>() / n,
+            accents.iter().map(|a| a.to_oklch().c).sum::<f64>() / n,
+        )
+    };
+
+    // Closest (slot, accent) pairs first, each accent used at most once
+    let targets: Vec<Rgba> = HUES.iter().map(|&h| Oklch { l, c, h }.to_rgba(1.0)).collect();
+    let mut pairs: Vec<(f64, usize, usize)> = Vec::new();
+    for (slot, target) in targets.iter().enumerate() {
+        for (index, accent) in accents.iter().enumerate() {
+            let distance = accent.oklab_distance(*target);
+            if distance <= MAX_DISTANCE {
+                pairs.push((distance, slot, index));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut chosen: Vec<Option<Rgba>> = vec![None; HUES.len()];
+    let mut used = vec![false; accents.len()];
+    for (_, slot, index) in pairs {
+        if chosen[slot].is_none() && !used[index] {
+            chosen[slot] = Some(accents[index]);
+            used[index] = true;
+        }
+    }
+
+    let dark_theme = background.relative_luminance() < foreground.relative_luminance();
+    let (dark, light) = if dark_theme { (background, foreground) } else { (foreground, background) };
+    let mut normal = [Rgba::BLACK; 8];
+    normal[0] = dark.lighten(0.08);
+    for (slot, (choice, target)) in normal[1..7].iter_mut().zip(chosen.into_iter().zip(targets)) {
+        *slot = choice.unwrap_or(target);
+    }
+    normal[7] = light.darken(0.12);
+
+    let mut ansi = [Rgba::BLACK; 16];
+    for (i, color) in normal.into_iter().enumerate() {
+        ansi[i] = color;
+        ansi[i + 8] = match i {
+            0 => dark.lighten(0.3),
+            7 => light,
+            _ => color.lighten(0.06),
+        };
+    }
+
+    Scheme {
+        name: theme.display_name.clone().unwrap_or_else(|| theme.name.clone()),
+        background,
+        foreground,
+        cursor: color("editorCursor.foreground").unwrap_or(foreground),
+        selection: color("editor.selectionBackground").unwrap_or_else(|| dark.lighten(0.15)),
+        ansi,
+    }
+}
+
+impl Scheme {
+    /// The same scheme with every color moved to its xterm-256 index, named
+    /// apart so both variants can be installed side by side.
+    pub fn downsampled(&self) -> Scheme {
+        let mut colors = vec![self.background, self.foreground, self.cursor, self.selection];
+        colors.extend(self.ansi);
+        let mut quantized = quantize::assign(&colors).into_iter().map(quantize::xterm);
+        let mut next = || quantized.next().unwrap_or(Rgba::BLACK);
+        Scheme {
+            name: format!("{} 256", self.name),
+            background: next(),
+            foreground: next(),
+            cursor: next(),
+            selection: next(),
+            ansi: std::array::from_fn(|_| next()),
+        }
+    }
+}
+
+/// A Windows Terminal `schemes` entry.
+pub fn windows_terminal(theme: &Theme) -> String {
+    windows_terminal_scheme(&scheme(theme))
+}
+
+/// [`windows_terminal`] for terminals without truecolor.
+pub fn windows_terminal_256(theme: &Theme) -> String {
+    windows_terminal_scheme(&scheme(theme).downsampled())
+}
+
+fn windows_terminal_scheme(scheme: &Scheme) -> String {
+    let mut fields = vec![
+        ("name".to_owned(), serde_json::Value::from(scheme.name.clone())),
+        ("background".to_owned(), scheme.background.to_hex().into()),
+        ("foreground".to_owned(), scheme.foreground.to_hex().into()),
+        ("cursorColor".to_owned(), scheme.cursor.to_hex().into()),
+        ("selectionBackground".to_owned(), scheme.selection.to_hex().into()),
+    ];
+    for (i, color) in scheme.ansi.iter().enumerate() {
+        let name = NAMES[i % 8];
+        let key = if i < 8 { name.to_owned() } else { format!("bright{}{}", name[..1].to_uppercase(), &name[1..]) };
+        fields.push((key, color.to_hex().into()));
+    }
+    let map: serde_json::Map<_, _> = fields.into_iter().collect();
+    let mut json = serde_json::to_string_pretty(&map).expect("scheme serializes");
+    json.push('\n');
+    json
+}
+
+/// An iTerm2 `.itermcolors` property list.
+pub fn iterm(theme: &Theme) -> String {
+    iterm_scheme(&scheme(theme))
+}
+
+/// [`iterm`] for terminals without truecolor.
+pub fn iterm_256(theme: &Theme) -> String {
+    iterm_scheme(&scheme(theme).downsampled())
+}
+
+fn iterm_scheme(scheme: &Scheme) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+        "<plist version=\"1.0\">\n<dict>\n",
+    ));
+    for (i, color) in scheme.ansi.iter().enumerate() {
+        iterm_color(&mut out, &format!("Ansi {i} Color"), *color);
+    }
+    iterm_color(&mut out, "Background Color", scheme.background);
+    iterm_color(&mut out, "Foreground Color", scheme.foreground);
+    iterm_color(&mut out, "Cursor Color", scheme.cursor);
+    iterm_color(&mut out, "Selection Color", scheme.selection);
+    out.push_str("</dict>\n</plist>\n");
+    out
+}
+
+fn iterm_color(out: &mut String, key: &str, color: Rgba) {
+    let _ = write!(out, "\t<key>{key}</key>\n\t<dict>\n");
+    for (component, value) in [("Alpha", color.a), ("Blue", color.b), ("Green", color.g), ("Red", color.r)] {
+        let _ = write!(out, "\t\t<key>{component} Component</key>\n\t\t<real>{value:.6}</real>\n");
+    }
+    out.push_str("\t\t<key>Color Space</key>\n\t\t<string>sRGB</string>\n\t</dict>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn accents_stand_in_for_nearby_hues() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#ffffff", "background": "#1a2638" } },
+                { "scope": "keyword", "settings": { "foreground": "#ff8fa3" } },
+                { "scope": "string", "settings": { "foreground": "#ffc56d" } },
+                { "scope": "entity.name.function", "settings": { "foreground": "#38c7ff" } },
+                { "scope": "comment", "settings": { "foreground": "#94a1b3" } }
+            ]
+        }))
+        .unwrap();
+        let scheme = scheme(&theme);
+        assert_eq!(scheme.ansi[1].to_hex(), "#ff8fa3");
+        assert_eq!(scheme.ansi[3].to_hex(), "#ffc56d");
+        assert_eq!(scheme.ansi[4].to_hex(), "#38c7ff");
+        // Each accent fills one slot; the rest are synthesized at their hue
+        assert_ne!(scheme.ansi[6].to_hex(), "#38c7ff");
+        assert_ne!(scheme.ansi[5].to_hex(), "#ff8fa3");
+        assert_eq!(scheme.ansi[15].to_hex(), "#ffffff");
+
+        let terminal: serde_json::Value = serde_json::from_str(&windows_terminal(&theme)).unwrap();
+        assert_eq!(terminal["red"], "#ff8fa3");
+        assert_eq!(terminal["brightWhite"], "#ffffff");
+        assert_eq!(terminal["background"], "#1a2638");
+
+        let downsampled = scheme.downsampled();
+        assert_eq!(downsampled.name, "t 256");
+        assert!(downsampled.ansi.iter().all(|&c| c == quantize::xterm(quantize::nearest(c))));
+        assert_ne!(downsampled.background, downsampled.ansi[0]);
+    }
+}
@@ -0,0 +1,131 @@
+//! TextMate `.tmTheme` property lists, read by Sublime Text, bat and
+//! syntect.
+
+use std::fmt::Write;
+
+use crate::resolver::Resolver;
+use crate::theme::{Settings, Theme, ThemeKind};
+
+/// Global settings taken from the VS Code `colors` map.
+const GLOBALS: &[(&str, &str)] = &[
+    ("editorCursor.foreground", "caret"),
+    ("editor.selectionBackground", "selection"),
+    ("editor.lineHighlightBackground", "lineHighlight"),
+    ("editorWhitespace.foreground", "invisibles"),
+    ("editorLineNumber.foreground", "gutterForeground"),
+];
+
+pub fn tmtheme(theme: &Theme) -> String {
+    let resolver = Resolver::new(theme);
+    let name = theme.display_name.as_deref().unwrap_or(&theme.name);
+
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+        "<plist version=\"1.0\">\n<dict>\n",
+    ));
+    entry(&mut out, 1, "name", name);
+    out.push_str("\t<key>settings</key>\n\t<array>\n");
+
+    // The unscoped first entry holds the editor-wide colors
+    out.push_str("\t\t<dict>\n\t\t\t<key>settings</key>\n\t\t\t<dict>\n");
+    entry(&mut out, 4, "background", resolver.default_background());
+    entry(&mut out, 4, "foreground", resolver.default_foreground());
+    for (key, global) in GLOBALS {
+        if let Some(color) = theme.colors.get(*key) {
+            entry(&mut out, 4, global, color.as_str());
+        }
+    }
+    out.push_str("\t\t\t</dict>\n\t\t</dict>\n");
+
+    for rule in theme.token_colors.iter().filter(|rule| !rule.is_default()) {
+        let scope = rule.selectors().join(", ");
+        out.push_str("\t\t<dict>\n");
+        entry(&mut out, 3, "name", rule.name.as_deref().unwrap_or(&scope));
+        entry(&mut out, 3, "scope", &scope);
+        out.push_str("\t\t\t<key>settings</key>\n\t\t\t<dict>\n");
+        settings(&mut out, &rule.settings);
+        out.push_str("\t\t\t</dict>\n\t\t</dict>\n");
+    }
+
+    out.push_str("\t</array>\n");
+    entry(&mut out, 1, "colorSpaceName", "sRGB");
+    entry(&mut out, 1, "semanticClass", &format!("theme.{}.{}", kind(theme), theme.name));
+    out.push_str("</dict>\n</plist>\n");
+    out
+}
+
+fn settings(out: &mut String, settings: &Settings) {
+    if let Some(color) = &settings.foreground {
+        entry(out, 4, "foreground", color.as_str());
+    }
+    if let Some(color) = &settings.background {
+        entry(out, 4, "background", color.as_str());
+    }
+    if let Some(style) = settings.font_style {
+        entry(out, 4, "fontStyle", &style.to_string());
+    }
+}
+
+fn kind(theme: &Theme) -> &'static str {
+    match theme.kind {
+        ThemeKind::Dark => "dark",
+        ThemeKind::Light => "light",
+    }
+}
+
+fn entry(out: &mut String, depth: usize, key: &str, value: &str) {
+    let tabs = "\t".repeat(depth);
+    let _ = writeln!(out, "{tabs}<key>{key}</key>\n{tabs}<string>{}</string>", escape(value));
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    use serde_json::json;
+    use syntect::highlighting::{Color, FontStyle, Highlighter, ThemeSet};
+    use syntect::parsing::ScopeStack;
+
+    use super::*;
+
+    fn theme() -> Theme {
+        serde_json::from_value(json!({
+            "name": "t",
+            "displayName": "T & Co",
+            "colors": { "editor.background": "#102030", "editorCursor.foreground": "#abcdef" },
+            "tokenColors": [
+                { "settings": { "foreground": "#eeeeee" } },
+                { "scope": ["keyword", "storage.type"], "settings": { "foreground": "#ff0000", "fontStyle": "bold" } },
+                { "scope": "source.rust string", "settings": { "foreground": "#00ff00" } }
+            ]
+        }))
+        .unwrap()
+    }
+
+    fn rgb(color: Color) -> (u8, u8, u8) {
+        (color.r, color.g, color.b)
+    }
+
+    #[test]
+    fn syntect_reads_the_same_colors() {
+        let exported = tmtheme(&theme());
+        let loaded = ThemeSet::load_from_reader(&mut Cursor::new(exported)).unwrap();
+        assert_eq!(loaded.name.as_deref(), Some("T & Co"));
+        assert_eq!(loaded.settings.background.map(rgb), Some((0x10, 0x20, 0x30)));
+        assert_eq!(loaded.settings.caret.map(rgb), Some((0xab, 0xcd, 0xef)));
+
+        let highlighter = Highlighter::new(&loaded);
+        let style = |stack: &str| highlighter.style_for_stack(ScopeStack::from_str(stack).unwrap().as_slice());
+        let keyword = style("source.rust storage.type.rust");
+        assert_eq!(rgb(keyword.foreground), (0xff, 0, 0));
+        assert_eq!(keyword.font_style, FontStyle::BOLD);
+        assert_eq!(rgb(style("source.rust string.quoted.rust").foreground), (0, 0xff, 0));
+        assert_eq!(rgb(style("source.python string.quoted.python").foreground), (0xee, 0xee, 0xee));
+    }
+}
@@ -0,0 +1,172 @@
+//! Vim/Neovim colorschemes (`colors/<name>.vim`).
+//!
+//! Each highlight group is paired with a representative TextMate scope
+//! stack and styled the way the resolver styles that stack, so the editor
+//! follows whatever the theme's rules say rather than a second copy of the
+//! palette. Tree-sitter captures are only set under Neovim, since Vim
+//! rejects `@` in group names.
+
+use std::fmt::Write;
+
+use crate::resolver::{FontStyle, Resolver};
+use crate::theme::{Theme, ThemeKind};
+
+/// Classic syntax groups, understood by both Vim and Neovim.
+const GROUPS: &[(&str, &[&str])] = &[
+    ("Comment", &["source", "comment.line"]),
+    ("Constant", &["source", "constant.other"]),
+    ("String", &["source", "string.quoted.double"]),
+    ("Character", &["source", "constant.character"]),
+    ("Number", &["source", "constant.numeric"]),
+    ("Boolean", &["source", "constant.language"]),
+    ("Identifier", &["source", "variable.other"]),
+    ("Function", &["source", "entity.name.function"]),
+    ("Statement", &["source", "keyword.control"]),
+    ("Keyword", &["source", "keyword"]),
+    ("Operator", &["source", "keyword.operator"]),
+    ("PreProc", &["source", "meta.preprocessor"]),
+    ("Include", &["source", "keyword.control.import"]),
+    ("Type", &["source", "entity.name.type"]),
+    ("StorageClass", &["source", "storage.modifier"]),
+    ("Structure", &["source", "storage.type"]),
+    ("Special", &["source", "constant.character.escape"]),
+    ("Delimiter", &["source", "punctuation.separator"]),
+    ("Tag", &["text.html", "entity.name.tag"]),
+    ("Title", &["text.html.markdown", "markup.heading"]),
+    ("Underlined", &["text.html.markdown", "markup.underline.link"]),
+    ("Error", &["source", "invalid.illegal"]),
+    ("DiffAdd", &["source.diff", "markup.inserted"]),
+    ("DiffDelete", &["source.diff", "markup.deleted"]),
+    ("DiffChange", &["source.diff", "markup.changed"]),
+];
+
+/// Neovim tree-sitter captures.
+const CAPTURES: &[(&str, &[&str])] = &[
+    ("@comment", &["source", "comment.line"]),
+    ("@string", &["source", "string.quoted.double"]),
+    ("@string.escape", &["source", "constant.character.escape"]),
+    ("@string.regexp", &["source", "string.regexp"]),
+    ("@character", &["source", "constant.character"]),
+    ("@number", &["source", "constant.numeric"]),
+    ("@boolean", &["source", "constant.language"]),
+    ("@constant", &["source", "variable.other.constant"]),
+    ("@constant.builtin", &["source", "constant.language"]),
+    ("@variable", &["source", "variable.other.readwrite"]),
+    ("@variable.parameter", &["source", "variable.parameter"]),
+    ("@variable.member", &["source", "variable.other.property"]),
+    ("@property", &["source", "variable.other.property"]),
+    ("@function", &["source", "entity.name.function"]),
+    ("@function.call", &["source", "meta.function-call", "entity.name.function"]),
+    ("@function.builtin", &["source", "support.function"]),
+    ("@function.macro", &["source.rust", "entity.name.function.macro.rust"]),
+    ("@keyword", &["source", "keyword"]),
+    ("@keyword.function", &["source", "storage.type.function"]),
+    ("@keyword.return", &["source", "keyword.control"]),
+    ("@keyword.import", &["source", "keyword.control.import"]),
+    ("@keyword.operator", &["source", "keyword.operator"]),
+    ("@operator", &["source", "keyword.operator"]),
+    ("@type", &["source", "entity.name.type"]),
+    ("@type.builtin", &["source", "support.type.primitive"]),
+    ("@module", &["source", "entity.name.namespace"]),
+    ("@attribute", &["source", "entity.other.attribute-name"]),
+    ("@punctuation", &["source", "punctuation"]),
+    ("@punctuation.bracket", &["source", "punctuation.section"]),
+    ("@punctuation.delimiter", &["source", "punctuation.separator"]),
+    ("@tag", &["text.html", "entity.name.tag"]),
+    ("@tag.attribute", &["text.html", "entity.other.attribute-name"]),
+    ("@markup.heading", &["text.html.markdown", "markup.heading"]),
+    ("@markup.strong", &["text.html.markdown", "markup.bold"]),
+    ("@markup.italic", &["text.html.markdown", "markup.italic"]),
+    ("@markup.link.url", &["text.html.markdown", "markup.underline.link"]),
+    ("@markup.raw", &["text.html.markdown", "markup.raw"]),
+    ("@markup.quote", &["text.html.markdown", "markup.quote"]),
+    ("@diff.plus", &["source.diff", "markup.inserted"]),
+    ("@diff.minus", &["source.diff", "markup.deleted"]),
+    ("@diff.delta", &["source.diff", "markup.changed"]),
+];
+
+pub fn vim(theme: &Theme) -> String {
+    let resolver = Resolver::new(theme);
+    let name = theme.display_name.as_deref().unwrap_or(&theme.name);
+    let background = match theme.kind {
+        ThemeKind::Dark => "dark",
+        ThemeKind::Light => "light",
+    };
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "\" {name}\n\" Generated from {}.json by `cargo xtask export`; do not edit.\n\n\
+         hi clear\nif exists('syntax_on')\n  syntax reset\nendif\nset background={background}\nlet g:colors_name = '{}'\n\n",
+        theme.name, theme.name
+    );
+    let _ = writeln!(
+        out,
+        "hi Normal guifg={} guibg={} gui=NONE",
+        resolver.default_foreground(),
+        resolver.default_background()
+    );
+    for (group, stack) in GROUPS {
+        highlight(&mut out, &resolver, group, stack, "");
+    }
+    out.push_str("\nif has('nvim')\n");
+    for (capture, stack) in CAPTURES {
+        highlight(&mut out, &resolver, capture, stack, "  ");
+    }
+    out.push_str("endif\n");
+    out
+}
+
+fn highlight(out: &mut String, resolver: &Resolver, group: &str, stack: &[&str], indent: &str) {
+    let style = resolver.resolve(stack);
+    let _ = write!(out, "{indent}hi {group} guifg={}", style.foreground);
+    if style.background_rule.is_some() {
+        let _ = write!(out, " guibg={}", style.background);
+    }
+    let _ = writeln!(out, " gui={}", attributes(style.font_style));
+}
+
+fn attributes(style: FontStyle) -> String {
+    let names: Vec<&str> = [
+        (FontStyle::BOLD, "bold"),
+        (FontStyle::ITALIC, "italic"),
+        (FontStyle::UNDERLINE, "underline"),
+        (FontStyle::STRIKETHROUGH, "strikethrough"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| style.contains(*flag))
+    .map(|(_, name)| name)
+    .collect();
+    if names.is_empty() {
+        "NONE".to_owned()
+    } else {
+        names.join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn groups_follow_the_theme_rules() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#eeeeee", "background": "#111111" } },
+                { "scope": "comment", "settings": { "foreground": "#888888", "fontStyle": "italic" } },
+                { "scope": "invalid", "settings": { "foreground": "#111111", "background": "#ff0000" } }
+            ]
+        }))
+        .unwrap();
+        let vim = vim(&theme);
+        assert!(vim.contains("let g:colors_name = 't'\n"));
+        assert!(vim.contains("hi Normal guifg=#eeeeee guibg=#111111 gui=NONE\n"));
+        assert!(vim.contains("hi Comment guifg=#888888 gui=italic\n"));
+        assert!(vim.contains("hi Error guifg=#111111 guibg=#ff0000 gui=NONE\n"));
+        assert!(vim.contains("hi Keyword guifg=#eeeeee gui=NONE\n"));
+        assert!(vim.contains("  hi @comment guifg=#888888 gui=italic\n"));
+    }
+}
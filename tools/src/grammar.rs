@@ -0,0 +1,415 @@
+//! Loads the TextMate JSON grammars Shiki ships and converts them for
+//! syntect.
+//!
+//! syntect only reads `.sublime-syntax`, so each grammar is translated the
+//! way Sublime Text itself imports `.tmLanguage` files:
+//!
+//! - `match` rules become match patterns with the same captures
+//! - `begin`/`end` rules push an anonymous context whose `meta_scope` is the
+//!   rule's `name`, whose `meta_content_scope` is its `contentName`, and
+//!   whose first pattern pops on `end` (last with `applyEndPatternLast`)
+//! - `begin`/`while` rules pop when a line no longer matches `while`
+//! - `#name`, `$self`, `source.x` and `source.x#name` includes map to named
+//!   contexts and `scope:` references
+//!
+//! Injections and patterns nested inside captures are not supported.
+//! The result is emitted as flow-style YAML and parsed by syntect, since its
+//! context references cannot be constructed directly.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+use syntect::parsing::SyntaxDefinition;
+
+use crate::{Error, Result};
+
+/// Sample file extensions for each Shiki language id.
+pub const LANGUAGE_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("c", &["c", "h"]),
+    ("cpp", &["cpp", "cc", "hpp"]),
+    ("csharp", &["cs"]),
+    ("css", &["css"]),
+    ("go", &["go"]),
+    ("html", &["html"]),
+    ("java", &["java"]),
+    ("javascript", &["js", "mjs", "cjs"]),
+    ("json", &["json"]),
+    ("kotlin", &["kt", "kts"]),
+    ("markdown", &["md"]),
+    ("php", &["php"]),
+    ("python", &["py"]),
+    ("ruby", &["rb"]),
+    ("rust", &["rs"]),
+    ("shellscript", &["sh", "bash"]),
+    ("sql", &["sql"]),
+    ("swift", &["swift"]),
+    ("typescript", &["ts"]),
+    ("yaml", &["yaml", "yml"]),
+];
+
+/// syntect rejects scopes with more atoms than this.
+const MAX_ATOMS: usize = 8;
+
+/// Reads every grammar in `dir`: plain `*.json` files (as in the
+/// `tm-grammars` package) or Shiki's `@shikijs/langs/dist/*.mjs` modules.
+pub fn load_dir(dir: &Path) -> Result<Vec<SyntaxDefinition>> {
+    let mut definitions = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for path in grammar_files(dir)? {
+        for grammar in read_grammars(&path)? {
+            let Some(scope) = grammar.get("scopeName").and_then(Value::as_str) else {
+                continue;
+            };
+            // a directory may hold both the .json and .mjs form of a grammar
+            if seen.insert(scope.to_owned()) {
+                let fail = |message: String| Error::Grammar { path: path.clone(), message };
+                definitions.push(to_syntax_definition(&grammar).map_err(fail)?);
+            }
+        }
+    }
+    Ok(definitions)
+}
+
+/// The grammar files [`load_dir`] reads from `dir`, sorted.
+pub fn grammar_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| Error::io(dir, e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json" || ext == "mjs"))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_grammars(path: &Path) -> Result<Vec<Value>> {
+    let text = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+    let fail = |message: String| Error::Grammar { path: path.to_path_buf(), message };
+
+    if path.extension().is_some_and(|ext| ext == "json") {
+        return serde_json::from_str(&text).map(|g| vec![g]).map_err(|e| fail(e.to_string()));
+    }
+
+    grammars_from_module(&text).map_err(fail)
+}
+
+/// Extracts the grammars embedded in a Shiki language module, which look
+/// like `const lang = Object.freeze(JSON.parse("{...}"))`.
+pub fn grammars_from_module(text: &str) -> Result<Vec<Value>, String> {
+    let mut grammars = Vec::new();
+    for (start, _) in text.match_indices("JSON.parse(") {
+        let literal = &text[start + "JSON.parse(".len()..];
+        let json: String = serde_json::Deserializer::from_str(literal)
+            .into_iter()
+            .next()
+            .ok_or("empty JSON.parse call")?
+            .map_err(|e| e.to_string())?;
+        grammars.push(serde_json::from_str(&json).map_err(|e| e.to_string())?);
+    }
+    Ok(grammars)
+}
+
+pub fn to_syntax_definition(grammar: &Value) -> Result<SyntaxDefinition, String> {
+    let yaml = to_sublime_syntax(grammar)?;
+    SyntaxDefinition::load_from_str(&yaml, true, None).map_err(|e| e.to_string())
+}
+
+/// Translates a TextMate grammar into `.sublime-syntax` YAML.
+pub fn to_sublime_syntax(grammar: &Value) -> Result<String, String> {
+    let scope = grammar.get("scopeName").and_then(Value::as_str).ok_or("grammar has no scopeName")?;
+    let name = grammar.get("name").and_then(Value::as_str).unwrap_or(scope);
+
+    let mut extensions: Vec<Value> = LANGUAGE_EXTENSIONS
+        .iter()
+        .filter(|(id, _)| *id == name)
+        .flat_map(|(_, exts)| exts.iter().map(|e| Value::from(*e)))
+        .collect();
+    if let Some(types) = grammar.get("fileTypes").and_then(Value::as_array) {
+        for file_type in types {
+            if !extensions.contains(file_type) {
+                extensions.push(file_type.clone());
+            }
+        }
+    }
+
+    let mut contexts = Map::new();
+    contexts.insert("main".to_owned(), Value::Array(patterns(grammar)));
+    if let Some(Value::Object(repo)) = grammar.get("repository") {
+        collect_repository(repo, &mut contexts);
+    }
+
+    let mut root = Map::new();
+    root.insert("name".to_owned(), name.into());
+    root.insert("scope".to_owned(), scope.into());
+    root.insert("file_extensions".to_owned(), Value::Array(extensions));
+    root.insert("contexts".to_owned(), Value::Object(contexts));
+
+    let mut out = String::from("%YAML 1.2\n---\n");
+    emit(&Value::Object(root), &mut out);
+    out.push('\n');
+    Ok(out)
+}
+
+fn collect_repository(repo: &Map<String, Value>, contexts: &mut Map<String, Value>) {
+    for (key, rule) in repo {
+        let body = if rule.get("match").is_some() || rule.get("begin").is_some() || rule.get("include").is_some() {
+            convert_rule(rule).into_iter().collect()
+        } else {
+            patterns(rule)
+        };
+        contexts.insert(context_name(key), Value::Array(body));
+        if let Some(Value::Object(nested)) = rule.get("repository") {
+            collect_repository(nested, contexts);
+        }
+    }
+}
+
+fn context_name(key: &str) -> String {
+    format!("repo_{key}")
+}
+
+fn patterns(rule: &Value) -> Vec<Value> {
+    rule.get("patterns").and_then(Value::as_array).into_iter().flatten().filter_map(convert_rule).collect()
+}
+
+fn convert_rule(rule: &Value) -> Option<Value> {
+    if rule.get("disabled").and_then(Value::as_i64) == Some(1) {
+        return None;
+    }
+    let str_field = |key: &str| rule.get(key).and_then(Value::as_str);
+
+    if let Some(include) = str_field("include") {
+        return include_reference(include).map(|r| Value::Object(object([("include", r.into())])));
+    }
+
+    if let Some(regex) = str_field("match") {
+        let mut pattern = object([("match", regex.into())]);
+        if let Some(name) = str_field("name") {
+            pattern.insert("scope".to_owned(), scopes(name).into());
+        }
+        insert_captures(&mut pattern, rule.get("captures"));
+        return Some(Value::Object(pattern));
+    }
+
+    let begin = str_field("begin")?;
+    let mut context = Vec::new();
+    if let Some(name) = str_field("name") {
+        context.push(Value::Object(object([("meta_scope", scopes(name).into())])));
+    }
+    if let Some(name) = str_field("contentName") {
+        context.push(Value::Object(object([("meta_content_scope", scopes(name).into())])));
+    }
+
+    let exit = if let Some(end) = str_field("end") {
+        let mut pop = object([("match", end.into()), ("pop", true.into())]);
+        insert_captures(&mut pop, rule.get("endCaptures").or_else(|| rule.get("captures")));
+        pop
+    } else if let Some(condition) = str_field("while") {
+        object([("match", format!("^(?!{condition})").into()), ("pop", true.into())])
+    } else {
+        object([("match", "(?!)".into()), ("pop", true.into())])
+    };
+
+    let nested = patterns(rule);
+    if rule.get("applyEndPatternLast").and_then(Value::as_i64) == Some(1) {
+        context.extend(nested);
+        context.push(Value::Object(exit));
+    } else {
+        context.push(Value::Object(exit));
+        context.extend(nested);
+    }
+
+    let mut push = object([("match", begin.into()), ("push", Value::Array(context))]);
+    insert_captures(&mut push, rule.get("beginCaptures").or_else(|| rule.get("captures")));
+    Some(Value::Object(push))
+}
+
+fn include_reference(include: &str) -> Option<String> {
+    match include {
+        "$self" | "$base" => Some("main".to_owned()),
+        _ if include.starts_with('#') => Some(context_name(&include[1..])),
+        _ => match include.split_once('#') {
+            Some((scope, key)) => Some(format!("scope:{scope}#{}", context_name(key))),
+            None if !include.is_empty() => Some(format!("scope:{include}")),
+            None => None,
+        },
+    }
+}
+
+fn insert_captures(pattern: &mut Map<String, Value>, captures: Option<&Value>) {
+    let Some(Value::Object(captures)) = captures else {
+        return;
+    };
+    let mapped: Map<String, Value> = captures
+        .iter()
+        .filter(|(key, _)| key.parse::<usize>().is_ok())
+        .filter_map(|(key, capture)| Some((key.clone(), scopes(capture.get("name")?.as_str()?).into())))
+        .collect();
+    if !mapped.is_empty() {
+        pattern.insert("captures".to_owned(), Value::Object(mapped));
+    }
+}
+
+/// Space-separated scope names, truncated to what syntect accepts.
+fn scopes(name: &str) -> String {
+    name.split_whitespace()
+        .map(|scope| scope.split('.').take(MAX_ATOMS).collect::<Vec<_>>().join("."))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn object<const N: usize>(entries: [(&str, Value); N]) -> Map<String, Value> {
+    entries.into_iter().map(|(k, v)| (k.to_owned(), v)).collect()
+}
+
+// JSON is valid flow-style YAML, except that capture indexes must be
+// integer keys, so numeric keys are written unquoted.
+fn emit(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            out.push('{');
+            for (i, (key, value)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                if key.parse::<usize>().is_ok() {
+                    out.push_str(key);
+                } else {
+                    let _ = write!(out, "{}", Value::from(key.as_str()));
+                }
+                out.push_str(": ");
+                emit(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                emit(item, out);
+            }
+            out.push(']');
+        }
+        other => {
+            let _ = write!(out, "{other}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use syntect::parsing::{ParseState, ScopeStack, SyntaxSetBuilder};
+
+    /// Scope stacks (innermost scope only) of each non-blank piece of one line.
+    fn innermost(grammar: Value, line: &str) -> Vec<(String, String)> {
+        let mut builder = SyntaxSetBuilder::new();
+        builder.add(to_syntax_definition(&grammar).unwrap());
+        let set = builder.build();
+        let syntax = set.find_syntax_by_extension("toy").unwrap();
+
+        let mut state = ParseState::new(syntax);
+        let mut stack = ScopeStack::new();
+        let mut out = Vec::new();
+        let mut start = 0;
+        let ops = state.parse_line(line, &set).unwrap();
+        for (pos, op) in ops.into_iter().chain([(line.len(), syntect::parsing::ScopeStackOp::Noop)]) {
+            if pos > start && !line[start..pos].trim().is_empty() {
+                let top = stack.as_slice().last().map(|s| s.build_string()).unwrap_or_default();
+                out.push((line[start..pos].trim().to_owned(), top));
+            }
+            start = start.max(pos);
+            stack.apply(&op).unwrap();
+        }
+        out
+    }
+
+    fn toy(patterns: Value, repository: Value) -> Value {
+        json!({
+            "name": "toy",
+            "scopeName": "source.toy",
+            "fileTypes": ["toy"],
+            "patterns": patterns,
+            "repository": repository,
+        })
+    }
+
+    #[test]
+    fn converts_match_rules_with_captures() {
+        let grammar = toy(
+            json!([{ "match": "\\b(fn)\\s+(\\w+)", "captures": {
+                "1": { "name": "keyword.other.fn.toy" },
+                "2": { "name": "entity.name.function.toy" }
+            } }]),
+            json!({}),
+        );
+        assert_eq!(
+            innermost(grammar, "fn main\n"),
+            [("fn".into(), "keyword.other.fn.toy".into()), ("main".into(), "entity.name.function.toy".into())]
+        );
+    }
+
+    #[test]
+    fn converts_begin_end_with_repository_includes() {
+        let grammar = toy(
+            json!([{ "include": "#strings" }]),
+            json!({
+                "strings": {
+                    "name": "string.quoted.double.toy",
+                    "begin": "\"",
+                    "end": "\"",
+                    "beginCaptures": { "0": { "name": "punctuation.definition.string.begin.toy" } },
+                    "endCaptures": { "0": { "name": "punctuation.definition.string.end.toy" } },
+                    "patterns": [{ "include": "#escape" }]
+                },
+                "escape": { "match": "\\\\.", "name": "constant.character.escape.toy" }
+            }),
+        );
+        let tokens = innermost(grammar, "\"a\\n\"\n");
+        assert_eq!(tokens[0].1, "punctuation.definition.string.begin.toy");
+        assert_eq!(tokens[1], ("a".into(), "string.quoted.double.toy".into()));
+        assert_eq!(tokens[2], ("\\n".into(), "constant.character.escape.toy".into()));
+        assert_eq!(tokens[3].1, "punctuation.definition.string.end.toy");
+    }
+
+    #[test]
+    fn end_patterns_can_reference_begin_captures() {
+        let grammar = toy(json!([{ "name": "string.raw.toy", "begin": "r(#*)\"", "end": "\"(\\1)" }]), json!({}));
+        let tokens = innermost(grammar, "r#\"a\"b\"# x\n");
+        assert!(tokens.iter().all(|(text, scope)| scope == "string.raw.toy" || text == "x"));
+        assert_eq!(tokens.last().unwrap(), &("x".to_owned(), "source.toy".to_owned()));
+    }
+
+    #[test]
+    fn maps_include_forms() {
+        assert_eq!(include_reference("$self").as_deref(), Some("main"));
+        assert_eq!(include_reference("#expr").as_deref(), Some("repo_expr"));
+        assert_eq!(include_reference("source.css").as_deref(), Some("scope:source.css"));
+        assert_eq!(include_reference("source.js#expr").as_deref(), Some("scope:source.js#repo_expr"));
+    }
+
+    #[test]
+    fn reads_grammars_from_shiki_modules() {
+        let module = r#"import css from './css.mjs'
+
+const lang = Object.freeze(JSON.parse("{\"name\":\"toy\",\"scopeName\":\"source.toy\",\"patterns\":[]}"))
+
+export default [
+...css,
+lang
+]
+"#;
+        let grammars = grammars_from_module(module).unwrap();
+        assert_eq!(grammars.len(), 1);
+        assert_eq!(grammars[0]["scopeName"], "source.toy");
+    }
+
+    #[test]
+    fn truncates_overlong_scopes() {
+        assert_eq!(scopes("a.b.c.d.e.f.g.h.i meta.x"), "a.b.c.d.e.f.g.h meta.x");
+    }
+}
@@ -0,0 +1,236 @@
+//! Shiki-compatible HTML output for highlighted tokens.
+
+use std::fmt::Write;
+
+use crate::resolver::{FontStyle, Resolver};
+use crate::selector::matches_scope;
+use crate::theme::{BRACKET_PREFIX, UNEXPECTED_BRACKET};
+use crate::tokenizer::Token;
+
+/// Renders tokens to a `<pre class="shiki">` block matching the markup
+/// Shiki's `codeToHtml` produces. `line_count` keeps blank trailing lines.
+pub fn highlight(resolver: &Resolver, tokens: &[Token], line_count: usize, class: &str) -> String {
+    highlight_with(resolver, tokens, line_count, class, Options::default())
+}
+
+/// [`highlight`], with brackets outside strings and comments colored by
+/// nesting depth from the theme's `editorBracketHighlight` colors, as
+/// Shiki's colorized-brackets transformer does.
+pub fn highlight_brackets(resolver: &Resolver, tokens: &[Token], line_count: usize, class: &str) -> String {
+    highlight_with(resolver, tokens, line_count, class, Options { brackets: true, ..Options::default() })
+}
+
+/// What [`highlight_with`] adds to Shiki's markup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+    /// See [`highlight_brackets`].
+    pub brackets: bool,
+    /// Tag each token with `data-scope`, its scope stack, and `data-rule`,
+    /// the theme rule behind its color.
+    pub inspect: bool,
+}
+
+const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+pub fn highlight_with(
+    resolver: &Resolver,
+    tokens: &[Token],
+    line_count: usize,
+    class: &str,
+    options: Options,
+) -> String {
+    let brackets = options.brackets && !resolver.bracket_colors().is_empty();
+    let mut out = format!(
+        r#"<pre class="shiki {class}" style="background-color:{};color:{}" tabindex="0"><code>"#,
+        resolver.default_background(),
+        resolver.default_foreground()
+    );
+    // Closing brackets still expected, innermost last
+    let mut open: Vec<char> = Vec::new();
+    let mut tokens = tokens.iter().peekable();
+    for line in 1..=line_count.max(1) {
+        if line > 1 {
+            out.push('\n');
+        }
+        out.push_str(r#"<span class="line">"#);
+        while let Some(token) = tokens.next_if(|t| t.line == line) {
+            let style = resolver.resolve(&token.scopes);
+            let font = font_css(style.font_style);
+            let data = |rule: &str| {
+                if !options.inspect {
+                    return String::new();
+                }
+                format!(r#" data-scope="{}" data-rule="{}""#, escape(&token.scopes.join(" ")), escape(rule))
+            };
+            let is_brackets = brackets
+                && token.text.chars().all(|c| BRACKETS.iter().any(|&(o, cl)| c == o || c == cl))
+                && !token.scopes.iter().any(|s| matches_scope(s, "string") || matches_scope(s, "comment"));
+            if !is_brackets {
+                let rule = match style.foreground_rule {
+                    Some(index) => match resolver.matched_selector(index, &token.scopes) {
+                        Some(selector) => format!("tokenColors[{index}] {selector}"),
+                        None => format!("tokenColors[{index}]"),
+                    },
+                    None => "default foreground".to_owned(),
+                };
+                let _ = write!(
+                    out,
+                    r#"<span style="color:{}{font}"{}>{}</span>"#,
+                    style.foreground,
+                    data(&rule),
+                    escape(&token.text)
+                );
+                continue;
+            }
+            for c in token.text.chars() {
+                let (color, rule) = if let Some(&(_, close)) = BRACKETS.iter().find(|(o, _)| *o == c) {
+                    open.push(close);
+                    bracket_color(resolver, open.len() - 1)
+                } else if open.last() == Some(&c) {
+                    open.pop();
+                    bracket_color(resolver, open.len())
+                } else {
+                    match resolver.unexpected_bracket() {
+                        Some(color) => (color, UNEXPECTED_BRACKET.to_owned()),
+                        None => (style.foreground, "default foreground".to_owned()),
+                    }
+                };
+                let _ = write!(out, r#"<span style="color:{color}{font}"{}>{c}</span>"#, data(&rule));
+            }
+        }
+        out.push_str("</span>");
+    }
+    out.push_str("</code></pre>");
+    out
+}
+
+/// The color for a bracket at `depth` and the `colors` key it comes from.
+fn bracket_color(resolver: &Resolver, depth: usize) -> (&str, String) {
+    let colors = resolver.bracket_colors();
+    let index = depth % colors.len();
+    (&colors[index], format!("{BRACKET_PREFIX}{}", index + 1))
+}
+
+/// Plain block for files without a grammar, in the theme's default colors.
+pub fn plain(resolver: &Resolver, source: &str, class: &str) -> String {
+    format!(
+        r#"<pre class="shiki {class}" style="background-color:{};color:{}" tabindex="0"><code>{}</code></pre>"#,
+        resolver.default_background(),
+        resolver.default_foreground(),
+        escape(source)
+    )
+}
+
+pub(crate) fn font_css(style: FontStyle) -> String {
+    let mut css = String::new();
+    if style.contains(FontStyle::ITALIC) {
+        css.push_str(";font-style:italic");
+    }
+    if style.contains(FontStyle::BOLD) {
+        css.push_str(";font-weight:bold");
+    }
+    let decorations: Vec<&str> = [(FontStyle::UNDERLINE, "underline"), (FontStyle::STRIKETHROUGH, "line-through")]
+        .into_iter()
+        .filter(|(flag, _)| style.contains(*flag))
+        .map(|(_, name)| name)
+        .collect();
+    if !decorations.is_empty() {
+        let _ = write!(css, ";text-decoration:{}", decorations.join(" "));
+    }
+    css
+}
+
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::theme::Theme;
+    use crate::tokenizer::Tokenizer;
+
+    #[test]
+    fn brackets_cycle_through_the_depth_colors() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "colors": {
+                "editorBracketHighlight.foreground1": "#111111",
+                "editorBracketHighlight.foreground2": "#222222",
+                "editorBracketHighlight.unexpectedBracket.foreground": "#ff0000"
+            },
+            "tokenColors": [{ "settings": { "foreground": "#ffffff", "background": "#000000" } }]
+        }))
+        .unwrap();
+        let resolver = Resolver::new(&theme);
+        let tokenizer = Tokenizer::new();
+        let path = Path::new("t.rs");
+        let source = "f(a[b(\"(\")]);\n}\n";
+        let tokens = tokenizer.tokenize(tokenizer.syntax_for(path).unwrap(), path, source).unwrap();
+
+        let html = highlight_brackets(&resolver, &tokens, 2, "t");
+        let colors: Vec<(&str, &str)> = html
+            .split(r#"<span style="color:"#)
+            .skip(1)
+            .map(|span| (&span[..7], span[9..].split("</span>").next().unwrap()))
+            .filter(|(_, text)| ["(", ")", "[", "]"].contains(text))
+            .collect();
+        // The paren inside the string keeps the token color
+        assert_eq!(
+            colors,
+            [
+                ("#111111", "("),
+                ("#222222", "["),
+                ("#111111", "("),
+                ("#ffffff", "("),
+                ("#111111", ")"),
+                ("#222222", "]"),
+                ("#111111", ")")
+            ]
+        );
+        assert!(html.contains(r#"color:#ff0000">}</span>"#));
+        assert_eq!(highlight(&resolver, &tokens, 2, "t").matches("#111111").count(), 0);
+    }
+
+    #[test]
+    fn inspected_tokens_carry_their_scopes_and_rule() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#ffffff", "background": "#000000" } },
+                { "scope": "string, source.rust keyword", "settings": { "foreground": "#ff8fa3" } }
+            ]
+        }))
+        .unwrap();
+        let resolver = Resolver::new(&theme);
+        let token = |column: usize, text: &str, scopes: &[&str]| Token {
+            line: 1,
+            column,
+            text: text.to_owned(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        };
+        let tokens = [token(1, "if", &["source.rust", "keyword.control.rust"]), token(3, " x", &["source.rust"])];
+
+        let html = highlight_with(&resolver, &tokens, 1, "t", Options { inspect: true, ..Options::default() });
+        assert!(html.contains(
+            r#"<span style="color:#ff8fa3" data-scope="source.rust keyword.control.rust" data-rule="tokenColors[1] source.rust keyword">if</span>"#
+        ));
+        assert!(html.contains(r#"data-scope="source.rust" data-rule="default foreground"> x</span>"#));
+        assert!(!highlight(&resolver, &tokens, 1, "t").contains("data-"));
+    }
+}
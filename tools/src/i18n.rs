@@ -0,0 +1,494 @@
+//! English and Japanese text for what the tools print: report summaries,
+//! headings, warnings and errors.
+//!
+//! Messages with arguments are variants of [`Message`], so a translation
+//! cannot drop or misname an argument without failing to compile. Fixed
+//! headings go through [`label`], keyed by their English text. Output meant
+//! for machines (JSON, SARIF, annotations) stays in English.
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::Error;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Lang {
+    #[default]
+    En,
+    Ja,
+}
+
+impl Lang {
+    /// The BCP 47 tag, for `<html lang>` and the like.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Ja => "ja",
+        }
+    }
+}
+
+/// A line of human-readable output and its arguments.
+#[derive(Debug, Clone)]
+pub enum Message<'a> {
+    /// The audit skipped a sample.
+    NoGrammar {
+        path: &'a Path,
+    },
+    /// A report skipped a sample.
+    Skipped {
+        path: &'a Path,
+    },
+    NoGrammarFor {
+        path: &'a Path,
+    },
+    NoSample {
+        name: &'a str,
+    },
+    NoRepro {
+        scope: &'a str,
+    },
+    NoStandaloneRepro {
+        scope: &'a str,
+        occurrences: usize,
+        max_lines: usize,
+    },
+    Unthemed {
+        scopes: usize,
+        files: usize,
+    },
+    ContrastFailing {
+        failing: usize,
+        total: usize,
+        threshold: f64,
+    },
+    NearPairs {
+        pairs: usize,
+        threshold: f64,
+    },
+    DeadRules {
+        rules: usize,
+        total: usize,
+    },
+    TokensDiffer {
+        differing: usize,
+        total: usize,
+        percent: f64,
+    },
+    TokensChanged {
+        tokens: usize,
+        samples: usize,
+        path: &'a Path,
+    },
+    SnapshotsOutdated {
+        count: usize,
+    },
+    SnapshotsUpToDate,
+    Wrote {
+        path: &'a Path,
+    },
+    Updated {
+        path: &'a Path,
+    },
+    Changed {
+        path: &'a Path,
+    },
+    /// A scope a watch session had not seen unthemed before, at `location`.
+    NewUnthemed {
+        scope: &'a str,
+        location: &'a str,
+        example: &'a str,
+    },
+    ReportTitle {
+        name: &'a str,
+    },
+    ReferenceTitle {
+        name: &'a str,
+    },
+    Confusable {
+        pairs: usize,
+        threshold: f64,
+        path: &'a Path,
+    },
+    CoverageSize {
+        languages: usize,
+        groups: usize,
+    },
+    Diverging {
+        diverging: usize,
+        total: usize,
+    },
+    GrammarScopes {
+        exercised: usize,
+        total: usize,
+    },
+    NoGrammarNamed {
+        language: &'a str,
+    },
+    NoSampleUses {
+        grammar: &'a str,
+    },
+    Unformatted {
+        count: usize,
+    },
+    Formatted {
+        path: &'a Path,
+    },
+    LintWarnings {
+        count: usize,
+        path: &'a Path,
+    },
+    /// Bytes, rules and selectors of a theme before and after optimizing.
+    OptimizedSize {
+        before: (usize, usize, usize),
+        after: (usize, usize, usize),
+    },
+    StacksChecked {
+        stacks: usize,
+    },
+    NotOptimized {
+        tokens: usize,
+    },
+    FallbackTokens {
+        tokens: usize,
+        percent: f64,
+    },
+    TokensAcross {
+        tokens: usize,
+        samples: usize,
+    },
+    Captured {
+        count: usize,
+        path: &'a Path,
+    },
+    ScreenshotsChanged {
+        changed: usize,
+        total: usize,
+        threshold: f64,
+    },
+    Serving {
+        url: &'a str,
+    },
+    Rerendered {
+        files: usize,
+        elapsed: Duration,
+    },
+}
+
+impl Message<'_> {
+    pub fn text(&self, lang: Lang) -> String {
+        match lang {
+            Lang::En => self.en(),
+            Lang::Ja => self.ja(),
+        }
+    }
+
+    fn en(&self) -> String {
+        match *self {
+            Self::NoGrammar { path } => format!("{}: skipped, no grammar", path.display()),
+            Self::Skipped { path } => format!("skipped {}: no grammar", path.display()),
+            Self::NoGrammarFor { path } => format!("no grammar for {}", path.display()),
+            Self::NoSample { name } => format!("no sample named `{name}`"),
+            Self::NoRepro { scope } => format!("no sample token has the scope `{scope}`"),
+            Self::NoStandaloneRepro { scope, occurrences, max_lines } => format!(
+                "{occurrences} token(s) have the scope `{scope}`, but no snippet of up to {max_lines} line(s) \
+                 around them has it on its own; try a larger --max-lines"
+            ),
+            Self::Unthemed { scopes, files } => format!("{scopes} unthemed scope(s) across {files} file(s)"),
+            Self::ContrastFailing { failing, total, threshold } => {
+                format!("{failing} of {total} color(s) below {threshold}:1")
+            }
+            Self::NearPairs { pairs, threshold } => {
+                format!("{pairs} pair(s) of colors sharing a line below ΔE {threshold}")
+            }
+            Self::DeadRules { rules, total } => format!("{rules} of {total} rule(s) style no sample token"),
+            Self::TokensDiffer { differing, total, percent } => {
+                format!("{differing} of {total} token(s) differ ({percent:.1}%)")
+            }
+            Self::TokensChanged { tokens, samples, path } => {
+                format!("{tokens} token(s) in {samples} sample(s) changed; wrote {}", path.display())
+            }
+            Self::SnapshotsOutdated { count } => {
+                format!("{count} snapshot(s) out of date; rerun with --update and review the diff")
+            }
+            Self::SnapshotsUpToDate => "snapshots up to date".to_owned(),
+            Self::Wrote { path } => format!("wrote {}", path.display()),
+            Self::Updated { path } => format!("updated {}", path.display()),
+            Self::Changed { path } => format!("changed {}", path.display()),
+            Self::NewUnthemed { scope, location, example } => {
+                format!("  new unthemed {scope:<40} {location} `{example}`")
+            }
+            Self::ReportTitle { name } => format!("{name} health"),
+            Self::ReferenceTitle { name } => format!("{name} token reference"),
+            Self::Confusable { pairs, threshold, path } => {
+                format!("{pairs} confusable pair(s) below {threshold}; gallery at {}", path.display())
+            }
+            Self::CoverageSize { languages, groups } => format!("{languages} language(s) × {groups} scope group(s)"),
+            Self::Diverging { diverging, total } => format!("{diverging} of {total} semantic token(s) diverge"),
+            Self::GrammarScopes { exercised, total } => format!("{exercised} of {total} grammar scope(s) exercised"),
+            Self::NoGrammarNamed { language } => format!("no grammar for `{language}`"),
+            Self::NoSampleUses { grammar } => format!("no sample uses the {grammar} grammar"),
+            Self::Unformatted { count } => format!("{count} file(s) need formatting"),
+            Self::Formatted { path } => format!("formatted {}", path.display()),
+            Self::LintWarnings { count, path } => format!("{count} warning(s) in {}", path.display()),
+            Self::OptimizedSize { before: (a, b, c), after: (x, y, z) } => {
+                format!("{a} bytes, {b} rules, {c} selectors -> {x} bytes, {y} rules, {z} selectors")
+            }
+            Self::StacksChecked { stacks } => format!("checked {stacks} distinct scope stack(s) from the samples"),
+            Self::NotOptimized { tokens } => {
+                format!("the optimized theme styles {tokens} corpus token(s) differently; not written")
+            }
+            Self::FallbackTokens { tokens, percent } => {
+                format!("{tokens} token(s) ({percent:.1}%) use the default foreground")
+            }
+            Self::TokensAcross { tokens, samples } => format!("{tokens} token(s) across {samples} sample(s)"),
+            Self::Captured { count, path } => format!("captured {count} sample(s) into {}", path.display()),
+            Self::ScreenshotsChanged { changed, total, threshold } => {
+                format!("{changed} of {total} sample(s) changed beyond {threshold}")
+            }
+            Self::Serving { url } => format!("serving the preview on {url}"),
+            Self::Rerendered { files, elapsed } => format!("{files} file(s) changed, re-rendered in {elapsed:?}"),
+        }
+    }
+
+    fn ja(&self) -> String {
+        match *self {
+            Self::NoGrammar { path } => format!("{}: 文法がないためスキップしました", path.display()),
+            Self::Skipped { path } => format!("{} をスキップしました: 文法がありません", path.display()),
+            Self::NoGrammarFor { path } => format!("{} の文法がありません", path.display()),
+            Self::NoSample { name } => format!("`{name}` という名前のサンプルはありません"),
+            Self::NoRepro { scope } => format!("スコープ `{scope}` を持つサンプルのトークンはありません"),
+            Self::NoStandaloneRepro { scope, occurrences, max_lines } => format!(
+                "スコープ `{scope}` を持つトークンは {occurrences} 件ありますが、周囲 {max_lines} 行以内の断片だけでは\
+                 このスコープになりません。--max-lines を大きくしてください"
+            ),
+            Self::Unthemed { scopes, files } => format!("{files} ファイル中、未着色のスコープが {scopes} 件"),
+            Self::ContrastFailing { failing, total, threshold } => {
+                format!("{total} 色中 {failing} 色がコントラスト比 {threshold}:1 未満")
+            }
+            Self::NearPairs { pairs, threshold } => {
+                format!("同じ行に並ぶ ΔE {threshold} 未満の色の組が {pairs} 件")
+            }
+            Self::DeadRules { rules, total } => {
+                format!("{total} ルール中 {rules} 件がどのサンプルのトークンにも効いていません")
+            }
+            Self::TokensDiffer { differing, total, percent } => {
+                format!("{total} トークン中 {differing} 件が異なります（{percent:.1}%）")
+            }
+            Self::TokensChanged { tokens, samples, path } => {
+                format!("{samples} サンプルで {tokens} トークンが変わりました。{} に書き出しました", path.display())
+            }
+            Self::SnapshotsOutdated { count } => {
+                format!(
+                    "スナップショット {count} 件が古くなっています。--update を付けて再実行し、差分を確認してください"
+                )
+            }
+            Self::SnapshotsUpToDate => "スナップショットは最新です".to_owned(),
+            Self::Wrote { path } => format!("{} に書き出しました", path.display()),
+            Self::Updated { path } => format!("{} を更新しました", path.display()),
+            Self::Changed { path } => format!("{} が変更されました", path.display()),
+            Self::NewUnthemed { scope, location, example } => {
+                format!("  新たに未着色 {scope:<40} {location} `{example}`")
+            }
+            Self::ReportTitle { name } => format!("{name} の健康診断"),
+            Self::ReferenceTitle { name } => format!("{name} のトークン一覧"),
+            Self::Confusable { pairs, threshold, path } => {
+                format!("{threshold} 未満で見分けにくい色の組が {pairs} 件。ギャラリーは {}", path.display())
+            }
+            Self::CoverageSize { languages, groups } => format!("{languages} 言語 × {groups} スコープグループ"),
+            Self::Diverging { diverging, total } => {
+                format!("セマンティックトークン {total} 件中 {diverging} 件の色が食い違っています")
+            }
+            Self::GrammarScopes { exercised, total } => {
+                format!("文法のスコープ {total} 件中 {exercised} 件をサンプルが使っています")
+            }
+            Self::NoGrammarNamed { language } => format!("`{language}` の文法がありません"),
+            Self::NoSampleUses { grammar } => format!("{grammar} の文法を使うサンプルがありません"),
+            Self::Unformatted { count } => format!("{count} ファイルの整形が必要です"),
+            Self::Formatted { path } => format!("{} を整形しました", path.display()),
+            Self::LintWarnings { count, path } => format!("{} に警告が {count} 件", path.display()),
+            Self::OptimizedSize { before: (a, b, c), after: (x, y, z) } => {
+                format!("{a} バイト、{b} ルール、{c} セレクター → {x} バイト、{y} ルール、{z} セレクター")
+            }
+            Self::StacksChecked { stacks } => format!("サンプルの異なるスコープスタック {stacks} 件を確認しました"),
+            Self::NotOptimized { tokens } => {
+                format!("最適化したテーマではコーパスの {tokens} トークンの見た目が変わるため、書き出しませんでした")
+            }
+            Self::FallbackTokens { tokens, percent } => {
+                format!("{tokens} トークン（{percent:.1}%）が既定の前景色です")
+            }
+            Self::TokensAcross { tokens, samples } => format!("{samples} サンプルで {tokens} トークン"),
+            Self::Captured { count, path } => format!("{count} サンプルを {} に撮影しました", path.display()),
+            Self::ScreenshotsChanged { changed, total, threshold } => {
+                format!("{total} サンプル中 {changed} 件が {threshold} を超えて変わりました")
+            }
+            Self::Serving { url } => format!("プレビューを {url} で配信しています"),
+            Self::Rerendered { files, elapsed } => {
+                format!("{files} ファイルが変わり、{elapsed:?} で再描画しました")
+            }
+        }
+    }
+}
+
+/// Headings without arguments, as (English, Japanese).
+const LABELS: &[(&str, &str)] = &[
+    ("Unthemed scopes", "未着色のスコープ"),
+    ("Contrast", "コントラスト"),
+    ("Rules", "ルール"),
+    ("Color distance", "色の近さ"),
+    ("Filter", "絞り込み"),
+    ("Scope", "スコープ"),
+    ("File", "ファイル"),
+    ("Line", "行"),
+    ("Column", "列"),
+    ("Tokens", "トークン数"),
+    ("Example", "例"),
+    ("Foreground", "前景色"),
+    ("Background", "背景色"),
+    ("Ratio", "コントラスト比"),
+    ("Rule", "ルール"),
+    ("Selectors", "セレクター"),
+    ("Color", "色"),
+    ("Lines", "行数"),
+    ("First", "最初の出現"),
+    ("Defaults", "既定"),
+    ("Font style", "フォントスタイル"),
+    ("No sample token uses this rule", "このルールが効くサンプルのトークンはありません"),
+    ("Tokens per rule", "ルールごとのトークン数"),
+];
+
+/// `text` in `lang`; text without a translation is returned as is.
+pub fn label(text: &'static str, lang: Lang) -> &'static str {
+    match lang {
+        Lang::En => text,
+        Lang::Ja => LABELS.iter().find(|(en, _)| *en == text).map_or(text, |(_, ja)| ja),
+    }
+}
+
+/// What error lines start with.
+pub fn error_prefix(lang: Lang) -> &'static str {
+    match lang {
+        Lang::En => "error: ",
+        Lang::Ja => "エラー: ",
+    }
+}
+
+/// The line printed for an error that ends a command.
+pub fn error_line(err: &Error, lang: Lang) -> String {
+    match lang {
+        Lang::En => format!("{}{err}", error_prefix(lang)),
+        Lang::Ja => format!("{}{}", error_prefix(lang), error_ja(err)),
+    }
+}
+
+/// `err` in Japanese; the underlying system and parser messages are not
+/// translated.
+fn error_ja(err: &Error) -> String {
+    let path = |path: &Path| path.display().to_string();
+    match err {
+        Error::Io { path: p, source } => format!("{} を読み込めませんでした: {source}", path(p)),
+        Error::Theme { path: p, source } => format!("テーマ {} が不正です: {source}", path(p)),
+        Error::InvalidRule { path: p, index, message } => {
+            format!("{} のルール #{index} が不正です: {message}", path(p))
+        }
+        Error::Grammar { path: p, message } => format!("未対応の文法 {}: {message}", path(p)),
+        Error::Tokenize { path: p, message } => format!("{} をトークン化できませんでした: {message}", path(p)),
+        Error::Screenshot { path: p, message } => format!("スクリーンショット {} に失敗しました: {message}", path(p)),
+        Error::Watch { path: p, message } => format!("{} を監視できませんでした: {message}", path(p)),
+        Error::Config { path: p, message } => format!("{} が不正です: {message}", path(p)),
+        Error::Git { rev, path: p, message } => format!("git show {rev}:{} に失敗しました: {message}", path(p)),
+        Error::Shiki { message } => format!("Shiki の実行に失敗しました: {message}"),
+        Error::Listen { addr, source } => format!("{addr} で待ち受けできませんでした: {source}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::io;
+
+    use super::*;
+
+    fn every_message() -> Vec<Message<'static>> {
+        let path = Path::new("rust/a.rs");
+        vec![
+            Message::NoGrammar { path },
+            Message::Skipped { path },
+            Message::NoGrammarFor { path },
+            Message::NoSample { name: "rust/x" },
+            Message::NoRepro { scope: "keyword.control" },
+            Message::NoStandaloneRepro { scope: "meta.embedded.json", occurrences: 12, max_lines: 10 },
+            Message::Unthemed { scopes: 3, files: 61 },
+            Message::ContrastFailing { failing: 2, total: 11, threshold: 4.5 },
+            Message::NearPairs { pairs: 1, threshold: 0.05 },
+            Message::DeadRules { rules: 1, total: 13 },
+            Message::TokensDiffer { differing: 5, total: 200, percent: 2.5 },
+            Message::TokensChanged { tokens: 7, samples: 2, path },
+            Message::SnapshotsOutdated { count: 4 },
+            Message::SnapshotsUpToDate,
+            Message::Wrote { path },
+            Message::Updated { path },
+            Message::Changed { path },
+            Message::NewUnthemed { scope: "variable.other", location: "rust/a.rs:1:2", example: "x" },
+            Message::ReportTitle { name: "Zenn" },
+            Message::ReferenceTitle { name: "Zenn" },
+            Message::Confusable { pairs: 2, threshold: 0.05, path },
+            Message::CoverageSize { languages: 40, groups: 31 },
+            Message::Diverging { diverging: 3, total: 52 },
+            Message::GrammarScopes { exercised: 80, total: 95 },
+            Message::NoGrammarNamed { language: "cobol" },
+            Message::NoSampleUses { grammar: "Rust" },
+            Message::Unformatted { count: 2 },
+            Message::Formatted { path },
+            Message::LintWarnings { count: 4, path },
+            Message::OptimizedSize { before: (9120, 40, 160), after: (8011, 36, 151) },
+            Message::StacksChecked { stacks: 2301 },
+            Message::NotOptimized { tokens: 6 },
+            Message::FallbackTokens { tokens: 870, percent: 12.5 },
+            Message::TokensAcross { tokens: 6403, samples: 61 },
+            Message::Captured { count: 61, path },
+            Message::ScreenshotsChanged { changed: 1, total: 61, threshold: 0.001 },
+            Message::Serving { url: "http://127.0.0.1:3001" },
+            Message::Rerendered { files: 2, elapsed: Duration::from_millis(41) },
+        ]
+    }
+
+    #[test]
+    fn every_message_is_translated_with_its_arguments() {
+        for message in every_message() {
+            let (en, ja) = (message.text(Lang::En), message.text(Lang::Ja));
+            assert_ne!(en, ja, "{message:?} has no Japanese text");
+            assert!(!ja.is_ascii(), "{ja:?} is not Japanese");
+            // Every number and path in the English text survives translation
+            for argument in en.split(|c: char| c.is_whitespace() || "`():;%,".contains(c)) {
+                if argument.chars().any(|c| c.is_ascii_digit()) || argument.contains('/') {
+                    assert!(ja.contains(argument), "{ja:?} lost {argument:?}");
+                }
+            }
+        }
+        assert_eq!(
+            Message::ContrastFailing { failing: 2, total: 11, threshold: 4.5 }.text(Lang::Ja),
+            "11 色中 2 色がコントラスト比 4.5:1 未満"
+        );
+    }
+
+    #[test]
+    fn labels_fall_back_to_english() {
+        let english: HashSet<&str> = LABELS.iter().map(|(en, _)| *en).collect();
+        assert_eq!(english.len(), LABELS.len(), "a label is listed twice");
+        assert!(LABELS.iter().all(|(en, ja)| !ja.is_empty() && en != ja));
+        assert_eq!(label("Scope", Lang::Ja), "スコープ");
+        assert_eq!(label("Scope", Lang::En), "Scope");
+        assert_eq!(label("ΔE", Lang::Ja), "ΔE");
+    }
+
+    #[test]
+    fn errors_keep_their_details() {
+        let err = Error::io("src/themes/zenn.json", io::Error::new(io::ErrorKind::NotFound, "not found"));
+        assert_eq!(error_line(&err, Lang::En), "error: failed to read src/themes/zenn.json: not found");
+        assert_eq!(error_line(&err, Lang::Ja), "エラー: src/themes/zenn.json を読み込めませんでした: not found");
+        let err = Error::InvalidRule { path: "t.json".into(), index: 3, message: "bad scope".into() };
+        assert_eq!(error_line(&err, Lang::Ja), "エラー: t.json のルール #3 が不正です: bad scope");
+    }
+}
@@ -0,0 +1,122 @@
+//! Compares the scopes a grammar can produce with those the samples hit.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use syntect::parsing::syntax_definition::Pattern;
+use syntect::parsing::SyntaxDefinition;
+
+use crate::tokenizer::{Token, Tokenizer};
+
+/// Every scope name `definition` can assign, mapped to the first regex
+/// that assigns it (empty for context meta scopes).
+pub fn grammar_scopes(definition: &SyntaxDefinition) -> BTreeMap<String, String> {
+    let mut scopes = BTreeMap::new();
+    let mut names: Vec<&String> = definition.contexts.keys().collect();
+    names.sort();
+
+    for name in names {
+        let context = &definition.contexts[name];
+        for scope in context.meta_scope.iter().chain(&context.meta_content_scope) {
+            scopes.entry(scope.build_string()).or_insert_with(String::new);
+        }
+        for pattern in &context.patterns {
+            let Pattern::Match(pattern) = pattern else {
+                continue;
+            };
+            let regex = pattern.regex.regex_str();
+            let captured = pattern.captures.iter().flatten().flat_map(|(_, scopes)| scopes);
+            for scope in pattern.scope.iter().chain(captured) {
+                scopes.entry(scope.build_string()).or_insert_with(|| regex.to_owned());
+            }
+        }
+    }
+    scopes.remove(&definition.scope.build_string());
+    scopes
+}
+
+/// Every scope any grammar of `tokenizer` can assign, root scopes included.
+pub fn known_scopes(tokenizer: &Tokenizer) -> BTreeSet<String> {
+    let mut known = BTreeSet::new();
+    for definition in tokenizer.definitions() {
+        known.insert(definition.scope.build_string());
+        known.extend(grammar_scopes(&definition).into_keys());
+    }
+    known
+}
+
+/// Every scope appearing anywhere in the tokens' scope stacks.
+pub fn observed_scopes<'a>(tokens: impl IntoIterator<Item = &'a Token>) -> BTreeSet<String> {
+    tokens.into_iter().flat_map(|t| t.scopes.iter().cloned()).collect()
+}
+
+/// Grammar scopes never observed, with their example regex.
+pub fn missing(grammar: &BTreeMap<String, String>, observed: &BTreeSet<String>) -> Vec<(String, String)> {
+    grammar.iter().filter(|(scope, _)| !observed.contains(*scope)).map(|(s, r)| (s.clone(), r.clone())).collect()
+}
+
+/// A skeleton sample listing each missing scope as a TODO comment, grouped
+/// by its first atom (`keyword`, `storage`, ...).
+pub fn skeleton(language: &str, extension: &str, checked: &[String], missing: &[(String, String)]) -> String {
+    let comment = |text: &str| match extension {
+        "py" | "rb" | "sh" | "bash" | "yaml" | "yml" => format!("# {text}"),
+        "sql" => format!("-- {text}"),
+        "html" | "md" => format!("<!-- {text} -->"),
+        "css" => format!("/* {text} */"),
+        _ => format!("// {text}"),
+    };
+
+    let mut lines = vec![
+        comment(&format!("{language} sample skeleton")),
+        comment(&format!("{} grammar scope(s) not exercised by {}", missing.len(), checked.join(", "))),
+    ];
+    let mut groups: BTreeMap<&str, Vec<&(String, String)>> = BTreeMap::new();
+    for entry in missing {
+        groups.entry(entry.0.split('.').next().unwrap_or_default()).or_default().push(entry);
+    }
+    for (group, entries) in groups {
+        lines.push(String::new());
+        lines.push(comment(&format!("--- {group} ---")));
+        for (scope, regex) in entries {
+            let todo = if regex.is_empty() {
+                format!("TODO {scope}")
+            } else {
+                format!("TODO {scope}  (matched by `{}`)", abbreviate(regex, 60))
+            };
+            lines.push(comment(&todo));
+        }
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn abbreviate(text: &str, max: usize) -> String {
+    let flat: String = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= max {
+        flat
+    } else {
+        let cut: String = flat.chars().take(max).collect();
+        format!("{cut}…")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokenizer::Tokenizer;
+
+    #[test]
+    fn reports_scopes_the_source_never_hits() {
+        let tokenizer = Tokenizer::new();
+        let syntax = tokenizer.find_syntax("rs").unwrap();
+        let grammar = grammar_scopes(&tokenizer.definition(syntax).unwrap());
+        assert!(grammar.contains_key("constant.language.rust"));
+
+        let tokens = tokenizer.tokenize(syntax, "a.rs".as_ref(), "fn main() { let x = true; }\n").unwrap();
+        let missing = missing(&grammar, &observed_scopes(&tokens));
+        assert!(!missing.iter().any(|(scope, _)| scope == "constant.language.rust"));
+        assert!(missing.iter().any(|(scope, _)| scope == "comment.block.rust"));
+
+        let skeleton = skeleton("Rust", "rs", &["a.rs".into()], &missing);
+        assert!(skeleton.contains("// TODO comment.block.rust"));
+    }
+}
@@ -0,0 +1,115 @@
+//! Tooling for developing the Zenn Shiki theme.
+//!
+//! The `tools` binary's command tree in [`cli`] and the `xtask` automation
+//! share the theme model, tokenizer and resolver defined here so that every
+//! report agrees on how a token is styled.
+
+#[cfg(feature = "tokenizer")]
+pub mod ansi;
+#[cfg(feature = "tokenizer")]
+pub mod audit;
+#[cfg(feature = "tokenizer")]
+pub mod cache;
+#[cfg(feature = "cli")]
+pub mod cli;
+pub mod colorblind;
+pub mod colors;
+#[cfg(feature = "tokenizer")]
+pub mod compare;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod contrast;
+#[cfg(feature = "tokenizer")]
+pub mod coverage;
+#[cfg(feature = "tokenizer")]
+pub mod diff;
+#[cfg(feature = "tokenizer")]
+pub mod distance;
+pub mod error;
+pub mod export;
+#[cfg(feature = "tokenizer")]
+pub mod grammar;
+#[cfg(feature = "tokenizer")]
+pub mod html;
+pub mod i18n;
+#[cfg(feature = "tokenizer")]
+pub mod inventory;
+pub mod lint;
+#[cfg(feature = "tokenizer")]
+pub mod markdown;
+pub mod normalize;
+pub mod optimize;
+pub mod palette;
+#[cfg(feature = "tokenizer")]
+pub mod parity;
+pub mod pixels;
+#[cfg(feature = "tokenizer")]
+pub mod preview;
+pub mod quantize;
+#[cfg(feature = "tokenizer")]
+pub mod reference;
+#[cfg(feature = "tokenizer")]
+pub mod report;
+#[cfg(feature = "tokenizer")]
+pub mod repro;
+pub mod resolver;
+pub mod samples;
+#[cfg(feature = "screenshots")]
+pub mod screenshot;
+pub mod selector;
+pub mod semantic;
+#[cfg(feature = "tokenizer")]
+pub mod semantic_diff;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "tokenizer")]
+pub mod snapshot;
+#[cfg(feature = "tokenizer")]
+pub mod stats;
+pub mod theme;
+#[cfg(feature = "tokenizer")]
+pub mod tokenizer;
+pub mod vscode;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "watch")]
+pub mod watch;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub use error::{Error, Result};
+
+/// Path of the theme JSON relative to the repository root.
+pub const THEME_PATH: &str = "src/themes/zenn.json";
+
+/// Directory holding every theme variant, relative to the repository root.
+pub const THEMES_DIR: &str = "src/themes";
+
+/// Directory holding the preview sample files, relative to the repository root.
+pub const SAMPLES_DIR: &str = "src/sampleCodes";
+
+/// Directory holding the golden token snapshots, relative to the repository root.
+pub const SNAPSHOTS_DIR: &str = "tools/tests/snapshots";
+
+/// Path of the generated theme JSON Schema, relative to the repository root.
+/// `.vscode/settings.json` maps the theme files to it for editor validation.
+pub const SCHEMA_PATH: &str = "tools/theme.schema.json";
+
+/// Returns the repository root (the parent of this crate).
+pub fn repo_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("tools crate lives inside the repository").to_path_buf()
+}
+
+/// The `*.json` theme files in `dir`, sorted by name.
+pub fn theme_paths(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| Error::io(dir, e))? {
+        let path = entry.map_err(|e| Error::io(dir, e))?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
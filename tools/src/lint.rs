@@ -0,0 +1,222 @@
+//! Finds theme rules that can never take effect.
+//!
+//! With the resolver's semantics, a field a selector sets is lost in only
+//! two ways: a later rule with the identical selector sets it too, or, for
+//! the scopes beneath it, a deeper plain selector does. A selector is
+//! shadowed when every field it sets is lost for every scope it matches; a
+//! rule whose selectors are all shadowed or match nothing is unreachable.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::selector::{matches_scope, Selector};
+use crate::theme::{Settings, Theme};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintKind {
+    /// Overridden everywhere by the listed rules.
+    Shadowed { by: BTreeSet<usize> },
+    /// Targets a scope no known grammar produces.
+    Unmatched,
+    /// Every selector of the rule is shadowed or unmatched.
+    Unreachable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// Index into `tokenColors`.
+    pub rule: usize,
+    /// The selector concerned, or `None` for global rules and rule-level
+    /// findings.
+    pub selector: Option<String>,
+    pub kind: LintKind,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let subject = match &self.selector {
+            Some(selector) => format!("`{selector}`"),
+            None => "the global rule".to_owned(),
+        };
+        match &self.kind {
+            LintKind::Shadowed { by } => {
+                let by: Vec<String> = by.iter().map(|i| format!("#{i}")).collect();
+                write!(f, "rule #{}: {subject} is always overridden (by {})", self.rule, by.join(", "))
+            }
+            LintKind::Unmatched => {
+                write!(f, "rule #{}: {subject} matches no scope a known grammar produces", self.rule)
+            }
+            LintKind::Unreachable => write!(f, "rule #{} never applies and can be removed", self.rule),
+        }
+    }
+}
+
+/// Lints `theme` against `known`, the scopes the grammars can produce (see
+/// `inventory::known_scopes`). Findings are ordered by rule.
+pub fn lint(theme: &Theme, known: &BTreeSet<String>) -> Vec<Lint> {
+    // (rule, selector); `None` stands for the global rules
+    let mut entries: Vec<(usize, Option<Selector>)> = Vec::new();
+    for (index, rule) in theme.token_colors.iter().enumerate() {
+        if rule.is_default() {
+            entries.push((index, None));
+        } else {
+            entries.extend(rule.selectors().into_iter().filter_map(Selector::parse).map(|s| (index, Some(s))));
+        }
+    }
+
+    // The last rule setting each field, per selector
+    let mut last: BTreeMap<Option<String>, [Option<usize>; 3]> = BTreeMap::new();
+    for (index, selector) in &entries {
+        let setters = last.entry(selector.as_ref().map(ToString::to_string)).or_default();
+        for (field, set) in fields(&theme.token_colors[*index].settings).into_iter().enumerate() {
+            if set {
+                setters[field] = Some(*index);
+            }
+        }
+    }
+    let plain: BTreeMap<&str, [Option<usize>; 3]> = entries
+        .iter()
+        .filter_map(|(_, selector)| selector.as_ref().filter(|s| is_plain(s)))
+        .map(|s| (s.scope.as_str(), last[&Some(s.to_string())]))
+        .collect();
+
+    let mut lints = Vec::new();
+    let mut dead: BTreeMap<usize, bool> = BTreeMap::new();
+    for (index, selector) in &entries {
+        let key = selector.as_ref().map(ToString::to_string);
+        let matched: Vec<&String> = match selector {
+            Some(s) => known.iter().filter(|scope| matches_scope(scope, &s.scope)).collect(),
+            None => Vec::new(),
+        };
+        if selector.is_some() && matched.is_empty() {
+            lints.push(Lint { rule: *index, selector: key, kind: LintKind::Unmatched });
+            dead.entry(*index).or_insert(true);
+            continue;
+        }
+
+        let mut by = BTreeSet::new();
+        let mut alive = false;
+        for (field, set) in fields(&theme.token_colors[*index].settings).into_iter().enumerate() {
+            if !set {
+                continue;
+            }
+            match last[&key][field] {
+                Some(later) if later != *index => {
+                    by.insert(later);
+                    continue;
+                }
+                _ => {}
+            }
+            let Some(selector) = selector.as_ref().filter(|s| is_plain(s)) else {
+                alive = true;
+                continue;
+            };
+            // Each matched scope takes the field from the deepest plain
+            // selector above it that sets it
+            for scope in &matched {
+                let deeper = plain
+                    .iter()
+                    .filter(|(target, setters)| {
+                        target.len() > selector.scope.len() && matches_scope(scope, target) && setters[field].is_some()
+                    })
+                    .max_by_key(|(target, _)| target.len());
+                match deeper {
+                    Some((_, setters)) => by.extend(setters[field]),
+                    None => alive = true,
+                }
+            }
+        }
+        if alive {
+            dead.insert(*index, false);
+        } else {
+            lints.push(Lint { rule: *index, selector: key, kind: LintKind::Shadowed { by } });
+            dead.entry(*index).or_insert(true);
+        }
+    }
+
+    for (rule, dead) in dead {
+        if dead {
+            lints.push(Lint { rule, selector: None, kind: LintKind::Unreachable });
+        }
+    }
+    lints.sort_by_key(|lint| (lint.rule, lint.kind == LintKind::Unreachable));
+    lints
+}
+
+fn is_plain(selector: &Selector) -> bool {
+    selector.parents.is_empty() && selector.excludes.is_empty()
+}
+
+fn fields(settings: &Settings) -> [bool; 3] {
+    [settings.foreground.is_some(), settings.background.is_some(), settings.font_style.is_some()]
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn lint_rules(rules: serde_json::Value, known: &[&str]) -> Vec<String> {
+        let theme: Theme = serde_json::from_value(json!({ "name": "t", "tokenColors": rules })).unwrap();
+        let known = known.iter().map(|s| (*s).to_owned()).collect();
+        lint(&theme, &known).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn finds_rules_overwritten_by_the_same_selector() {
+        let lints = lint_rules(
+            json!([
+                { "scope": "string", "settings": { "foreground": "#111111" } },
+                { "scope": "string, comment", "settings": { "foreground": "#222222", "fontStyle": "italic" } },
+                { "settings": { "foreground": "#333333" } },
+                { "settings": { "foreground": "#444444" } },
+            ]),
+            &["string.quoted.rust", "comment.line.rust"],
+        );
+        assert_eq!(
+            lints,
+            [
+                "rule #0: `string` is always overridden (by #1)",
+                "rule #0 never applies and can be removed",
+                "rule #2: the global rule is always overridden (by #3)",
+                "rule #2 never applies and can be removed",
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_rules_shadowed_by_deeper_selectors_and_unmatched_ones() {
+        let lints = lint_rules(
+            json!([
+                { "scope": "keyword", "settings": { "foreground": "#111111", "fontStyle": "bold" } },
+                { "scope": "keyword.control", "settings": { "foreground": "#222222" } },
+                { "scope": "keyword.operator", "settings": { "foreground": "#333333" } },
+                { "scope": ["constant", "markup.heading"], "settings": { "foreground": "#444444" } },
+                { "scope": "markup.heading", "settings": { "fontStyle": "bold" } },
+            ]),
+            &["keyword.control.rust", "keyword.operator.rust", "constant.numeric.rust"],
+        );
+        assert_eq!(
+            lints,
+            [
+                "rule #3: `markup.heading` matches no scope a known grammar produces",
+                "rule #4: `markup.heading` matches no scope a known grammar produces",
+                "rule #4 never applies and can be removed",
+            ]
+        );
+
+        let lints = lint_rules(
+            json!([
+                { "scope": "keyword", "settings": { "foreground": "#111111" } },
+                { "scope": "keyword.control", "settings": { "foreground": "#222222" } },
+                { "scope": "keyword.operator", "settings": { "foreground": "#333333" } },
+            ]),
+            &["keyword.control.rust", "keyword.operator.rust"],
+        );
+        assert_eq!(
+            lints,
+            ["rule #0: `keyword` is always overridden (by #1, #2)", "rule #0 never applies and can be removed"]
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Fenced code blocks from Zenn articles, highlighted as the article
+//! would show them.
+
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::html::{self, escape};
+use crate::preview::STYLE;
+use crate::resolver::Resolver;
+use crate::tokenizer::Tokenizer;
+use crate::Result;
+
+/// One fenced block. Zenn's info string is `lang`, `lang:filename` or
+/// `diff lang`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// Line of the opening fence, 1-based.
+    pub line: usize,
+    pub language: String,
+    pub filename: Option<String>,
+    pub code: String,
+}
+
+impl CodeBlock {
+    /// The grammar to look up: `diff` for diff blocks, otherwise the
+    /// language with Zenn's common aliases expanded.
+    pub fn grammar(&self) -> &str {
+        let language = self.language.split_whitespace().next().unwrap_or_default();
+        match language {
+            "shell" | "zsh" | "console" => "sh",
+            "golang" => "go",
+            "yml" => "yaml",
+            "tsx" => "ts",
+            "jsx" => "js",
+            other => other,
+        }
+    }
+}
+
+/// Every fenced block (` ``` ` or `~~~`, longer fences allowed) in order.
+/// An unclosed fence runs to the end of the document, as in CommonMark.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(String, CodeBlock)> = None;
+
+    for (index, line) in markdown.lines().enumerate() {
+        let trimmed = line.trim_start_matches(' ');
+        let indented = line.len() - trimmed.len() > 3;
+
+        if let Some((fence, block)) = &mut open {
+            if indented || !closes(trimmed, fence) {
+                block.code.push_str(line);
+                block.code.push('\n');
+                continue;
+            }
+            blocks.extend(open.take().map(|(_, block)| block));
+            continue;
+        }
+
+        if indented {
+            continue;
+        }
+        let Some(marker) = trimmed.chars().next().filter(|c| *c == '`' || *c == '~') else {
+            continue;
+        };
+        let fence_len = trimmed.chars().take_while(|c| *c == marker).count();
+        if fence_len < 3 {
+            continue;
+        }
+        let info = trimmed[fence_len..].trim();
+        if marker == '`' && info.contains('`') {
+            continue;
+        }
+        let (language, filename) = match info.split_once(':') {
+            Some((language, filename)) => (language, Some(filename.to_owned())),
+            None => (info, None),
+        };
+        let block = CodeBlock { line: index + 1, language: language.to_owned(), filename, code: String::new() };
+        open = Some((marker.to_string().repeat(fence_len), block));
+    }
+    blocks.extend(open.map(|(_, block)| block));
+    blocks
+}
+
+/// A closing fence repeats the opening marker at least as many times and
+/// has nothing else on the line.
+fn closes(line: &str, fence: &str) -> bool {
+    let line = line.trim_end();
+    let marker = fence.chars().next().unwrap_or('`');
+    line.len() >= fence.len() && line.chars().all(|c| c == marker)
+}
+
+/// A standalone page with every block in `markdown` highlighted in order.
+pub fn render(resolver: &Resolver, tokenizer: &Tokenizer, path: &Path, markdown: &str) -> Result<String> {
+    let title = path.file_name().unwrap_or_default().to_string_lossy();
+    let blocks = code_blocks(markdown);
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"ja\">\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{0}</h1>\n<p class=\"theme-name\">{1} code block(s)</p>\n",
+        escape(&title),
+        blocks.len()
+    );
+    for block in &blocks {
+        let label = match &block.filename {
+            Some(filename) => format!("{} — {}", block.language, filename),
+            None if block.language.is_empty() => "(no language)".to_owned(),
+            None => block.language.clone(),
+        };
+        let _ = write!(out, "<section>\n<h2>line {}: {}</h2>\n", block.line, escape(&label));
+
+        let html = match tokenizer.find_syntax(block.grammar()).filter(|_| !block.grammar().is_empty()) {
+            Some(syntax) => {
+                let tokens = tokenizer.tokenize(syntax, path, &block.code)?;
+                html::highlight(resolver, &tokens, block.code.lines().count(), "article")
+            }
+            None => {
+                out.push_str("<p class=\"note\">No grammar for this language; shown unhighlighted.</p>\n");
+                html::plain(resolver, &block.code, "article")
+            }
+        };
+        let _ = write!(out, "<div class=\"znc\">{html}</div>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_zenn_info_strings() {
+        let markdown =
+            "# Title\n\n```rust:src/main.rs\nfn main() {}\n```\n\n~~~diff js\n-a\n+b\n~~~\n\n```\nplain\n```\n";
+        let blocks = code_blocks(markdown);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].line, 3);
+        assert_eq!(blocks[0].language, "rust");
+        assert_eq!(blocks[0].filename.as_deref(), Some("src/main.rs"));
+        assert_eq!(blocks[0].code, "fn main() {}\n");
+        assert_eq!(blocks[1].grammar(), "diff");
+        assert_eq!(blocks[1].code, "-a\n+b\n");
+        assert_eq!(blocks[2].language, "");
+    }
+
+    #[test]
+    fn longer_fences_nest_shorter_ones() {
+        let markdown = "````md\n```js\nx\n```\n````\n";
+        let blocks = code_blocks(markdown);
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].code, "```js\nx\n```\n");
+    }
+
+    #[test]
+    fn unclosed_fence_runs_to_the_end() {
+        let blocks = code_blocks("```py\nprint(1)\n");
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].code, "print(1)\n");
+    }
+}
@@ -0,0 +1,162 @@
+//! Canonical form of a theme file, so hand edits diff only where they
+//! change something.
+//!
+//! Rule order only matters between rules with the same selector, where the
+//! later one wins each field it sets. Those are folded together first; after
+//! that every selector is independent of its position, so selectors with
+//! identical settings can share one rule and the rules can be sorted.
+
+use std::collections::BTreeMap;
+
+use crate::selector::Selector;
+use crate::theme::{Color, Scope, SemanticTokenStyle, Settings, Theme, TokenColor};
+
+/// Returns `theme` normalized:
+///
+/// - colors are lowercase `#rrggbb` or `#rrggbbaa`
+/// - the global rules are folded into one, listed first
+/// - repeated selectors are folded, later fields winning
+/// - selectors with identical settings share a rule
+/// - selectors within a rule, and rules by their first selector, are sorted
+///
+/// Styles resolve exactly as they did before.
+pub fn normalize(theme: &Theme) -> Theme {
+    let mut defaults: Option<(Option<String>, Settings)> = None;
+    let mut selectors: BTreeMap<String, (Option<String>, Settings)> = BTreeMap::new();
+    for rule in &theme.token_colors {
+        let settings = normalize_settings(&rule.settings);
+        if rule.is_default() {
+            fold(defaults.get_or_insert_with(|| (rule.name.clone(), Settings::default())), &rule.name, &settings);
+            continue;
+        }
+        for selector in rule.selectors().into_iter().filter_map(Selector::parse) {
+            let entry =
+                selectors.entry(selector.to_string()).or_insert_with(|| (rule.name.clone(), Settings::default()));
+            fold(entry, &rule.name, &settings);
+        }
+    }
+
+    // `selectors` iterates in order, so each group's scopes come out sorted
+    // and the groups are already ordered by their first scope
+    let mut groups: Vec<(Option<String>, Settings, Vec<String>)> = Vec::new();
+    for (selector, (name, settings)) in selectors {
+        match groups.iter_mut().find(|(_, s, _)| *s == settings) {
+            Some((group_name, _, scopes)) => {
+                if group_name.is_none() {
+                    *group_name = name;
+                }
+                scopes.push(selector);
+            }
+            None => groups.push((name, settings, vec![selector])),
+        }
+    }
+
+    let mut token_colors: Vec<TokenColor> =
+        defaults.map(|(name, settings)| TokenColor { name, scope: None, settings }).into_iter().collect();
+    token_colors.extend(groups.into_iter().map(|(name, settings, mut scopes)| {
+        let scope = if scopes.len() == 1 { Scope::One(scopes.remove(0)) } else { Scope::Many(scopes) };
+        TokenColor { name, scope: Some(scope), settings }
+    }));
+
+    Theme {
+        colors: theme.colors.iter().map(|(key, color)| (key.clone(), color.normalized())).collect(),
+        token_colors,
+        semantic_token_colors: theme
+            .semantic_token_colors
+            .iter()
+            .map(|(key, style)| (key.clone(), normalize_semantic(style)))
+            .collect(),
+        ..theme.clone()
+    }
+}
+
+fn fold((name, into): &mut (Option<String>, Settings), rule_name: &Option<String>, settings: &Settings) {
+    if name.is_none() {
+        name.clone_from(rule_name);
+    }
+    if settings.foreground.is_some() {
+        into.foreground.clone_from(&settings.foreground);
+    }
+    if settings.background.is_some() {
+        into.background.clone_from(&settings.background);
+    }
+    if settings.font_style.is_some() {
+        into.font_style = settings.font_style;
+    }
+}
+
+fn normalize_settings(settings: &Settings) -> Settings {
+    Settings {
+        foreground: settings.foreground.as_ref().map(Color::normalized),
+        background: settings.background.as_ref().map(Color::normalized),
+        font_style: settings.font_style,
+    }
+}
+
+fn normalize_semantic(style: &SemanticTokenStyle) -> SemanticTokenStyle {
+    match style {
+        SemanticTokenStyle::Color(color) => SemanticTokenStyle::Color(color.normalized()),
+        SemanticTokenStyle::Style(style) => {
+            let mut style = style.clone();
+            style.foreground = style.foreground.as_ref().map(Color::normalized);
+            SemanticTokenStyle::Style(style)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::resolver::Resolver;
+
+    fn theme(rules: serde_json::Value) -> Theme {
+        serde_json::from_value(json!({
+            "name": "t",
+            "colors": { "editor.background": "#FFF" },
+            "tokenColors": rules,
+            "semanticTokenColors": { "function": "#AbC", "type": { "foreground": "#00FF0080" } }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn normalizes_colors() {
+        let normalized = normalize(&theme(json!([{ "scope": "string", "settings": { "foreground": "#A1b" } }])));
+        assert_eq!(normalized.colors["editor.background"].as_str(), "#ffffff");
+        assert_eq!(normalized.token_colors[0].settings.foreground.as_ref().unwrap().as_str(), "#aa11bb");
+        let json = serde_json::to_value(&normalized.semantic_token_colors).unwrap();
+        assert_eq!(json, json!({ "function": "#aabbcc", "type": { "foreground": "#00ff0080" } }));
+    }
+
+    #[test]
+    fn folds_merges_and_sorts_rules() {
+        let original = theme(json!([
+            { "scope": "string", "settings": { "foreground": "#aaaaaa", "fontStyle": "italic" } },
+            { "name": "Keywords", "scope": "keyword, storage", "settings": { "foreground": "#bbbbbb" } },
+            { "settings": { "foreground": "#111111" } },
+            { "scope": "meta.block keyword", "settings": { "fontStyle": "bold" } },
+            { "scope": ["string"], "settings": { "foreground": "#bbbbbb" } },
+            { "scope": "constant", "settings": { "foreground": "#BBB", "fontStyle": "italic" } },
+        ]));
+        let normalized = normalize(&original);
+        let rules = serde_json::to_value(&normalized.token_colors).unwrap();
+        assert_eq!(
+            rules,
+            json!([
+                { "settings": { "foreground": "#111111" } },
+                { "scope": ["constant", "string"], "settings": { "foreground": "#bbbbbb", "fontStyle": "italic" } },
+                { "name": "Keywords", "scope": ["keyword", "storage"], "settings": { "foreground": "#bbbbbb" } },
+                { "scope": "meta.block keyword", "settings": { "fontStyle": "bold" } },
+            ])
+        );
+
+        let (before, after) = (Resolver::new(&original), Resolver::new(&normalized));
+        for stack in [&["source", "string.quoted"][..], &["source", "meta.block", "keyword.control"], &["source"]] {
+            let (a, b) = (before.resolve(stack), after.resolve(stack));
+            assert_eq!((a.foreground.to_lowercase(), a.font_style), (b.foreground.to_owned(), b.font_style));
+        }
+        assert_eq!(normalize(&normalized), normalized);
+    }
+}
@@ -0,0 +1,191 @@
+//! Shrinks a theme without changing how it styles anything.
+//!
+//! On top of [`normalize`], which already merges rules with identical
+//! settings, this drops each field a selector sets to the value it would
+//! get anyway: from the deepest plain selector above it that sets the
+//! field, or from the global defaults when none does.
+//!
+//! Inheriting from an ancestor selector is exact unless a parent-scoped
+//! selector targets that ancestor, which could then start matching where
+//! the dropped rule used to win; such selectors are kept. Falling back to
+//! the global defaults is exact only when no enclosing scope can set the
+//! field either, so a field equal to the default is dropped only when no
+//! other selector sets it to anything else. [`equivalent`] checks the
+//! result against real scope stacks.
+
+use std::collections::BTreeMap;
+
+use crate::colors::Rgba;
+use crate::normalize::normalize;
+use crate::resolver::{Resolved, Resolver};
+use crate::selector::{matches_scope, Selector};
+use crate::theme::{FontStyle, Scope, Settings, Theme, TokenColor};
+
+/// The optimized theme, normalized.
+pub fn optimize(theme: &Theme) -> Theme {
+    let theme = normalize(theme);
+    let resolver = Resolver::new(&theme);
+    let defaults = Settings {
+        foreground: resolver.default_foreground().parse().ok(),
+        background: resolver.default_background().parse().ok(),
+        font_style: Some(FontStyle::NONE),
+    };
+
+    let selectors: Vec<(Selector, &TokenColor)> = theme
+        .token_colors
+        .iter()
+        .filter(|rule| !rule.is_default())
+        .flat_map(|rule| rule.selectors().into_iter().filter_map(Selector::parse).map(move |s| (s, rule)))
+        .collect();
+    // Normalized, so every plain scope appears once
+    let plain: BTreeMap<&str, &Settings> = selectors
+        .iter()
+        .filter(|(s, _)| s.parents.is_empty() && s.excludes.is_empty())
+        .map(|(s, rule)| (s.scope.as_str(), &rule.settings))
+        .collect();
+    let guarded = |scope: &str| {
+        selectors.iter().any(|(s, _)| {
+            (!s.parents.is_empty() || !s.excludes.is_empty()) && s.scope != scope && matches_scope(scope, &s.scope)
+        })
+    };
+
+    let mut token_colors: Vec<TokenColor> =
+        theme.token_colors.iter().filter(|rule| rule.is_default()).cloned().collect();
+    for (selector, rule) in &selectors {
+        let mut settings = rule.settings.clone();
+        if selector.parents.is_empty() && selector.excludes.is_empty() && !guarded(&selector.scope) {
+            for (field, value) in FIELDS.into_iter().enumerate() {
+                let inherited = plain
+                    .iter()
+                    .filter(|(scope, settings)| {
+                        scope.len() < selector.scope.len()
+                            && matches_scope(&selector.scope, scope)
+                            && value(settings).is_some()
+                    })
+                    .max_by_key(|(scope, _)| scope.len())
+                    .map_or_else(
+                        || {
+                            let default = value(&defaults);
+                            let overridden = selectors.iter().any(|(other, rule)| {
+                                other != selector && value(&rule.settings).is_some_and(|v| Some(v) != default)
+                            });
+                            if overridden {
+                                None
+                            } else {
+                                default
+                            }
+                        },
+                        |(_, settings)| value(settings),
+                    );
+                if value(&settings).is_some() && value(&settings) == inherited {
+                    clear(&mut settings, field);
+                }
+            }
+        }
+        if settings != Settings::default() {
+            token_colors.push(TokenColor {
+                name: rule.name.clone(),
+                scope: Some(Scope::One(selector.to_string())),
+                settings,
+            });
+        }
+    }
+    normalize(&Theme { token_colors, ..theme.clone() })
+}
+
+const FIELDS: [fn(&Settings) -> Option<String>; 3] = [
+    |s| s.foreground.as_ref().map(|c| c.as_str().to_owned()),
+    |s| s.background.as_ref().map(|c| c.as_str().to_owned()),
+    |s| s.font_style.map(|f| f.to_string()),
+];
+
+fn clear(settings: &mut Settings, field: usize) {
+    match field {
+        0 => settings.foreground = None,
+        1 => settings.background = None,
+        _ => settings.font_style = None,
+    }
+}
+
+/// A token of `stacks` that `before` and `after` style differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    pub scopes: Vec<String>,
+    pub before: String,
+    pub after: String,
+}
+
+/// Resolves every scope stack with both themes and returns the first few
+/// that differ in color or font style.
+pub fn equivalent<'a>(
+    before: &Theme,
+    after: &Theme,
+    stacks: impl IntoIterator<Item = &'a [String]>,
+    limit: usize,
+) -> Vec<Difference> {
+    let (a, b) = (Resolver::new(before), Resolver::new(after));
+    let hex = |color: &str| Rgba::parse(color).map_or_else(|| color.to_owned(), Rgba::to_hex);
+    let describe = |style: Resolved<'_>| {
+        format!("{} on {} {}", hex(style.foreground), hex(style.background), style.font_style).trim_end().to_owned()
+    };
+    stacks
+        .into_iter()
+        .filter_map(|scopes| {
+            let (before, after) = (describe(a.resolve(scopes)), describe(b.resolve(scopes)));
+            (before != after).then(|| Difference { scopes: scopes.to_vec(), before, after })
+        })
+        .take(limit)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn drops_fields_that_repeat_inherited_values() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "colors": { "editor.foreground": "#111111", "editor.background": "#000000" },
+            "tokenColors": [
+                { "scope": "keyword", "settings": { "foreground": "#aaaaaa", "fontStyle": "bold" } },
+                { "scope": "keyword.control", "settings": { "foreground": "#aaaaaa", "fontStyle": "italic" } },
+                { "scope": "keyword.other", "settings": { "foreground": "#AAA" } },
+                { "scope": "variable", "settings": { "foreground": "#111111" } },
+                { "scope": "markup", "settings": { "background": "#000000" } },
+                { "scope": "string", "settings": { "foreground": "#222222" } },
+                { "scope": "string.unquoted", "settings": { "foreground": "#222222" } },
+                { "scope": "meta.embedded string", "settings": { "fontStyle": "bold" } },
+            ]
+        }))
+        .unwrap();
+        let optimized = optimize(&theme);
+        let rules = serde_json::to_value(&optimized.token_colors).unwrap();
+        assert_eq!(
+            rules,
+            json!([
+                { "scope": "keyword", "settings": { "foreground": "#aaaaaa", "fontStyle": "bold" } },
+                { "scope": "keyword.control", "settings": { "fontStyle": "italic" } },
+                { "scope": "meta.embedded string", "settings": { "fontStyle": "bold" } },
+                { "scope": ["string", "string.unquoted"], "settings": { "foreground": "#222222" } },
+                { "scope": "variable", "settings": { "foreground": "#111111" } },
+            ])
+        );
+
+        let stacks: Vec<Vec<String>> = [
+            &["source", "keyword.control.rust"][..],
+            &["source", "keyword.other.fn"],
+            &["source", "variable.other"],
+            &["source", "string", "variable.other"],
+            &["source", "markup.bold"],
+            &["source", "meta.embedded", "string.unquoted"],
+        ]
+        .iter()
+        .map(|s| s.iter().map(|s| (*s).to_owned()).collect())
+        .collect();
+        assert_eq!(equivalent(&theme, &optimized, stacks.iter().map(Vec::as_slice), 10), []);
+        assert_eq!(optimize(&optimized), optimized);
+    }
+}
@@ -0,0 +1,530 @@
+//! The theme's palette and role mapping as data, from which
+//! `src/themes/zenn.json` is generated.
+//!
+//! Edit a color here and run `cargo xtask build-theme`; every rule using
+//! that role follows, in the main theme and in every variant. Variants are
+//! [`Overlay`]s that state only how they differ from the base, so they
+//! cannot drift from it. The checked-in JSON is verified against this
+//! module by `tests/palette.rs`.
+
+use std::collections::BTreeMap;
+
+use crate::colors::{Oklch, Rgba};
+use crate::contrast::{contrast_ratio, AAA_NORMAL, AA_NORMAL};
+use crate::optimize::optimize;
+use crate::theme::{
+    Color, FontStyle, Scope, SemanticStyle, SemanticTokenStyle, Settings, Theme, ThemeKind, TokenColor, BRACKET_PREFIX,
+    UNEXPECTED_BRACKET,
+};
+
+use self::Role::*;
+
+/// What a color is for, independent of its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    Background,
+    /// Plain text, identifiers and type names.
+    Foreground,
+    Comment,
+    /// Keywords, storage, tags and headings.
+    Keyword,
+    /// Functions, links, object keys and inserted lines.
+    Function,
+    /// Strings, numbers, constants and operators.
+    Literal,
+    Punctuation,
+}
+
+impl Role {
+    pub const ALL: [Role; 7] = [
+        Role::Background,
+        Role::Foreground,
+        Role::Comment,
+        Role::Keyword,
+        Role::Function,
+        Role::Literal,
+        Role::Punctuation,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub background: Rgba,
+    pub foreground: Rgba,
+    pub comment: Rgba,
+    pub keyword: Rgba,
+    pub function: Rgba,
+    pub literal: Rgba,
+    pub punctuation: Rgba,
+}
+
+impl Palette {
+    /// The colors of the shipped Zenn theme.
+    pub fn zenn() -> Self {
+        Self {
+            background: hex("#1a2638"),
+            foreground: hex("#ffffff"),
+            comment: hex("#94a1b3"),
+            keyword: hex("#ff8fa3"),
+            function: hex("#38c7ff"),
+            literal: hex("#ffc56d"),
+            punctuation: hex("#939bc1"),
+        }
+    }
+
+    pub fn get(&self, role: Role) -> Rgba {
+        match role {
+            Role::Background => self.background,
+            Role::Foreground => self.foreground,
+            Role::Comment => self.comment,
+            Role::Keyword => self.keyword,
+            Role::Function => self.function,
+            Role::Literal => self.literal,
+            Role::Punctuation => self.punctuation,
+        }
+    }
+
+    pub fn set(&mut self, role: Role, color: Rgba) {
+        let slot = match role {
+            Role::Background => &mut self.background,
+            Role::Foreground => &mut self.foreground,
+            Role::Comment => &mut self.comment,
+            Role::Keyword => &mut self.keyword,
+            Role::Function => &mut self.function,
+            Role::Literal => &mut self.literal,
+            Role::Punctuation => &mut self.punctuation,
+        };
+        *slot = color;
+    }
+}
+
+/// Bracket pair colors by nesting depth, for
+/// `editorBracketHighlight.foreground1..6`: the literal, keyword and
+/// function accents, then the hues halfway between each neighbouring pair,
+/// all lifted to `target` against the background. Nesting cycles through
+/// them, so neighbouring depths never share a hue family.
+pub fn bracket_colors(palette: &Palette, target: f64) -> [Rgba; 6] {
+    let [literal, keyword, function] = [palette.literal, palette.keyword, palette.function].map(Rgba::to_oklch);
+    let between = |a: Oklch, b: Oklch| {
+        let turn = (b.h - a.h + 540.0).rem_euclid(360.0) - 180.0;
+        let lch = Oklch { l: (a.l + b.l) / 2.0, c: (a.c + b.c) / 2.0, h: (a.h + turn / 2.0).rem_euclid(360.0) };
+        lch.to_rgba(1.0)
+    };
+    [
+        palette.literal,
+        palette.keyword,
+        palette.function,
+        between(literal, keyword),
+        between(keyword, function),
+        between(function, literal),
+    ]
+    .map(|color| lift(color, palette.background, target))
+}
+
+/// Derives a variant in which every role clears `target` against the
+/// background, by moving OKLCH lightness away from it (keeping hue) only as
+/// far as needed. Roles that already clear it are left alone.
+pub fn high_contrast(palette: &Palette, target: f64) -> Palette {
+    let mut out = *palette;
+    for role in Role::ALL {
+        if role != Role::Background {
+            out.set(role, lift(palette.get(role), palette.background, target));
+        }
+    }
+    out
+}
+
+/// A first draft of the palette for the opposite [`ThemeKind`]: OKLCH
+/// lightness mirrored within the palette's own range, so the background and
+/// the lightest role trade places, with hue and chroma kept; then every role
+/// is lifted to `target` as [`high_contrast`] does. Mirroring within the
+/// range rather than around `0.5` keeps a dark blue-gray background from
+/// turning into a washed-out light gray.
+pub fn invert(palette: &Palette, target: f64) -> Palette {
+    let lightness = Role::ALL.map(|role| palette.get(role).to_oklch().l);
+    let (min, max) = lightness.iter().fold((1.0_f64, 0.0_f64), |(lo, hi), &l| (lo.min(l), hi.max(l)));
+    let mut out = *palette;
+    for role in Role::ALL {
+        let color = palette.get(role);
+        let lch = color.to_oklch();
+        out.set(role, Oklch { l: min + max - lch.l, ..lch }.to_rgba(color.a));
+    }
+    high_contrast(&out, target)
+}
+
+/// `color`, with its OKLCH lightness moved away from `background` just far
+/// enough to clear `target`.
+fn lift(color: Rgba, background: Rgba, target: f64) -> Rgba {
+    let direction = if background.relative_luminance() < 0.5 { 1.0 } else { -1.0 };
+    // Judge the color as it will be written, rounded to hex
+    let passes = |color: Rgba| contrast_ratio(Rgba::from(&Color::from(color)), background) >= target;
+    if passes(color) {
+        return color;
+    }
+    let (mut lo, mut hi) = (0.0, 1.0);
+    for _ in 0..32 {
+        let mid = (lo + hi) / 2.0;
+        if passes(color.lighten(direction * mid)) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    color.lighten(direction * hi)
+}
+
+/// A variant of the base theme, stated as its differences from it.
+#[derive(Debug, Clone, Copy)]
+pub struct Overlay {
+    pub name: &'static str,
+    pub display_name: &'static str,
+    pub kind: ThemeKind,
+    /// Roles whose color differs from the base palette.
+    pub colors: &'static [(Role, &'static str)],
+    /// Then lifts every role to this contrast against the background, as
+    /// [`high_contrast`] does.
+    pub min_contrast: Option<f64>,
+    /// Merged over [`RULES`]: a selector the base already has keeps the
+    /// fields the overlay leaves unset.
+    pub rules: &'static [Rule],
+}
+
+impl Overlay {
+    /// The base palette with this overlay's colors applied.
+    pub fn palette(&self, base: &Palette) -> Palette {
+        let mut palette = *base;
+        for &(role, color) in self.colors {
+            palette.set(role, hex(color));
+        }
+        match self.min_contrast {
+            Some(target) => high_contrast(&palette, target),
+            None => palette,
+        }
+    }
+
+    pub fn generate(&self, base: &Palette) -> Theme {
+        let rules: Vec<Rule> = RULES.iter().chain(self.rules).copied().collect();
+        let bracket_contrast = self.min_contrast.map_or(AA_NORMAL, |target| target.max(AA_NORMAL));
+        build(self.name, self.display_name, self.kind, &self.palette(base), &rules, bracket_contrast)
+    }
+}
+
+/// The shipped variants of [`Palette::zenn`].
+pub static OVERLAYS: &[Overlay] = &[
+    Overlay {
+        name: "zenn-high-contrast",
+        display_name: "Zenn High Contrast",
+        kind: ThemeKind::Dark,
+        colors: &[],
+        min_contrast: Some(AAA_NORMAL),
+        rules: &[],
+    },
+    Overlay {
+        name: "zenn-light",
+        display_name: "Zenn Light",
+        kind: ThemeKind::Light,
+        colors: &[
+            (Background, "#ffffff"),
+            (Foreground, "#1a2638"),
+            (Comment, "#6b7789"),
+            (Keyword, "#d6336c"),
+            (Function, "#0b7bb5"),
+            (Literal, "#a8650e"),
+            (Punctuation, "#5d6593"),
+        ],
+        min_contrast: Some(AA_NORMAL),
+        rules: &[],
+    },
+    Overlay {
+        name: "zenn-deuteranopia",
+        display_name: "Zenn Deuteranopia",
+        kind: ThemeKind::Dark,
+        colors: &[(Keyword, "#ff6b8b")],
+        min_contrast: None,
+        // Diff lines stay apart by more than hue
+        rules: &[style(&["markup.deleted"], FontStyle::STRIKETHROUGH)],
+    },
+];
+
+/// Every theme file generated from the palette, by file name under
+/// `src/themes`.
+pub fn shipped() -> Vec<(String, Theme)> {
+    let zenn = Palette::zenn();
+    let mut themes = vec![("zenn.json".to_owned(), generate("zenn", "Zenn", &zenn))];
+    themes.extend(OVERLAYS.iter().map(|overlay| (format!("{}.json", overlay.name), overlay.generate(&zenn))));
+    themes
+}
+
+fn hex(text: &str) -> Rgba {
+    Rgba::parse(text).expect("palette colors are valid hex")
+}
+
+/// One `tokenColors` entry in terms of roles.
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    /// Empty for the default rule; otherwise one selector per entry.
+    pub scopes: &'static [&'static str],
+    pub foreground: Option<Role>,
+    pub background: Option<Role>,
+    pub font_style: Option<FontStyle>,
+}
+
+const fn fg(scopes: &'static [&'static str], role: Role) -> Rule {
+    Rule { scopes, foreground: Some(role), background: None, font_style: None }
+}
+
+const fn fg_bg(scopes: &'static [&'static str], foreground: Role, background: Role) -> Rule {
+    Rule { scopes, foreground: Some(foreground), background: Some(background), font_style: None }
+}
+
+const fn fg_style(scopes: &'static [&'static str], role: Role, font_style: FontStyle) -> Rule {
+    Rule { scopes, foreground: Some(role), background: None, font_style: Some(font_style) }
+}
+
+const fn style(scopes: &'static [&'static str], font_style: FontStyle) -> Rule {
+    Rule { scopes, foreground: None, background: None, font_style: Some(font_style) }
+}
+
+/// Builds the full theme from `palette` and [`RULES`], optimized (and so
+/// normalized) to keep the JSON Shiki parses small and the checked-in files
+/// passing `tools fmt --check`.
+pub fn generate(name: &str, display_name: &str, palette: &Palette) -> Theme {
+    generate_kind(name, display_name, ThemeKind::Dark, palette)
+}
+
+/// [`generate`] for a palette of either kind, such as one from [`invert`].
+pub fn generate_kind(name: &str, display_name: &str, kind: ThemeKind, palette: &Palette) -> Theme {
+    build(name, display_name, kind, palette, RULES, AA_NORMAL)
+}
+
+fn build(
+    name: &str,
+    display_name: &str,
+    kind: ThemeKind,
+    palette: &Palette,
+    rules: &[Rule],
+    bracket_contrast: f64,
+) -> Theme {
+    let color = |role: Role| Color::from(palette.get(role));
+    let token_colors = rules
+        .iter()
+        .map(|rule| TokenColor {
+            name: None,
+            scope: match rule.scopes {
+                [] => None,
+                [one] => Some(Scope::One((*one).to_owned())),
+                many => Some(Scope::Many(many.iter().map(|s| (*s).to_owned()).collect())),
+            },
+            settings: Settings {
+                foreground: rule.foreground.map(color),
+                background: rule.background.map(color),
+                font_style: rule.font_style,
+            },
+        })
+        .collect();
+
+    let mut colors = BTreeMap::from([
+        ("editor.background".to_owned(), color(Role::Background)),
+        ("editor.foreground".to_owned(), color(Role::Foreground)),
+        // Read by VS Code and by Shiki's colorized-brackets transformer
+        (UNEXPECTED_BRACKET.to_owned(), color(Role::Keyword)),
+    ]);
+    for (depth, bracket) in bracket_colors(palette, bracket_contrast).into_iter().enumerate() {
+        colors.insert(format!("{BRACKET_PREFIX}{}", depth + 1), Color::from(bracket));
+    }
+
+    let theme = Theme {
+        schema: None,
+        name: name.to_owned(),
+        display_name: Some(display_name.to_owned()),
+        kind,
+        semantic_highlighting: Some(true),
+        colors,
+        token_colors,
+        semantic_token_colors: SEMANTIC_RULES
+            .iter()
+            .map(|&(selector, role, font_style)| {
+                let style = match (role, font_style) {
+                    (Some(role), None) => SemanticTokenStyle::Color(color(role)),
+                    (foreground, font_style) => SemanticTokenStyle::Style(SemanticStyle {
+                        foreground: foreground.map(color),
+                        font_style,
+                        ..SemanticStyle::default()
+                    }),
+                };
+                (selector.to_owned(), style)
+            })
+            .collect(),
+    };
+    optimize(&theme)
+}
+
+/// `semanticTokenColors` for rust-analyzer's token types and modifiers
+/// (see [`crate::semantic`]), chosen to agree with what the TextMate rules
+/// give the same constructs so VS Code's semantic pass does not recolor
+/// code. Types left out inherit from their supertype.
+pub static SEMANTIC_RULES: &[(&str, Option<Role>, Option<FontStyle>)] = &[
+    ("comment", Some(Comment), None),
+    ("string", Some(Literal), None),
+    ("number", Some(Literal), None),
+    ("boolean", Some(Literal), None),
+    ("character", Some(Literal), None),
+    ("escapeSequence", Some(Literal), None),
+    ("formatSpecifier", Some(Literal), None),
+    ("operator", Some(Literal), None),
+    ("keyword", Some(Keyword), None),
+    ("lifetime", Some(Keyword), None),
+    ("function", Some(Function), None),
+    ("macro", Some(Function), None),
+    ("type", Some(Foreground), None),
+    ("namespace", Some(Foreground), None),
+    ("variable", Some(Foreground), None),
+    ("parameter", Some(Foreground), None),
+    ("property", Some(Foreground), None),
+    ("enumMember", Some(Foreground), None),
+    ("const", Some(Foreground), None),
+    ("static", Some(Foreground), None),
+    ("label", Some(Foreground), None),
+    ("attribute", Some(Foreground), None),
+    ("punctuation", Some(Punctuation), None),
+    ("unresolvedReference", Some(Foreground), None),
+    // Attribute macros read as attributes, not calls
+    ("*.attribute", Some(Foreground), None),
+];
+
+/// Every rule of the theme, in output order. Later rules win ties, as in
+/// the JSON.
+#[rustfmt::skip]
+pub static RULES: &[Rule] = &[
+    fg_bg(&[], Foreground, Background),
+    style(&["emphasis"], FontStyle::ITALIC),
+    style(&["strong"], FontStyle::BOLD),
+    fg(&["comment"], Comment),
+    fg(&["punctuation.definition.comment", "punctuation.end.definition.comment", "punctuation.start.definition.comment"], Comment),
+    fg(&["constant.character"], Literal),
+    fg(&["constant.character.escape"], Literal),
+    fg(&["constant.language"], Literal),
+    fg(&["constant.numeric"], Literal),
+    fg(&["constant.regexp"], Literal),
+    fg(&["constant.other"], Foreground),
+    fg(&["entity.name.class", "entity.name.type.class"], Foreground),
+    fg(&["entity.name.function"], Function),
+    fg(&["entity.name.tag"], Keyword),
+    fg(&["entity.other.attribute-name"], Foreground),
+    fg_style(&["entity.other.inherited-class"], Foreground, FontStyle::BOLD),
+    fg(&["entity.name.type"], Foreground),
+    fg(&["entity.name.namespace"], Foreground),
+    fg_bg(&["invalid.deprecated"], Background, Literal),
+    fg_bg(&["invalid.illegal"], Background, Keyword),
+    fg(&["keyword"], Keyword),
+    fg(&["keyword.operator"], Literal),
+    fg(&["keyword.other.new"], Keyword),
+    fg(&["keyword.control"], Keyword),
+    fg(&["keyword.control.import"], Keyword),
+    fg(&["keyword.control.export"], Keyword),
+    fg(&["keyword.control.from"], Keyword),
+    fg(&["keyword.control.as"], Keyword),
+    style(&["markup.bold"], FontStyle::BOLD),
+    fg(&["markup.changed"], Literal),
+    fg(&["markup.deleted"], Keyword),
+    fg(&["markup.inserted"], Function),
+    style(&["markup.italic"], FontStyle::ITALIC),
+    fg_style(&["markup.heading"], Keyword, FontStyle::BOLD),
+    fg(&["markup.quote"], Comment),
+    fg(&["markup.raw"], Literal),
+    fg(&["markup.underline.link"], Function),
+    fg(&["meta.preprocessor"], Keyword),
+    fg(&["meta.brace"], Punctuation),
+    fg(&["meta.function-call"], Function),
+    fg(&["punctuation"], Punctuation),
+    fg(&["punctuation.definition.method-parameters", "punctuation.definition.function-parameters", "punctuation.definition.parameters"], Punctuation),
+    fg(&["punctuation.definition.tag"], Keyword),
+    fg(&["punctuation.section"], Punctuation),
+    fg(&["punctuation.section.embedded.begin", "punctuation.section.embedded.end"], Punctuation),
+    fg(&["punctuation.terminator"], Punctuation),
+    fg(&["punctuation.definition.variable"], Punctuation),
+    fg(&["punctuation.separator"], Punctuation),
+    fg(&["punctuation.accessor"], Punctuation),
+    fg(&["punctuation.definition.string"], Literal),
+    fg(&["punctuation.definition.template-expression"], Punctuation),
+    fg(&["storage"], Keyword),
+    fg(&["storage.type"], Keyword),
+    fg(&["storage.modifier"], Keyword),
+    fg(&["string"], Literal),
+    fg(&["string.regexp"], Literal),
+    fg(&["string.template"], Literal),
+    fg(&["support.class"], Foreground),
+    fg(&["support.constant"], Foreground),
+    fg(&["support.function"], Function),
+    fg(&["support.function.construct"], Keyword),
+    fg(&["support.type"], Foreground),
+    fg(&["support.type.builtin"], Foreground),
+    fg(&["support.type.primitive"], Foreground),
+    fg(&["support.variable"], Foreground),
+    fg(&["variable.other"], Foreground),
+    fg(&["variable.language"], Keyword),
+    fg(&["variable.parameter"], Foreground),
+    fg(&["variable.other.constant"], Foreground),
+    fg(&["variable.other.property"], Foreground),
+    fg(&["variable.other.object"], Foreground),
+    fg(&["meta.object-literal.key"], Function),
+    fg(&["variable.other.readwrite"], Foreground),
+    fg(&["source.css support.type.property-name"], Foreground),
+    fg(&["source.css constant.other.color"], Literal),
+    fg(&["source.css meta.property-value"], Function),
+    fg(&["source.css keyword.control.at-rule"], Keyword),
+    fg(&["source.css entity.other.attribute-name.class"], Function),
+    fg(&["source.css entity.other.attribute-name.id"], Literal),
+    fg(&["source.diff meta.diff.range.context"], Function),
+    fg(&["source.diff meta.diff.header.from-file"], Function),
+    fg(&["source.diff punctuation.definition.from-file"], Function),
+    fg(&["source.diff punctuation.definition.range"], Function),
+    fg(&["source.diff punctuation.definition.separator"], Keyword),
+    fg(&["source.json string.quoted.double"], Literal),
+    fg(&["source.json support.type.property-name"], Function),
+    fg(&["source.json constant.language"], Keyword),
+    fg(&["source.yaml entity.name.tag"], Function),
+    fg(&["source.yaml string.unquoted"], Literal),
+    fg(&["text.html.basic entity.name.tag"], Keyword),
+    fg(&["text.html.basic entity.other.attribute-name"], Function),
+    fg(&["source.python keyword.operator.logical"], Keyword),
+    fg(&["source.python support.function.builtin"], Function),
+    fg(&["source.python support.type.python"], Foreground),
+    fg(&["source.python constant.language"], Literal),
+    fg(&["source.python meta.function-call.generic"], Function),
+    fg(&["source.rust entity.name.type"], Foreground),
+    fg(&["source.rust keyword.other"], Keyword),
+    fg(&["source.rust support.function"], Function),
+    fg(&["source.go keyword.function"], Keyword),
+    fg(&["source.go keyword.var"], Keyword),
+    fg(&["source.go keyword.const"], Keyword),
+    fg(&["source.go constant.other.placeholder"], Literal),
+    fg(&["source.java keyword.other.documentation"], Function),
+    fg(&["source.java meta.method-call meta.method"], Function),
+    fg(&["source.java storage.modifier"], Keyword),
+    fg(&["source.ts keyword.operator.type"], Keyword),
+    fg(&["source.ts entity.name.type"], Foreground),
+    fg(&["source.ts support.type"], Foreground),
+    fg(&["source.tsx keyword.operator.type"], Keyword),
+    fg(&["source.tsx entity.name.type"], Foreground),
+    fg(&["source.tsx support.type"], Foreground),
+    fg(&["constant.language.null", "constant.language.undefined", "constant.language.boolean"], Keyword),
+    fg(&["source.ruby keyword.control"], Keyword),
+    fg(&["source.ruby constant.other.symbol"], Literal),
+    fg(&["source.php keyword.other"], Keyword),
+    fg(&["source.php support.function"], Function),
+    fg(&["source.shell keyword.control"], Keyword),
+    fg(&["source.shell support.function.builtin"], Function),
+    fg(&["source.sql keyword"], Keyword),
+    fg(&["source.sql support.function"], Function),
+    fg_style(&["source.markdown markup.heading"], Keyword, FontStyle::BOLD),
+    fg(&["source.markdown markup.inline.raw"], Literal),
+    fg(&["source.markdown markup.underline.link"], Function),
+    fg(&["source.markdown string.other.link"], Function),
+    fg(&["punctuation.definition.begin.frontmatter", "punctuation.definition.end.frontmatter"], Punctuation),
+    fg(&["entity.name.tag.yaml"], Function),
+    fg(&["string.quoted.double.yaml", "string.quoted.single.yaml", "string.unquoted.plain.out.yaml"], Literal),
+    fg(&["constant.language.boolean.yaml"], Keyword),
+    fg(&["constant.numeric.integer.yaml", "constant.numeric.float.yaml"], Literal),
+    fg(&["punctuation.separator.key-value.mapping.yaml"], Punctuation),
+];
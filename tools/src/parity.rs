@@ -0,0 +1,234 @@
+//! Cross-checks the syntect tokenizer against the real Shiki, run through
+//! Node, so everything built on our tokens keeps describing what Zenn
+//! renders.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::colors::Rgba;
+use crate::diff::Look;
+use crate::resolver::{FontStyle, Resolver};
+use crate::samples::Sample;
+use crate::tokenizer::Token;
+use crate::{Error, Result};
+
+/// The Node script that runs Shiki, relative to the repository root.
+pub const SCRIPT_PATH: &str = "tools/shiki-parity.mjs";
+
+/// A token as Shiki produced it, before merging equally styled neighbours.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShikiToken {
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub text: String,
+    pub scopes: Vec<String>,
+    pub foreground: String,
+    pub font_style: FontStyle,
+}
+
+impl ShikiToken {
+    fn look(&self) -> Look {
+        let foreground = Rgba::parse(&self.foreground).map_or_else(|| self.foreground.clone(), Rgba::to_hex);
+        Look { foreground, font_style: self.font_style }
+    }
+}
+
+/// Tokenizes `samples` with Shiki and the theme at `theme`, giving `None`
+/// for samples whose language Shiki does not bundle. The language is the
+/// file extension, which Shiki accepts as an alias.
+pub fn shiki_tokens(root: &Path, theme: &Path, samples: &[Sample]) -> Result<Vec<Option<Vec<ShikiToken>>>> {
+    let failed = |message: String| Error::Shiki { message };
+    // Node runs from `root`, so a relative `--theme` must not reach it as is
+    let theme = fs::canonicalize(theme).map_err(|e| Error::io(theme, e))?;
+    let input = json!({
+        "theme": theme,
+        "samples": samples
+            .iter()
+            .map(|s| json!({
+                "lang": s.path.extension().unwrap_or_default().to_string_lossy(),
+                "source": s.source,
+            }))
+            .collect::<Vec<_>>(),
+    });
+
+    let mut child = Command::new("node")
+        .arg(SCRIPT_PATH)
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| failed(format!("could not run node: {e}")))?;
+    // A script that fails on startup closes stdin early; its stderr says why
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let written = stdin.write_all(input.to_string().as_bytes());
+    drop(stdin);
+    let output = child.wait_with_output().map_err(|e| failed(e.to_string()))?;
+    if !output.status.success() {
+        return Err(failed(String::from_utf8_lossy(&output.stderr).trim().to_owned()));
+    }
+    written.map_err(|e| failed(e.to_string()))?;
+    serde_json::from_slice(&output.stdout).map_err(|e| failed(format!("unexpected output: {e}")))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    /// Styled the same, but under a different scope stack.
+    Scope,
+    /// Styled differently, which Zenn readers would see.
+    Style,
+}
+
+impl Kind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Scope => "scope",
+            Self::Style => "style",
+        }
+    }
+}
+
+/// A run of characters on one line where the two tokenizers disagree the
+/// same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub kind: Kind,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub ours: Vec<String>,
+    pub shiki: Vec<String>,
+    pub our_look: Look,
+    pub shiki_look: Look,
+}
+
+#[derive(Debug, Clone)]
+pub struct SampleParity {
+    pub path: PathBuf,
+    /// Non-whitespace characters compared.
+    pub chars: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl SampleParity {
+    /// Characters that disagree in the given way.
+    pub fn count(&self, kind: Kind) -> usize {
+        self.mismatches.iter().filter(|m| m.kind == kind).map(|m| m.text.chars().count()).sum()
+    }
+}
+
+/// Compares our tokens, styled by `resolver`, with Shiki's character by
+/// character, ignoring whitespace.
+pub fn compare(path: &Path, resolver: &Resolver, ours: &[Token], shiki: &[ShikiToken]) -> SampleParity {
+    let looks: Vec<Look> = ours.iter().map(|t| Look::from(resolver.resolve(&t.scopes))).collect();
+    let mut at: HashMap<(usize, usize), usize> = HashMap::new();
+    for (index, token) in ours.iter().enumerate() {
+        for offset in 0..token.text.chars().count() {
+            at.insert((token.line, token.column + offset), index);
+        }
+    }
+    let fallback = Look::from(resolver.resolve(&[] as &[&str]));
+
+    let mut parity = SampleParity { path: path.to_path_buf(), chars: 0, mismatches: Vec::new() };
+    for token in shiki {
+        let shiki_look = token.look();
+        for (offset, ch) in token.text.chars().enumerate() {
+            if ch.is_whitespace() {
+                continue;
+            }
+            parity.chars += 1;
+            let column = token.column + offset;
+            let (scopes, look) = match at.get(&(token.line, column)) {
+                Some(&index) => (ours[index].scopes.as_slice(), &looks[index]),
+                None => (&[] as &[String], &fallback),
+            };
+            let kind = if *look != shiki_look {
+                Kind::Style
+            } else if scopes != token.scopes {
+                Kind::Scope
+            } else {
+                continue;
+            };
+            let extends = parity.mismatches.last_mut().filter(|m| {
+                m.kind == kind
+                    && m.line == token.line
+                    && m.column + m.text.chars().count() == column
+                    && m.ours == scopes
+                    && m.shiki == token.scopes
+            });
+            match extends {
+                Some(m) => m.text.push(ch),
+                None => parity.mismatches.push(Mismatch {
+                    kind,
+                    line: token.line,
+                    column,
+                    text: ch.to_string(),
+                    ours: scopes.to_vec(),
+                    shiki: token.scopes.clone(),
+                    our_look: look.clone(),
+                    shiki_look: shiki_look.clone(),
+                }),
+            }
+        }
+    }
+    parity
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::theme::Theme;
+
+    #[test]
+    fn reports_runs_of_differently_styled_characters() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#ffffff", "background": "#000000" } },
+                { "scope": "keyword", "settings": { "foreground": "#ff8fa3" } }
+            ]
+        }))
+        .unwrap();
+        let resolver = Resolver::new(&theme);
+        let token = |column: usize, text: &str, scopes: &[&str]| Token {
+            line: 1,
+            column,
+            text: text.to_owned(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        };
+        let shiki = |column: usize, text: &str, scopes: &[&str], foreground: &str| ShikiToken {
+            line: 1,
+            column,
+            text: text.to_owned(),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            foreground: foreground.to_owned(),
+            font_style: FontStyle::NONE,
+        };
+        // `let x` where Shiki reads `let` as a keyword and we do not, and both
+        // style `x` alike under different scopes
+        let ours = [token(1, "let", &["source", "storage"]), token(4, " ", &["source"]), token(5, "x", &["source"])];
+        let theirs = [
+            shiki(1, "let", &["source", "keyword"], "#FF8FA3"),
+            shiki(4, " ", &["source"], "#FFFFFF"),
+            shiki(5, "x", &["source", "variable"], "#FFFFFF"),
+        ];
+
+        let parity = compare(Path::new("t.rs"), &resolver, &ours, &theirs);
+        assert_eq!(parity.chars, 4);
+        assert_eq!(parity.mismatches.len(), 2);
+        assert_eq!((parity.mismatches[0].kind, parity.mismatches[0].text.as_str()), (Kind::Style, "let"));
+        assert_eq!(parity.mismatches[0].shiki_look.foreground, "#ff8fa3");
+        assert_eq!((parity.mismatches[1].kind, parity.mismatches[1].column), (Kind::Scope, 5));
+        assert_eq!((parity.count(Kind::Style), parity.count(Kind::Scope)), (3, 1));
+    }
+}
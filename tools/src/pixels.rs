@@ -0,0 +1,92 @@
+//! Pixel comparison of preview screenshots.
+
+/// An 8-bit RGBA image, rows top to bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Image {
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        self.rgba.get(i..i + 4).map(|p| [p[0], p[1], p[2], p[3]])
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PixelDiff {
+    pub changed: usize,
+    pub total: usize,
+    /// The baseline dimmed, with changed pixels in solid red.
+    pub mask: Image,
+}
+
+impl PixelDiff {
+    /// Fraction of pixels that changed.
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.changed as f64 / self.total as f64
+        }
+    }
+}
+
+/// Compares two screenshots pixel by pixel. A pixel changed when any channel
+/// differs by more than `tolerance`, which absorbs antialiasing noise. When
+/// the sizes differ, the area covered by only one image counts as changed.
+pub fn diff(before: &Image, after: &Image, tolerance: u8) -> PixelDiff {
+    let (width, height) = (before.width.max(after.width), before.height.max(after.height));
+    let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+    let mut changed = 0;
+    for y in 0..height {
+        for x in 0..width {
+            let same = match (before.pixel(x, y), after.pixel(x, y)) {
+                (Some(a), Some(b)) => a.iter().zip(b).all(|(a, b)| a.abs_diff(b) <= tolerance),
+                _ => false,
+            };
+            if same {
+                let [r, g, b, _] = before.pixel(x, y).unwrap_or_default();
+                rgba.extend([r / 3 + 170, g / 3 + 170, b / 3 + 170, 255]);
+            } else {
+                changed += 1;
+                rgba.extend([255, 0, 0, 255]);
+            }
+        }
+    }
+    PixelDiff { changed, total: width as usize * height as usize, mask: Image { width, height, rgba } }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32, color: [u8; 4]) -> Image {
+        Image { width, height, rgba: color.repeat(width as usize * height as usize) }
+    }
+
+    #[test]
+    fn counts_pixels_beyond_tolerance() {
+        let before = solid(4, 2, [10, 20, 30, 255]);
+        let mut after = solid(4, 2, [12, 20, 30, 255]);
+        assert_eq!(diff(&before, &after, 2).changed, 0);
+
+        after.rgba[0..4].copy_from_slice(&[200, 20, 30, 255]);
+        let result = diff(&before, &after, 2);
+        assert_eq!((result.changed, result.total), (1, 8));
+        assert_eq!(result.mask.pixel(0, 0), Some([255, 0, 0, 255]));
+        assert_ne!(result.mask.pixel(1, 0), Some([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn size_changes_count_as_changed() {
+        let result = diff(&solid(2, 2, [0; 4]), &solid(2, 3, [0; 4]), 0);
+        assert_eq!((result.changed, result.total), (2, 6));
+        assert!((result.ratio() - 1.0 / 3.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,119 @@
+//! Self-contained HTML page showing every sample in every theme.
+
+use std::fmt::Write;
+
+use crate::html::{self, escape};
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::Result;
+
+/// Approximates the `.znc` code-block rules from zenn-content-css.
+pub(crate) const STYLE: &str = r#"
+body { margin: 0; padding: 2rem; background: #f5f6fa; color: #1a2638; font-family: -apple-system, BlinkMacSystemFont, "Hiragino Kaku Gothic ProN", sans-serif; }
+h1 { font-size: 1.5rem; }
+nav { columns: 4 12rem; margin-bottom: 2rem; font-size: .9rem; }
+nav a { display: block; color: #3ea8ff; text-decoration: none; }
+section { margin-bottom: 2.5rem; }
+h2 { font-size: 1.05rem; margin: 0 0 .5rem; }
+.themes { display: grid; grid-template-columns: repeat(auto-fit, minmax(32rem, 1fr)); gap: 1rem; }
+.theme-name { font-size: .8rem; color: #65717b; margin-bottom: .25rem; }
+.note { font-size: .8rem; color: #b4572f; }
+.znc pre { margin: 0; padding: 1.1rem; border-radius: 14px; overflow: auto; max-height: 40rem; font-size: .9em; line-height: 1.5; word-break: normal; word-wrap: normal; }
+.znc code { font-family: SFMono-Regular, Consolas, "Liberation Mono", Menlo, monospace; -webkit-font-smoothing: antialiased; }
+"#;
+
+/// Shows the `data-scope` and `data-rule` of the token under the pointer,
+/// included when a theme sets [`html::Options::inspect`]. The overlay lives
+/// outside `<body>`, so it survives `tools serve` swapping the body on
+/// reload.
+const INSPECTOR: &str = r#"<style>
+#inspector { position: fixed; z-index: 10; max-width: 36rem; padding: .5rem .75rem; border-radius: 6px; background: #0d1117ee; color: #e6edf3; font: .75rem/1.5 SFMono-Regular, Consolas, "Liberation Mono", Menlo, monospace; pointer-events: none; white-space: pre-wrap; }
+#inspector .rule { color: #ffc56d; }
+[data-scope]:hover { outline: 1px solid #3ea8ff; }
+</style>
+<script>
+(() => {
+  const inspector = document.createElement("div");
+  inspector.id = "inspector";
+  inspector.hidden = true;
+  document.documentElement.append(inspector);
+  document.addEventListener("mousemove", (event) => {
+    const token = event.target.closest && event.target.closest("[data-scope]");
+    if (!token) {
+      inspector.hidden = true;
+      return;
+    }
+    const rule = document.createElement("div");
+    rule.className = "rule";
+    rule.textContent = `${token.style.color} ← ${token.dataset.rule}`;
+    const scopes = document.createElement("div");
+    scopes.textContent = token.dataset.scope.split(" ").join("\n");
+    inspector.replaceChildren(rule, scopes);
+    inspector.hidden = false;
+    const x = Math.min(event.clientX + 12, window.innerWidth - inspector.offsetWidth - 8);
+    inspector.style.left = `${Math.max(x, 8)}px`;
+    inspector.style.top = `${event.clientY + 16}px`;
+  });
+})();
+</script>
+"#;
+
+pub struct PreviewTheme<'a> {
+    pub name: &'a str,
+    pub resolver: &'a Resolver,
+    /// Bracket colors and inspector attributes for this theme's blocks.
+    pub options: html::Options,
+}
+
+pub fn render(themes: &[PreviewTheme<'_>], tokenizer: &Tokenizer, samples: &[Sample]) -> Result<String> {
+    let tokenized = tokenizer.tokenize_all(samples)?;
+    let tokenized: Vec<(&Sample, Option<&[Token]>)> =
+        samples.iter().zip(&tokenized).map(|(s, t)| (s, t.as_deref())).collect();
+    Ok(render_tokenized(themes, &tokenized))
+}
+
+/// [`render`] for samples already tokenized; `None` for those without a
+/// grammar.
+pub fn render_tokenized(themes: &[PreviewTheme<'_>], samples: &[(&Sample, Option<&[Token]>)]) -> String {
+    let inspector = if themes.iter().any(|t| t.options.inspect) { INSPECTOR } else { "" };
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"ja\">\n<head>\n<meta charset=\"utf-8\">\n<title>Zenn theme preview</title>\n<style>{STYLE}</style>\n{inspector}</head>\n<body>\n<h1>Zenn theme preview</h1>\n<nav>\n"
+    );
+    for (sample, _) in samples {
+        let path = sample.path.display().to_string();
+        let _ = writeln!(out, r##"<a href="#{0}">{0}</a>"##, escape(&path));
+    }
+    out.push_str("</nav>\n");
+
+    for &(sample, tokens) in samples {
+        let path = sample.path.display().to_string();
+        let _ = write!(out, "<section id=\"{0}\">\n<h2>{0}</h2>\n", escape(&path));
+        if tokens.is_none() {
+            out.push_str("<p class=\"note\">No bundled grammar; shown unhighlighted.</p>\n");
+        }
+        out.push_str("<div class=\"themes\">\n");
+        for theme in themes {
+            let block = match tokens {
+                Some(tokens) => html::highlight_with(
+                    theme.resolver,
+                    tokens,
+                    sample.source.lines().count(),
+                    theme.name,
+                    theme.options,
+                ),
+                None => html::plain(theme.resolver, &sample.source, theme.name),
+            };
+            let _ = write!(
+                out,
+                "<div>\n<div class=\"theme-name\">{}</div>\n<div class=\"znc\">{block}</div>\n</div>\n",
+                escape(theme.name)
+            );
+        }
+        out.push_str("</div>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
@@ -0,0 +1,90 @@
+//! Maps colors onto the xterm 256-color palette, for terminals without
+//! truecolor.
+//!
+//! Only indices `16..=255` are used: the 6×6×6 cube and the gray ramp are
+//! the same everywhere, while `0..16` are whatever the user's scheme says.
+//! Colors are assigned together, closest pairs first, so two different
+//! colors never share an index even when one index is nearest to both.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::colors::Rgba;
+
+/// Channel levels of the 6×6×6 cube.
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The color xterm shows for `index`, for indices `16..=255`.
+pub fn xterm(index: u8) -> Rgba {
+    let channel = |v: u8| f64::from(v) / 255.0;
+    match index {
+        16..=231 => {
+            let i = usize::from(index - 16);
+            Rgba::opaque(channel(CUBE[i / 36]), channel(CUBE[i / 6 % 6]), channel(CUBE[i % 6]))
+        }
+        _ => {
+            let gray = channel(8 + 10 * index.saturating_sub(232));
+            Rgba::opaque(gray, gray, gray)
+        }
+    }
+}
+
+/// The perceptually nearest index, ignoring alpha.
+pub fn nearest(color: Rgba) -> u8 {
+    (16..=255).min_by(|&a, &b| color.oklab_distance(xterm(a)).total_cmp(&color.oklab_distance(xterm(b)))).unwrap_or(16)
+}
+
+/// One index per color, in order. Equal colors share an index; different
+/// ones never do.
+pub fn assign(colors: &[Rgba]) -> Vec<u8> {
+    let distinct: Vec<String> = colors.iter().map(|c| c.to_hex()).collect::<BTreeSet<_>>().into_iter().collect();
+    let mut pairs: Vec<(f64, usize, u8)> = Vec::new();
+    for (i, hex) in distinct.iter().enumerate() {
+        let color = Rgba::parse(hex).unwrap_or(Rgba::BLACK);
+        pairs.extend((16..=255).map(|index| (color.oklab_distance(xterm(index)), i, index)));
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut chosen: BTreeMap<&str, u8> = BTreeMap::new();
+    let mut used = [false; 256];
+    for (_, i, index) in pairs {
+        if !used[usize::from(index)] && !chosen.contains_key(distinct[i].as_str()) {
+            chosen.insert(&distinct[i], index);
+            used[usize::from(index)] = true;
+        }
+    }
+    colors.iter().map(|c| chosen[c.to_hex().as_str()]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contrast::AAA_NORMAL;
+    use crate::palette::{self, Palette, Role};
+
+    #[test]
+    fn matches_the_xterm_palette() {
+        assert_eq!(xterm(16).to_hex(), "#000000");
+        assert_eq!(xterm(196).to_hex(), "#ff0000");
+        assert_eq!(xterm(231).to_hex(), "#ffffff");
+        assert_eq!(xterm(232).to_hex(), "#080808");
+        assert_eq!(xterm(255).to_hex(), "#eeeeee");
+        assert_eq!(nearest(Rgba::parse("#fe0101").unwrap()), 196);
+        assert_eq!(nearest(Rgba::parse("#777777").unwrap()), 243);
+    }
+
+    #[test]
+    fn distinct_roles_keep_distinct_indices() {
+        let zenn = Palette::zenn();
+        for palette in [zenn, palette::high_contrast(&zenn, AAA_NORMAL)] {
+            let colors: Vec<Rgba> = Role::ALL.iter().map(|&role| palette.get(role)).collect();
+            let indices = assign(&colors);
+            assert_eq!(indices.iter().collect::<BTreeSet<_>>().len(), Role::ALL.len(), "{indices:?}");
+        }
+
+        // Both are nearest to 231, but only one can have it
+        let close = [Rgba::parse("#ffffff").unwrap(), Rgba::parse("#fdfdfd").unwrap(), Rgba::WHITE];
+        let indices = assign(&close);
+        assert_eq!(indices[0], indices[2]);
+        assert_ne!(indices[0], indices[1]);
+    }
+}
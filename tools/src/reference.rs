@@ -0,0 +1,219 @@
+//! The token reference: every theme rule with its selectors, its resolved
+//! style and the shortest sample line it styles, as one HTML page built
+//! from the theme and the samples rather than written by hand.
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::html::{self, escape};
+use crate::i18n::{label, Lang, Message};
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::selector::Selector;
+use crate::theme::Theme;
+use crate::tokenizer::Token;
+
+/// What the samples show of one `tokenColors` rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleReference {
+    pub rule: usize,
+    /// Each of the rule's selectors, with the tokens it was the match for.
+    pub selectors: Vec<(Selector, usize)>,
+    pub example: Option<Example>,
+}
+
+/// A sample line holding a token the rule styles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub path: PathBuf,
+    pub line: usize,
+    /// The line's tokens, renumbered as line 1 and without indentation.
+    pub tokens: Vec<Token>,
+    /// The token on the line the rule styles.
+    pub token: Token,
+}
+
+/// One entry per scoped rule, in `tokenColors` order. `tokenized` holds
+/// each sample's tokens, `None` where it has no grammar.
+pub fn collect(theme: &Theme, samples: &[Sample], tokenized: &[Option<Vec<Token>>]) -> Vec<RuleReference> {
+    let resolver = Resolver::new(theme);
+    let mut entries: Vec<RuleReference> = theme
+        .token_colors
+        .iter()
+        .enumerate()
+        .map(|(rule, token_color)| RuleReference {
+            rule,
+            selectors: token_color.selectors().into_iter().filter_map(Selector::parse).map(|s| (s, 0)).collect(),
+            example: None,
+        })
+        .collect();
+
+    for (sample, tokens) in samples.iter().zip(tokenized) {
+        let Some(tokens) = tokens else { continue };
+        for line in tokens.chunk_by(|a, b| a.line == b.line) {
+            for token in line.iter().filter(|t| !t.text.trim().is_empty()) {
+                let style = resolver.resolve(&token.scopes);
+                let mut rules = [style.foreground_rule, style.background_rule, style.font_style_rule];
+                rules.sort();
+                let mut previous = None;
+                for rule in rules.into_iter().flatten() {
+                    if previous == Some(rule) {
+                        continue;
+                    }
+                    previous = Some(rule);
+                    let entry = &mut entries[rule];
+                    if let Some(matched) = resolver.matched_selector(rule, &token.scopes) {
+                        if let Some((_, count)) = entry.selectors.iter_mut().find(|(s, _)| s == matched) {
+                            *count += 1;
+                        }
+                    }
+                    let better = entry.example.as_ref().is_none_or(|e| rank(token, line) < rank(&e.token, &e.tokens));
+                    if better {
+                        entry.example = Some(example(sample, line, token));
+                    }
+                }
+            }
+        }
+    }
+    entries.retain(|entry| !theme.token_colors[entry.rule].is_default());
+    entries
+}
+
+fn example(sample: &Sample, line: &[Token], token: &Token) -> Example {
+    let mut tokens: Vec<Token> =
+        line.iter().skip_while(|t| t.text.trim().is_empty()).map(|t| Token { line: 1, ..t.clone() }).collect();
+    if let Some(first) = tokens.first_mut() {
+        first.text = first.text.trim_start().to_owned();
+    }
+    Example { path: sample.path.clone(), line: token.line, tokens, token: token.clone() }
+}
+
+/// Orders candidate examples: a word before bare punctuation, so a comment
+/// rule shows a comment rather than a lone `#`, then the shortest line.
+fn rank(token: &Token, line: &[Token]) -> (bool, usize) {
+    let width = line.iter().map(|t| t.text.as_str()).collect::<String>().trim().chars().count();
+    (!token.text.chars().any(char::is_alphanumeric), width)
+}
+
+/// The page, with headings in `lang`.
+pub fn to_html(theme: &Theme, entries: &[RuleReference], lang: Lang) -> String {
+    let resolver = Resolver::new(theme);
+    let name = theme.display_name.as_deref().unwrap_or(&theme.name);
+    let title = escape(&Message::ReferenceTitle { name }.text(lang));
+    let color =
+        |color: &str| format!("<span class=\"swatch\" style=\"background:{0}\"></span><code>{0}</code>", escape(color));
+
+    let mut out = String::new();
+    let tag = lang.tag();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"{tag}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{STYLE}"
+    );
+    // Swatches are framed in the code background, as the colors are seen
+    let _ = write!(out, ".swatch {{ border-color: {}; }}\n</style>\n</head>\n<body>\n", resolver.default_background());
+    let _ = writeln!(out, "<h1>{title}</h1>\n<section id=\"defaults\">\n<h2>{}</h2>\n<dl>", label("Defaults", lang));
+    let _ = writeln!(out, "<dt>{}</dt><dd>{}</dd>", label("Foreground", lang), color(resolver.default_foreground()));
+    let _ = writeln!(out, "<dt>{}</dt><dd>{}</dd>", label("Background", lang), color(resolver.default_background()));
+    out.push_str("</dl>\n</section>\n");
+
+    for entry in entries {
+        let settings = &theme.token_colors[entry.rule].settings;
+        let _ = writeln!(out, "<section id=\"rule-{0}\">\n<h2>tokenColors[{0}]</h2>\n<dl>", entry.rule);
+        let _ = write!(out, "<dt>{}</dt>\n<dd><ul>", label("Selectors", lang));
+        for (selector, count) in &entry.selectors {
+            let class = if *count == 0 { " class=\"unused\"" } else { "" };
+            let _ = write!(out, "<li{class}><code>{}</code> ×{count}</li>", escape(&selector.to_string()));
+        }
+        out.push_str("</ul></dd>\n");
+        for (heading, value) in [("Foreground", &settings.foreground), ("Background", &settings.background)] {
+            if let Some(value) = value {
+                let _ = writeln!(out, "<dt>{}</dt><dd>{}</dd>", label(heading, lang), color(value.as_str()));
+            }
+        }
+        if let Some(font_style) = settings.font_style {
+            let shown = font_style.to_string();
+            let shown = if shown.is_empty() { "none" } else { &shown };
+            let _ = writeln!(out, "<dt>{}</dt><dd><code>{shown}</code></dd>", label("Font style", lang));
+        }
+        let _ = write!(out, "<dt>{}</dt>\n<dd>", label("Example", lang));
+        match &entry.example {
+            Some(example) => {
+                out.push_str(&html::highlight(&resolver, &example.tokens, 1, "reference"));
+                let _ = write!(
+                    out,
+                    "<p class=\"where\">{}:{} <code>{}</code> <code>{}</code></p>",
+                    escape(&example.path.display().to_string()),
+                    example.line,
+                    escape(example.token.text.trim()),
+                    escape(&example.token.scopes.join(" "))
+                );
+            }
+            None => {
+                let _ = write!(out, "<p class=\"where\">{}</p>", label("No sample token uses this rule", lang));
+            }
+        }
+        out.push_str("</dd>\n</dl>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+const STYLE: &str = "body { font-family: -apple-system, BlinkMacSystemFont, sans-serif; font-size: 14px; margin: 1rem auto; max-width: 60rem; }
+section { border-top: 1px solid #d6e3ed; padding: 0.5rem 0; }
+h2 { font-size: 1rem; font-family: monospace; }
+dl { display: grid; grid-template-columns: 8rem 1fr; gap: 4px 1rem; margin: 0; }
+dt { color: #65717b; }
+dd { margin: 0; }
+dd ul { list-style: none; margin: 0; padding: 0; }
+li.unused { color: #9aa5af; }
+pre.shiki { padding: 0.5rem 1rem; border-radius: 4px; margin: 0; overflow-x: auto; }
+p.where { margin: 2px 0 0; color: #65717b; font-size: 12px; }
+.swatch { display: inline-block; width: 1em; height: 1em; margin-right: 4px; vertical-align: middle;
+  border: 2px solid; outline: 1px solid #d6e3ed; }
+";
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::tokenizer::Tokenizer;
+
+    #[test]
+    fn each_rule_gets_its_shortest_sample_line() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#eeeeee", "background": "#111111" } },
+                { "scope": ["keyword", "markup.heading"], "settings": { "foreground": "#ff0000", "fontStyle": "bold" } },
+                { "scope": "comment", "settings": { "foreground": "#888888" } }
+            ]
+        }))
+        .unwrap();
+        let source = "fn main() {\n    let long_name = 1 + 2;\n    if x {}\n}\n";
+        let samples = [Sample { path: PathBuf::from("a.rs"), source: source.to_owned() }];
+        let tokenized = Tokenizer::new().tokenize_all(&samples).unwrap();
+        let entries = collect(&theme, &samples, &tokenized);
+
+        assert_eq!(entries.iter().map(|e| e.rule).collect::<Vec<_>>(), [1, 2]);
+        let keyword = &entries[0];
+        assert!(keyword.selectors[0].1 > 0);
+        assert_eq!(keyword.selectors[1].1, 0);
+        let example = keyword.example.as_ref().unwrap();
+        assert_eq!((example.line, example.token.text.as_str()), (3, "if"));
+        assert_eq!(example.tokens[0].text, "if");
+        assert!(entries[1].example.is_none());
+
+        let html = to_html(&theme, &entries, Lang::En);
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">"));
+        assert!(html.contains("<section id=\"rule-1\">\n<h2>tokenColors[1]</h2>"));
+        assert!(html.contains("<li class=\"unused\"><code>markup.heading</code> ×0</li>"));
+        assert!(html.contains("<dt>Font style</dt><dd><code>bold</code></dd>"));
+        assert!(html.contains("<p class=\"where\">a.rs:3 <code>if</code>"));
+        assert!(html.contains("No sample token uses this rule"));
+        let html = to_html(&theme, &entries, Lang::Ja);
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"ja\">"));
+        assert!(html.contains("<title>t のトークン一覧</title>"));
+        assert!(html.contains("<dt>フォントスタイル</dt>"));
+    }
+}
@@ -0,0 +1,288 @@
+//! A single-file HTML page of the theme's health: the unthemed scopes of
+//! the audit, contrast, rules no sample exercises and foregrounds too close
+//! to tell apart, each as a table that sorts by any column and filters as
+//! you type.
+
+use std::fmt::Write;
+
+use crate::audit::{self, FileReport};
+use crate::contrast::{self, Entry};
+use crate::distance::{Checker, NearPair};
+use crate::html::escape;
+use crate::i18n::{label, Lang, Message};
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::stats::{self, Stats};
+use crate::theme::Theme;
+use crate::tokenizer::Token;
+
+/// Everything the report shows, gathered from one tokenization of the
+/// samples.
+#[derive(Debug, Clone)]
+pub struct Health {
+    pub audit: audit::Report,
+    pub contrast: Vec<Entry>,
+    pub threshold: f64,
+    pub stats: Stats,
+    pub near: Vec<NearPair>,
+    pub min_distance: f64,
+}
+
+impl Health {
+    /// `tokenized` holds each sample's tokens, `None` where it has no
+    /// grammar, as [`Tokenizer::tokenize_all`](crate::tokenizer::Tokenizer::tokenize_all)
+    /// returns them.
+    pub fn collect(
+        theme: &Theme,
+        samples: &[Sample],
+        tokenized: &[Option<Vec<Token>>],
+        threshold: f64,
+        min_distance: f64,
+    ) -> Self {
+        let resolver = Resolver::new(theme);
+        let mut checker = Checker::new(&resolver);
+        let mut files = Vec::new();
+        for (sample, tokens) in samples.iter().zip(tokenized) {
+            let file = match tokens {
+                Some(tokens) => {
+                    checker.add(&sample.path, tokens);
+                    FileReport::Audited { unthemed: audit::file(&resolver, tokens) }
+                }
+                None => FileReport::NoGrammar,
+            };
+            files.push((sample.path.clone(), file));
+        }
+        Self {
+            audit: audit::Report { files },
+            contrast: contrast::check(theme),
+            threshold,
+            stats: stats::count(theme, tokenized.iter().flatten().map(Vec::as_slice)),
+            near: checker.below(min_distance),
+            min_distance,
+        }
+    }
+
+    pub fn failing_contrast(&self) -> usize {
+        self.contrast.iter().filter(|e| e.ratio < self.threshold).count()
+    }
+
+    /// The page, with headings in `lang`.
+    pub fn to_html(&self, theme: &Theme, lang: Lang) -> String {
+        let resolver = Resolver::new(theme);
+        let background = resolver.default_background();
+        let swatch = |color: &str| format!("<span class=\"swatch\" style=\"background:{}\"></span>", escape(color));
+        let name = theme.display_name.as_deref().unwrap_or(&theme.name);
+        let title = escape(&Message::ReportTitle { name }.text(lang));
+        let dead: Vec<usize> = self.stats.dead_rules(theme).collect();
+
+        let mut out = String::new();
+        let tag = lang.tag();
+        let _ = write!(
+            out,
+            "<!DOCTYPE html>\n<html lang=\"{tag}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{STYLE}"
+        );
+        // Swatches are framed in the code background, as the colors are seen
+        let _ = write!(out, ".swatch {{ border-color: {background}; }}\n</style>\n</head>\n<body>\n");
+        let _ = writeln!(out, "<h1>{title}</h1>\n<ul class=\"summary\">");
+        for (id, message) in [
+            ("coverage", Message::Unthemed { scopes: self.audit.unthemed_count(), files: self.audit.files.len() }),
+            (
+                "contrast",
+                Message::ContrastFailing {
+                    failing: self.failing_contrast(),
+                    total: self.contrast.len(),
+                    threshold: self.threshold,
+                },
+            ),
+            ("rules", Message::DeadRules { rules: dead.len(), total: theme.token_colors.len() }),
+            ("distance", Message::NearPairs { pairs: self.near.len(), threshold: self.min_distance }),
+        ] {
+            let _ = writeln!(out, "<li><a href=\"#{id}\">{}</a></li>", escape(&message.text(lang)));
+        }
+        out.push_str("</ul>\n");
+
+        section(
+            &mut out,
+            lang,
+            "coverage",
+            "Unthemed scopes",
+            &["Scope", "File", "Line", "Column", "Tokens", "Example"],
+        );
+        for (path, file) in &self.audit.files {
+            let FileReport::Audited { unthemed } = file else { continue };
+            for (scope, e) in unthemed {
+                let _ = writeln!(
+                    out,
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><code>{}</code></td></tr>",
+                    escape(scope),
+                    escape(&path.display().to_string()),
+                    e.line,
+                    e.column,
+                    e.count,
+                    escape(&e.example)
+                );
+            }
+        }
+        out.push_str("</tbody>\n</table>\n");
+
+        section(&mut out, lang, "contrast", "Contrast", &["Foreground", "Background", "Ratio", "", "Rule"]);
+        for e in &self.contrast {
+            let (class, mark) = if e.ratio < self.threshold { (" class=\"fail\"", "FAIL") } else { ("", "ok") };
+            let _ = writeln!(
+                out,
+                "<tr{class}><td>{}{}</td><td>{}{}</td><td data-sort=\"{:.4}\">{:.2}:1</td><td>{mark}</td><td>{}</td></tr>",
+                swatch(&e.foreground),
+                escape(&e.foreground),
+                swatch(&e.background),
+                escape(&e.background),
+                e.ratio,
+                e.ratio,
+                escape(&e.label)
+            );
+        }
+        out.push_str("</tbody>\n</table>\n");
+
+        section(&mut out, lang, "rules", "Rules", &["#", "Foreground", "Selectors", "Tokens"]);
+        for (index, rule) in theme.token_colors.iter().enumerate() {
+            let class = if dead.contains(&index) { " class=\"fail\"" } else { "" };
+            let foreground = rule.settings.foreground.as_ref().map(|c| c.as_str());
+            let _ = writeln!(
+                out,
+                "<tr{class}><td>{index}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                foreground.map(|c| format!("{}{}", swatch(c), escape(c))).unwrap_or_default(),
+                escape(&rule.selectors().join(", ")),
+                self.stats.rules.get(index).copied().unwrap_or_default()
+            );
+        }
+        out.push_str("</tbody>\n</table>\n");
+
+        section(
+            &mut out,
+            lang,
+            "distance",
+            "Color distance",
+            &["Color", "Scope", "Color", "Scope", "ΔE", "Lines", "First"],
+        );
+        for pair in &self.near {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}{}</td><td><code>{}</code></td><td>{}{}</td><td><code>{}</code></td>\
+                 <td data-sort=\"{:.5}\">{:.3}</td><td>{}</td><td>{}:{}</td></tr>",
+                swatch(&pair.a),
+                escape(&pair.a),
+                escape(&pair.a_scope),
+                swatch(&pair.b),
+                escape(&pair.b),
+                escape(&pair.b_scope),
+                pair.distance,
+                pair.distance,
+                pair.lines,
+                escape(&pair.path.display().to_string()),
+                pair.line
+            );
+        }
+        out.push_str("</tbody>\n</table>\n");
+
+        let _ = write!(out, "<script>\n{SCRIPT}</script>\n</body>\n</html>\n");
+        out
+    }
+}
+
+/// Opens a section's heading, filter box and table, up to its `<tbody>`.
+fn section(out: &mut String, lang: Lang, id: &str, title: &'static str, columns: &[&'static str]) {
+    let _ = writeln!(
+        out,
+        "<h2 id=\"{id}\">{}</h2>\n<input type=\"search\" placeholder=\"{}\" data-filter=\"{id}-table\">\n\
+         <table class=\"sortable\" id=\"{id}-table\">\n<thead>\n<tr>{}</tr>\n</thead>\n<tbody>",
+        label(title, lang),
+        label("Filter", lang),
+        columns.iter().map(|c| format!("<th>{}</th>", label(c, lang))).collect::<String>()
+    );
+}
+
+const STYLE: &str =
+    "body { font-family: -apple-system, BlinkMacSystemFont, sans-serif; font-size: 13px; margin: 1rem; }
+table { border-collapse: collapse; margin-bottom: 2rem; }
+th, td { border: 1px solid #d6e3ed; padding: 2px 6px; text-align: left; }
+th { cursor: pointer; user-select: none; background: #f5f6fa; }
+th[data-dir=asc]::after { content: \" ▲\"; }
+th[data-dir=desc]::after { content: \" ▼\"; }
+tr.fail { background: #fff1f0; }
+.swatch { display: inline-block; width: 1em; height: 1em; margin-right: 4px; vertical-align: middle;
+  border: 2px solid; outline: 1px solid #d6e3ed; }
+input[type=search] { margin-bottom: 4px; }
+";
+
+/// Sorts a table by the clicked column, numerically when both cells are
+/// numbers (or carry a `data-sort` value), and hides rows not containing
+/// the filter text.
+const SCRIPT: &str = r#"for (const table of document.querySelectorAll("table.sortable")) {
+  const headers = [...table.tHead.rows[0].cells];
+  headers.forEach((th, i) => th.addEventListener("click", () => {
+    const dir = th.dataset.dir === "asc" ? -1 : 1;
+    headers.forEach((h) => delete h.dataset.dir);
+    th.dataset.dir = dir === 1 ? "asc" : "desc";
+    const key = (row) => row.cells[i].dataset.sort ?? row.cells[i].textContent;
+    const rows = [...table.tBodies[0].rows].sort((a, b) => {
+      const [x, y] = [key(a), key(b)];
+      const [nx, ny] = [Number(x), Number(y)];
+      return dir * (x !== "" && y !== "" && !isNaN(nx) && !isNaN(ny) ? nx - ny : x.localeCompare(y));
+    });
+    table.tBodies[0].append(...rows);
+  }));
+}
+for (const input of document.querySelectorAll("input[data-filter]")) {
+  input.addEventListener("input", () => {
+    const needle = input.value.toLowerCase();
+    for (const row of document.getElementById(input.dataset.filter).tBodies[0].rows) {
+      row.hidden = !row.textContent.toLowerCase().includes(needle);
+    }
+  });
+}
+"#;
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::tokenizer::Tokenizer;
+
+    #[test]
+    fn report_lists_every_kind_of_finding() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#eeeeee", "background": "#111111" } },
+                { "scope": "keyword", "settings": { "foreground": "#222222" } },
+                { "scope": "markup.heading", "settings": { "foreground": "#00ff00" } },
+                { "scope": "storage.type", "settings": { "foreground": "#232323" } }
+            ]
+        }))
+        .unwrap();
+        let samples = [Sample { path: PathBuf::from("a<b>.rs"), source: "fn main() { let x = 1; }\n".to_owned() }];
+        let tokenized = Tokenizer::new().tokenize_all(&samples).unwrap();
+        let health = Health::collect(&theme, &samples, &tokenized, 4.5, 0.05);
+
+        assert!(health.audit.unthemed_count() > 0);
+        assert_eq!(health.failing_contrast(), 2);
+        assert_eq!(health.stats.dead_rules(&theme).collect::<Vec<_>>(), [2]);
+        assert_eq!((health.near[0].a.as_str(), health.near[0].b.as_str()), ("#222222", "#232323"));
+
+        let html = health.to_html(&theme, Lang::En);
+        assert!(html.contains("<html lang=\"en\">"));
+        assert!(html.contains("<td>a&lt;b&gt;.rs</td>"));
+        assert!(html.contains("<tr class=\"fail\"><td>2</td><td><span class=\"swatch\" style=\"background:#00ff00\">"));
+        assert!(html.contains(".swatch { border-color: #111111; }"));
+        for id in ["coverage", "contrast", "rules", "distance"] {
+            assert!(html.contains(&format!("data-filter=\"{id}-table\"")));
+        }
+        let html = health.to_html(&theme, Lang::Ja);
+        assert!(html.contains("<html lang=\"ja\">"));
+        assert!(html.contains("<title>t の健康診断</title>"));
+        assert!(html.contains("<h2 id=\"coverage\">未着色のスコープ</h2>"));
+        assert!(html.contains("<th>スコープ</th>"));
+    }
+}
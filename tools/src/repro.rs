@@ -0,0 +1,141 @@
+//! The smallest piece of the samples that still tokenizes to a given
+//! scope on its own, for pasting into an issue about one token's styling.
+//!
+//! Candidates are windows of whole lines around each token with the scope,
+//! dedented. A window counts only if tokenizing it by itself produces the
+//! scope again, since many scopes depend on lines before them (an open
+//! string, an enclosing block). A window whose token with the scope is a
+//! word beats one that only has punctuation with it (`"abc"` over `";`),
+//! then the shorter window wins. Lines are tried in that order, so the
+//! search stops as soon as no remaining line could beat the best window.
+
+use std::path::PathBuf;
+
+use crate::samples::Sample;
+use crate::selector::matches_scope;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::Result;
+
+/// How many lines a window may span unless asked otherwise.
+pub const MAX_LINES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repro {
+    pub path: PathBuf,
+    /// First and last sample line of the window, 1-based.
+    pub lines: (usize, usize),
+    /// The window, dedented, ending in a newline.
+    pub snippet: String,
+    /// A token of the snippet with the scope, the first with a word in it
+    /// if any.
+    pub token: Token,
+}
+
+/// The best window of `samples`, at most `max_lines` long, that
+/// tokenizes to a scope matching `scope` (`keyword.control` matches
+/// `keyword.control.rust`). `tokenized` holds each sample's tokens, `None`
+/// where it has no grammar.
+pub fn find(
+    tokenizer: &Tokenizer,
+    samples: &[Sample],
+    tokenized: &[Option<Vec<Token>>],
+    scope: &str,
+    max_lines: usize,
+) -> Result<Option<Repro>> {
+    let has_scope = |token: &Token| token.scopes.iter().any(|s| matches_scope(s, scope));
+    let lines: Vec<Vec<&str>> = samples.iter().map(|s| s.source.lines().collect()).collect();
+    // (rank, sample, line) of every line with the scope
+    let mut candidates: Vec<((bool, usize), usize, usize)> = Vec::new();
+    for (index, tokens) in tokenized.iter().enumerate() {
+        let Some(tokens) = tokens else { continue };
+        for line in tokens.chunk_by(|a, b| a.line == b.line) {
+            let hits: Vec<&Token> = line.iter().filter(|t| has_scope(t)).collect();
+            if let Some(&first) = hits.first() {
+                let width = lines[index].get(first.line - 1).map_or(0, |l| l.trim().chars().count());
+                candidates.push(((!hits.iter().any(|t| is_word(t)), width), index, first.line));
+            }
+        }
+    }
+    candidates.sort_unstable();
+
+    let mut best: Option<((bool, usize), Repro)> = None;
+    for (rank, index, line) in candidates {
+        if best.as_ref().is_some_and(|(best_rank, _)| rank >= *best_rank) {
+            break;
+        }
+        let (sample, lines) = (&samples[index], &lines[index]);
+        let Some(syntax) = tokenizer.syntax_for(&sample.path) else { continue };
+        let mut windows: Vec<(usize, usize, String)> = Vec::new();
+        for span in 1..=max_lines.max(1) {
+            for first in line.saturating_sub(span - 1).max(1)..=line {
+                let last = first + span - 1;
+                if last > lines.len() {
+                    break;
+                }
+                windows.push((first, last, dedent(&lines[first - 1..last])));
+            }
+        }
+        windows.sort_by_key(|(.., snippet)| snippet.trim_end().chars().count());
+        for (first, last, snippet) in windows {
+            let len = snippet.trim_end().chars().count();
+            if best.as_ref().is_some_and(|(best_rank, _)| (false, len) >= *best_rank) {
+                break;
+            }
+            let tokens = tokenizer.tokenize(syntax, &sample.path, &snippet)?;
+            let mut hits = tokens.into_iter().filter(|t| has_scope(t));
+            let Some(first_hit) = hits.next() else { continue };
+            let token = match is_word(&first_hit) {
+                true => first_hit,
+                false => hits.find(is_word).unwrap_or(first_hit),
+            };
+            let rank = (!is_word(&token), len);
+            if best.as_ref().is_none_or(|(best_rank, _)| rank < *best_rank) {
+                best = Some((rank, Repro { path: sample.path.clone(), lines: (first, last), snippet, token }));
+            }
+        }
+    }
+    Ok(best.map(|(_, repro)| repro))
+}
+
+/// How many tokens in `tokenized` have a scope matching `scope`, to tell a
+/// scope the samples lack from one no window reproduces.
+pub fn occurrences(tokenized: &[Option<Vec<Token>>], scope: &str) -> usize {
+    tokenized.iter().flatten().flatten().filter(|t| t.scopes.iter().any(|s| matches_scope(s, scope))).count()
+}
+
+/// Whether `token` shows a word rather than bare punctuation, which makes
+/// for a clearer example of the scope.
+fn is_word(token: &Token) -> bool {
+    token.text.chars().any(char::is_alphanumeric)
+}
+
+/// `lines` without their common indentation, one per line.
+fn dedent(lines: &[&str]) -> String {
+    let indent =
+        lines.iter().filter(|l| !l.trim().is_empty()).map(|l| l.len() - l.trim_start().len()).min().unwrap_or(0);
+    lines.iter().map(|l| format!("{}\n", l.get(indent..).unwrap_or("").trim_end())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_shortest_line_that_reproduces_alone() {
+        let source =
+            "fn main() {\n    let long_name = if a { 1 } else { 2 };\n    while x {\n        if y {}\n    }\n}\n";
+        let samples = [Sample { path: PathBuf::from("a.rs"), source: source.to_owned() }];
+        let tokenizer = Tokenizer::new();
+        let tokenized = tokenizer.tokenize_all(&samples).unwrap();
+
+        let repro = find(&tokenizer, &samples, &tokenized, "keyword.control", MAX_LINES).unwrap().unwrap();
+        assert_eq!(repro.lines, (4, 4));
+        assert_eq!(repro.snippet, "if y {}\n");
+        assert_eq!(repro.token.text, "if");
+        assert!(repro.token.scopes.iter().any(|s| s.starts_with("keyword.control")));
+
+        assert_eq!(find(&tokenizer, &samples, &tokenized, "markup.heading", MAX_LINES).unwrap(), None);
+        assert_eq!(occurrences(&tokenized, "keyword.control"), 4);
+        assert_eq!(occurrences(&tokenized, "markup.heading"), 0);
+    }
+}
@@ -0,0 +1,495 @@
+//! Resolves scope stacks to styles the way vscode-textmate, and therefore
+//! Shiki, does.
+//!
+//! Rules are inserted into a trie keyed by the dot segments of each
+//! selector's target scope. Looking up a scope walks to the deepest existing
+//! node; its rules are ordered by specificity (target depth, then parent
+//! scope lengths, then parent count) and the first whose parents match wins.
+//! A node inherits unset fields from the plain rules of its ancestors, and
+//! each level of the scope stack inherits whatever its winning rule leaves
+//! unset from the level outside it.
+//!
+//! At `trace` level (`RUST_LOG=zenn_theme_tools::resolver=trace`) every
+//! resolution logs the candidate rules at each level and the rule that set
+//! each field.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use tracing::Level;
+
+use crate::selector::{parents_match, Selector};
+pub use crate::theme::FontStyle;
+use crate::theme::{Color, Settings, Theme, ThemeKind, BRACKET_PREFIX, UNEXPECTED_BRACKET};
+
+/// The final style of a token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolved<'a> {
+    pub foreground: &'a str,
+    pub background: &'a str,
+    pub font_style: FontStyle,
+    /// Index into `tokenColors` of the rule that set each field, or `None`
+    /// when the field came from the theme defaults.
+    pub foreground_rule: Option<usize>,
+    pub background_rule: Option<usize>,
+    pub font_style_rule: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Attr<T> {
+    value: T,
+    rule: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Style {
+    font_style: Option<Attr<FontStyle>>,
+    foreground: Option<Attr<String>>,
+    background: Option<Attr<String>>,
+}
+
+impl Style {
+    fn from_settings(settings: &Settings, rule: usize) -> Self {
+        let color = |value: &Option<Color>| value.as_ref().map(|c| Attr { value: c.to_string(), rule });
+        Self {
+            font_style: settings.font_style.map(|value| Attr { value, rule }),
+            foreground: color(&settings.foreground),
+            background: color(&settings.background),
+        }
+    }
+
+    fn overwrite(&mut self, other: &Style) {
+        if other.font_style.is_some() {
+            self.font_style.clone_from(&other.font_style);
+        }
+        if other.foreground.is_some() {
+            self.foreground.clone_from(&other.foreground);
+        }
+        if other.background.is_some() {
+            self.background.clone_from(&other.background);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TrieRule {
+    depth: usize,
+    parents: Vec<String>,
+    excludes: Vec<Selector>,
+    style: Style,
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    main: TrieRule,
+    with_parents: Vec<TrieRule>,
+    children: HashMap<String, Node>,
+    /// `with_parents` plus `main`, most specific first; filled by `finish`.
+    ordered: Vec<TrieRule>,
+}
+
+impl Node {
+    fn insert(&mut self, depth: usize, scope: &str, selector: &Selector, style: &Style) {
+        if scope.is_empty() {
+            self.insert_here(depth, selector, style);
+            return;
+        }
+        let (head, tail) = scope.split_once('.').unwrap_or((scope, ""));
+        if !self.children.contains_key(head) {
+            let child = Node {
+                main: self.main.clone(),
+                with_parents: self.with_parents.clone(),
+                children: HashMap::new(),
+                ordered: Vec::new(),
+            };
+            self.children.insert(head.to_owned(), child);
+        }
+        if let Some(child) = self.children.get_mut(head) {
+            child.insert(depth + 1, tail, selector, style);
+        }
+    }
+
+    fn insert_here(&mut self, depth: usize, selector: &Selector, style: &Style) {
+        if selector.parents.is_empty() && selector.excludes.is_empty() {
+            self.main.depth = depth;
+            self.main.style.overwrite(style);
+            return;
+        }
+        if let Some(rule) =
+            self.with_parents.iter_mut().find(|r| r.parents == selector.parents && r.excludes == selector.excludes)
+        {
+            rule.depth = depth;
+            rule.style.overwrite(style);
+            return;
+        }
+        let mut inherited = self.main.style.clone();
+        inherited.overwrite(style);
+        self.with_parents.push(TrieRule {
+            depth,
+            parents: selector.parents.clone(),
+            excludes: selector.excludes.clone(),
+            style: inherited,
+        });
+    }
+
+    fn finish(&mut self) {
+        self.ordered = self.with_parents.iter().chain([&self.main]).cloned().collect();
+        self.ordered.sort_by(cmp_by_specificity);
+        self.children.values_mut().for_each(Node::finish);
+    }
+
+    fn lookup(&self, scope: &str) -> &[TrieRule] {
+        let mut node = self;
+        for segment in scope.split('.') {
+            match node.children.get(segment) {
+                Some(child) => node = child,
+                None => break,
+            }
+        }
+        &node.ordered
+    }
+}
+
+// Port of ThemeTrieElement._cmpBySpecificity
+fn cmp_by_specificity(a: &TrieRule, b: &TrieRule) -> Ordering {
+    if a.depth != b.depth {
+        return b.depth.cmp(&a.depth);
+    }
+    let (mut i, mut j) = (0, 0);
+    loop {
+        if a.parents.get(i).is_some_and(|p| p == ">") {
+            i += 1;
+        }
+        if b.parents.get(j).is_some_and(|p| p == ">") {
+            j += 1;
+        }
+        if i >= a.parents.len() || j >= b.parents.len() {
+            break;
+        }
+        match b.parents[j].len().cmp(&a.parents[i].len()) {
+            Ordering::Equal => {}
+            unequal => return unequal,
+        }
+        i += 1;
+        j += 1;
+    }
+    b.parents.len().cmp(&a.parents.len())
+}
+
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    root: Node,
+    foreground: String,
+    background: String,
+    font_style: FontStyle,
+    brackets: Vec<String>,
+    unexpected_bracket: Option<String>,
+    /// The selectors of each `tokenColors` rule, by index.
+    selectors: Vec<Vec<Selector>>,
+}
+
+impl Resolver {
+    pub fn new(theme: &Theme) -> Self {
+        let dark = theme.kind == ThemeKind::Dark;
+        let mut foreground = theme.colors.get("editor.foreground").map(ToString::to_string);
+        let mut background = theme.colors.get("editor.background").map(ToString::to_string);
+        let mut font_style = FontStyle::NONE;
+
+        let mut parsed = Vec::new();
+        for (index, rule) in theme.token_colors.iter().enumerate() {
+            if rule.is_default() {
+                let style = Style::from_settings(&rule.settings, index);
+                foreground = style.foreground.map(|a| a.value).or(foreground);
+                background = style.background.map(|a| a.value).or(background);
+                font_style = style.font_style.map_or(font_style, |a| a.value);
+                continue;
+            }
+            let style = Style::from_settings(&rule.settings, index);
+            for selector in rule.selectors().into_iter().filter_map(Selector::parse) {
+                parsed.push((selector, index, style.clone()));
+            }
+        }
+        // Plain selectors go in before conditional ones on the same scope, so
+        // the latter inherit the complete main rule wherever they appear in
+        // the file (vscode-textmate sorts on parent scopes for the same reason).
+        let conditional = |s: &Selector| !s.parents.is_empty() || !s.excludes.is_empty();
+        parsed.sort_by(|(a, ai, _), (b, bi, _)| {
+            a.scope.cmp(&b.scope).then(conditional(a).cmp(&conditional(b))).then(ai.cmp(bi))
+        });
+
+        let empty = TrieRule { depth: 0, parents: Vec::new(), excludes: Vec::new(), style: Style::default() };
+        let mut root = Node { main: empty, with_parents: Vec::new(), children: HashMap::new(), ordered: Vec::new() };
+        for (selector, _, style) in &parsed {
+            root.insert(0, &selector.scope, selector, style);
+        }
+        root.finish();
+        tracing::debug!(
+            theme = theme.name,
+            rules = theme.token_colors.len(),
+            selectors = parsed.len(),
+            "built resolver"
+        );
+
+        Self {
+            root,
+            foreground: foreground.unwrap_or_else(|| if dark { "#bbbbbb" } else { "#333333" }.to_owned()),
+            background: background.unwrap_or_else(|| if dark { "#1e1e1e" } else { "#ffffff" }.to_owned()),
+            font_style,
+            brackets: (1..)
+                .map_while(|depth| theme.colors.get(&format!("{BRACKET_PREFIX}{depth}")).map(ToString::to_string))
+                .collect(),
+            unexpected_bracket: theme.colors.get(UNEXPECTED_BRACKET).map(ToString::to_string),
+            selectors: theme
+                .token_colors
+                .iter()
+                .map(|rule| rule.selectors().into_iter().filter_map(Selector::parse).collect())
+                .collect(),
+        }
+    }
+
+    /// The theme's default foreground, used by tokens no rule colors.
+    pub fn default_foreground(&self) -> &str {
+        &self.foreground
+    }
+
+    pub fn default_background(&self) -> &str {
+        &self.background
+    }
+
+    /// The theme's bracket pair colors, by nesting depth from the outside.
+    pub fn bracket_colors(&self) -> &[String] {
+        &self.brackets
+    }
+
+    pub fn unexpected_bracket(&self) -> Option<&str> {
+        self.unexpected_bracket.as_deref()
+    }
+
+    /// The selector of the `tokenColors` rule at `rule` (as the `*_rule`
+    /// fields of [`Resolved`] number them) that matched `scopes`, at the
+    /// innermost level it matches.
+    pub fn matched_selector(&self, rule: usize, scopes: &[impl AsRef<str>]) -> Option<&Selector> {
+        let selectors = self.selectors.get(rule)?;
+        (1..=scopes.len()).rev().find_map(|len| selectors.iter().find(|s| s.matches(&scopes[..len])))
+    }
+
+    /// Resolves a scope stack (outermost first) to its style.
+    pub fn resolve(&self, scopes: &[impl AsRef<str>]) -> Resolved<'_> {
+        let mut resolved = Resolved {
+            foreground: &self.foreground,
+            background: &self.background,
+            font_style: self.font_style,
+            foreground_rule: None,
+            background_rule: None,
+            font_style_rule: None,
+        };
+        let tracing = tracing::enabled!(Level::TRACE);
+        for len in 1..=scopes.len() {
+            if tracing {
+                self.trace_candidates(&scopes[..len]);
+            }
+            let Some(rule) = self.match_innermost(&scopes[..len]) else {
+                continue;
+            };
+            if let Some(attr) = &rule.style.foreground {
+                resolved.foreground = &attr.value;
+                resolved.foreground_rule = Some(attr.rule);
+            }
+            if let Some(attr) = &rule.style.background {
+                resolved.background = &attr.value;
+                resolved.background_rule = Some(attr.rule);
+            }
+            if let Some(attr) = &rule.style.font_style {
+                resolved.font_style = attr.value;
+                resolved.font_style_rule = Some(attr.rule);
+            }
+        }
+        if tracing {
+            let winner = |rule: Option<usize>| match rule {
+                Some(rule) => match self.matched_selector(rule, scopes) {
+                    Some(selector) => format!("tokenColors[{rule}] {selector}"),
+                    None => format!("tokenColors[{rule}]"),
+                },
+                None => "default".to_owned(),
+            };
+            tracing::trace!(
+                scopes = %join(scopes),
+                foreground = resolved.foreground,
+                foreground_rule = %winner(resolved.foreground_rule),
+                background_rule = %winner(resolved.background_rule),
+                font_style_rule = %winner(resolved.font_style_rule),
+                "resolved"
+            );
+        }
+        resolved
+    }
+
+    /// Logs the rules the trie holds for the innermost scope of `scopes`,
+    /// most specific first, and whether each one's parents match.
+    fn trace_candidates(&self, scopes: &[impl AsRef<str>]) {
+        let Some((last, ancestors)) = scopes.split_last() else {
+            return;
+        };
+        for (rank, rule) in self.root.lookup(last.as_ref()).iter().enumerate() {
+            let matches =
+                parents_match(ancestors, &rule.parents) && !rule.excludes.iter().any(|e| e.matches_anywhere(scopes));
+            let fields = |attr: Option<usize>| attr.map_or("-".to_owned(), |rule| rule.to_string());
+            tracing::trace!(
+                scope = last.as_ref(),
+                rank,
+                depth = rule.depth,
+                parents = %rule.parents.iter().rev().cloned().collect::<Vec<_>>().join(" "),
+                foreground_rule = %fields(rule.style.foreground.as_ref().map(|a| a.rule)),
+                matches,
+                "candidate"
+            );
+            if matches {
+                break;
+            }
+        }
+    }
+
+    fn match_innermost(&self, scopes: &[impl AsRef<str>]) -> Option<&TrieRule> {
+        let (last, ancestors) = scopes.split_last()?;
+        self.root.lookup(last.as_ref()).iter().find(|rule| {
+            parents_match(ancestors, &rule.parents) && !rule.excludes.iter().any(|e| e.matches_anywhere(scopes))
+        })
+    }
+}
+
+fn join(scopes: &[impl AsRef<str>]) -> String {
+    scopes.iter().map(AsRef::as_ref).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn resolver(rules: serde_json::Value) -> Resolver {
+        let theme = json!({
+            "name": "test",
+            "colors": { "editor.foreground": "#111111", "editor.background": "#000000" },
+            "tokenColors": rules,
+        });
+        Resolver::new(&serde_json::from_value(theme).unwrap())
+    }
+
+    fn fg<'a>(r: &'a Resolver, scopes: &[&str]) -> &'a str {
+        r.resolve(scopes).foreground
+    }
+
+    #[test]
+    fn falls_back_to_defaults() {
+        let r = resolver(json!([
+            { "settings": { "foreground": "#ffffff" } },
+            { "scope": "keyword", "settings": { "foreground": "#ff0000" } },
+        ]));
+        let resolved = r.resolve(&["source.rust", "variable.other"]);
+        assert_eq!(resolved.foreground, "#ffffff");
+        assert_eq!(resolved.background, "#000000");
+        assert_eq!(resolved.foreground_rule, None);
+    }
+
+    #[test]
+    fn deeper_prefix_wins_regardless_of_order() {
+        let r = resolver(json!([
+            { "scope": "keyword.control", "settings": { "foreground": "#222222" } },
+            { "scope": "keyword", "settings": { "foreground": "#111112" } },
+        ]));
+        assert_eq!(fg(&r, &["source", "keyword.control.flow.rust"]), "#222222");
+        assert_eq!(fg(&r, &["source", "keyword.operator"]), "#111112");
+        assert_eq!(fg(&r, &["source", "keywords"]), "#111111");
+    }
+
+    #[test]
+    fn later_rule_overrides_same_selector() {
+        let r = resolver(json!([
+            { "scope": "string", "settings": { "foreground": "#aaaaaa", "fontStyle": "italic" } },
+            { "scope": ["string"], "settings": { "foreground": "#bbbbbb" } },
+        ]));
+        let resolved = r.resolve(&["source", "string.quoted"]);
+        assert_eq!(resolved.foreground, "#bbbbbb");
+        assert_eq!(resolved.foreground_rule, Some(1));
+        assert_eq!(resolved.font_style, FontStyle::ITALIC);
+        assert_eq!(resolved.font_style_rule, Some(0));
+    }
+
+    #[test]
+    fn parent_scoped_rule_beats_plain_rule_at_same_depth() {
+        let r = resolver(json!([
+            { "scope": "source.rust entity.name.type", "settings": { "foreground": "#333333" } },
+            { "scope": "entity.name.type", "settings": { "foreground": "#444444" } },
+        ]));
+        assert_eq!(fg(&r, &["source.rust", "entity.name.type.struct"]), "#333333");
+        assert_eq!(fg(&r, &["source.go", "entity.name.type"]), "#444444");
+    }
+
+    #[test]
+    fn parent_scoped_rule_inherits_plain_rule_listed_after_it() {
+        let r = resolver(json!([
+            { "scope": "meta.block keyword", "settings": { "fontStyle": "italic" } },
+            { "scope": "keyword", "settings": { "foreground": "#aaaaaa" } },
+        ]));
+        let resolved = r.resolve(&["source", "meta.block", "keyword.control"]);
+        assert_eq!((resolved.foreground, resolved.font_style), ("#aaaaaa", FontStyle::ITALIC));
+    }
+
+    #[test]
+    fn target_depth_beats_parent_scopes() {
+        let r = resolver(json!([
+            { "scope": "source.rust keyword", "settings": { "foreground": "#555555" } },
+            { "scope": "keyword.operator", "settings": { "foreground": "#666666" } },
+        ]));
+        assert_eq!(fg(&r, &["source.rust", "keyword.operator.arithmetic"]), "#666666");
+        assert_eq!(fg(&r, &["source.rust", "keyword.other"]), "#555555");
+    }
+
+    #[test]
+    fn longer_parent_scope_name_wins() {
+        let r = resolver(json!([
+            { "scope": "meta.function keyword", "settings": { "foreground": "#777777" } },
+            { "scope": "meta keyword", "settings": { "foreground": "#888888" } },
+            { "scope": "source meta keyword", "settings": { "foreground": "#999999" } },
+        ]));
+        assert_eq!(fg(&r, &["source", "meta.function", "keyword"]), "#777777");
+        // equal first parent length: more parents wins
+        assert_eq!(fg(&r, &["source", "meta.block", "keyword"]), "#999999");
+    }
+
+    #[test]
+    fn inner_levels_inherit_unset_fields() {
+        let r = resolver(json!([
+            { "scope": "string", "settings": { "foreground": "#aaaaaa" } },
+            { "scope": "markup.bold", "settings": { "fontStyle": "bold" } },
+        ]));
+        let resolved = r.resolve(&["text", "markup.bold", "string.quoted"]);
+        assert_eq!(resolved.foreground, "#aaaaaa");
+        assert_eq!(resolved.font_style, FontStyle::BOLD);
+    }
+
+    #[test]
+    fn nodes_inherit_from_ancestor_rules() {
+        let r = resolver(json!([
+            { "scope": "keyword", "settings": { "foreground": "#aaaaaa", "fontStyle": "bold" } },
+            { "scope": "keyword.control", "settings": { "foreground": "#bbbbbb" } },
+            { "scope": "keyword.other", "settings": { "fontStyle": "" } },
+        ]));
+        let control = r.resolve(&["source", "keyword.control"]);
+        assert_eq!((control.foreground, control.font_style), ("#bbbbbb", FontStyle::BOLD));
+        let other = r.resolve(&["source", "keyword.other"]);
+        assert_eq!((other.foreground, other.font_style), ("#aaaaaa", FontStyle::NONE));
+    }
+
+    #[test]
+    fn child_combinator_and_exclusions() {
+        let r = resolver(json!([
+            { "scope": "meta.tag > punctuation", "settings": { "foreground": "#aaaaaa" } },
+            { "scope": "string - meta.embedded", "settings": { "foreground": "#bbbbbb" } },
+        ]));
+        assert_eq!(fg(&r, &["text", "meta.tag", "punctuation.definition"]), "#aaaaaa");
+        assert_eq!(fg(&r, &["text", "meta.tag", "meta.attr", "punctuation"]), "#111111");
+        assert_eq!(fg(&r, &["source", "string.quoted"]), "#bbbbbb");
+        assert_eq!(fg(&r, &["source", "meta.embedded.expr", "string.quoted"]), "#111111");
+    }
+}
@@ -0,0 +1,41 @@
+//! Discovery of the preview sample files.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, Result};
+
+#[derive(Debug, Clone)]
+pub struct Sample {
+    /// Path relative to the samples directory, e.g. `rust/grpc.rs`.
+    pub path: PathBuf,
+    pub source: String,
+}
+
+/// Reads every file under `dir`, recursively, sorted by path.
+pub fn discover(dir: &Path) -> Result<Vec<Sample>> {
+    let mut paths = Vec::new();
+    collect(dir, &mut paths)?;
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let source = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+            let path = path.strip_prefix(dir).unwrap_or(&path).to_path_buf();
+            Ok(Sample { path, source })
+        })
+        .collect()
+}
+
+fn collect(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).map_err(|e| Error::io(dir, e))? {
+        let path = entry.map_err(|e| Error::io(dir, e))?.path();
+        if path.is_dir() {
+            collect(&path, out)?;
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
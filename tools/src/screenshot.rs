@@ -0,0 +1,85 @@
+//! Headless-Chrome captures of the preview page, one PNG per sample.
+
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use headless_chrome::protocol::cdp::Page::CaptureScreenshotFormatOption;
+use headless_chrome::{Browser, LaunchOptions};
+
+use crate::pixels::Image;
+use crate::samples::Sample;
+use crate::{Error, Result};
+
+/// Browser viewport. Fixed so captures only change when the page does.
+pub const WINDOW_SIZE: (u32, u32) = (1400, 1000);
+
+/// Where the capture of `sample` is stored under a screenshots directory:
+/// the sample's own path with `.png` appended.
+pub fn png_path(dir: &Path, sample: &Path) -> PathBuf {
+    dir.join(format!("{}.png", sample.display()))
+}
+
+/// Opens `page` (as written by `preview::render`) and captures the section
+/// of every sample into `out`.
+pub fn capture(page: &Path, samples: &[Sample], out: &Path) -> Result<Vec<PathBuf>> {
+    let failed = |message: String| Error::Screenshot { path: page.to_path_buf(), message };
+    let page = fs::canonicalize(page).map_err(|e| Error::io(page, e))?;
+
+    let options = LaunchOptions { window_size: Some(WINDOW_SIZE), ..LaunchOptions::default() };
+    let browser = Browser::new(options).map_err(|e| failed(e.to_string()))?;
+    let tab = browser.new_tab().map_err(|e| failed(e.to_string()))?;
+    tab.navigate_to(&format!("file://{}", page.display()))
+        .and_then(|tab| tab.wait_until_navigated())
+        .map_err(|e| failed(e.to_string()))?;
+
+    let mut written = Vec::new();
+    for sample in samples {
+        let id = sample.path.display().to_string().replace('"', "\\\"");
+        let png = tab
+            .find_element(&format!("section[id=\"{id}\"]"))
+            .and_then(|section| section.capture_screenshot(CaptureScreenshotFormatOption::Png))
+            .map_err(|e| Error::Screenshot { path: sample.path.clone(), message: e.to_string() })?;
+
+        let path = png_path(out, &sample.path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+        }
+        fs::write(&path, png).map_err(|e| Error::io(&path, e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+pub fn read_png(path: &Path) -> Result<Image> {
+    let failed = |message: String| Error::Screenshot { path: path.to_path_buf(), message };
+    let file = File::open(path).map_err(|e| Error::io(path, e))?;
+    let mut decoder = png::Decoder::new(file);
+    decoder.set_transformations(png::Transformations::normalize_to_color8() | png::Transformations::ALPHA);
+    let mut reader = decoder.read_info().map_err(|e| failed(e.to_string()))?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|e| failed(e.to_string()))?;
+    buf.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::GrayscaleAlpha => buf.chunks(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        other => return Err(failed(format!("unsupported color type {other:?}"))),
+    };
+    Ok(Image { width: info.width, height: info.height, rgba })
+}
+
+pub fn write_png(path: &Path, image: &Image) -> Result<()> {
+    let failed = |message: String| Error::Screenshot { path: path.to_path_buf(), message };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+    }
+    let file = File::create(path).map_err(|e| Error::io(path, e))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&image.rgba))
+        .map_err(|e| failed(e.to_string()))
+}
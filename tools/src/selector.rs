@@ -0,0 +1,191 @@
+//! Parsing and matching of TextMate scope selectors.
+//!
+//! Theme rules use a small subset of the selector language:
+//!
+//! - `a.b` matches any scope equal to `a.b` or starting with `a.b.`
+//! - `a b` (descendant) matches `b` when an outer scope matches `a`
+//! - `a > b` (child) requires `a` to match the direct parent of `b`
+//! - `a, b` lists alternatives
+//! - `a - b` matches `a` unless the scope stack also matches `b`
+//!
+//! Exclusions are a TextMate feature that VS Code ignores in themes; they are
+//! supported so rules using them can be checked, but this theme avoids them.
+
+/// One selector from a comma-separated list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    /// The scope the selector targets, i.e. its last segment.
+    pub scope: String,
+    /// Ancestor constraints, innermost first, with `>` markers kept in
+    /// place as VS Code stores them.
+    pub parents: Vec<String>,
+    /// Selectors that must not match the scope stack.
+    pub excludes: Vec<Selector>,
+}
+
+impl Selector {
+    /// Parses a comma-separated selector list. Blank entries are skipped.
+    pub fn parse_list(input: &str) -> Vec<Selector> {
+        input.split(',').filter_map(Selector::parse).collect()
+    }
+
+    /// Parses a single selector, or returns `None` if it is blank.
+    pub fn parse(input: &str) -> Option<Selector> {
+        let mut parts = input.split(" - ").map(str::trim);
+        let mut selector = Self::parse_path(parts.next()?)?;
+        selector.excludes = parts.filter_map(Self::parse_path).collect();
+        Some(selector)
+    }
+
+    fn parse_path(input: &str) -> Option<Selector> {
+        let mut segments: Vec<String> =
+            input.split_whitespace().flat_map(|s| split_child_marker(s)).map(str::to_owned).collect();
+        let scope = segments.pop().filter(|s| s != ">")?;
+        segments.reverse();
+        Some(Selector { scope, parents: segments, excludes: Vec::new() })
+    }
+
+    /// Number of dot-separated segments in the target scope.
+    pub fn depth(&self) -> usize {
+        self.scope.split('.').count()
+    }
+
+    /// Whether the selector matches the innermost scope of `stack`
+    /// (outermost first).
+    pub fn matches(&self, stack: &[impl AsRef<str>]) -> bool {
+        let Some((last, ancestors)) = stack.split_last() else {
+            return false;
+        };
+        matches_scope(last.as_ref(), &self.scope) && parents_match(ancestors, &self.parents) && !self.is_excluded(stack)
+    }
+
+    /// Whether any exclusion matches some scope of `stack`.
+    pub fn is_excluded(&self, stack: &[impl AsRef<str>]) -> bool {
+        self.excludes.iter().any(|exclude| exclude.matches_anywhere(stack))
+    }
+
+    /// Whether the selector matches any scope of `stack`, not only the
+    /// innermost one.
+    pub fn matches_anywhere(&self, stack: &[impl AsRef<str>]) -> bool {
+        (1..=stack.len()).any(|len| self.matches(&stack[..len]))
+    }
+}
+
+impl std::fmt::Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for parent in self.parents.iter().rev() {
+            write!(f, "{parent} ")?;
+        }
+        f.write_str(&self.scope)?;
+        for exclude in &self.excludes {
+            write!(f, " - {exclude}")?;
+        }
+        Ok(())
+    }
+}
+
+// `a>b` written without spaces is still a child combinator
+fn split_child_marker(segment: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut rest = segment;
+    while let Some(pos) = rest.find('>') {
+        out.extend([&rest[..pos], ">"].into_iter().filter(|s| !s.is_empty()));
+        rest = &rest[pos + 1..];
+    }
+    if !rest.is_empty() {
+        out.push(rest);
+    }
+    out
+}
+
+/// `scope` is `selector` or a more specific scope beneath it.
+pub fn matches_scope(scope: &str, selector: &str) -> bool {
+    scope.strip_prefix(selector).is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Checks ancestor constraints (innermost first) against `ancestors`
+/// (outermost first), mirroring vscode-textmate's
+/// `_scopePathMatchesParentScopes`.
+pub fn parents_match(ancestors: &[impl AsRef<str>], parents: &[String]) -> bool {
+    let mut remaining = ancestors.iter().rev();
+    let mut parents = parents.iter();
+    while let Some(mut pattern) = parents.next() {
+        let must_be_direct = pattern == ">";
+        if must_be_direct {
+            match parents.next() {
+                Some(next) => pattern = next,
+                None => return false,
+            }
+        }
+        loop {
+            match remaining.next() {
+                Some(scope) if matches_scope(scope.as_ref(), pattern) => break,
+                Some(_) if !must_be_direct => continue,
+                _ => return false,
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sel(input: &str) -> Selector {
+        Selector::parse(input).unwrap()
+    }
+
+    #[test]
+    fn parses_comma_lists_and_trims() {
+        let list = Selector::parse_list(" string.quoted ,, source.rust  keyword ");
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].scope, "string.quoted");
+        assert_eq!(list[1].scope, "keyword");
+        assert_eq!(list[1].parents, ["source.rust"]);
+    }
+
+    #[test]
+    fn stores_parents_innermost_first() {
+        let s = sel("source.ts meta.class > entity.name");
+        assert_eq!(s.parents, [">", "meta.class", "source.ts"]);
+        assert_eq!(s.to_string(), "source.ts meta.class > entity.name");
+        assert_eq!(sel("a>b").parents, [">", "a"]);
+    }
+
+    #[test]
+    fn prefix_matching_respects_segment_boundaries() {
+        assert!(matches_scope("string.quoted.double", "string"));
+        assert!(matches_scope("string", "string"));
+        assert!(!matches_scope("strings.quoted", "string"));
+        assert!(!matches_scope("string", "string.quoted"));
+    }
+
+    #[test]
+    fn descendant_parents_must_appear_in_order() {
+        let s = sel("source.rust meta.function entity.name");
+        assert!(s.matches(&["source.rust", "meta.block", "meta.function", "entity.name.function"]));
+        assert!(!s.matches(&["meta.function", "source.rust", "entity.name.function"]));
+        assert!(!s.matches(&["source.rust", "entity.name.function"]));
+    }
+
+    #[test]
+    fn child_combinator_requires_direct_parent() {
+        let s = sel("meta.class > entity.name");
+        assert!(s.matches(&["source", "meta.class", "entity.name.type"]));
+        assert!(!s.matches(&["meta.class", "meta.body", "entity.name.type"]));
+        assert!(Selector::parse(">").is_none());
+        assert!(Selector::parse("a >").is_none());
+    }
+
+    #[test]
+    fn exclusions_veto_anywhere_in_the_stack() {
+        let s = sel("string - string.quoted.single");
+        assert!(s.matches(&["source", "string.quoted.double"]));
+        assert!(!s.matches(&["source", "string.quoted.single"]));
+
+        let s = sel("punctuation - meta.embedded");
+        assert!(!s.matches(&["source", "meta.embedded.block", "punctuation.section"]));
+        assert_eq!(s.to_string(), "punctuation - meta.embedded");
+    }
+}
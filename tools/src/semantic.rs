@@ -0,0 +1,340 @@
+//! rust-analyzer semantic tokens and how a theme's `semanticTokenColors`
+//! style them.
+//!
+//! Selectors follow VS Code: `type`, `type.modifier…`, `*.modifier` and an
+//! optional `:language` suffix. Every matching rule is scored (exact type
+//! 10, a supertype one less per level, `*` 0; plus 100 per modifier and 10
+//! for the language) and the best-scoring rule wins each attribute, later
+//! rules winning ties.
+
+use crate::theme::{FontStyle, SemanticTokenStyle, Theme};
+
+/// Token types rust-analyzer reports, standard LSP ones included.
+pub const TOKEN_TYPES: &[&str] = &[
+    "angle",
+    "arithmetic",
+    "attribute",
+    "attributeBracket",
+    "bitwise",
+    "boolean",
+    "brace",
+    "bracket",
+    "builtinAttribute",
+    "builtinType",
+    "character",
+    "colon",
+    "comma",
+    "comment",
+    "comparison",
+    "const",
+    "constParameter",
+    "derive",
+    "deriveHelper",
+    "dot",
+    "enum",
+    "enumMember",
+    "escapeSequence",
+    "formatSpecifier",
+    "function",
+    "generic",
+    "interface",
+    "invalidEscapeSequence",
+    "keyword",
+    "label",
+    "lifetime",
+    "logical",
+    "macro",
+    "macroBang",
+    "method",
+    "namespace",
+    "number",
+    "operator",
+    "parameter",
+    "parenthesis",
+    "procMacro",
+    "property",
+    "punctuation",
+    "selfKeyword",
+    "selfTypeKeyword",
+    "semicolon",
+    "static",
+    "string",
+    "struct",
+    "toolModule",
+    "type",
+    "typeAlias",
+    "typeParameter",
+    "union",
+    "unresolvedReference",
+    "variable",
+];
+
+/// Token modifiers rust-analyzer reports.
+pub const TOKEN_MODIFIERS: &[&str] = &[
+    "associated",
+    "async",
+    "attribute",
+    "callable",
+    "constant",
+    "consuming",
+    "controlFlow",
+    "crateRoot",
+    "declaration",
+    "defaultLibrary",
+    "definition",
+    "documentation",
+    "injected",
+    "intraDocLink",
+    "library",
+    "macro",
+    "mutable",
+    "public",
+    "reference",
+    "static",
+    "trait",
+    "unsafe",
+];
+
+/// Each type's supertype as rust-analyzer declares it; a rule for the
+/// supertype applies when none names the type itself.
+pub const SUPERTYPES: &[(&str, &str)] = &[
+    ("angle", "punctuation"),
+    ("arithmetic", "operator"),
+    ("attributeBracket", "punctuation"),
+    ("bitwise", "operator"),
+    ("boolean", "keyword"),
+    ("brace", "punctuation"),
+    ("bracket", "punctuation"),
+    ("builtinAttribute", "attribute"),
+    ("builtinType", "type"),
+    ("colon", "punctuation"),
+    ("comma", "punctuation"),
+    ("comparison", "operator"),
+    ("constParameter", "typeParameter"),
+    ("derive", "attribute"),
+    ("deriveHelper", "attribute"),
+    ("dot", "punctuation"),
+    ("enum", "type"),
+    ("escapeSequence", "string"),
+    ("formatSpecifier", "string"),
+    ("interface", "type"),
+    ("invalidEscapeSequence", "string"),
+    ("logical", "operator"),
+    ("macroBang", "macro"),
+    ("method", "function"),
+    ("parenthesis", "punctuation"),
+    ("procMacro", "macro"),
+    ("selfKeyword", "keyword"),
+    ("selfTypeKeyword", "keyword"),
+    ("semicolon", "punctuation"),
+    ("struct", "type"),
+    ("typeAlias", "type"),
+    ("typeParameter", "type"),
+    ("union", "type"),
+];
+
+/// One token as rust-analyzer would report it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticToken {
+    pub line: usize,
+    /// 1-based, in characters.
+    pub column: usize,
+    pub text: String,
+    pub token_type: String,
+    pub modifiers: Vec<String>,
+}
+
+/// The style semantic highlighting gives a token; `None` fields fall back
+/// to the TextMate result.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SemanticStyle {
+    pub foreground: Option<String>,
+    pub font_style: Option<FontStyle>,
+}
+
+/// A parsed `semanticTokenColors` key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemanticSelector<'a> {
+    pub token_type: &'a str,
+    pub modifiers: Vec<&'a str>,
+    pub language: Option<&'a str>,
+}
+
+impl<'a> SemanticSelector<'a> {
+    pub fn parse(key: &'a str) -> Self {
+        let (rest, language) = match key.split_once(':') {
+            Some((rest, language)) => (rest, Some(language)),
+            None => (key, None),
+        };
+        let mut parts = rest.split('.');
+        let token_type = parts.next().unwrap_or("*");
+        Self { token_type, modifiers: parts.collect(), language }
+    }
+
+    /// Match score against a token, or `None` if the selector does not apply.
+    pub fn score(&self, token_type: &str, modifiers: &[impl AsRef<str>], language: &str) -> Option<u32> {
+        let type_score = if self.token_type == "*" {
+            0
+        } else {
+            let depth = supertype_chain(token_type).position(|t| t == self.token_type)?;
+            10 - depth as u32
+        };
+        if !self.modifiers.iter().all(|m| modifiers.iter().any(|have| have.as_ref() == *m)) {
+            return None;
+        }
+        let language_score = match self.language {
+            Some(wanted) if wanted != language => return None,
+            Some(_) => 10,
+            None => 0,
+        };
+        Some(type_score + 100 * self.modifiers.len() as u32 + language_score)
+    }
+}
+
+/// `token_type` followed by its supertypes, nearest first.
+pub fn supertype_chain(token_type: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(token_type), |t| SUPERTYPES.iter().find(|(sub, _)| sub == t).map(|(_, sup)| *sup))
+}
+
+/// Styles a token through the theme's `semanticTokenColors`.
+pub fn resolve(theme: &Theme, token_type: &str, modifiers: &[impl AsRef<str>], language: &str) -> SemanticStyle {
+    let mut foreground: Option<(u32, &str)> = None;
+    let mut font_style: Option<(u32, FontStyle)> = None;
+
+    for (key, style) in &theme.semantic_token_colors {
+        let Some(score) = SemanticSelector::parse(key).score(token_type, modifiers, language) else {
+            continue;
+        };
+        let (fg, fs) = match style {
+            SemanticTokenStyle::Color(color) => (Some(color.as_str()), None),
+            SemanticTokenStyle::Style(style) => (style.foreground.as_ref().map(|c| c.as_str()), font_style_of(style)),
+        };
+        if let Some(fg) = fg.filter(|_| foreground.is_none_or(|(best, _)| score >= best)) {
+            foreground = Some((score, fg));
+        }
+        if let Some(fs) = fs.filter(|_| font_style.is_none_or(|(best, _)| score >= best)) {
+            font_style = Some((score, fs));
+        }
+    }
+    SemanticStyle { foreground: foreground.map(|(_, fg)| fg.to_owned()), font_style: font_style.map(|(_, fs)| fs) }
+}
+
+/// `fontStyle` wins over the individual boolean flags, as in VS Code.
+fn font_style_of(style: &crate::theme::SemanticStyle) -> Option<FontStyle> {
+    if style.font_style.is_some() {
+        return style.font_style;
+    }
+    let flags = [
+        (style.bold, FontStyle::BOLD),
+        (style.italic, FontStyle::ITALIC),
+        (style.underline, FontStyle::UNDERLINE),
+        (style.strikethrough, FontStyle::STRIKETHROUGH),
+    ];
+    if flags.iter().all(|(set, _)| set.is_none()) {
+        return None;
+    }
+    Some(flags.into_iter().filter(|(set, _)| *set == Some(true)).fold(FontStyle::NONE, |acc, (_, f)| acc | f))
+}
+
+/// Reads caret annotations from a Rust fixture. A comment line such as
+///
+/// ```text
+/// let mut total = 0;
+/// //      ^^^^^ variable.declaration.mutable
+/// ```
+///
+/// marks the characters above the carets on the nearest preceding
+/// non-annotation line with a token type and its modifiers.
+pub fn annotations(source: &str) -> Vec<SemanticToken> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut tokens = Vec::new();
+    let mut target = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let Some((start, len, label)) = caret(line) else {
+            target = Some(index);
+            continue;
+        };
+        let Some(target) = target else {
+            continue;
+        };
+        let text: String = lines[target].chars().skip(start).take(len).collect();
+        let mut parts = label.split('.');
+        let token_type = parts.next().unwrap_or_default().to_owned();
+        tokens.push(SemanticToken {
+            line: target + 1,
+            column: start + 1,
+            text,
+            token_type,
+            modifiers: parts.map(str::to_owned).collect(),
+        });
+    }
+    tokens
+}
+
+/// `(column, length, label)` of a `// ^^^ label` line, 0-based.
+fn caret(line: &str) -> Option<(usize, usize, &str)> {
+    let body = line.trim_start().strip_prefix("//")?;
+    let start = line.find('^')?;
+    if !body.trim_start().starts_with('^') {
+        return None;
+    }
+    let len = line[start..].chars().take_while(|c| *c == '^').count();
+    let label = line[start + len..].trim();
+    (!label.is_empty()).then_some((line[..start].chars().count(), len, label))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn scores_follow_vs_code() {
+        let modifiers = ["declaration", "mutable"];
+        let score = |key: &str| SemanticSelector::parse(key).score("method", &modifiers, "rust");
+        assert_eq!(score("method"), Some(10));
+        assert_eq!(score("function"), Some(9));
+        assert_eq!(score("*"), Some(0));
+        assert_eq!(score("*.mutable"), Some(100));
+        assert_eq!(score("method.declaration.mutable:rust"), Some(220));
+        assert_eq!(score("method:python"), None);
+        assert_eq!(score("method.unsafe"), None);
+        assert_eq!(score("variable"), None);
+    }
+
+    #[test]
+    fn best_rule_wins_each_attribute() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "semanticTokenColors": {
+                "function": "#111111",
+                "method": { "foreground": "#222222" },
+                "*.mutable": { "underline": true },
+                "*.unsafe": { "foreground": "#333333", "fontStyle": "bold" }
+            }
+        }))
+        .unwrap();
+        let style = resolve(&theme, "method", &["mutable"], "rust");
+        assert_eq!(style.foreground.as_deref(), Some("#222222"));
+        assert_eq!(style.font_style, Some(FontStyle::UNDERLINE));
+        let style = resolve(&theme, "function", &["unsafe"], "rust");
+        assert_eq!(style.foreground.as_deref(), Some("#333333"));
+        assert_eq!(style.font_style, Some(FontStyle::BOLD));
+        assert_eq!(resolve(&theme, "struct", &[] as &[&str], "rust"), SemanticStyle::default());
+    }
+
+    #[test]
+    fn reads_caret_annotations() {
+        let source = "fn main() {\n    let mut total = 0;\n    //      ^^^^^ variable.declaration.mutable\n    //  ^^^ keyword\n}\n";
+        let tokens = annotations(source);
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].line, 2);
+        assert_eq!(tokens[0].column, 13);
+        assert_eq!(tokens[0].text, "total");
+        assert_eq!(tokens[0].token_type, "variable");
+        assert_eq!(tokens[0].modifiers, ["declaration", "mutable"]);
+        assert_eq!(tokens[1].text, "mut");
+    }
+}
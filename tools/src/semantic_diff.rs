@@ -0,0 +1,97 @@
+//! Tokens whose color differs between TextMate and semantic highlighting.
+//!
+//! VS Code paints rust-analyzer's semantic tokens over the grammar's
+//! result, so a theme whose two paths disagree looks different there than
+//! on Zenn, where only the grammar runs.
+
+use std::path::Path;
+
+use crate::resolver::{FontStyle, Resolver};
+use crate::semantic::{self, SemanticToken};
+use crate::theme::Theme;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::{Error, Result};
+
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub token: SemanticToken,
+    /// Innermost TextMate scope at the token's first character.
+    pub scope: String,
+    pub textmate: (String, FontStyle),
+    pub semantic: (String, FontStyle),
+}
+
+/// Compares both paths for every semantic token in `tokens`. Attributes the
+/// semantic rules leave unset keep the TextMate value, as in VS Code.
+pub fn compare(
+    theme: &Theme,
+    tokenizer: &Tokenizer,
+    path: &Path,
+    source: &str,
+    tokens: &[SemanticToken],
+) -> Result<Vec<Divergence>> {
+    let syntax = tokenizer
+        .syntax_for(path)
+        .ok_or_else(|| Error::Tokenize { path: path.to_path_buf(), message: "no grammar for this file".to_owned() })?;
+    let textmate = tokenizer.tokenize(syntax, path, source)?;
+    let resolver = Resolver::new(theme);
+    let language = syntax.name.to_lowercase();
+
+    let mut divergences = Vec::new();
+    for token in tokens {
+        let Some(covering) = covering(&textmate, token.line, token.column) else {
+            continue;
+        };
+        let style = resolver.resolve(&covering.scopes);
+        let ours = semantic::resolve(theme, &token.token_type, &token.modifiers, &language);
+        let textmate = (style.foreground.to_owned(), style.font_style);
+        let semantic = (ours.foreground.unwrap_or_else(|| textmate.0.clone()), ours.font_style.unwrap_or(textmate.1));
+
+        if !textmate.0.eq_ignore_ascii_case(&semantic.0) || textmate.1 != semantic.1 {
+            divergences.push(Divergence {
+                token: token.clone(),
+                scope: covering.scopes.last().cloned().unwrap_or_default(),
+                textmate,
+                semantic,
+            });
+        }
+    }
+    Ok(divergences)
+}
+
+/// The grammar token containing the character at `line`:`column`.
+fn covering(tokens: &[Token], line: usize, column: usize) -> Option<&Token> {
+    tokens.iter().filter(|t| t.line == line).find(|t| (t.column..t.column + t.text.chars().count()).contains(&column))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn reports_only_tokens_whose_paths_disagree() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#ffffff" } },
+                { "scope": "entity.name.function", "settings": { "foreground": "#00ff00" } },
+                { "scope": "storage.type", "settings": { "foreground": "#ff0000" } }
+            ],
+            "semanticTokenColors": {
+                "function": "#00ff00",
+                "keyword": "#0000ff"
+            }
+        }))
+        .unwrap();
+        let source = "    fn main() {}\n//  ^^ keyword\n//     ^^^^ function.declaration\n";
+        let tokens = semantic::annotations(source);
+
+        let divergences = compare(&theme, &Tokenizer::new(), Path::new("a.rs"), source, &tokens).unwrap();
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].token.text, "fn");
+        assert_eq!(divergences[0].textmate.0, "#ff0000");
+        assert_eq!(divergences[0].semantic.0, "#0000ff");
+    }
+}
@@ -0,0 +1,143 @@
+//! Live-reload preview server.
+//!
+//! Serves the preview page and re-renders it whenever a theme or sample
+//! file changes, pushing the new page to every open tab over a websocket.
+//! Tabs swap in the new body in place, so the scroll position survives.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::{Html, Response};
+use axum::routing::get;
+use axum::Router;
+use tokio::sync::broadcast;
+
+use crate::html::{escape, Options};
+use crate::preview::{self, PreviewTheme, STYLE};
+use crate::resolver::Resolver;
+use crate::theme::Theme;
+use crate::tokenizer::Tokenizer;
+use crate::watch::Watcher;
+use crate::{samples, theme_paths, Error, Result, SAMPLES_DIR, THEMES_DIR};
+
+const RELOAD_SCRIPT: &str = r#"<script>
+(() => {
+  const connect = () => {
+    const socket = new WebSocket(`ws://${location.host}/ws`);
+    socket.onmessage = (event) => {
+      const next = new DOMParser().parseFromString(event.data, "text/html");
+      const y = window.scrollY;
+      document.body.replaceWith(next.body);
+      window.scrollTo(0, y);
+    };
+    socket.onclose = () => setTimeout(connect, 1000);
+  };
+  connect();
+})();
+</script>
+"#;
+
+/// What [`serve`] reports as it runs, for the caller to print.
+#[derive(Debug, Clone, Copy)]
+pub enum Event {
+    /// Bound to the address and accepting connections.
+    Listening,
+    /// `files` changed and the page was re-rendered in `elapsed`.
+    Rerendered { files: usize, elapsed: Duration },
+}
+
+#[derive(Clone)]
+struct Shared {
+    page: Arc<RwLock<Arc<String>>>,
+    updates: broadcast::Sender<Arc<String>>,
+}
+
+/// Serves on `addr` until the process is stopped, highlighting every theme
+/// with `options`. `report` is called with each [`Event`], from whichever
+/// thread it happens on.
+pub async fn serve(
+    root: &Path,
+    addr: &str,
+    options: Options,
+    report: impl Fn(Event) + Send + Sync + 'static,
+) -> Result<()> {
+    let report = Arc::new(report);
+    let tokenizer = Tokenizer::discover(root)?;
+    let watched: Vec<PathBuf> = vec![root.join(THEMES_DIR), root.join(SAMPLES_DIR)];
+    let watcher = Watcher::new(&watched)?;
+
+    let (updates, _) = broadcast::channel(4);
+    let shared = Shared { page: Arc::new(RwLock::new(Arc::new(render(root, &tokenizer, options)))), updates };
+
+    let root = root.to_path_buf();
+    let background = shared.clone();
+    let background_report = Arc::clone(&report);
+    std::thread::spawn(move || {
+        while let Some(changed) = watcher.next_batch() {
+            let started = Instant::now();
+            let page = Arc::new(render(&root, &tokenizer, options));
+            *background.page.write().unwrap_or_else(|e| e.into_inner()) = Arc::clone(&page);
+            // No receivers only means no tab is open
+            let _ = background.updates.send(page);
+            background_report(Event::Rerendered { files: changed.len(), elapsed: started.elapsed() });
+        }
+    });
+
+    let app = Router::new().route("/", get(index)).route("/ws", get(socket)).with_state(shared);
+    let listener =
+        tokio::net::TcpListener::bind(addr).await.map_err(|source| Error::Listen { addr: addr.to_owned(), source })?;
+    report(Event::Listening);
+    axum::serve(listener, app).await.map_err(|source| Error::Listen { addr: addr.to_owned(), source })
+}
+
+async fn index(State(shared): State<Shared>) -> Html<String> {
+    let page = shared.page.read().unwrap_or_else(|e| e.into_inner());
+    Html(page.as_str().to_owned())
+}
+
+async fn socket(State(shared): State<Shared>, upgrade: WebSocketUpgrade) -> Response {
+    let updates = shared.updates.subscribe();
+    upgrade.on_upgrade(|socket| push(socket, updates))
+}
+
+async fn push(mut socket: WebSocket, mut updates: broadcast::Receiver<Arc<String>>) {
+    loop {
+        let page = match updates.recv().await {
+            Ok(page) => page,
+            // Only the newest page matters, so skipped ones are fine
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        if socket.send(Message::Text(page.as_str().to_owned())).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// The preview page with the reload script, or an error page while a theme
+/// is mid-edit and does not load.
+fn render(root: &Path, tokenizer: &Tokenizer, options: Options) -> String {
+    let page = render_preview(root, tokenizer, options).unwrap_or_else(|err| {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"ja\">\n<head>\n<meta charset=\"utf-8\">\n<title>Zenn theme preview</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>Zenn theme preview</h1>\n<p class=\"note\">error: {}</p>\n</body>\n</html>\n",
+            escape(&err.to_string())
+        )
+    });
+    page.replacen("</head>", &format!("{RELOAD_SCRIPT}</head>"), 1)
+}
+
+fn render_preview(root: &Path, tokenizer: &Tokenizer, options: Options) -> Result<String> {
+    let themes: Vec<(String, Resolver)> = theme_paths(&root.join(THEMES_DIR))?
+        .iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+            Theme::load(path).map(|theme| (name, Resolver::new(&theme)))
+        })
+        .collect::<Result<_>>()?;
+    let themes: Vec<PreviewTheme<'_>> =
+        themes.iter().map(|(name, resolver)| PreviewTheme { name, resolver, options }).collect();
+    preview::render(&themes, tokenizer, &samples::discover(&root.join(SAMPLES_DIR))?)
+}
@@ -0,0 +1,126 @@
+//! Stable text rendering of highlighted samples for golden tests.
+//!
+//! Each non-blank token becomes one line:
+//!
+//! ```text
+//! 78:9\t#ff8fa3 -\t"fn"\tstorage.type.function.rust
+//! ```
+//!
+//! i.e. position, resolved foreground and font style (`-` for none), the
+//! token text in Rust debug quoting, and the innermost scope, separated by
+//! tabs (shown as `\t`).
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::{Error, Result};
+
+pub fn render(resolver: &Resolver, tokens: &[Token]) -> String {
+    let mut out = String::new();
+    for token in tokens.iter().filter(|t| !t.text.trim().is_empty()) {
+        let style = resolver.resolve(&token.scopes);
+        let font = if style.font_style.is_empty() { "-".to_owned() } else { style.font_style.to_string() };
+        let scope = token.scopes.last().map_or("", String::as_str);
+        let _ = writeln!(
+            out,
+            "{}:{}\t{} {}\t{:?}\t{}",
+            token.line, token.column, style.foreground, font, token.text, scope
+        );
+    }
+    out
+}
+
+/// Compares every sample with its `<sample>.tokens` file under `dir` and
+/// returns one message per mismatch or orphaned snapshot. With `update`,
+/// rewrites the snapshots instead and removes orphans.
+pub fn check(
+    resolver: &Resolver,
+    tokenizer: &Tokenizer,
+    samples: &[Sample],
+    dir: &Path,
+    update: bool,
+) -> Result<Vec<String>> {
+    let mut stale = BTreeSet::new();
+    existing(dir, &mut stale);
+    let mut failures = Vec::new();
+
+    for (sample, tokens) in samples.iter().zip(tokenizer.tokenize_all(samples)?) {
+        let Some(tokens) = tokens else {
+            continue;
+        };
+        let actual = render(resolver, &tokens);
+
+        let mut name = sample.path.clone().into_os_string();
+        name.push(".tokens");
+        let path = dir.join(name);
+        stale.remove(&path);
+
+        if update {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|e| Error::io(parent, e))?;
+            }
+            fs::write(&path, &actual).map_err(|e| Error::io(&path, e))?;
+            continue;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_default();
+        if expected != actual {
+            let line = expected
+                .lines()
+                .zip(actual.lines())
+                .position(|(e, a)| e != a)
+                .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+            failures.push(format!("{}: first difference at snapshot line {}", sample.path.display(), line + 1));
+        }
+    }
+
+    for path in &stale {
+        if update {
+            fs::remove_file(path).map_err(|e| Error::io(path, e))?;
+        } else {
+            failures.push(format!("{}: no matching sample", path.display()));
+        }
+    }
+    Ok(failures)
+}
+
+fn existing(dir: &Path, out: &mut BTreeSet<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            existing(&path, out);
+        } else if path.extension().is_some_and(|e| e == "tokens") {
+            out.insert(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::theme::Theme;
+
+    #[test]
+    fn rows_match_the_module_docs() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [{ "scope": "storage", "settings": { "foreground": "#ff8fa3" } }]
+        }))
+        .unwrap();
+        let scopes = ["source.rust", "meta.function.rust", "storage.type.function.rust"].map(str::to_owned).to_vec();
+        let tokens = [
+            Token { line: 78, column: 9, text: "fn".to_owned(), scopes: scopes.clone() },
+            Token { line: 78, column: 11, text: " ".to_owned(), scopes },
+        ];
+        assert_eq!(render(&Resolver::new(&theme), &tokens), "78:9\t#ff8fa3 -\t\"fn\"\tstorage.type.function.rust\n");
+    }
+}
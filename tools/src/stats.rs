@@ -0,0 +1,101 @@
+//! How often each theme rule styles a token across the samples.
+
+use std::collections::BTreeMap;
+
+use crate::resolver::Resolver;
+use crate::samples::Sample;
+use crate::theme::Theme;
+use crate::tokenizer::{Token, Tokenizer};
+use crate::Result;
+
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    /// Non-blank tokens seen.
+    pub tokens: usize,
+    /// Tokens each `tokenColors` rule contributed a setting to, by index.
+    pub rules: Vec<usize>,
+    /// Innermost scopes of tokens no rule colored, with their counts.
+    pub fallback: BTreeMap<String, usize>,
+}
+
+impl Stats {
+    /// Scoped rules that styled no token at all.
+    pub fn dead_rules<'a>(&'a self, theme: &'a Theme) -> impl Iterator<Item = usize> + 'a {
+        self.rules
+            .iter()
+            .enumerate()
+            .filter(|&(i, &count)| count == 0 && !theme.token_colors[i].is_default())
+            .map(|(i, _)| i)
+    }
+
+    pub fn fallback_tokens(&self) -> usize {
+        self.fallback.values().sum()
+    }
+}
+
+pub fn collect(theme: &Theme, tokenizer: &Tokenizer, samples: &[Sample]) -> Result<Stats> {
+    let tokenized = tokenizer.tokenize_all(samples)?;
+    Ok(count(theme, tokenized.iter().flatten().map(Vec::as_slice)))
+}
+
+/// [`collect`] over samples already tokenized.
+pub fn count<'a>(theme: &Theme, tokenized: impl IntoIterator<Item = &'a [Token]>) -> Stats {
+    let resolver = Resolver::new(theme);
+    let mut stats = Stats { rules: vec![0; theme.token_colors.len()], ..Stats::default() };
+
+    for tokens in tokenized {
+        for token in tokens {
+            if token.text.trim().is_empty() {
+                continue;
+            }
+            stats.tokens += 1;
+
+            let style = resolver.resolve(&token.scopes);
+            let mut rules = [style.foreground_rule, style.background_rule, style.font_style_rule];
+            rules.sort();
+            let mut previous = None;
+            for rule in rules.into_iter().flatten() {
+                if previous != Some(rule) {
+                    stats.rules[rule] += 1;
+                }
+                previous = Some(rule);
+            }
+
+            if style.foreground_rule.is_none() {
+                let scope = token.scopes.last().cloned().unwrap_or_default();
+                *stats.fallback.entry(scope).or_default() += 1;
+            }
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn counts_rules_and_dead_rules() {
+        let theme: Theme = serde_json::from_value(json!({
+            "name": "t",
+            "tokenColors": [
+                { "settings": { "foreground": "#ffffff" } },
+                { "scope": "keyword", "settings": { "foreground": "#ff0000" } },
+                { "scope": "storage.type", "settings": { "fontStyle": "italic" } },
+                { "scope": "markup.heading", "settings": { "foreground": "#00ff00" } }
+            ]
+        }))
+        .unwrap();
+        let samples = [Sample { path: PathBuf::from("a.rs"), source: "fn main() { let x = 1; }\n".to_owned() }];
+
+        let stats = collect(&theme, &Tokenizer::new(), &samples).unwrap();
+        assert!(stats.rules[1] > 0);
+        assert!(stats.rules[2] > 0);
+        assert_eq!(stats.dead_rules(&theme).collect::<Vec<_>>(), [3]);
+        assert!(stats.fallback_tokens() > 0);
+    }
+}
@@ -0,0 +1,442 @@
+//! Typed model of the VS Code/Shiki theme JSON.
+//!
+//! Unknown fields, duplicate keys, malformed colors and unknown font styles
+//! are rejected while deserializing, so a theme that loads is one Shiki will
+//! render the way the tools below report.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::selector::Selector;
+use crate::{Error, Result};
+
+/// `colors` key of the bracket pair color at nesting depth `n`, with `n`
+/// (from 1) appended.
+pub const BRACKET_PREFIX: &str = "editorBracketHighlight.foreground";
+
+/// `colors` key of the color for a bracket that closes nothing.
+pub const UNEXPECTED_BRACKET: &str = "editorBracketHighlight.unexpectedBracket.foreground";
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Theme {
+    #[serde(rename = "$schema", default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(rename = "type", default)]
+    pub kind: ThemeKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semantic_highlighting: Option<bool>,
+    #[serde(default, deserialize_with = "unique_map", skip_serializing_if = "BTreeMap::is_empty")]
+    pub colors: BTreeMap<String, Color>,
+    #[serde(default)]
+    pub token_colors: Vec<TokenColor>,
+    #[serde(default, deserialize_with = "unique_map", skip_serializing_if = "BTreeMap::is_empty")]
+    pub semantic_token_colors: BTreeMap<String, SemanticTokenStyle>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeKind {
+    #[default]
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TokenColor {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scope>,
+    pub settings: Settings,
+}
+
+/// A rule's `scope` field: either one selector string (possibly
+/// comma-separated) or a list of selectors.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Scope {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Settings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_style: Option<FontStyle>,
+}
+
+/// A `semanticTokenColors` value: a bare color or a style object.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum SemanticTokenStyle {
+    Color(Color),
+    Style(SemanticStyle),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SemanticStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_style: Option<FontStyle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underline: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strikethrough: Option<bool>,
+}
+
+/// A hex color in one of the forms VS Code accepts: `#rgb`, `#rgba`,
+/// `#rrggbb` or `#rrggbbaa`. The original spelling is preserved.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color(String);
+
+impl Color {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The same color spelled in lowercase with two digits per channel.
+    pub fn normalized(&self) -> Color {
+        let hex = &self.0[1..];
+        let digits: String = match hex.len() {
+            3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+            _ => hex.to_owned(),
+        };
+        Color(format!("#{}", digits.to_ascii_lowercase()))
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let valid = s
+            .strip_prefix('#')
+            .is_some_and(|hex| matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit()));
+        if valid {
+            Ok(Self(s.to_owned()))
+        } else {
+            Err(format!("invalid color `{s}`, expected #rgb, #rgba, #rrggbb or #rrggbbaa"))
+        }
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        s.parse()
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> String {
+        color.0
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Color {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Color".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "A hex color: #rgb, #rgba, #rrggbb or #rrggbbaa.",
+            "type": "string",
+            "pattern": "^#([0-9a-fA-F]{3,4}|[0-9a-fA-F]{6}|[0-9a-fA-F]{8})$",
+        })
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A `fontStyle` value: space-separated `italic`, `bold`, `underline` and
+/// `strikethrough`. The empty string is meaningful and resets inherited
+/// styles to none.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontStyle(u8);
+
+impl FontStyle {
+    pub const NONE: Self = Self(0);
+    pub const ITALIC: Self = Self(1);
+    pub const BOLD: Self = Self(2);
+    pub const UNDERLINE: Self = Self(4);
+    pub const STRIKETHROUGH: Self = Self(8);
+
+    const NAMES: [(Self, &'static str); 4] = [
+        (Self::ITALIC, "italic"),
+        (Self::BOLD, "bold"),
+        (Self::UNDERLINE, "underline"),
+        (Self::STRIKETHROUGH, "strikethrough"),
+    ];
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl std::ops::BitOr for FontStyle {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl FromStr for FontStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        s.split_whitespace().try_fold(Self::NONE, |style, word| {
+            Self::NAMES
+                .iter()
+                .find(|(_, name)| *name == word)
+                .map(|(flag, _)| style | *flag)
+                .ok_or_else(|| format!("unknown font style `{word}`"))
+        })
+    }
+}
+
+impl fmt::Display for FontStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let words: Vec<&str> =
+            Self::NAMES.iter().filter(|(flag, _)| self.contains(*flag)).map(|(_, name)| *name).collect();
+        f.write_str(&words.join(" "))
+    }
+}
+
+impl<'de> Deserialize<'de> for FontStyle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for FontStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for FontStyle {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "FontStyle".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let word = Self::NAMES.iter().map(|(_, name)| *name).collect::<Vec<_>>().join("|");
+        schemars::json_schema!({
+            "description": "Space-separated font styles; the empty string resets inherited styles.",
+            "type": "string",
+            "pattern": format!("^\\s*(({word})(\\s+({word}))*)?\\s*$"),
+        })
+    }
+}
+
+impl Theme {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+        Self::parse(path, &text)
+    }
+
+    /// Parses and validates theme JSON read from somewhere other than
+    /// `path`, which only names it in errors.
+    pub fn parse(path: &Path, text: &str) -> Result<Self> {
+        let theme: Theme =
+            serde_json::from_str(text).map_err(|source| Error::Theme { path: path.to_path_buf(), source })?;
+        theme.validate().map_err(|(index, message)| Error::InvalidRule { path: path.to_path_buf(), index, message })?;
+        Ok(theme)
+    }
+
+    /// Pretty-printed JSON with a trailing newline, as the theme files are
+    /// checked in.
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).expect("themes always serialize");
+        json.push('\n');
+        json
+    }
+
+    /// The JSON Schema of the theme format, pretty-printed with a trailing
+    /// newline as it is checked in.
+    #[cfg(feature = "schema")]
+    pub fn schema() -> String {
+        let mut json = serde_json::to_string_pretty(&schemars::schema_for!(Theme)).expect("schemas always serialize");
+        json.push('\n');
+        json
+    }
+
+    /// Checks constraints serde cannot express: every rule must set at least
+    /// one field, and a rule with a `scope` must contain a usable selector.
+    pub fn validate(&self) -> Result<(), (usize, String)> {
+        for (index, rule) in self.token_colors.iter().enumerate() {
+            if rule.settings == Settings::default() {
+                return Err((index, "rule has empty settings".to_owned()));
+            }
+            if rule.scope.is_some() && rule.is_default() {
+                continue;
+            }
+            if let Some(bad) = rule.selectors().into_iter().find(|s| Selector::parse(s).is_none()) {
+                return Err((index, format!("invalid selector `{bad}`")));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TokenColor {
+    /// Individual selectors of this rule, with comma lists split out.
+    pub fn selectors(&self) -> Vec<&str> {
+        let raw: Vec<&str> = match &self.scope {
+            None => Vec::new(),
+            Some(Scope::One(s)) => vec![s.as_str()],
+            Some(Scope::Many(list)) => list.iter().map(String::as_str).collect(),
+        };
+        raw.into_iter().flat_map(|s| s.split(',')).map(str::trim).filter(|s| !s.is_empty()).collect()
+    }
+
+    /// Whether this is a global rule (no scope, or a blank one) that sets the
+    /// theme defaults.
+    pub fn is_default(&self) -> bool {
+        match &self.scope {
+            None => true,
+            Some(Scope::One(s)) => s.trim_matches(|c: char| c == ',' || c.is_whitespace()).is_empty(),
+            Some(Scope::Many(_)) => false,
+        }
+    }
+}
+
+// Like `BTreeMap`'s own impl, but a repeated key is an error rather than
+// silently overwriting the earlier value.
+fn unique_map<'de, D, V>(deserializer: D) -> Result<BTreeMap<String, V>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    struct UniqueMap<V>(PhantomData<V>);
+
+    impl<'de, V: Deserialize<'de>> Visitor<'de> for UniqueMap<V> {
+        type Value = BTreeMap<String, V>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a map with unique keys")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut map = BTreeMap::new();
+            while let Some((key, value)) = access.next_entry::<String, V>()? {
+                if map.contains_key(&key) {
+                    return Err(de::Error::custom(format!("duplicate key `{key}`")));
+                }
+                map.insert(key, value);
+            }
+            Ok(map)
+        }
+    }
+
+    deserializer.deserialize_map(UniqueMap(PhantomData))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Result<Theme, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn parses_colors_and_font_styles() {
+        let theme = parse(
+            r##"{
+                "name": "t",
+                "type": "light",
+                "colors": { "editor.background": "#fff" },
+                "tokenColors": [
+                    { "scope": ["a", "b"], "settings": { "foreground": "#1a2638cc", "fontStyle": "bold italic" } },
+                    { "scope": "c", "settings": { "fontStyle": "" } }
+                ],
+                "semanticTokenColors": { "variable.readonly": "#ffc56d", "type": { "bold": true } }
+            }"##,
+        )
+        .unwrap();
+        assert_eq!(theme.kind, ThemeKind::Light);
+        let style = theme.token_colors[0].settings.font_style.unwrap();
+        assert!(style.contains(FontStyle::BOLD | FontStyle::ITALIC));
+        assert_eq!(style.to_string(), "italic bold");
+        assert_eq!(theme.token_colors[1].settings.font_style, Some(FontStyle::NONE));
+        assert_eq!(theme.semantic_token_colors.len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_colors() {
+        for bad in ["gray", "#12", "#12345", "1a2638", "#gggggg"] {
+            let json = format!(r#"{{ "name": "t", "tokenColors": [{{ "settings": {{ "foreground": "{bad}" }} }}] }}"#);
+            assert!(parse(&json).unwrap_err().contains("invalid color"), "{bad}");
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_font_styles_and_fields() {
+        let err = parse(r#"{ "name": "t", "tokenColors": [{ "settings": { "fontStyle": "bold oblique" } }] }"#);
+        assert!(err.unwrap_err().contains("unknown font style `oblique`"));
+
+        let err = parse(r##"{ "name": "t", "tokenColors": [{ "settings": { "foregound": "#fff" } }] }"##);
+        assert!(err.unwrap_err().contains("unknown field `foregound`"));
+    }
+
+    #[test]
+    fn rejects_duplicate_keys() {
+        let err = parse(r##"{ "name": "t", "colors": { "editor.background": "#000", "editor.background": "#fff" } }"##);
+        assert!(err.unwrap_err().contains("duplicate key `editor.background`"));
+
+        let err = parse(r##"{ "name": "t", "tokenColors": [{ "scope": "a", "scope": "b", "settings": {} }] }"##);
+        assert!(err.unwrap_err().contains("duplicate field `scope`"));
+    }
+
+    #[test]
+    fn validate_flags_empty_rules() {
+        let theme = parse(r#"{ "name": "t", "tokenColors": [{ "scope": "a", "settings": {} }] }"#).unwrap();
+        assert_eq!(theme.validate().unwrap_err().0, 0);
+    }
+}
@@ -0,0 +1,259 @@
+//! Turns source text into tokens carrying their TextMate scope stack.
+
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use syntect::parsing::{ParseState, ScopeStack, SyntaxDefinition, SyntaxReference, SyntaxSet};
+
+use crate::cache::Fnv;
+use crate::samples::Sample;
+use crate::{grammar, Error, Result};
+
+/// Where `pnpm install` puts the grammars Shiki highlights with.
+pub const SHIKI_GRAMMARS_DIR: &str = "node_modules/@shikijs/langs/dist";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Token {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub text: String,
+    /// Scope names, outermost first.
+    pub scopes: Vec<String>,
+}
+
+/// Where [`Tokenizer::discover`] looks for Shiki's grammars: `$SHIKI_GRAMMARS`,
+/// else [`SHIKI_GRAMMARS_DIR`] under `root`. It may not exist.
+pub fn shiki_grammars_dir(root: &Path) -> PathBuf {
+    std::env::var_os("SHIKI_GRAMMARS").map(PathBuf::from).unwrap_or_else(|| root.join(SHIKI_GRAMMARS_DIR))
+}
+
+pub struct Tokenizer {
+    syntaxes: SyntaxSet,
+    /// Identifies the grammars loaded, for cache keys.
+    fingerprint: u64,
+}
+
+impl Default for Tokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tokenizer {
+    /// Uses the grammars bundled with syntect.
+    pub fn new() -> Self {
+        Self { syntaxes: SyntaxSet::load_defaults_newlines(), fingerprint: Self::bundled_fingerprint() }
+    }
+
+    /// The fingerprint of the bundled grammars alone. They only change with
+    /// syntect, whose locked version build.rs passes in.
+    fn bundled_fingerprint() -> u64 {
+        Fnv::default().write(b"syntect").write(env!("SYNTECT_VERSION").as_bytes()).finish()
+    }
+
+    /// Adds the TextMate grammars in `dir` (see [`grammar::load_dir`]) on top
+    /// of the bundled ones; for a given file extension they take precedence.
+    pub fn with_grammars(dir: &Path) -> Result<Self> {
+        let _span = tracing::debug_span!("load_grammars", dir = %dir.display()).entered();
+        let mut builder = SyntaxSet::load_defaults_newlines().into_builder();
+        for definition in grammar::load_dir(dir)? {
+            tracing::trace!(scope = %definition.scope, name = definition.name, "grammar");
+            builder.add(definition);
+        }
+        let mut fingerprint = Fnv::default();
+        fingerprint.write(&Self::bundled_fingerprint().to_le_bytes());
+        for path in grammar::grammar_files(dir)? {
+            let bytes = std::fs::read(&path).map_err(|e| Error::io(&path, e))?;
+            fingerprint.write(path.file_name().unwrap_or_default().as_encoded_bytes()).write(&bytes);
+        }
+        Ok(Self { syntaxes: builder.build(), fingerprint: fingerprint.finish() })
+    }
+
+    /// A hash of the grammars this tokenizer was built from, which changes
+    /// whenever one of them does.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Prefers Shiki's own grammars: those in `$SHIKI_GRAMMARS`, else in
+    /// [`SHIKI_GRAMMARS_DIR`] under `root`, else the bundled grammars.
+    pub fn discover(root: &Path) -> Result<Self> {
+        let dir = shiki_grammars_dir(root);
+        if dir.is_dir() {
+            Self::with_grammars(&dir)
+        } else {
+            tracing::debug!(dir = %dir.display(), "no Shiki grammars, using the bundled ones");
+            Ok(Self::new())
+        }
+    }
+
+    /// Finds a grammar for `path` by its file extension.
+    pub fn syntax_for(&self, path: &Path) -> Option<&SyntaxReference> {
+        let ext = path.extension()?.to_str()?;
+        self.syntaxes.find_syntax_by_extension(ext)
+    }
+
+    /// Looks a grammar up by name (e.g. `Rust`) or file extension.
+    pub fn find_syntax(&self, token: &str) -> Option<&SyntaxReference> {
+        self.syntaxes.find_syntax_by_token(token)
+    }
+
+    /// The full definition behind `syntax`, for tools that inspect its
+    /// contexts. This unpacks the whole set and is not cheap.
+    pub fn definition(&self, syntax: &SyntaxReference) -> Option<SyntaxDefinition> {
+        let builder = self.syntaxes.clone().into_builder();
+        builder.syntaxes().iter().rev().find(|d| d.scope == syntax.scope).cloned()
+    }
+
+    /// Every grammar's full definition; see [`Tokenizer::definition`].
+    pub fn definitions(&self) -> Vec<SyntaxDefinition> {
+        self.syntaxes.clone().into_builder().syntaxes().to_vec()
+    }
+
+    pub fn tokenize(&self, syntax: &SyntaxReference, path: &Path, source: &str) -> Result<Vec<Token>> {
+        let _span = tracing::debug_span!("tokenize", path = %path.display(), grammar = syntax.name).entered();
+        let mut tokens = Vec::new();
+        for line in self.tokenize_lines(syntax, path, source.as_bytes()) {
+            tokens.extend(line?.1);
+        }
+        tracing::debug!(tokens = tokens.len(), "tokenized");
+        Ok(tokens)
+    }
+
+    /// Tokenizes every sample on the rayon pool, keeping their order; `None`
+    /// for samples without a grammar.
+    pub fn tokenize_all(&self, samples: &[Sample]) -> Result<Vec<Option<Vec<Token>>>> {
+        samples
+            .par_iter()
+            .map(|sample| match self.syntax_for(&sample.path) {
+                Some(syntax) => self.tokenize(syntax, &sample.path, &sample.source).map(Some),
+                None => Ok(None),
+            })
+            .collect()
+    }
+
+    /// [`Tokenizer::tokenize`] one line at a time, reading `reader` as it
+    /// goes, so memory stays bounded by the longest line rather than the
+    /// input. Only the grammar's state is carried between lines.
+    pub fn tokenize_lines<'a, R: BufRead>(
+        &'a self,
+        syntax: &SyntaxReference,
+        path: &'a Path,
+        reader: R,
+    ) -> Lines<'a, R> {
+        Lines {
+            syntaxes: &self.syntaxes,
+            path,
+            reader,
+            buffer: String::new(),
+            state: ParseState::new(syntax),
+            stack: ScopeStack::new(),
+            line: 0,
+            done: false,
+        }
+    }
+}
+
+/// The tokens of each line of a reader, as `(line number, tokens)`; see
+/// [`Tokenizer::tokenize_lines`]. Stops after the first error.
+pub struct Lines<'a, R> {
+    syntaxes: &'a SyntaxSet,
+    path: &'a Path,
+    reader: R,
+    buffer: String,
+    state: ParseState,
+    stack: ScopeStack,
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> Lines<'_, R> {
+    fn next_line(&mut self) -> Result<Option<(usize, Vec<Token>)>> {
+        let fail = |message: String| Error::Tokenize { path: self.path.to_path_buf(), message };
+
+        self.buffer.clear();
+        if self.reader.read_line(&mut self.buffer).map_err(|e| Error::io(self.path, e))? == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        let line = self.buffer.as_str();
+        let ops = self.state.parse_line(line, self.syntaxes).map_err(|e| fail(e.to_string()))?;
+
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        for (pos, op) in ops.into_iter().map(|(pos, op)| (Some(pos), Some(op))).chain([(None, None)]) {
+            let end = pos.unwrap_or(line.len());
+            if end > start {
+                let text = line[start..end].trim_end_matches(['\n', '\r']);
+                if !text.is_empty() {
+                    tokens.push(Token {
+                        line: self.line,
+                        column: line[..start].chars().count() + 1,
+                        text: text.to_owned(),
+                        scopes: self.stack.as_slice().iter().map(|s| s.build_string()).collect(),
+                    });
+                }
+                start = end;
+            }
+            if let Some(op) = op {
+                self.stack.apply(&op).map_err(|e| fail(e.to_string()))?;
+            }
+        }
+        Ok(Some((self.line, tokens)))
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<'_, R> {
+    type Item = Result<(usize, Vec<Token>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.next_line().transpose();
+        self.done = !matches!(next, Some(Ok(_)));
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_match_whole_file_tokenization() {
+        let tokenizer = Tokenizer::new();
+        let path = Path::new("t.rs");
+        let syntax = tokenizer.syntax_for(path).unwrap();
+        let source = "/* a\n b */ fn main() {\n\n    let s = \"x\";\r\n}";
+
+        let lines: Vec<(usize, Vec<Token>)> =
+            tokenizer.tokenize_lines(syntax, path, source.as_bytes()).collect::<Result<_>>().unwrap();
+        assert_eq!(lines.iter().map(|(n, _)| *n).collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+        // The open comment carries over into the second line
+        assert!(lines[1].1[0].scopes.iter().any(|s| s.starts_with("comment.block")));
+        let flat: Vec<Token> = lines.into_iter().flat_map(|(_, tokens)| tokens).collect();
+        assert_eq!(flat, tokenizer.tokenize(syntax, path, source).unwrap());
+
+        let mut broken = tokenizer.tokenize_lines(syntax, path, &b"fn x() {}\n\xff\nfn y() {}\n"[..]);
+        assert!(matches!(broken.next(), Some(Ok((1, _)))));
+        assert!(matches!(broken.next(), Some(Err(_))));
+        assert!(broken.next().is_none());
+    }
+
+    #[test]
+    fn tokenize_all_keeps_sample_order() {
+        let tokenizer = Tokenizer::new();
+        let sample = |path: &str, source: &str| Sample { path: PathBuf::from(path), source: source.to_owned() };
+        let samples = [sample("a.rs", "fn a() {}\n"), sample("b.unknown", "b\n"), sample("c.py", "def c(): pass\n")];
+        let tokenized = tokenizer.tokenize_all(&samples).unwrap();
+        assert_eq!(tokenized.len(), 3);
+        assert_eq!(tokenized[0].as_ref().unwrap()[0].text, "fn");
+        assert!(tokenized[1].is_none());
+        assert_eq!(tokenized[2].as_ref().unwrap()[0].text, "def");
+    }
+}
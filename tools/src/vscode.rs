@@ -0,0 +1,124 @@
+//! Packaging of the themes as a VS Code extension: a directory that
+//! `vsce package` turns into a `.vsix` as is, with the manifest generated
+//! from the theme files instead of maintained by hand.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::theme::{Theme, ThemeKind};
+use crate::{Error, Result};
+
+pub const EXTENSION_NAME: &str = "zenn-shiki-theme";
+pub const PUBLISHER: &str = "stinbox";
+pub const REPOSITORY: &str = "https://github.com/stinbox/zenn-shiki-theme";
+
+/// The extension's `package.json`, contributing each of `themes` (keyed by
+/// file name) at `themes/<file>`. Without a `license` text the extension is
+/// marked `UNLICENSED`, as npm does for packages that grant no license.
+pub fn manifest(themes: &[(String, Theme)], version: &str, license: Option<&str>) -> Value {
+    let contributes: Vec<Value> = themes
+        .iter()
+        .map(|(file, theme)| {
+            json!({
+                "label": theme.display_name.as_deref().unwrap_or(&theme.name),
+                "uiTheme": match theme.kind {
+                    ThemeKind::Dark => "vs-dark",
+                    ThemeKind::Light => "vs",
+                },
+                "path": format!("./themes/{file}"),
+            })
+        })
+        .collect();
+    json!({
+        "name": EXTENSION_NAME,
+        "displayName": "Zenn Shiki Theme",
+        "description": "The code block colors of Zenn, as a VS Code color theme",
+        "version": version,
+        "publisher": PUBLISHER,
+        "license": if license.is_some() { "SEE LICENSE IN LICENSE" } else { "UNLICENSED" },
+        "repository": { "type": "git", "url": REPOSITORY },
+        "engines": { "vscode": "^1.75.0" },
+        "categories": ["Themes"],
+        "keywords": ["zenn", "shiki", "theme"],
+        "contributes": { "themes": contributes },
+    })
+}
+
+/// Writes the extension directory to `out`: the manifest, a README,
+/// `license` as LICENSE when there is one and the themes, replacing any
+/// themes from an earlier run. Returns the paths written.
+pub fn package(themes: &[(String, Theme)], version: &str, license: Option<&str>, out: &Path) -> Result<Vec<PathBuf>> {
+    let themes_dir = out.join("themes");
+    if themes_dir.exists() {
+        fs::remove_dir_all(&themes_dir).map_err(|e| Error::io(&themes_dir, e))?;
+    }
+    fs::create_dir_all(&themes_dir).map_err(|e| Error::io(&themes_dir, e))?;
+
+    let mut manifest = serde_json::to_string_pretty(&manifest(themes, version, license)).expect("manifests serialize");
+    manifest.push('\n');
+    let mut files = vec![(out.join("package.json"), manifest), (out.join("README.md"), readme(themes))];
+    let license_path = out.join("LICENSE");
+    match license {
+        Some(license) => files.push((license_path, license.to_owned())),
+        None if license_path.exists() => fs::remove_file(&license_path).map_err(|e| Error::io(&license_path, e))?,
+        None => {}
+    }
+    files.extend(themes.iter().map(|(file, theme)| (themes_dir.join(file), theme.to_json())));
+
+    for (path, contents) in &files {
+        fs::write(path, contents).map_err(|e| Error::io(path, e))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+fn readme(themes: &[(String, Theme)]) -> String {
+    let mut out = String::from(
+        "# Zenn Shiki Theme\n\nThe code block colors of [Zenn](https://zenn.dev), for VS Code.\n\n## Themes\n\n",
+    );
+    for (_, theme) in themes {
+        out.push_str(&format!("- {}\n", theme.display_name.as_deref().unwrap_or(&theme.name)));
+    }
+    out.push_str(&format!("\nGenerated from the palette in {REPOSITORY}.\n"));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette;
+
+    #[test]
+    fn manifest_contributes_every_theme() {
+        let themes = palette::shipped();
+        let manifest = manifest(&themes, "1.2.3", None);
+        assert_eq!(manifest["version"], "1.2.3");
+
+        let contributed = manifest["contributes"]["themes"].as_array().unwrap();
+        assert_eq!(contributed.len(), themes.len());
+        let light = contributed.iter().find(|t| t["path"] == "./themes/zenn-light.json").unwrap();
+        assert_eq!((&light["label"], &light["uiTheme"]), (&json!("Zenn Light"), &json!("vs")));
+        assert_eq!(contributed[0]["uiTheme"], "vs-dark");
+        assert_eq!(manifest["license"], "UNLICENSED");
+    }
+
+    #[test]
+    fn package_ships_the_license_it_is_given() {
+        let out = std::env::temp_dir().join(format!("zenn-vscode-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&out);
+        let read_manifest =
+            || serde_json::from_str::<Value>(&fs::read_to_string(out.join("package.json")).unwrap()).unwrap();
+
+        let written = package(&palette::shipped(), "1.2.3", Some("License text\n"), &out).unwrap();
+        assert!(written.contains(&out.join("LICENSE")));
+        assert_eq!(fs::read_to_string(out.join("LICENSE")).unwrap(), "License text\n");
+        assert_eq!(read_manifest()["license"], "SEE LICENSE IN LICENSE");
+
+        let written = package(&palette::shipped(), "1.2.3", None, &out).unwrap();
+        assert!(!written.contains(&out.join("LICENSE")));
+        assert!(!out.join("LICENSE").exists());
+        assert_eq!(read_manifest()["license"], "UNLICENSED");
+        fs::remove_dir_all(&out).unwrap();
+    }
+}
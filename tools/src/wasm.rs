@@ -0,0 +1,71 @@
+//! wasm-bindgen exports of the theme resolver for the browser playground.
+//!
+//! Build with
+//! `wasm-pack build tools --target web --no-default-features --features wasm`
+//! and use it from JavaScript as:
+//!
+//! ```js
+//! const resolver = new ThemeResolver(JSON.stringify(theme));
+//! const style = resolver.resolve("source.rust meta.function entity.name.function.rust");
+//! span.style.color = style.foreground;
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::resolver::Resolver;
+use crate::theme::Theme;
+
+/// The style a scope stack resolves to.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct Style {
+    pub foreground: String,
+    pub background: String,
+    /// Space-separated `italic`, `bold`, `underline` and `strikethrough`.
+    #[wasm_bindgen(js_name = fontStyle)]
+    pub font_style: String,
+    /// Index into `tokenColors` of the rule that set the foreground, or
+    /// `undefined` for the theme default.
+    #[wasm_bindgen(js_name = foregroundRule)]
+    pub foreground_rule: Option<usize>,
+}
+
+#[wasm_bindgen]
+pub struct ThemeResolver {
+    resolver: Resolver,
+}
+
+#[wasm_bindgen]
+impl ThemeResolver {
+    /// Parses and validates a theme JSON string.
+    #[wasm_bindgen(constructor)]
+    pub fn new(theme_json: &str) -> Result<ThemeResolver, JsError> {
+        Ok(Self { resolver: parse(theme_json)? })
+    }
+
+    /// Replaces the theme, e.g. after each edit in the playground. On error
+    /// the previous theme stays in effect.
+    #[wasm_bindgen(js_name = setTheme)]
+    pub fn set_theme(&mut self, theme_json: &str) -> Result<(), JsError> {
+        self.resolver = parse(theme_json)?;
+        Ok(())
+    }
+
+    /// Resolves a space-separated scope stack, outermost scope first.
+    pub fn resolve(&self, scopes: &str) -> Style {
+        let scopes: Vec<&str> = scopes.split_whitespace().collect();
+        let resolved = self.resolver.resolve(&scopes);
+        Style {
+            foreground: resolved.foreground.to_owned(),
+            background: resolved.background.to_owned(),
+            font_style: resolved.font_style.to_string(),
+            foreground_rule: resolved.foreground_rule,
+        }
+    }
+}
+
+fn parse(theme_json: &str) -> Result<Resolver, JsError> {
+    let theme: Theme = serde_json::from_str(theme_json)?;
+    theme.validate().map_err(|(index, message)| JsError::new(&format!("invalid rule #{index}: {message}")))?;
+    Ok(Resolver::new(&theme))
+}
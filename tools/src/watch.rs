@@ -0,0 +1,61 @@
+//! Filesystem watching for the live tools.
+//!
+//! Editors save in several steps (write a temp file, rename, touch), so
+//! events are collected until the tree has been quiet for [`DEBOUNCE`] and
+//! handed over as one batch.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+
+use crate::{Error, Result};
+
+pub const DEBOUNCE: Duration = Duration::from_millis(150);
+
+pub struct Watcher {
+    // Dropping the watcher stops the events
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+}
+
+impl Watcher {
+    /// Watches every path in `paths`, recursively for directories.
+    pub fn new(paths: &[PathBuf]) -> Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let failed = |path: &Path, e: notify::Error| Error::Watch { path: path.to_path_buf(), message: e.to_string() };
+        let mut watcher = notify::recommended_watcher(tx).map_err(|e| failed(Path::new("."), e))?;
+        for path in paths {
+            watcher.watch(path, RecursiveMode::Recursive).map_err(|e| failed(path, e))?;
+        }
+        Ok(Self { _watcher: watcher, events })
+    }
+
+    /// Blocks until something changes, then returns the changed paths once
+    /// the burst is over. Returns `None` if the watcher has shut down.
+    pub fn next_batch(&self) -> Option<Vec<PathBuf>> {
+        let mut changed = BTreeSet::new();
+        let mut timeout = None;
+        loop {
+            let event = match timeout {
+                None => self.events.recv().ok(),
+                Some(timeout) => match self.events.recv_timeout(timeout) {
+                    Ok(event) => Some(event),
+                    Err(RecvTimeoutError::Timeout) => return Some(changed.into_iter().collect()),
+                    Err(RecvTimeoutError::Disconnected) => None,
+                },
+            }?;
+            // Errors such as a vanished temp file only mean that path is gone
+            let Ok(event) = event else { continue };
+            if matches!(event.kind, EventKind::Access(_)) {
+                continue;
+            }
+            changed.extend(event.paths);
+            if !changed.is_empty() {
+                timeout = Some(DEBOUNCE);
+            }
+        }
+    }
+}
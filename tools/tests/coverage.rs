@@ -0,0 +1,64 @@
+//! Fails when a sample token falls through to the default foreground and its
+//! scope is neither a known gap in `tests/unthemed-scopes.txt` nor allowed
+//! on purpose by `[audit] allow` in `tools.toml`, so new sample code cannot
+//! silently introduce uncovered scopes. Tokenized with the bundled grammars,
+//! as the snapshots are.
+
+use std::fs;
+use std::path::Path;
+
+use zenn_theme_tools::audit::{self, FileReport};
+use zenn_theme_tools::config::Config;
+use zenn_theme_tools::resolver::Resolver;
+use zenn_theme_tools::selector::matches_scope;
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::tokenizer::Tokenizer;
+use zenn_theme_tools::{repo_root, samples, SAMPLES_DIR, THEME_PATH};
+
+/// The scopes `tools audit` is told to allow, then the known gaps.
+fn allowlist(root: &Path) -> Vec<String> {
+    let mut allowlist = Config::load(root).unwrap().audit.allow;
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/unthemed-scopes.txt");
+    let gaps = fs::read_to_string(path).unwrap();
+    let gaps = gaps.lines().map(|line| line.split('#').next().unwrap_or_default().trim());
+    allowlist.extend(gaps.filter(|line| !line.is_empty()).map(str::to_owned));
+    allowlist
+}
+
+#[test]
+fn every_unthemed_scope_is_allowlisted() {
+    let root = repo_root();
+    let theme = Theme::load(&root.join(THEME_PATH)).unwrap();
+    let samples = samples::discover(&root.join(SAMPLES_DIR)).unwrap();
+    let report = audit::run(&Resolver::new(&theme), &Tokenizer::new(), &samples).unwrap();
+    let allowlist = allowlist(&root);
+
+    let mut uncovered = Vec::new();
+    let mut used = vec![false; allowlist.len()];
+    for (path, file) in &report.files {
+        let FileReport::Audited { unthemed } = file else {
+            continue;
+        };
+        for (scope, found) in unthemed {
+            let matching: Vec<usize> = (0..allowlist.len()).filter(|&i| matches_scope(scope, &allowlist[i])).collect();
+            if matching.is_empty() {
+                uncovered.push(format!(
+                    "{scope} at {}:{}:{} `{}`",
+                    path.display(),
+                    found.line,
+                    found.column,
+                    found.example
+                ));
+            }
+            matching.into_iter().for_each(|i| used[i] = true);
+        }
+    }
+    let stale: Vec<&str> = allowlist.iter().zip(&used).filter(|(_, used)| !**used).map(|(s, _)| s.as_str()).collect();
+
+    assert!(
+        uncovered.is_empty(),
+        "tokens with no theme color; add a rule, or list the scope in tests/unthemed-scopes.txt or tools.toml:\n  {}",
+        uncovered.join("\n  ")
+    );
+    assert!(stale.is_empty(), "allowlisted scopes no sample leaves unthemed any more:\n  {}", stale.join("\n  "));
+}
@@ -0,0 +1,32 @@
+//! Golden output of the export backends for every shipped theme.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite `tests/exports/` after an
+//! intentional theme or exporter change, then review the diff.
+
+use std::fs;
+use std::path::Path;
+
+use zenn_theme_tools::export::helix;
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::{repo_root, theme_paths, THEMES_DIR};
+
+#[test]
+fn helix_themes_match_snapshots() {
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/exports");
+    let mut failures = Vec::new();
+
+    for path in theme_paths(&repo_root().join(THEMES_DIR)).unwrap() {
+        let theme = Theme::load(&path).unwrap();
+        let actual = helix::helix(&theme);
+        toml::from_str::<toml::Table>(&actual).unwrap_or_else(|e| panic!("{}: invalid TOML: {e}", path.display()));
+
+        let snapshot = dir.join(format!("{}.helix.toml", theme.name));
+        if update {
+            fs::write(&snapshot, &actual).unwrap();
+        } else if fs::read_to_string(&snapshot).ok().as_deref() != Some(actual.as_str()) {
+            failures.push(snapshot.display().to_string());
+        }
+    }
+    assert!(failures.is_empty(), "stale export snapshots (rerun with UPDATE_SNAPSHOTS=1):\n{}", failures.join("\n"));
+}
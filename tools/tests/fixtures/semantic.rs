@@ -0,0 +1,88 @@
+//! Semantic-token fixture: what rust-analyzer reports for each marked
+//! token, written as caret annotations under the line they refer to.
+
+use std::collections::HashMap;
+//  ^^^ namespace.library
+//       ^^^^^^^^^^^ namespace.library
+//                    ^^^^^^^ struct.library
+
+/// Running totals keyed by name.
+//  ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ comment.documentation
+#[derive(Debug, Default)]
+//^^^^^^ attribute.attribute.defaultLibrary
+//       ^^^^^ derive.attribute.defaultLibrary
+pub struct Ledger<'a> {
+//         ^^^^^^ struct.declaration.public
+//                ^^ lifetime.declaration
+    entries: HashMap<&'a str, i64>,
+//  ^^^^^^^ property.declaration
+//                    ^^ lifetime
+//                            ^^^ builtinType
+}
+
+const LIMIT: i64 = 1_000;
+//    ^^^^^ const.declaration
+//                 ^^^^^ number
+
+static mut SEEN: usize = 0;
+//         ^^^^ static.declaration.mutable.static
+
+impl<'a> Ledger<'a> {
+    pub fn add(&mut self, name: &'a str, amount: i64) -> bool {
+//         ^^^ method.associated.declaration.public
+//                  ^^^^ selfKeyword.declaration.mutable.reference
+//                        ^^^^ parameter.declaration
+//                                       ^^^^^^ parameter.declaration
+        let total = self.entries.entry(name).or_default();
+//          ^^^^^ variable.declaration.mutable.reference
+//                       ^^^^^^^ property
+//                               ^^^^^ method.defaultLibrary.library
+        *total += amount;
+//      ^ operator.mutable
+//       ^^^^^ variable.mutable.reference
+        if *total > LIMIT {
+//      ^^ keyword.controlFlow
+//                  ^^^^^ const
+            return false;
+//          ^^^^^^ keyword.controlFlow
+//                 ^^^^^ boolean
+        }
+        unsafe { SEEN += 1 };
+//      ^^^^^^ keyword.unsafe
+//               ^^^^ static.mutable.static.unsafe
+        true
+//      ^^^^ boolean
+    }
+
+    pub fn report(&self) -> String {
+        let mut lines = Vec::new();
+//              ^^^^^ variable.declaration.mutable
+//                      ^^^ struct.defaultLibrary.library
+        for (name, amount) in &self.entries {
+            lines.push(format!("{name}: {amount:>6}"));
+//                     ^^^^^^ macro.defaultLibrary.library
+//                               ^^^^ variable.reference
+//                                             ^^^ formatSpecifier
+        }
+        'outer: loop {
+//      ^^^^^^ label.declaration
+            break 'outer;
+//                ^^^^^^ label
+        }
+        lines.join("\n")
+//                 ^^^^ string
+//                  ^^ escapeSequence
+    }
+}
+
+#[tokio::main]
+//^^^^^ namespace.library
+//       ^^^^ procMacro.attribute.library
+async fn main() {
+//       ^^^^ function.async.declaration
+    let ledger = Ledger::<'static>::default();
+//               ^^^^^^ struct
+//                                  ^^^^^^^ function.defaultLibrary.library.trait
+    println!("{}", ledger.report());
+//  ^^^^^^^ macro.defaultLibrary.library
+}
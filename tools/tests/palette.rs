@@ -0,0 +1,109 @@
+//! The checked-in themes are exactly what the palette module generates.
+
+use std::fs;
+
+use zenn_theme_tools::colorblind::{self, Deficiency, MIN_DISTANCE};
+use zenn_theme_tools::colors::Rgba;
+use zenn_theme_tools::contrast::{self, AAA_NORMAL, AA_NORMAL};
+use zenn_theme_tools::palette::{self, Overlay, Palette, Role};
+use zenn_theme_tools::resolver::Resolver;
+use zenn_theme_tools::theme::ThemeKind;
+use zenn_theme_tools::{repo_root, THEMES_DIR};
+
+#[test]
+fn shipped_themes_match_generated_output() {
+    for (file, theme) in palette::shipped() {
+        let shipped = fs::read_to_string(repo_root().join(THEMES_DIR).join(&file)).unwrap();
+        assert!(theme.to_json() == shipped, "src/themes/{file} is out of date; run `cargo xtask build-theme`");
+    }
+}
+
+#[test]
+fn generation_is_deterministic() {
+    let first = palette::generate("zenn", "Zenn", &Palette::zenn()).to_json();
+    let second = palette::generate("zenn", "Zenn", &Palette::zenn()).to_json();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn high_contrast_variant_clears_aaa() {
+    let theme = shipped("zenn-high-contrast.json");
+    for entry in contrast::check(&theme) {
+        assert!(entry.ratio >= AAA_NORMAL, "{} is {:.2}:1 on {}", entry.label, entry.ratio, entry.background);
+    }
+}
+
+fn shipped(file: &str) -> zenn_theme_tools::theme::Theme {
+    palette::shipped().into_iter().find(|(name, _)| name == file).unwrap().1
+}
+
+#[test]
+fn empty_overlay_reproduces_the_base() {
+    let overlay = Overlay {
+        name: "zenn",
+        display_name: "Zenn",
+        kind: ThemeKind::Dark,
+        colors: &[],
+        min_contrast: None,
+        rules: &[],
+    };
+    assert_eq!(overlay.generate(&Palette::zenn()), shipped("zenn.json"));
+}
+
+#[test]
+fn light_variant_clears_aa() {
+    let theme = shipped("zenn-light.json");
+    assert_eq!(theme.kind, ThemeKind::Light);
+    for entry in contrast::check(&theme) {
+        assert!(entry.ratio >= AA_NORMAL, "{} is {:.2}:1 on {}", entry.label, entry.ratio, entry.background);
+    }
+}
+
+#[test]
+fn deuteranopia_variant_has_no_confusable_pairs() {
+    let theme = shipped("zenn-deuteranopia.json");
+    for deficiency in Deficiency::ALL {
+        let found = colorblind::confusions(&theme, deficiency);
+        assert!(found.is_empty(), "{}: {} confusable pair(s)", deficiency.name(), found.len());
+    }
+}
+
+#[test]
+fn bracket_colors_are_readable_and_apart() {
+    for (file, theme) in palette::shipped() {
+        let resolver = Resolver::new(&theme);
+        let background = Rgba::parse(resolver.default_background()).unwrap();
+        let colors: Vec<Rgba> = resolver.bracket_colors().iter().map(|c| Rgba::parse(c).unwrap()).collect();
+        assert_eq!(colors.len(), 6, "{file}");
+        for (i, a) in colors.iter().enumerate() {
+            let ratio = contrast::contrast_ratio(*a, background);
+            assert!(ratio >= AA_NORMAL, "{file}: bracket color {} is {ratio:.2}:1", i + 1);
+            for (j, b) in colors.iter().enumerate().skip(i + 1) {
+                let distance = a.oklab_distance(*b);
+                assert!(
+                    distance >= MIN_DISTANCE,
+                    "{file}: bracket colors {} and {} are {distance:.3} apart",
+                    i + 1,
+                    j + 1
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn inverted_palette_is_a_readable_light_draft() {
+    let base = Palette::zenn();
+    let draft = palette::invert(&base, AA_NORMAL);
+    assert!(draft.background.relative_luminance() > 0.9, "background {}", draft.background);
+    // Hue survives wherever the lift left enough chroma to have one
+    for role in [Role::Keyword, Role::Function, Role::Literal] {
+        let (from, to) = (base.get(role).to_oklch(), draft.get(role).to_oklch());
+        let turn = (to.h - from.h + 540.0).rem_euclid(360.0) - 180.0;
+        assert!(turn.abs() < 10.0, "{role:?} hue moved {turn:.1}°");
+    }
+    let theme = palette::generate_kind("draft", "Draft", ThemeKind::Light, &draft);
+    for entry in contrast::check(&theme) {
+        assert!(entry.ratio >= AA_NORMAL, "{} is {:.2}:1 on {}", entry.label, entry.ratio, entry.background);
+    }
+}
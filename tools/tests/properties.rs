@@ -0,0 +1,102 @@
+//! Invariants every theme keeps over the sample corpus, checked on whole
+//! samples and on random line ranges of them, so a regression shows up as
+//! one broken rule instead of a wall of snapshot changes. Tokenized with the
+//! bundled grammars, as the snapshots are.
+
+use std::sync::OnceLock;
+
+use proptest::prelude::*;
+use zenn_theme_tools::colors::Rgba;
+use zenn_theme_tools::resolver::{FontStyle, Resolver};
+use zenn_theme_tools::samples::{self, Sample};
+use zenn_theme_tools::selector::matches_scope;
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::tokenizer::{Token, Tokenizer};
+use zenn_theme_tools::{repo_root, theme_paths, SAMPLES_DIR, THEMES_DIR};
+
+struct Corpus {
+    tokenizer: Tokenizer,
+    themes: Vec<(String, Theme)>,
+    samples: Vec<Sample>,
+}
+
+fn corpus() -> &'static Corpus {
+    static CORPUS: OnceLock<Corpus> = OnceLock::new();
+    CORPUS.get_or_init(|| {
+        let root = repo_root();
+        let themes = theme_paths(&root.join(THEMES_DIR))
+            .unwrap()
+            .into_iter()
+            .map(|path| (path.file_stem().unwrap().to_string_lossy().into_owned(), Theme::load(&path).unwrap()))
+            .collect();
+        let samples = samples::discover(&root.join(SAMPLES_DIR)).unwrap();
+        Corpus { tokenizer: Tokenizer::new(), themes, samples }
+    })
+}
+
+fn tokenize(sample: &Sample, source: &str) -> Option<Vec<Token>> {
+    let tokenizer = &corpus().tokenizer;
+    let syntax = tokenizer.syntax_for(&sample.path)?;
+    Some(tokenizer.tokenize(syntax, &sample.path, source).unwrap())
+}
+
+fn within(token: &Token, selector: &str) -> bool {
+    token.scopes.iter().any(|scope| matches_scope(scope, selector))
+}
+
+fn same_color(a: &str, b: &str) -> bool {
+    Rgba::parse(a).map(Rgba::to_hex) == Rgba::parse(b).map(Rgba::to_hex)
+}
+
+/// The first token breaking an invariant under `resolver`, described.
+fn violation(resolver: &Resolver, tokens: &[Token]) -> Option<String> {
+    tokens.iter().filter(|t| !t.text.trim().is_empty()).find_map(|token| {
+        let style = resolver.resolve(&token.scopes);
+        let plain = same_color(style.foreground, resolver.default_foreground());
+        let problem = if within(token, "keyword") && plain {
+            "keyword token in the editor foreground"
+        } else if within(token, "comment") && style.font_style.contains(FontStyle::BOLD) {
+            "bold comment"
+        } else {
+            return None;
+        };
+        Some(format!("{problem}: {}:{} `{}` {}", token.line, token.column, token.text, token.scopes.join(" ")))
+    })
+}
+
+fn check(sample: &Sample, source: &str) -> Result<(), String> {
+    let Some(tokens) = tokenize(sample, source) else { return Ok(()) };
+    for (name, theme) in &corpus().themes {
+        if let Some(problem) = violation(&Resolver::new(theme), &tokens) {
+            return Err(format!("{name}, {}: {problem}", sample.path.display()));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn whole_samples_keep_the_invariants() {
+    let failures: Vec<String> = corpus().samples.iter().filter_map(|s| check(s, &s.source).err()).collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    // Starting mid-file puts the grammar in states the whole samples never
+    // reach, such as a string or comment body read as code
+    #[test]
+    fn sliced_samples_keep_the_invariants(
+        index in any::<prop::sample::Index>(),
+        start in any::<prop::sample::Index>(),
+        len in 1..40usize,
+    ) {
+        let sample = index.get(&corpus().samples);
+        let lines: Vec<&str> = sample.source.split_inclusive('\n').collect();
+        let start = start.index(lines.len().max(1));
+        let source: String = lines.iter().skip(start).take(len).copied().collect();
+        if let Err(problem) = check(sample, &source) {
+            return Err(TestCaseError::fail(problem));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! The Rust samples that need nothing beyond std must compile, so readers
+//! can paste them as they are. The others depend on crates the samples do
+//! not declare and are only tokenized.
+
+use std::fs;
+use std::process::Command;
+
+use zenn_theme_tools::{repo_root, SAMPLES_DIR};
+
+/// Modules under `rust/` that build with std alone.
+const STD_ONLY: &[&str] = &[
+    "adapters",
+    "allocator_api",
+    "bindings_generated",
+    "expressions",
+    "macro_export",
+    "matrix",
+    "mem_utils",
+    "nested_generics",
+    "plugins",
+    "retry",
+    "slices",
+    "thread_local",
+    "track_caller",
+    "typed_builder",
+    "unicode",
+    "units",
+    "vec_capacity",
+    "visitor",
+];
+
+#[test]
+fn std_only_samples_compile() {
+    let samples = repo_root().join(SAMPLES_DIR).join("rust");
+    let out = std::env::temp_dir().join(format!("zenn-samples-{}", std::process::id()));
+    fs::create_dir_all(&out).unwrap();
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+
+    let mut failures = Vec::new();
+    for module in STD_ONLY {
+        // Each module on its own crate root, so macros expand at `crate::`
+        let root = out.join(format!("{module}.rs"));
+        let path = samples.join(format!("{module}.rs"));
+        fs::write(&root, format!("#[path = {:?}]\nmod {module};\n", path.display().to_string())).unwrap();
+        let output = Command::new(&rustc)
+            .args(["--edition", "2021", "--crate-type", "lib", "--emit", "metadata", "-A", "warnings"])
+            .arg("--out-dir")
+            .arg(&out)
+            .arg(&root)
+            .output()
+            .unwrap();
+        if !output.status.success() {
+            failures.push(format!("{module}:\n{}", String::from_utf8_lossy(&output.stderr)));
+        }
+    }
+    let _ = fs::remove_dir_all(&out);
+    assert!(failures.is_empty(), "samples that no longer compile:\n{}", failures.join("\n"));
+}
@@ -0,0 +1,11 @@
+use std::fs;
+
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::{repo_root, SCHEMA_PATH};
+
+#[test]
+fn committed_schema_matches_the_models() {
+    let path = repo_root().join(SCHEMA_PATH);
+    let committed = fs::read_to_string(&path).unwrap_or_default();
+    assert!(committed == Theme::schema(), "{} is out of date; run `cargo xtask schema`", path.display());
+}
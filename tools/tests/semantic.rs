@@ -0,0 +1,42 @@
+//! The annotated semantic-token fixture is well formed and every
+//! annotation is styled by the shipped theme's semantic rules.
+
+use std::fs;
+use std::path::Path;
+
+use zenn_theme_tools::semantic::{self, TOKEN_MODIFIERS, TOKEN_TYPES};
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::{repo_root, THEME_PATH};
+
+fn fixture() -> String {
+    fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/semantic.rs")).unwrap()
+}
+
+#[test]
+fn annotations_use_known_types_and_modifiers() {
+    let tokens = semantic::annotations(&fixture());
+    assert!(tokens.len() > 40);
+    for token in &tokens {
+        let at = format!("line {} `{}`", token.line, token.text);
+        assert!(TOKEN_TYPES.contains(&token.token_type.as_str()), "{at}: unknown type {}", token.token_type);
+        for modifier in &token.modifiers {
+            assert!(TOKEN_MODIFIERS.contains(&modifier.as_str()), "{at}: unknown modifier {modifier}");
+        }
+        assert!(!token.text.trim().is_empty() && token.text.trim() == token.text, "{at}: carets misaligned");
+    }
+}
+
+#[test]
+fn shipped_theme_styles_every_annotated_token() {
+    let theme = Theme::load(&repo_root().join(THEME_PATH)).unwrap();
+    for token in semantic::annotations(&fixture()) {
+        let style = semantic::resolve(&theme, &token.token_type, &token.modifiers, "rust");
+        assert!(
+            style.foreground.is_some(),
+            "line {} `{}` ({}) has no semantic color",
+            token.line,
+            token.text,
+            token.token_type
+        );
+    }
+}
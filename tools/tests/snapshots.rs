@@ -0,0 +1,28 @@
+//! Golden token streams for every sample file.
+//!
+//! Run with `UPDATE_SNAPSHOTS=1` to rewrite `tests/snapshots/` after an
+//! intentional theme or sample change, then review the diff. The streams
+//! come from the bundled grammars, so the test runs without Node; `tools
+//! parity` checks those against Shiki's.
+
+use zenn_theme_tools::resolver::Resolver;
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::tokenizer::Tokenizer;
+use zenn_theme_tools::{repo_root, samples, snapshot, SAMPLES_DIR, SNAPSHOTS_DIR, THEME_PATH};
+
+#[test]
+fn sample_token_streams_match_snapshots() {
+    let root = repo_root();
+    let theme = Theme::load(&root.join(THEME_PATH)).unwrap();
+    let samples = samples::discover(&root.join(SAMPLES_DIR)).unwrap();
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+
+    let failures =
+        snapshot::check(&Resolver::new(&theme), &Tokenizer::new(), &samples, &root.join(SNAPSHOTS_DIR), update)
+            .unwrap();
+    assert!(
+        failures.is_empty(),
+        "snapshots out of date (rerun with UPDATE_SNAPSHOTS=1 and review the diff):\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,9 @@
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::{repo_root, THEME_PATH};
+
+#[test]
+fn shipped_theme_loads_and_validates() {
+    let theme = Theme::load(&repo_root().join(THEME_PATH)).unwrap_or_else(|e| panic!("{e}"));
+    assert!(!theme.token_colors.is_empty());
+    assert!(theme.token_colors[0].is_default(), "first rule should set the global colors");
+}
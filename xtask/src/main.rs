@@ -0,0 +1,164 @@
+//! Repository automation, run with `cargo xtask <command>`.
+//!
+//! Commands that need the tokenizer forward to the `zenn-theme-tools`
+//! crate's `tools` binary through `cargo run`, so they build exactly what a
+//! contributor would run by hand; any further arguments are passed through.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitCode};
+
+use zenn_theme_tools::theme::Theme;
+use zenn_theme_tools::{export, palette, vscode};
+use zenn_theme_tools::{repo_root, theme_paths, Error, Result, SCHEMA_PATH, THEMES_DIR};
+
+const USAGE: &str = "usage: cargo xtask <command> [args...]
+
+commands:
+  build-theme       regenerate the palette-driven themes under src/themes and
+                    validate every theme (--check: fail if one is stale)
+  schema            regenerate the theme JSON Schema from the serde models
+                    (--check: fail if it is stale)
+  audit             report scopes no theme rule colors (tools audit)
+  preview           write the HTML preview page (tools preview)
+  snapshot-update   rewrite the golden token snapshots
+  export [<dir>]    write every theme as normalized JSON, .tmTheme, Vim, Helix,
+                    truecolor and 256-color terminal schemes and CSS variables
+                    to <dir> (default target/dist)
+  package [<dir>]   assemble the themes as a VS Code extension in <dir>, ready
+                    for `vsce package` (default target/vscode)";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let Some(command) = args.next() else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let rest: Vec<String> = args.collect();
+    let root = repo_root();
+
+    let result = match command.as_str() {
+        "build-theme" => build_theme(&root, rest.iter().any(|a| a == "--check")),
+        "schema" => schema(&root, rest.iter().any(|a| a == "--check")),
+        "audit" => cargo(&root, &["run", "-p", "zenn-theme-tools", "--bin", "tools", "--", "audit"], &rest, &[]),
+        "preview" => cargo(&root, &["run", "-p", "zenn-theme-tools", "--bin", "tools", "--", "preview"], &rest, &[]),
+        "snapshot-update" => cargo(
+            &root,
+            &["test", "-p", "zenn-theme-tools", "--test", "snapshots"],
+            &rest,
+            &[("UPDATE_SNAPSHOTS", "1")],
+        ),
+        "export" => {
+            let out = rest.first().map_or_else(|| root.join("target/dist"), |dir| dir.into());
+            export(&root, &out).map(|()| true)
+        }
+        "package" => {
+            let out = rest.first().map_or_else(|| root.join("target/vscode"), |dir| dir.into());
+            package(&root, &out).map(|()| true)
+        }
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs cargo from the repository root; `Ok(false)` when it exits non-zero.
+fn cargo(root: &Path, args: &[&str], rest: &[String], env: &[(&str, &str)]) -> Result<bool> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let mut command = Command::new(&cargo);
+    command.current_dir(root).args(args).args(rest);
+    command.envs(env.iter().copied());
+    let status = command.status().map_err(|e| Error::io(Path::new(&cargo), e))?;
+    Ok(status.success())
+}
+
+/// `Ok(false)` when checking and a generated theme differs from disk.
+fn build_theme(root: &Path, check: bool) -> Result<bool> {
+    let mut fresh = true;
+    for (file, theme) in palette::shipped() {
+        let path = root.join(THEMES_DIR).join(file);
+        let generated = theme.to_json();
+        let current = fs::read_to_string(&path).unwrap_or_default();
+        if generated == current {
+            continue;
+        }
+        if check {
+            eprintln!("{} is out of date; run `cargo xtask build-theme`", path.display());
+            fresh = false;
+        } else {
+            fs::write(&path, generated).map_err(|e| Error::io(&path, e))?;
+            println!("wrote {}", path.display());
+        }
+    }
+    if !fresh {
+        return Ok(false);
+    }
+
+    for path in theme_paths(&root.join(THEMES_DIR))? {
+        let theme = Theme::load(&path)?;
+        println!("ok  {}  ({} rules, {} colors)", path.display(), theme.token_colors.len(), theme.colors.len());
+    }
+    Ok(true)
+}
+
+/// `Ok(false)` when checking and the committed schema differs.
+fn schema(root: &Path, check: bool) -> Result<bool> {
+    let path = root.join(SCHEMA_PATH);
+    let generated = Theme::schema();
+    if fs::read_to_string(&path).unwrap_or_default() == generated {
+        return Ok(true);
+    }
+    if check {
+        eprintln!("{} is out of date; run `cargo xtask schema`", path.display());
+        return Ok(false);
+    }
+    fs::write(&path, generated).map_err(|e| Error::io(&path, e))?;
+    println!("wrote {}", path.display());
+    Ok(true)
+}
+
+fn export(root: &Path, out: &Path) -> Result<()> {
+    for path in theme_paths(&root.join(THEMES_DIR))? {
+        let theme = Theme::load(&path)?;
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        for target in export::write_all(&theme, &stem, out)? {
+            println!("wrote {}", target.display());
+        }
+    }
+    Ok(())
+}
+
+/// Stamps the extension with the workspace version, so the tools and the
+/// extension are released together.
+fn package(root: &Path, out: &Path) -> Result<()> {
+    let mut themes = Vec::new();
+    for path in theme_paths(&root.join(THEMES_DIR))? {
+        let file = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        themes.push((file, Theme::load(&path)?));
+    }
+    // The owner decides the license; until there is a LICENSE, ship none
+    let license_path = root.join("LICENSE");
+    let license = match fs::read_to_string(&license_path) {
+        Ok(license) => Some(license),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            eprintln!("warning: no {}; packaging the extension as UNLICENSED", license_path.display());
+            None
+        }
+        Err(e) => return Err(Error::io(&license_path, e)),
+    };
+    for path in vscode::package(&themes, env!("CARGO_PKG_VERSION"), license.as_deref(), out)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}